@@ -0,0 +1,276 @@
+// Copyright 2023 Raven Industries inc.
+//! Procedural macros that embed ISO 11783-6 object pools at compile time
+//!
+//! The pool is parsed and validated while compiling, a pool that can't be parsed or that
+//! references objects it does not contain is a compile error. The macros expand to the IOP data
+//! and an `ObjectId` constant per object, so application code can only refer to objects that
+//! exist. Expand them in their own module:
+//!
+//! ```ignore
+//! mod pool {
+//!     ag_iso_stack_macros::include_object_pool_xml!("resources/pool.xml");
+//! }
+//!
+//! let pool = pool::object_pool();
+//! let mask = pool.data_mask_object_by_id(pool::MAIN_MASK);
+//! ```
+use ag_iso_stack::object_pool::{NameTable, Object, ObjectPool};
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use syn::{parse_macro_input, LitByteStr, LitStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Iop,
+    Xml,
+}
+
+/// Embeds an object pool in IOP format
+///
+/// The path is relative to the directory of the crate's `Cargo.toml`. The constants are named
+/// after the type and ID of the objects, like `DATA_MASK_1000`.
+///
+/// Expands to:
+/// - `IOP: &[u8]`, the object pool
+/// - `fn object_pool() -> ObjectPool`, parses `IOP`
+/// - an `ObjectId` constant per object
+#[proc_macro]
+pub fn include_object_pool(input: TokenStream) -> TokenStream {
+    include(input, Format::Iop)
+}
+
+/// Embeds an object pool in XML format, see `ObjectPool::from_xml`
+///
+/// Works like [`include_object_pool!`], the constants are named after the `name` attribute of
+/// the objects in upper case, like `MAIN_MASK` for `main_mask`. Objects without a name are named
+/// after their type and ID.
+#[proc_macro]
+pub fn include_object_pool_xml(input: TokenStream) -> TokenStream {
+    include(input, Format::Xml)
+}
+
+fn include(input: TokenStream, format: Format) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = Path::new(&manifest_dir).join(path.value());
+    match expand(&path, format) {
+        Ok(tokens) => tokens.into(),
+        Err(message) => syn::Error::new(Span::call_site(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+fn expand(path: &Path, format: Format) -> Result<TokenStream2, String> {
+    let data = std::fs::read(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+    let (pool, names) = load(&data, format)?;
+    pool.validate().map_err(|errors| {
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        format!(
+            "invalid object pool {}: {}",
+            path.display(),
+            errors.join(", ")
+        )
+    })?;
+
+    let constants = constants(&pool, &names)?;
+    let names = constants.keys().map(|name| format_ident!("{}", name));
+    let ids = constants.values();
+    let iop = pool
+        .as_iop()
+        .map_err(|e| format!("can't encode {}: {}", path.display(), e))?;
+    let iop = LitByteStr::new(&iop, Span::call_site());
+    let path = path_str(path)?;
+    Ok(quote! {
+        // Rebuilds when the file changes
+        const _: &[u8] = include_bytes!(#path);
+
+        /// The object pool in IOP format
+        pub const IOP: &[u8] = #iop;
+
+        pub fn object_pool() -> ::ag_iso_stack::object_pool::ObjectPool {
+            ::ag_iso_stack::object_pool::ObjectPool::from_iop(IOP.iter().copied())
+        }
+
+        #(pub const #names: ::ag_iso_stack::object_pool::ObjectId =
+            ::ag_iso_stack::object_pool::ObjectId::new_const(#ids);)*
+    })
+}
+
+fn load(data: &[u8], format: Format) -> Result<(ObjectPool, NameTable), String> {
+    match format {
+        Format::Iop => {
+            let pool = ObjectPool::try_from_iop(data.iter().copied())
+                .map_err(|e| format!("invalid IOP data: {}", e))?;
+            Ok((pool, NameTable::new()))
+        }
+        Format::Xml => {
+            let xml = std::str::from_utf8(data).map_err(|e| format!("invalid UTF-8: {}", e))?;
+            ObjectPool::from_xml(xml).map_err(|e| format!("invalid XML object pool: {}", e))
+        }
+    }
+}
+
+/// The constant name and ID of every object, sorted by name
+fn constants(pool: &ObjectPool, names: &NameTable) -> Result<BTreeMap<String, u16>, String> {
+    let mut constants = BTreeMap::new();
+    for object in pool.objects() {
+        let id = u16::from(object.id());
+        let constant = match names.name(object.id()) {
+            Some(name) => name_constant(name),
+            None => constant_name(object),
+        };
+        if let Some(other) = constants.insert(constant.clone(), id) {
+            return Err(format!(
+                "objects {} and {} both have the constant name {}",
+                other, id, constant
+            ));
+        }
+    }
+    Ok(constants)
+}
+
+/// Names an object after its type and ID, a data mask with ID 1000 becomes `DATA_MASK_1000`
+fn constant_name(object: &Object) -> String {
+    let mut constant = String::new();
+    for c in format!("{:?}", object.object_type()).chars() {
+        if c.is_ascii_uppercase() && !constant.is_empty() {
+            constant.push('_');
+        }
+        constant.push(c.to_ascii_uppercase());
+    }
+    format!("{}_{}", constant, u16::from(object.id()))
+}
+
+/// Turns an object name into an upper case identifier, `main-mask` becomes `MAIN_MASK`
+fn name_constant(name: &str) -> String {
+    let mut constant: String = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => c.to_ascii_uppercase(),
+            _ => '_',
+        })
+        .collect();
+    if !constant.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        constant.insert(0, 'O');
+    }
+    constant
+}
+
+fn path_str(path: &Path) -> Result<String, String> {
+    let path: PathBuf = path
+        .canonicalize()
+        .map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("{} is not valid UTF-8", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ag_iso_stack::object_pool::{NumberVariable, ObjectId};
+
+    fn resource(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/test")
+            .join(name)
+    }
+
+    #[test]
+    fn constant_name_test() {
+        let id = ObjectId::new(1000).unwrap();
+        let variable = Object::NumberVariable(NumberVariable { id, value: 0 });
+        assert_eq!(constant_name(&variable), "NUMBER_VARIABLE_1000");
+
+        let pool = ObjectPool::from_iop(std::fs::read(resource("simple-pool.iop")).unwrap());
+        let constants = constants(&pool, &NameTable::new()).unwrap();
+        assert_eq!(constants["WORKING_SET_0"], 0);
+        assert_eq!(constants["FONT_ATTRIBUTES_23000"], 23000);
+        assert_eq!(constants.len(), pool.objects().len());
+    }
+
+    #[test]
+    fn name_constant_test() {
+        assert_eq!(name_constant("main_mask"), "MAIN_MASK");
+        assert_eq!(name_constant("Speed-Display 2"), "SPEED_DISPLAY_2");
+        assert_eq!(name_constant("2nd_mask"), "O2ND_MASK");
+        assert_eq!(name_constant("_hidden"), "O_HIDDEN");
+    }
+
+    #[test]
+    fn expand_test() {
+        let tokens = expand(&resource("simple-pool.iop"), Format::Iop)
+            .unwrap()
+            .to_string();
+        assert!(tokens.contains("pub const IOP"));
+        assert!(tokens.contains("fn object_pool"));
+        assert!(tokens.contains("pub const DATA_MASK_1000"));
+
+        let tokens = expand(&resource("simple-pool.xml"), Format::Xml)
+            .unwrap()
+            .to_string();
+        assert!(tokens.contains("pub const IOP"));
+        assert!(tokens.contains("pub const MAIN_MASK"));
+    }
+
+    #[test]
+    fn expand_errors_test() {
+        let missing = expand(&resource("missing.iop"), Format::Iop).unwrap_err();
+        assert!(missing.starts_with("can't read"));
+
+        let mut data = std::fs::read(resource("simple-pool.iop")).unwrap();
+        let path = std::env::temp_dir().join("ag-iso-stack-macros-truncated.iop");
+        data.truncate(data.len() - 1);
+        std::fs::write(&path, &data).unwrap();
+        let truncated = expand(&path, Format::Iop).unwrap_err();
+        assert!(truncated.starts_with("invalid IOP data"));
+
+        // Parses, but the working set references a mask that is not in the pool
+        let pool =
+            ObjectPool::try_from_iop(std::fs::read(resource("simple-pool.iop")).unwrap()).unwrap();
+        let mut dangling = ObjectPool::new();
+        for object in pool.objects() {
+            if !matches!(object, Object::DataMask(_)) {
+                dangling.add(object.clone());
+            }
+        }
+        std::fs::write(&path, dangling.as_iop().unwrap()).unwrap();
+        let invalid = expand(&path, Format::Iop).unwrap_err();
+        assert!(invalid.starts_with("invalid object pool"));
+        std::fs::remove_file(&path).unwrap();
+
+        let not_xml = expand(&resource("simple-pool.iop"), Format::Xml).unwrap_err();
+        assert!(not_xml.starts_with("invalid"));
+
+        // Parses, but the name is not the name of an object
+        let path = std::env::temp_dir().join("ag-iso-stack-macros-unknown-name.xml");
+        std::fs::write(
+            &path,
+            r#"<objectpool><objectpointer value="nothing"/></objectpool>"#,
+        )
+        .unwrap();
+        let unknown = expand(&path, Format::Xml).unwrap_err();
+        assert!(unknown.starts_with("invalid XML object pool"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn duplicate_constant_test() {
+        let xml = r#"<objectpool>
+            <numbervariable name="speed" value="0"/>
+            <numbervariable name="SPEED" value="0"/>
+        </objectpool>"#;
+        let (pool, names) = load(xml.as_bytes(), Format::Xml).unwrap();
+        assert!(constants(&pool, &names).is_err());
+
+        let (pool, names) =
+            load(xml.replace(r#" name="SPEED""#, "").as_bytes(), Format::Xml).unwrap();
+        let constants = constants(&pool, &names).unwrap();
+        assert_eq!(constants["SPEED"], 0);
+        assert_eq!(constants["NUMBER_VARIABLE_1"], 1);
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2023 Raven Industries inc.
+use ag_iso_stack::object_pool::{Object, ObjectId};
+
+mod iop_pool {
+    ag_iso_stack_macros::include_object_pool!("../resources/test/simple-pool.iop");
+}
+
+mod xml_pool {
+    ag_iso_stack_macros::include_object_pool_xml!("../resources/test/simple-pool.xml");
+}
+
+#[test]
+fn include_object_pool_test() {
+    let pool = iop_pool::object_pool();
+    assert_eq!(pool.objects().len(), 7);
+    assert_eq!(pool.as_iop().unwrap(), iop_pool::IOP);
+
+    assert_eq!(iop_pool::WORKING_SET_0, ObjectId::new(0).unwrap());
+    assert!(pool
+        .data_mask_object_by_id(iop_pool::DATA_MASK_1000)
+        .is_some());
+    assert!(pool
+        .font_attributes_object_by_id(iop_pool::FONT_ATTRIBUTES_23000)
+        .is_some());
+}
+
+#[test]
+fn include_object_pool_xml_test() {
+    let pool = xml_pool::object_pool();
+    assert_eq!(pool.as_iop().unwrap(), iop_pool::IOP);
+
+    let mask = pool.data_mask_object_by_id(xml_pool::MAIN_MASK).unwrap();
+    assert_eq!(mask.soft_key_mask, xml_pool::SOFT_KEYS.into());
+    let Some(Object::OutputNumber(speed)) = pool.object_by_id(xml_pool::SPEED) else {
+        panic!("expected an output number");
+    };
+    assert_eq!(speed.font_attributes, xml_pool::FONT);
+    assert_eq!(speed.variable_reference, xml_pool::SPEED_VALUE.into());
+}
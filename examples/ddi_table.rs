@@ -0,0 +1,117 @@
+// Copyright 2023 Raven Industries inc.
+
+use std::path::PathBuf;
+
+use ag_iso_stack::task_controller::DdiDefinition;
+use clap::Parser;
+
+/// Print the data dictionary table of `src/task_controller/ddi.rs` from the export of the
+/// ISO 11783-11 online data base
+///
+/// The export is the text file with one block per DDI, like
+///
+///     DD Entity: 1 Setpoint Volume Per Area Application Rate
+///     Unit: mm³/m² - Capacity per area unit
+///     Resolution: 0,01
+///     Range: 0 - 2147483647
+///
+/// Proprietary DDIs are left out.
+#[derive(Debug, Parser)]
+#[clap(name = "ddi_table", verbatim_doc_comment)]
+struct Options {
+    /// The export of the data dictionary
+    pub export: PathBuf,
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    ddi: u16,
+    name: String,
+    unit: String,
+    resolution: String,
+    min: i64,
+    max: i64,
+}
+
+fn bound(value: i64) -> String {
+    match value.clamp(i32::MIN.into(), i32::MAX.into()) {
+        v if v == i64::from(i32::MAX) => "MAX".to_string(),
+        v if v == i64::from(i32::MIN) => "MIN".to_string(),
+        v => v.to_string(),
+    }
+}
+
+fn parse(export: &str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+    // The lines after a left out entity belong to no entry
+    let mut skipping = true;
+    for line in export.lines().map(str::trim) {
+        if let Some(entity) = line.strip_prefix("DD Entity:") {
+            let (ddi, name) = entity.trim().split_once(' ').unwrap_or((entity.trim(), ""));
+            skipping = true;
+            if let Ok(ddi) = ddi.parse() {
+                if !DdiDefinition::is_proprietary(ddi) {
+                    entries.push(Entry {
+                        ddi,
+                        name: name.trim().to_string(),
+                        resolution: "1".to_string(),
+                        min: i32::MIN.into(),
+                        max: i32::MAX.into(),
+                        ..Default::default()
+                    });
+                    skipping = false;
+                }
+            }
+            continue;
+        }
+        let Some(entry) = entries.last_mut().filter(|_| !skipping) else {
+            continue;
+        };
+        if let Some(unit) = line.strip_prefix("Unit:") {
+            let unit = unit.split(" -").next().unwrap_or_default().trim();
+            if unit != "n.a." {
+                entry.unit = unit.to_string();
+            }
+        } else if let Some(resolution) = line.strip_prefix("Resolution:") {
+            let resolution = resolution.trim().replace(',', ".");
+            if resolution.parse::<f64>().is_ok() {
+                entry.resolution = resolution;
+            }
+        } else if let Some(range) = line.strip_prefix("Range:") {
+            // The bounds may be negative, so only " - " separates them
+            if let Some((min, max)) = range.split_once(" - ") {
+                let digits = |s: &str| s.trim().replace(['.', ' '], "").parse().ok();
+                if let (Some(min), Some(max)) = (digits(min), digits(max)) {
+                    entry.min = min;
+                    entry.max = max;
+                }
+            }
+        }
+    }
+    entries.sort_by_key(|e| e.ddi);
+    entries.dedup_by_key(|e| e.ddi);
+    entries
+}
+
+fn main() {
+    let opts = Options::parse();
+    let data = std::fs::read(&opts.export).expect("the export can't be read");
+    // Older exports are ISO 8859-1
+    let export = String::from_utf8(data)
+        .unwrap_or_else(|e| e.into_bytes().iter().map(|&b| char::from(b)).collect());
+    for entry in parse(&export) {
+        let mut resolution = entry.resolution;
+        if !resolution.contains('.') {
+            resolution.push_str(".0");
+        }
+        println!(
+            "    {} {:?}, {:?}, {}, {}, {};",
+            entry.ddi,
+            entry.name,
+            entry.unit,
+            resolution,
+            bound(entry.min),
+            bound(entry.max)
+        );
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright 2023 Raven Industries inc.
+//! Reads arbitrary data as object pool objects, run with `cargo +nightly fuzz run object_read`
+#![no_main]
+
+use ag_iso_stack::object_pool::{Object, ObjectPool};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut objects = data.iter().copied();
+    while Object::read(&mut objects).is_ok() {}
+
+    let _ = ObjectPool::try_from_iop(data.iter().copied());
+    let _ = ObjectPool::from_iop_lenient(data.iter().copied());
+});
@@ -6,3 +6,5 @@
 pub mod driver;
 pub mod network_management;
 pub mod object_pool;
+pub mod task_controller;
+pub mod virtual_terminal;
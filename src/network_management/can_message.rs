@@ -2,6 +2,7 @@
 use super::name::NAME;
 use crate::driver::CanId;
 
+#[derive(Debug, Clone)]
 pub struct CANMessage {
     data: Vec<u8>,
     identifier: CanId,
@@ -10,7 +11,7 @@ pub struct CANMessage {
 }
 
 impl CANMessage {
-    pub(super) fn new(data: Vec<u8>, identifier: CanId) -> CANMessage {
+    pub fn new(data: Vec<u8>, identifier: CanId) -> CANMessage {
         CANMessage {
             data,
             identifier,
@@ -11,6 +11,7 @@ pub enum CommonParameterGroupNumbers {
     GuidanceSystemCommand = 0x00AD00,
     ExtendedTransportProtocolData = 0x00C700,
     ExtendedTransportProtocolCommand = 0x00C800,
+    ProcessData = 0x00CB00,
     RequestForRepetitionRate = 0x00CC00,
     BinaryDataTransfer = 0x00D700,
     MemoryAccessResponse = 0x00D800,
@@ -34,6 +35,7 @@ pub enum CommonParameterGroupNumbers {
     EcuIdentificationInformation = 0x00FDC5,
     WorkingSetMaster = 0x00FE0D,
     ResponseForRepetitionRate = 0x00FE0E,
+    LanguageCommand = 0x00FE0F,
     MaintainPower = 0x00FE47,
     WheelBasedSpeedAndDistance = 0x00FE48,
     GroundBasedSpeedAndDistance = 0x00FE49,
@@ -0,0 +1,602 @@
+// Copyright 2023 Raven Industries inc.
+//! The Language Command (ISO 11783-7), the language, formats and units an operator selected
+//!
+//! A VT or TC broadcasts the Language Command when it starts up, when the operator changes a
+//! setting and when it is requested. Applications follow it with a
+//! [`LanguageCommandInterface`] and use the [`LanguageCommand`] to display values the way the
+//! operator wants them.
+use crate::driver::{Address, CanId, Pgn, Priority};
+use crate::network_management::can_message::CANMessage;
+use crate::network_management::common_parameter_group_numbers::CommonParameterGroupNumbers;
+
+/// The number of bytes in a Language Command
+pub const LANGUAGE_COMMAND_LENGTH: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LanguageCommandError {
+    /// The message is not a Language Command
+    UnexpectedPgn(Pgn),
+    /// The message is not 8 bytes long
+    UnexpectedLength(usize),
+}
+
+impl std::fmt::Display for LanguageCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for LanguageCommandError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalSymbol {
+    Comma,
+    Point,
+    Reserved,
+    NotAvailable,
+}
+
+impl From<u8> for DecimalSymbol {
+    fn from(value: u8) -> Self {
+        match value & 0x03 {
+            0 => DecimalSymbol::Comma,
+            1 => DecimalSymbol::Point,
+            2 => DecimalSymbol::Reserved,
+            _ => DecimalSymbol::NotAvailable,
+        }
+    }
+}
+
+impl From<DecimalSymbol> for u8 {
+    fn from(value: DecimalSymbol) -> Self {
+        value as u8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeFormat {
+    TwentyFourHour,
+    TwelveHour,
+    Reserved,
+    NotAvailable,
+}
+
+impl From<u8> for TimeFormat {
+    fn from(value: u8) -> Self {
+        match value & 0x03 {
+            0 => TimeFormat::TwentyFourHour,
+            1 => TimeFormat::TwelveHour,
+            2 => TimeFormat::Reserved,
+            _ => TimeFormat::NotAvailable,
+        }
+    }
+}
+
+impl From<TimeFormat> for u8 {
+    fn from(value: TimeFormat) -> Self {
+        value as u8
+    }
+}
+
+/// The order of the day, month and year in a date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateFormat {
+    DdMmYyyy,
+    DdYyyyMm,
+    MmYyyyDd,
+    MmDdYyyy,
+    YyyyMmDd,
+    YyyyDdMm,
+    Reserved(u8),
+}
+
+impl From<u8> for DateFormat {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => DateFormat::DdMmYyyy,
+            1 => DateFormat::DdYyyyMm,
+            2 => DateFormat::MmYyyyDd,
+            3 => DateFormat::MmDdYyyy,
+            4 => DateFormat::YyyyMmDd,
+            5 => DateFormat::YyyyDdMm,
+            value => DateFormat::Reserved(value),
+        }
+    }
+}
+
+impl From<DateFormat> for u8 {
+    fn from(value: DateFormat) -> Self {
+        match value {
+            DateFormat::DdMmYyyy => 0,
+            DateFormat::DdYyyyMm => 1,
+            DateFormat::MmYyyyDd => 2,
+            DateFormat::MmDdYyyy => 3,
+            DateFormat::YyyyMmDd => 4,
+            DateFormat::YyyyDdMm => 5,
+            DateFormat::Reserved(value) => value,
+        }
+    }
+}
+
+/// The units system of a quantity
+///
+/// Distance, area, temperature, pressure and force only distinguish metric from imperial/US,
+/// those are [`UnitSystem::Imperial`], [`UnitSystem::Us`] is reserved for them. Volume and mass
+/// have separate imperial and US units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+    Us,
+    NotAvailable,
+}
+
+impl From<u8> for UnitSystem {
+    fn from(value: u8) -> Self {
+        match value & 0x03 {
+            0 => UnitSystem::Metric,
+            1 => UnitSystem::Imperial,
+            2 => UnitSystem::Us,
+            _ => UnitSystem::NotAvailable,
+        }
+    }
+}
+
+impl From<UnitSystem> for u8 {
+    fn from(value: UnitSystem) -> Self {
+        value as u8
+    }
+}
+
+impl UnitSystem {
+    fn is_metric(self) -> bool {
+        matches!(self, UnitSystem::Metric | UnitSystem::NotAvailable)
+    }
+}
+
+/// A value converted to the units the operator selected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayValue {
+    pub value: f64,
+    pub unit: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageCommand {
+    /// Two letter ISO 639 language code in lower case, like `en`
+    pub language_code: String,
+    pub decimal_symbol: DecimalSymbol,
+    pub time_format: TimeFormat,
+    pub date_format: DateFormat,
+    pub distance_units: UnitSystem,
+    pub area_units: UnitSystem,
+    pub volume_units: UnitSystem,
+    pub mass_units: UnitSystem,
+    pub temperature_units: UnitSystem,
+    pub pressure_units: UnitSystem,
+    pub force_units: UnitSystem,
+    /// The units of quantities not covered by the other units
+    pub generic_units: UnitSystem,
+    /// Two letter ISO 3166-1 country code in upper case, `None` if not available
+    pub country_code: Option<String>,
+}
+
+impl Default for LanguageCommand {
+    /// English with metric units, what a VT assumes until told otherwise
+    fn default() -> Self {
+        LanguageCommand {
+            language_code: "en".to_string(),
+            decimal_symbol: DecimalSymbol::Point,
+            time_format: TimeFormat::TwentyFourHour,
+            date_format: DateFormat::DdMmYyyy,
+            distance_units: UnitSystem::Metric,
+            area_units: UnitSystem::Metric,
+            volume_units: UnitSystem::Metric,
+            mass_units: UnitSystem::Metric,
+            temperature_units: UnitSystem::Metric,
+            pressure_units: UnitSystem::Metric,
+            force_units: UnitSystem::Metric,
+            generic_units: UnitSystem::Metric,
+            country_code: None,
+        }
+    }
+}
+
+impl LanguageCommand {
+    /// Decodes the data of a Language Command
+    pub fn from_bytes(data: &[u8]) -> Result<Self, LanguageCommandError> {
+        if data.len() != LANGUAGE_COMMAND_LENGTH {
+            return Err(LanguageCommandError::UnexpectedLength(data.len()));
+        }
+        let country_code = match [data[6], data[7]] {
+            [0xFF, 0xFF] => None,
+            code => Some(code.iter().map(|&b| char::from(b)).collect()),
+        };
+        Ok(LanguageCommand {
+            language_code: data[..2].iter().map(|&b| char::from(b)).collect(),
+            decimal_symbol: (data[2] >> 6).into(),
+            time_format: (data[2] >> 4).into(),
+            date_format: data[3].into(),
+            distance_units: (data[4] >> 6).into(),
+            area_units: (data[4] >> 4).into(),
+            volume_units: (data[4] >> 2).into(),
+            mass_units: data[4].into(),
+            temperature_units: (data[5] >> 6).into(),
+            pressure_units: (data[5] >> 4).into(),
+            force_units: (data[5] >> 2).into(),
+            generic_units: data[5].into(),
+            country_code,
+        })
+    }
+
+    /// Decodes a received Language Command
+    pub fn from_message(message: &CANMessage) -> Result<Self, LanguageCommandError> {
+        let pgn = message.get_identifier().pgn();
+        if pgn != Pgn::from_raw(CommonParameterGroupNumbers::LanguageCommand as u32) {
+            return Err(LanguageCommandError::UnexpectedPgn(pgn));
+        }
+        Self::from_bytes(message.get_data())
+    }
+
+    /// Encodes the Language Command, codes that are not two ASCII letters are sent as spaces
+    pub fn to_bytes(&self) -> [u8; LANGUAGE_COMMAND_LENGTH] {
+        let language = code_bytes(&self.language_code);
+        let country = self
+            .country_code
+            .as_deref()
+            .map_or([0xFF, 0xFF], code_bytes);
+        [
+            language[0],
+            language[1],
+            u8::from(self.decimal_symbol) << 6 | u8::from(self.time_format) << 4 | 0x0F,
+            self.date_format.into(),
+            u8::from(self.distance_units) << 6
+                | u8::from(self.area_units) << 4
+                | u8::from(self.volume_units) << 2
+                | u8::from(self.mass_units),
+            u8::from(self.temperature_units) << 6
+                | u8::from(self.pressure_units) << 4
+                | u8::from(self.force_units) << 2
+                | u8::from(self.generic_units),
+            country[0],
+            country[1],
+        ]
+    }
+
+    /// The broadcast message sending this Language Command from `source`
+    pub fn to_message(&self, source: Address) -> CANMessage {
+        CANMessage::new(self.to_bytes().to_vec(), Self::can_id(source))
+    }
+
+    /// The request for the Language Command of `destination`, or of every node if global
+    pub fn request(source: Address, destination: Address) -> CANMessage {
+        let pgn = CommonParameterGroupNumbers::LanguageCommand as u32;
+        let id = CanId::try_encode(
+            Pgn::from_raw(CommonParameterGroupNumbers::ParameterGroupNumberRequest as u32),
+            source,
+            destination,
+            Priority::Six,
+        );
+        CANMessage::new(pgn.to_le_bytes()[..3].to_vec(), id.unwrap_or_default())
+    }
+
+    fn can_id(source: Address) -> CanId {
+        // A broadcast PGN sent to the global address always encodes
+        CanId::try_encode(
+            Pgn::from_raw(CommonParameterGroupNumbers::LanguageCommand as u32),
+            source,
+            Address::GLOBAL,
+            Priority::Six,
+        )
+        .unwrap_or_default()
+    }
+
+    /// Writes `value` with `decimals` decimals and the selected decimal symbol
+    pub fn format_decimal(&self, value: f64, decimals: usize) -> String {
+        let text = format!("{:.*}", decimals, value);
+        match self.decimal_symbol {
+            DecimalSymbol::Comma => text.replace('.', ","),
+            _ => text,
+        }
+    }
+
+    /// A distance in metres in metres or feet
+    pub fn distance(&self, metres: f64) -> DisplayValue {
+        match self.distance_units.is_metric() {
+            true => DisplayValue::new(metres, "m"),
+            false => DisplayValue::new(metres / 0.3048, "ft"),
+        }
+    }
+
+    /// A speed in metres per second in km/h or mph, following the distance units
+    pub fn speed(&self, metres_per_second: f64) -> DisplayValue {
+        match self.distance_units.is_metric() {
+            true => DisplayValue::new(metres_per_second * 3.6, "km/h"),
+            false => DisplayValue::new(metres_per_second * 3600.0 / 1609.344, "mph"),
+        }
+    }
+
+    /// An area in square metres in hectares or acres
+    pub fn area(&self, square_metres: f64) -> DisplayValue {
+        match self.area_units.is_metric() {
+            true => DisplayValue::new(square_metres / 10_000.0, "ha"),
+            false => DisplayValue::new(square_metres / 4_046.856_422_4, "ac"),
+        }
+    }
+
+    /// A volume in litres in litres, imperial gallons or US gallons
+    pub fn volume(&self, litres: f64) -> DisplayValue {
+        match self.volume_units {
+            UnitSystem::Imperial => DisplayValue::new(litres / 4.546_09, "gal (UK)"),
+            UnitSystem::Us => DisplayValue::new(litres / 3.785_411_784, "gal (US)"),
+            _ => DisplayValue::new(litres, "L"),
+        }
+    }
+
+    /// A mass in kilograms in kilograms or pounds
+    pub fn mass(&self, kilograms: f64) -> DisplayValue {
+        match self.mass_units.is_metric() {
+            true => DisplayValue::new(kilograms, "kg"),
+            false => DisplayValue::new(kilograms / 0.453_592_37, "lb"),
+        }
+    }
+
+    /// A temperature in degrees Celsius in degrees Celsius or Fahrenheit
+    pub fn temperature(&self, celsius: f64) -> DisplayValue {
+        match self.temperature_units.is_metric() {
+            true => DisplayValue::new(celsius, "°C"),
+            false => DisplayValue::new(celsius * 1.8 + 32.0, "°F"),
+        }
+    }
+
+    /// A pressure in kilopascals in kilopascals or pounds per square inch
+    pub fn pressure(&self, kilopascals: f64) -> DisplayValue {
+        match self.pressure_units.is_metric() {
+            true => DisplayValue::new(kilopascals, "kPa"),
+            false => DisplayValue::new(kilopascals / 6.894_757_293, "psi"),
+        }
+    }
+
+    /// A force in newtons in newtons or pounds-force
+    pub fn force(&self, newtons: f64) -> DisplayValue {
+        match self.force_units.is_metric() {
+            true => DisplayValue::new(newtons, "N"),
+            false => DisplayValue::new(newtons / 4.448_221_615, "lbf"),
+        }
+    }
+}
+
+impl DisplayValue {
+    fn new(value: f64, unit: &'static str) -> Self {
+        DisplayValue { value, unit }
+    }
+}
+
+fn code_bytes(code: &str) -> [u8; 2] {
+    match code.as_bytes() {
+        &[a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => [a, b],
+        _ => [b' ', b' '],
+    }
+}
+
+/// A change of the Language Command, see [`LanguageCommandInterface::process_message`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageChange {
+    /// The command before the change, `None` for the first command received
+    pub previous: Option<LanguageCommand>,
+    pub current: LanguageCommand,
+}
+
+impl LanguageChange {
+    pub fn language_changed(&self) -> bool {
+        self.previous.as_ref().is_none_or(|previous| {
+            previous.language_code != self.current.language_code
+                || previous.country_code != self.current.country_code
+        })
+    }
+
+    /// Whether the decimal symbol, time or date format changed
+    pub fn format_changed(&self) -> bool {
+        self.previous.as_ref().is_none_or(|previous| {
+            previous.decimal_symbol != self.current.decimal_symbol
+                || previous.time_format != self.current.time_format
+                || previous.date_format != self.current.date_format
+        })
+    }
+
+    pub fn units_changed(&self) -> bool {
+        self.previous
+            .as_ref()
+            .is_none_or(|previous| previous.to_bytes()[4..6] != self.current.to_bytes()[4..6])
+    }
+}
+
+/// Follows the Language Command broadcast by a VT or TC
+#[derive(Debug, Clone, Default)]
+pub struct LanguageCommandInterface {
+    source: Option<Address>,
+    command: Option<LanguageCommand>,
+}
+
+impl LanguageCommandInterface {
+    /// Follows the Language Command of any node
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follows the Language Command of the node at `source`, like the VT the pool is shown on
+    pub fn with_source(source: Address) -> Self {
+        LanguageCommandInterface {
+            source: Some(source),
+            command: None,
+        }
+    }
+
+    pub fn source(&self) -> Option<Address> {
+        self.source
+    }
+
+    /// Follows the node at `source` from now on, forgetting the command received so far
+    pub fn set_source(&mut self, source: Option<Address>) {
+        if self.source != source {
+            self.source = source;
+            self.command = None;
+        }
+    }
+
+    /// The last Language Command received, `None` until one is received
+    pub fn command(&self) -> Option<&LanguageCommand> {
+        self.command.as_ref()
+    }
+
+    /// The last Language Command received, or the default if none was received yet
+    pub fn command_or_default(&self) -> LanguageCommand {
+        self.command.clone().unwrap_or_default()
+    }
+
+    /// Handles a received message, returns the change if it is a new Language Command
+    ///
+    /// Other messages, Language Commands from other nodes and invalid Language Commands are
+    /// ignored.
+    pub fn process_message(&mut self, message: &CANMessage) -> Option<LanguageChange> {
+        let source = message.get_identifier().source_address();
+        if self.source.is_some_and(|s| s != source) {
+            return None;
+        }
+        let current = LanguageCommand::from_message(message).ok()?;
+        if self.command.as_ref() == Some(&current) {
+            return None;
+        }
+        let previous = self.command.replace(current.clone());
+        Some(LanguageChange { previous, current })
+    }
+
+    /// The request for the Language Command of the followed node, sent from `source`
+    pub fn request(&self, source: Address) -> CANMessage {
+        LanguageCommand::request(source, self.source.unwrap_or(Address::GLOBAL))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GERMAN: [u8; 8] = [b'd', b'e', 0b0000_1111, 0, 0, 0, b'D', b'E'];
+    const AMERICAN: [u8; 8] = [b'e', b'n', 0b0101_1111, 3, 0x5A, 0x55, 0xFF, 0xFF];
+
+    fn message(data: &[u8], source: u8) -> CANMessage {
+        LanguageCommand::from_bytes(data)
+            .unwrap()
+            .to_message(Address(source))
+    }
+
+    #[test]
+    fn decode_test() {
+        let command = LanguageCommand::from_bytes(&GERMAN).unwrap();
+        assert_eq!(command.language_code, "de");
+        assert_eq!(command.decimal_symbol, DecimalSymbol::Comma);
+        assert_eq!(command.time_format, TimeFormat::TwentyFourHour);
+        assert_eq!(command.date_format, DateFormat::DdMmYyyy);
+        assert_eq!(command.distance_units, UnitSystem::Metric);
+        assert_eq!(command.country_code.as_deref(), Some("DE"));
+        assert_eq!(command.to_bytes(), GERMAN);
+
+        let command = LanguageCommand::from_bytes(&AMERICAN).unwrap();
+        assert_eq!(command.decimal_symbol, DecimalSymbol::Point);
+        assert_eq!(command.time_format, TimeFormat::TwelveHour);
+        assert_eq!(command.date_format, DateFormat::MmDdYyyy);
+        assert_eq!(command.distance_units, UnitSystem::Imperial);
+        assert_eq!(command.area_units, UnitSystem::Imperial);
+        assert_eq!(command.volume_units, UnitSystem::Us);
+        assert_eq!(command.mass_units, UnitSystem::Us);
+        assert_eq!(command.generic_units, UnitSystem::Imperial);
+        assert_eq!(command.country_code, None);
+        assert_eq!(command.to_bytes(), AMERICAN);
+
+        assert_eq!(
+            LanguageCommand::from_bytes(&GERMAN[..7]),
+            Err(LanguageCommandError::UnexpectedLength(7))
+        );
+    }
+
+    #[test]
+    fn message_test() {
+        let message = message(&GERMAN, 0x26);
+        assert_eq!(message.get_identifier().raw() & 0x1FFF_FFFF, 0x18FE_0F26);
+        assert_eq!(message.get_data(), GERMAN);
+        assert_eq!(
+            LanguageCommand::from_message(&message),
+            LanguageCommand::from_bytes(&GERMAN)
+        );
+
+        let request = LanguageCommand::request(Address(0x80), Address(0x26));
+        assert_eq!(request.get_identifier().raw() & 0x1FFF_FFFF, 0x18EA_2680);
+        assert_eq!(request.get_data(), [0x0F, 0xFE, 0x00]);
+        assert_eq!(
+            LanguageCommand::from_message(&request),
+            Err(LanguageCommandError::UnexpectedPgn(Pgn::from_raw(0xEA00)))
+        );
+    }
+
+    #[test]
+    fn conversion_test() {
+        let metric = LanguageCommand::from_bytes(&GERMAN).unwrap();
+        let us = LanguageCommand::from_bytes(&AMERICAN).unwrap();
+        assert_eq!(metric.distance(10.0), DisplayValue::new(10.0, "m"));
+        assert!((us.distance(0.3048).value - 1.0).abs() < 1e-9);
+        assert!((metric.speed(10.0).value - 36.0).abs() < 1e-9);
+        assert!((us.speed(0.44704).value - 1.0).abs() < 1e-9);
+        assert_eq!(us.speed(1.0).unit, "mph");
+        assert!((metric.area(25_000.0).value - 2.5).abs() < 1e-9);
+        assert!((us.area(4_046.856_422_4).value - 1.0).abs() < 1e-9);
+        assert_eq!(metric.volume(5.0), DisplayValue::new(5.0, "L"));
+        assert!((us.volume(3.785_411_784).value - 1.0).abs() < 1e-9);
+        assert!((us.mass(0.453_592_37).value - 1.0).abs() < 1e-9);
+        assert_eq!(metric.temperature(20.0).unit, "°C");
+
+        let mut imperial = us.clone();
+        imperial.volume_units = UnitSystem::Imperial;
+        imperial.temperature_units = UnitSystem::Imperial;
+        assert!((imperial.volume(4.546_09).value - 1.0).abs() < 1e-9);
+        assert!((imperial.temperature(100.0).value - 212.0).abs() < 1e-9);
+
+        assert_eq!(metric.format_decimal(1.5, 2), "1,50");
+        assert_eq!(us.format_decimal(1.5, 2), "1.50");
+    }
+
+    #[test]
+    fn interface_test() {
+        let mut interface = LanguageCommandInterface::with_source(Address(0x26));
+        assert_eq!(interface.command(), None);
+        assert_eq!(interface.command_or_default().language_code, "en");
+
+        // Other nodes are ignored
+        assert_eq!(interface.process_message(&message(&GERMAN, 0x27)), None);
+
+        let change = interface.process_message(&message(&GERMAN, 0x26)).unwrap();
+        assert_eq!(change.previous, None);
+        assert!(change.language_changed() && change.units_changed());
+        assert_eq!(interface.command().unwrap().language_code, "de");
+
+        // Repeated commands are not a change
+        assert_eq!(interface.process_message(&message(&GERMAN, 0x26)), None);
+
+        let mut swiss = GERMAN;
+        swiss[6..].copy_from_slice(b"CH");
+        let change = interface.process_message(&message(&swiss, 0x26)).unwrap();
+        assert!(change.language_changed());
+        assert!(!change.units_changed() && !change.format_changed());
+
+        let change = interface
+            .process_message(&message(&AMERICAN, 0x26))
+            .unwrap();
+        assert!(change.units_changed() && change.format_changed());
+
+        assert_eq!(
+            interface.request(Address(0x80)).get_identifier().raw() & 0x1FFF_FFFF,
+            0x18EA_2680
+        );
+        interface.set_source(None);
+        assert_eq!(interface.command(), None);
+        assert!(interface.process_message(&message(&GERMAN, 0x27)).is_some());
+    }
+}
@@ -2,5 +2,6 @@
 pub mod can_message;
 pub mod common_parameter_group_numbers;
 pub mod control_function;
+pub mod language_command;
 pub mod name;
 pub mod network_manager;
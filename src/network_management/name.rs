@@ -1,6 +1,6 @@
 // Copyright 2023 Raven Industries inc.
 
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum NameField {
     IdentityNumber(u32),
     ShortIdentityNumber(u16),
@@ -204,6 +204,38 @@ impl From<NAME> for [u8; 8] {
     }
 }
 
+/// Human readable formats get the NAME as a hex string like `"0xA00082000C2FE5A8"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for NAME {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("{:#018X}", self.raw_name))
+        } else {
+            serializer.serialize_u64(self.raw_name)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NAME {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        if !deserializer.is_human_readable() {
+            return u64::deserialize(deserializer).map(NAME::new);
+        }
+        let value = String::deserialize(deserializer)?;
+        value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+            .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+            .map(NAME::new)
+            .ok_or_else(|| {
+                D::Error::invalid_value(serde::de::Unexpected::Str(&value), &"a hex NAME")
+            })
+    }
+}
+
 #[derive(Default)]
 pub struct NameBuilder {
     self_configurable_address: bool,
@@ -337,7 +369,7 @@ mod tests {
             .self_configurable_address(true)
             .build();
 
-        assert_eq!(10881826125818888196_u64, name_under_test.into());
+        assert_eq!(10881826125818888196_u64, u64::from(name_under_test));
     }
 
     #[test]
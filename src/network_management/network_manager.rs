@@ -158,7 +158,7 @@ impl NetworkManager {
                     self.get_control_function_address_by_name(destination.get_name()),
                     priority,
                 )
-                .unwrap_or(CanId::default());
+                .unwrap_or_default();
 
                 if message_id.raw() != CanId::default().raw() {
                     self.enqueue_can_message(
@@ -172,6 +172,35 @@ impl NetworkManager {
         CANTransmitState::Fail
     }
 
+    /// Sends a message to all nodes, like the Language Command or other broadcast PGNs
+    pub fn send_broadcast_can_message(
+        &mut self,
+        parameter_group_number: Pgn,
+        data: &[u8],
+        source: Rc<RefCell<ControlFunction>>,
+        priority: Priority,
+    ) -> CANTransmitState {
+        if !data.is_empty() && data.len() <= 8 {
+            let source_address =
+                self.get_control_function_address_by_name(source.borrow().get_name());
+            if source_address != Address::NULL {
+                if let Ok(message_id) = CanId::try_encode(
+                    parameter_group_number,
+                    source_address,
+                    Address::GLOBAL,
+                    priority,
+                ) {
+                    self.enqueue_can_message(
+                        CANMessage::new(data.to_vec(), message_id),
+                        MessageQueuePriority::Normal,
+                    );
+                    return CANTransmitState::Success;
+                }
+            }
+        }
+        CANTransmitState::Fail
+    }
+
     fn update_address_claiming(&mut self) {
         let mut state_machines = std::mem::take(&mut self.address_claim_state_machines);
         for address_claimer in &mut state_machines {
@@ -357,7 +386,7 @@ mod tests {
 
         assert_eq!(
             <NAME as Into<u64>>::into(new_cf.borrow().get_name()),
-            test_name.into()
+            u64::from(test_name)
         );
     }
 }
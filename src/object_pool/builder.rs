@@ -0,0 +1,740 @@
+// Copyright 2023 Raven Industries inc.
+use crate::network_management::name::NAME;
+use crate::object_pool::colour::Colour;
+use crate::object_pool::object::*;
+use crate::object_pool::object_attributes::*;
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::{ObjectPool, ObjectType, ValidationError};
+use std::collections::BTreeSet;
+
+/// IDs below this are not allocated automatically, Macro objects need IDs below 256 to be
+/// referenced by other objects
+pub(super) const FIRST_ALLOCATED_ID: u16 = 256;
+
+/// An object under construction that gets its ID when it is built
+///
+/// Every object type has a builder, created with `<Object>::builder`, like
+/// [`OutputString::builder`]. Fields that have no sensible default, like the font of an output
+/// string, are arguments of `builder`, all other fields start with a default value and have a
+/// setter named like the field.
+pub trait ObjectBuilder {
+    fn build(self, id: ObjectId) -> Object;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolBuilderError {
+    /// The parent passed to `add_child` is not in the pool
+    UnknownParent(ObjectId),
+    /// The parent passed to `add_child` is of a type that has no children with a position
+    NotAParent(ObjectId, ObjectType),
+    /// All object IDs are in use
+    NoFreeObjectId,
+    /// The finished pool does not pass [`ObjectPool::validate`]
+    Invalid(Vec<ValidationError>),
+}
+
+impl std::fmt::Display for PoolBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for PoolBuilderError {}
+
+/// Builds an object pool, allocating object IDs and checking the result
+///
+/// ```
+/// # use ag_iso_stack::object_pool::*;
+/// let mut builder = ObjectPoolBuilder::new();
+/// let font = builder.add(FontAttributes::builder().font_size(FontSize::Size8x12));
+/// let mask = builder.add(DataMask::builder());
+/// let title = builder.add(OutputString::builder(font).value("Hello".to_string()));
+/// builder.add_child(mask, title, 10, 10).unwrap();
+///
+/// // The Working Set is added automatically, with the first data mask as active mask
+/// let pool = builder.build().unwrap();
+/// assert_eq!(pool.working_set_object().unwrap().active_mask, mask);
+/// ```
+#[derive(Debug, Default)]
+pub struct ObjectPoolBuilder {
+    objects: Vec<Object>,
+    used_ids: BTreeSet<ObjectId>,
+}
+
+impl ObjectPoolBuilder {
+    pub fn new() -> Self {
+        ObjectPoolBuilder::default()
+    }
+
+    /// Adds an object with the lowest free object ID from 256 up, returns the ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if all object IDs are in use.
+    pub fn add(&mut self, object: impl ObjectBuilder) -> ObjectId {
+        let id = self
+            .free_id()
+            .expect("an object pool can't have more than 65535 objects");
+        self.add_with_id(id, object)
+    }
+
+    /// Adds an object with a chosen object ID
+    pub fn add_with_id(&mut self, id: ObjectId, object: impl ObjectBuilder) -> ObjectId {
+        self.add_object(object.build(id))
+    }
+
+    /// Adds a finished object, like a [`Macro`] from a [`MacroBuilder`](super::MacroBuilder)
+    pub fn add_object(&mut self, object: Object) -> ObjectId {
+        let id = object.id();
+        self.used_ids.insert(id);
+        self.objects.push(object);
+        id
+    }
+
+    /// Places `child` in `parent` with an offset of `x`, `y` from the top left corner of `parent`
+    pub fn add_child(
+        &mut self,
+        parent: ObjectId,
+        child: ObjectId,
+        x: i16,
+        y: i16,
+    ) -> Result<(), PoolBuilderError> {
+        let parent = self
+            .objects
+            .iter_mut()
+            .find(|o| o.id() == parent)
+            .ok_or(PoolBuilderError::UnknownParent(parent))?;
+        let error = PoolBuilderError::NotAParent(parent.id(), parent.object_type());
+        let object_refs = object_refs_mut(parent).ok_or(error)?;
+        object_refs.push(ObjectRef {
+            id: child,
+            offset: Point { x, y },
+        });
+        Ok(())
+    }
+
+    /// Finishes the pool
+    ///
+    /// If no Working Set was added, one is added with the first Data Mask or Alarm Mask as its
+    /// active mask. Returns the validation errors if the pool does not pass
+    /// [`ObjectPool::validate`].
+    pub fn build(mut self) -> Result<ObjectPool, PoolBuilderError> {
+        let has_working_set = self
+            .objects
+            .iter()
+            .any(|o| o.object_type() == ObjectType::WorkingSet);
+        let first_mask = self
+            .objects
+            .iter()
+            .find(|o| matches!(o, Object::DataMask(_) | Object::AlarmMask(_)))
+            .map(Object::id);
+        if let (false, Some(mask)) = (has_working_set, first_mask) {
+            let id = self.free_id().ok_or(PoolBuilderError::NoFreeObjectId)?;
+            self.objects.insert(0, WorkingSet::builder(mask).build(id));
+        }
+
+        let mut pool = ObjectPool::new();
+        for object in self.objects {
+            pool.add(object);
+        }
+        pool.validate().map_err(PoolBuilderError::Invalid)?;
+        Ok(pool)
+    }
+
+    fn free_id(&self) -> Option<ObjectId> {
+        (FIRST_ALLOCATED_ID..u16::MAX)
+            .filter_map(|id| ObjectId::new(id).ok())
+            .find(|id| !self.used_ids.contains(id))
+    }
+}
+
+/// The list of children with a position, for objects that have one
+fn object_refs_mut(object: &mut Object) -> Option<&mut Vec<ObjectRef>> {
+    match object {
+        Object::WorkingSet(o) => Some(&mut o.object_refs),
+        Object::DataMask(o) => Some(&mut o.object_refs),
+        Object::AlarmMask(o) => Some(&mut o.object_refs),
+        Object::Container(o) => Some(&mut o.object_refs),
+        Object::Key(o) => Some(&mut o.object_refs),
+        Object::Button(o) => Some(&mut o.object_refs),
+        Object::AuxiliaryFunctionType1(o) => Some(&mut o.object_refs),
+        Object::AuxiliaryInputType1(o) => Some(&mut o.object_refs),
+        Object::AuxiliaryFunctionType2(o) => Some(&mut o.object_refs),
+        Object::AuxiliaryInputType2(o) => Some(&mut o.object_refs),
+        Object::WindowMask(o) => Some(&mut o.object_refs),
+        Object::Animation(o) => Some(&mut o.object_refs),
+        _ => None,
+    }
+}
+
+/// Defines a builder for each object type
+///
+/// `Object => Builder(required: Type) { field: Type = default } refs [field] extra [method];`,
+/// `refs` are nullable references that default to NULL and accept an [`ObjectId`] in their
+/// setter, `extra` adds `child` and `macro_ref` methods to objects that have those lists.
+macro_rules! object_builders {
+    ($(
+        $object:ident => $builder:ident ($($arg:ident: $arg_ty:ty),*) {
+            $($field:ident: $ty:ty = $default:expr),* $(,)?
+        }
+        $(refs [$($reference:ident),* $(,)?])?
+        $(extra [$($extra:ident),*])?;
+    )*) => {$(
+        #[doc = concat!("Builds a [`", stringify!($object), "`] object, see [`ObjectBuilder`]")]
+        #[derive(Debug, Clone)]
+        pub struct $builder {
+            object: $object,
+        }
+
+        impl $object {
+            pub fn builder($($arg: $arg_ty),*) -> $builder {
+                $builder {
+                    object: $object {
+                        id: ObjectId::default(),
+                        $($arg,)*
+                        $($field: $default,)*
+                        $($($reference: NullableObjectId::NULL,)*)?
+                    },
+                }
+            }
+        }
+
+        impl $builder {
+            $(pub fn $field(mut self, $field: $ty) -> Self {
+                self.object.$field = $field;
+                self
+            })*
+            $($(pub fn $reference(mut self, $reference: impl Into<NullableObjectId>) -> Self {
+                self.object.$reference = $reference.into();
+                self
+            })*)?
+            $($(object_builders!(@$extra);)*)?
+        }
+
+        impl ObjectBuilder for $builder {
+            fn build(mut self, id: ObjectId) -> Object {
+                self.object.id = id;
+                Object::$object(self.object)
+            }
+        }
+    )*};
+    (@children) => {
+        /// Adds a child at an offset from the top left corner of the object
+        pub fn child(mut self, id: ObjectId, x: i16, y: i16) -> Self {
+            self.object.object_refs.push(ObjectRef {
+                id,
+                offset: Point { x, y },
+            });
+            self
+        }
+    };
+    (@macros) => {
+        /// Runs the macro with ID `macro_id` on the event `event_id`
+        pub fn macro_ref(mut self, event_id: u8, macro_id: u8) -> Self {
+            self.object.macro_refs.push(MacroRef { macro_id, event_id });
+            self
+        }
+    };
+}
+
+object_builders! {
+    WorkingSet => WorkingSetBuilder(active_mask: ObjectId) {
+        background_colour: Colour = Colour::WHITE,
+        selectable: bool = true,
+        object_refs: Vec<ObjectRef> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+        language_codes: Vec<String> = vec![],
+    } extra [children, macros];
+
+    DataMask => DataMaskBuilder() {
+        background_colour: u8 = 1,
+        object_refs: Vec<ObjectRef> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [soft_key_mask] extra [children, macros];
+
+    AlarmMask => AlarmMaskBuilder() {
+        background_colour: u8 = 1,
+        priority: u8 = 2,
+        acoustic_signal: u8 = 3,
+        object_refs: Vec<ObjectRef> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [soft_key_mask] extra [children, macros];
+
+    Container => ContainerBuilder() {
+        width: u16 = 100,
+        height: u16 = 100,
+        hidden: bool = false,
+        object_refs: Vec<ObjectRef> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [children, macros];
+
+    SoftKeyMask => SoftKeyMaskBuilder() {
+        background_colour: u8 = 1,
+        objects: Vec<ObjectId> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [macros];
+
+    Key => KeyBuilder() {
+        background_colour: u8 = 1,
+        key_code: u8 = 0,
+        object_refs: Vec<ObjectRef> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [children, macros];
+
+    Button => ButtonBuilder() {
+        width: u16 = 80,
+        height: u16 = 30,
+        background_colour: u8 = 1,
+        border_colour: u8 = 0,
+        key_code: u8 = 0,
+        options: ButtonOptions = 0.into(),
+        object_refs: Vec<ObjectRef> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [children, macros];
+
+    InputBoolean => InputBooleanBuilder(foreground_colour: ObjectId) {
+        background_colour: u8 = 1,
+        width: u16 = 20,
+        value: bool = false,
+        enabled: bool = true,
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference] extra [macros];
+
+    InputString => InputStringBuilder(font_attributes: ObjectId) {
+        width: u16 = 100,
+        height: u16 = 20,
+        background_colour: u8 = 1,
+        options: InputStringOptions = 0.into(),
+        justification: Alignment = 0.into(),
+        value: String = String::new(),
+        enabled: bool = true,
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [input_attributes, variable_reference] extra [macros];
+
+    InputNumber => InputNumberBuilder(font_attributes: ObjectId) {
+        width: u16 = 50,
+        height: u16 = 20,
+        background_colour: u8 = 1,
+        options: NumberOptions = 0.into(),
+        value: u32 = 0,
+        min_value: u32 = 0,
+        max_value: u32 = u32::MAX,
+        offset: i32 = 0,
+        scale: f32 = 1.0,
+        nr_of_decimals: u8 = 0,
+        format: FormatType = false.into(),
+        justification: Alignment = 0.into(),
+        options2: InputNumberOptions = 1.into(),
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference] extra [macros];
+
+    InputList => InputListBuilder() {
+        width: u16 = 100,
+        height: u16 = 20,
+        value: u8 = 0,
+        options: InputListOptions = 1.into(),
+        list_items: Vec<NullableObjectId> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference] extra [macros];
+
+    OutputString => OutputStringBuilder(font_attributes: ObjectId) {
+        width: u16 = 100,
+        height: u16 = 20,
+        background_colour: u8 = 1,
+        options: OutputStringOptions = 0.into(),
+        justification: Alignment = 0.into(),
+        value: String = String::new(),
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference] extra [macros];
+
+    OutputNumber => OutputNumberBuilder(font_attributes: ObjectId) {
+        width: u16 = 50,
+        height: u16 = 20,
+        background_colour: u8 = 1,
+        options: NumberOptions = 0.into(),
+        value: u32 = 0,
+        offset: i32 = 0,
+        scale: f32 = 1.0,
+        nr_of_decimals: u8 = 0,
+        format: FormatType = false.into(),
+        justification: Alignment = 0.into(),
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference] extra [macros];
+
+    OutputList => OutputListBuilder() {
+        width: u16 = 100,
+        height: u16 = 20,
+        value: u8 = 0,
+        list_items: Vec<NullableObjectId> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference] extra [macros];
+
+    OutputLine => OutputLineBuilder(line_attributes: ObjectId) {
+        width: u16 = 100,
+        height: u16 = 1,
+        line_direction: LineDirection = LineDirection::TopLeftToBottomRight,
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [macros];
+
+    OutputRectangle => OutputRectangleBuilder(line_attributes: ObjectId) {
+        width: u16 = 100,
+        height: u16 = 100,
+        line_suppression: u8 = 0,
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [fill_attributes] extra [macros];
+
+    OutputEllipse => OutputEllipseBuilder(line_attributes: ObjectId) {
+        width: u16 = 100,
+        height: u16 = 100,
+        ellipse_type: u8 = 0,
+        start_angle: u8 = 0,
+        end_angle: u8 = 0,
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [fill_attributes] extra [macros];
+
+    OutputPolygon => OutputPolygonBuilder(line_attributes: ObjectId) {
+        width: u16 = 100,
+        height: u16 = 100,
+        polygon_type: u8 = 0,
+        points: Vec<Point<u16>> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [fill_attributes] extra [macros];
+
+    OutputMeter => OutputMeterBuilder() {
+        width: u16 = 100,
+        needle_colour: u8 = 0,
+        border_colour: u8 = 0,
+        arc_and_tick_colour: u8 = 0,
+        options: OutputMeterOptions = 0x07.into(),
+        nr_of_ticks: u8 = 5,
+        start_angle: u8 = 180,
+        end_angle: u8 = 0,
+        min_value: u16 = 0,
+        max_value: u16 = 100,
+        value: u16 = 0,
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference] extra [macros];
+
+    OutputLinearBarGraph => OutputLinearBarGraphBuilder() {
+        width: u16 = 20,
+        height: u16 = 100,
+        colour: u8 = 0,
+        target_line_colour: u8 = 0,
+        options: OutputLinearBarGraphOptions = 0x01.into(),
+        nr_of_ticks: u8 = 0,
+        min_value: u16 = 0,
+        max_value: u16 = 100,
+        value: u16 = 0,
+        target_value: u16 = 0,
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference, target_value_variable_reference] extra [macros];
+
+    OutputArchedBarGraph => OutputArchedBarGraphBuilder() {
+        width: u16 = 100,
+        height: u16 = 100,
+        colour: u8 = 0,
+        target_line_colour: u8 = 0,
+        options: OutputArchedBarGraphOptions = 0x01.into(),
+        start_angle: u8 = 180,
+        end_angle: u8 = 0,
+        bar_graph_width: u16 = 10,
+        min_value: u16 = 0,
+        max_value: u16 = 100,
+        value: u16 = 0,
+        target_value: u16 = 0,
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [variable_reference, target_value_variable_reference] extra [macros];
+
+    PictureGraphic => PictureGraphicBuilder() {
+        width: u16 = 0,
+        actual_width: u16 = 0,
+        actual_height: u16 = 0,
+        format: u8 = 2,
+        options: PictureGraphicOptions = 0.into(),
+        transparency_colour: u8 = 0,
+        data: Vec<u8> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [macros];
+
+    NumberVariable => NumberVariableBuilder() {
+        value: u32 = 0,
+    };
+
+    StringVariable => StringVariableBuilder() {
+        value: String = String::new(),
+    };
+
+    FontAttributes => FontAttributesBuilder() {
+        font_colour: u8 = 0,
+        font_size: FontSize = FontSize::Size6x8,
+        font_type: FontType = FontType::Iso8859_1,
+        font_style: FontStyle = FontStyle::default(),
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [macros];
+
+    LineAttributes => LineAttributesBuilder() {
+        line_colour: u8 = 0,
+        line_width: u8 = 1,
+        line_art: LineArt = LineArt::SOLID,
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [macros];
+
+    FillAttributes => FillAttributesBuilder() {
+        fill_type: FillType = FillType::FillColour,
+        fill_colour: u8 = 1,
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [fill_pattern] extra [macros];
+
+    InputAttributes => InputAttributesBuilder() {
+        validation_type: ValidationType = ValidationType::ValidCharacters,
+        validation_string: String = String::new(),
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [macros];
+
+    ObjectPointer => ObjectPointerBuilder() {} refs [value];
+
+    AuxiliaryFunctionType1 => AuxiliaryFunctionType1Builder() {
+        background_colour: u8 = 1,
+        function_type: u8 = 0,
+        object_refs: Vec<ObjectRef> = vec![],
+    } extra [children];
+
+    AuxiliaryInputType1 => AuxiliaryInputType1Builder() {
+        background_colour: u8 = 1,
+        function_type: u8 = 0,
+        input_id: u8 = 0,
+        object_refs: Vec<ObjectRef> = vec![],
+    } extra [children];
+
+    AuxiliaryFunctionType2 => AuxiliaryFunctionType2Builder() {
+        background_colour: u8 = 1,
+        function_attributes: u8 = 0,
+        object_refs: Vec<ObjectRef> = vec![],
+    } extra [children];
+
+    AuxiliaryInputType2 => AuxiliaryInputType2Builder() {
+        background_colour: u8 = 1,
+        function_attributes: u8 = 0,
+        object_refs: Vec<ObjectRef> = vec![],
+    } extra [children];
+
+    AuxiliaryControlDesignatorType2 => AuxiliaryControlDesignatorType2Builder() {
+        pointer_type: u8 = 0,
+    } refs [auxiliary_object_id];
+
+    WindowMask => WindowMaskBuilder() {
+        cell_format: WindowMaskCellFormat = 0x0101.into(),
+        window_type: WindowType = WindowType::FreeForm,
+        background_colour: u8 = 1,
+        options: WindowMaskOptions = 0x01.into(),
+        objects: Vec<NullableObjectId> = vec![],
+        object_refs: Vec<ObjectRef> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [name, window_title, window_icon] extra [children, macros];
+
+    KeyGroup => KeyGroupBuilder(name: ObjectId) {
+        options: KeyGroupOptions = 0x01.into(),
+        objects: Vec<ObjectId> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [key_group_icon] extra [macros];
+
+    GraphicsContext => GraphicsContextBuilder() {
+        viewport_width: u16 = 100,
+        viewport_height: u16 = 100,
+        viewport_x: i16 = 0,
+        viewport_y: i16 = 0,
+        canvas_width: u16 = 100,
+        canvas_height: u16 = 100,
+        viewport_zoom: f32 = 1.0,
+        graphics_cursor_x: i16 = 0,
+        graphics_cursor_y: i16 = 0,
+        foreground_colour: u8 = 0,
+        background_colour: u8 = 1,
+        format: ColorFormat = ColorFormat::Color8Bit,
+        options: GraphicsContextOptions = 0.into(),
+        transparency_colour: u8 = 0,
+    } refs [font_attributes_object, line_attributes_object, fill_attributes_object];
+    ColourMap => ColourMapBuilder() {
+        colour_map: Vec<u8> = (0..=255).collect(),
+    };
+
+    ObjectLabelReferenceList => ObjectLabelReferenceListBuilder() {
+        object_labels: Vec<ObjectLabel> = vec![],
+    };
+
+    ExternalObjectDefinition => ExternalObjectDefinitionBuilder(name: NAME) {
+        options: ExternalObjectDefinitionOptions = 0x01.into(),
+        objects: Vec<ObjectId> = vec![],
+    };
+
+    ExternalReferenceName => ExternalReferenceNameBuilder(name: NAME) {
+        options: ExternalReferenceNameOptions = 0x01.into(),
+    };
+
+    ExternalObjectPointer => ExternalObjectPointerBuilder() {}
+    refs [default_object_id, external_reference_name_id, external_object_id];
+
+    Animation => AnimationBuilder() {
+        width: u16 = 100,
+        height: u16 = 100,
+        refresh_interval: u16 = 100,
+        value: u8 = 0,
+        enabled: bool = true,
+        first_child_index: u8 = 0,
+        last_child_index: u8 = 0,
+        default_child_index: u8 = 0,
+        options: AnimationOptions = 0.into(),
+        object_refs: Vec<ObjectRef> = vec![],
+        macro_refs: Vec<MacroRef> = vec![],
+    } extra [children, macros];
+
+    ColourPalette => ColourPaletteBuilder() {
+        options: ColourPaletteOptions = 0.into(),
+        colours: Vec<Colour> = Colour::COLOUR_PALETTE.to_vec(),
+    };
+
+    GraphicData => GraphicDataBuilder() {
+        format: u8 = 0,
+        data: Vec<u8> = vec![],
+    };
+
+    WorkingSetSpecialControls => WorkingSetSpecialControlsBuilder() {
+        language_pairs: Vec<(String, String)> = vec![],
+    } refs [id_of_colour_map, id_of_colour_palette];
+
+    ScaledGraphic => ScaledGraphicBuilder() {
+        width: u16 = 100,
+        height: u16 = 100,
+        scale_type: u8 = 0,
+        options: ScaledGraphicOptions = 0.into(),
+        macro_refs: Vec<MacroRef> = vec![],
+    } refs [value] extra [macros];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::Field;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    #[test]
+    fn object_builder_defaults_test() {
+        let object = OutputString::builder(id(300))
+            .width(120)
+            .value("Speed".to_string())
+            .variable_reference(id(301))
+            .macro_ref(1, 2)
+            .build(id(400));
+
+        let Object::OutputString(output_string) = object else {
+            panic!("expected an output string");
+        };
+        assert_eq!(output_string.id, id(400));
+        assert_eq!(output_string.font_attributes, id(300));
+        assert_eq!(output_string.width, 120);
+        assert_eq!(output_string.height, 20);
+        assert_eq!(output_string.value, "Speed");
+        assert_eq!(output_string.variable_reference, id(301).into());
+        assert_eq!(
+            output_string.macro_refs,
+            vec![MacroRef {
+                macro_id: 2,
+                event_id: 1
+            }]
+        );
+
+        let Object::ColourMap(colour_map) = ColourMap::builder().build(id(1)) else {
+            panic!("expected a colour map");
+        };
+        assert_eq!(colour_map.colour_map, (0..=255).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn pool_builder_test() {
+        let mut builder = ObjectPoolBuilder::new();
+        let font = builder.add(FontAttributes::builder());
+        let working_set = builder.add_with_id(id(0), WorkingSet::builder(id(1000)));
+        let mask = builder.add_with_id(id(1000), DataMask::builder());
+        let container = builder.add(Container::builder().width(200));
+        let text = builder.add(OutputString::builder(font));
+        builder.add_child(mask, container, 0, 10).unwrap();
+        builder.add_child(container, text, -5, 5).unwrap();
+        builder.add_child(working_set, text, 0, 0).unwrap();
+
+        // IDs are allocated from 256 up, skipping the ones in use
+        assert_eq!(font, id(256));
+        assert_eq!(container, id(257));
+        assert_eq!(text, id(258));
+        assert_eq!(
+            builder.add_child(text, font, 0, 0),
+            Err(PoolBuilderError::NotAParent(text, ObjectType::OutputString))
+        );
+        assert_eq!(
+            builder.add_child(id(2000), font, 0, 0),
+            Err(PoolBuilderError::UnknownParent(id(2000)))
+        );
+
+        let pool = builder.build().unwrap();
+        assert_eq!(pool.objects().len(), 5);
+        let container = pool.container_object_by_id(container).unwrap();
+        assert_eq!(
+            container.object_refs,
+            vec![ObjectRef {
+                id: text,
+                offset: Point { x: -5, y: 5 }
+            }]
+        );
+        assert_eq!(
+            ObjectPool::from_iop(pool.as_iop().unwrap()).objects(),
+            pool.objects()
+        );
+    }
+
+    #[test]
+    fn pool_builder_working_set_test() {
+        let mut builder = ObjectPoolBuilder::new();
+        builder.add(FontAttributes::builder());
+        let mask = builder.add(AlarmMask::builder());
+        let pool = builder.build().unwrap();
+        let working_set = pool.working_set_object().unwrap();
+        assert_eq!(working_set.active_mask, mask);
+        assert_eq!(working_set.id, id(258));
+
+        // Without a mask there is no active mask for the Working Set
+        let mut builder = ObjectPoolBuilder::new();
+        builder.add(FontAttributes::builder());
+        assert_eq!(
+            builder.build().err(),
+            Some(PoolBuilderError::Invalid(vec![
+                ValidationError::MissingWorkingSet
+            ]))
+        );
+    }
+
+    #[test]
+    fn pool_builder_validation_test() {
+        let mut builder = ObjectPoolBuilder::new();
+        let mask = builder.add(DataMask::builder());
+        let text = builder.add(OutputString::builder(id(5000)));
+        builder.add_child(mask, text, 0, 0).unwrap();
+        builder.add_object(Object::Macro(
+            Macro::builder(id(1)).hide_object(text).build(),
+        ));
+
+        assert_eq!(
+            builder.build().err(),
+            Some(PoolBuilderError::Invalid(vec![
+                ValidationError::MissingObject {
+                    object: text,
+                    field: Field {
+                        name: "font_attributes",
+                        index: None
+                    },
+                    reference: id(5000),
+                }
+            ]))
+        );
+    }
+}
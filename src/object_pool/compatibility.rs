@@ -0,0 +1,482 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::object::{Object, ObjectPointer};
+use crate::object_pool::object_attributes::FontType;
+use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::validation::{map_references, references};
+use crate::object_pool::{ObjectPool, ObjectType, ValidationError, VtVersion};
+use std::collections::HashSet;
+
+/// An object, or an attribute of an object, that needs a minimum VT version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionRequirement {
+    pub object: ObjectId,
+    pub object_type: ObjectType,
+    /// The attribute that needs the version, `None` if the object type itself needs it
+    pub attribute: Option<&'static str>,
+    pub version: VtVersion,
+}
+
+impl ObjectType {
+    /// The VT version that introduced this object type
+    ///
+    /// Object types that exist since the first version of ISO 11783-6 return
+    /// [`VtVersion::Version0`].
+    pub fn minimum_vt_version(&self) -> VtVersion {
+        match self {
+            ObjectType::AuxiliaryFunctionType2
+            | ObjectType::AuxiliaryInputType2
+            | ObjectType::AuxiliaryControlDesignatorType2 => VtVersion::Version3,
+            ObjectType::WindowMask
+            | ObjectType::KeyGroup
+            | ObjectType::GraphicsContext
+            | ObjectType::OutputList
+            | ObjectType::ExtendedInputAttributes
+            | ObjectType::ColourMap
+            | ObjectType::ObjectLabelReferenceList => VtVersion::Version4,
+            ObjectType::ExternalObjectDefinition
+            | ObjectType::ExternalReferenceName
+            | ObjectType::ExternalObjectPointer
+            | ObjectType::Animation => VtVersion::Version5,
+            ObjectType::ColourPalette
+            | ObjectType::GraphicData
+            | ObjectType::WorkingSetSpecialControls
+            | ObjectType::ScaledGraphic => VtVersion::Version6,
+            _ => VtVersion::Version0,
+        }
+    }
+}
+
+impl Object {
+    /// The lowest VT version that supports this object with all of its attributes
+    pub fn required_vt_version(&self) -> VtVersion {
+        attribute_requirements(self)
+            .into_iter()
+            .map(|(_, version)| version)
+            .fold(self.object_type().minimum_vt_version(), VtVersion::max)
+    }
+}
+
+/// Returns the attributes of `object` that are only supported by newer VTs than the object type
+fn attribute_requirements(object: &Object) -> Vec<(&'static str, VtVersion)> {
+    let mut requirements = Vec::new();
+    let mut require = |attribute, used: bool| {
+        if used {
+            requirements.push((attribute, VtVersion::Version4));
+        }
+    };
+    match object {
+        Object::Button(o) => {
+            let options = &o.options;
+            require("options.suppress_border", options.suppress_border);
+            require(
+                "options.transparent_background",
+                options.transparent_background,
+            );
+            require("options.disabled", options.disabled);
+            require("options.no_border", options.no_border);
+        }
+        Object::InputString(o) => require("options.wrap_on_hyphen", o.options.wrap_on_hyphen),
+        Object::OutputString(o) => require("options.wrap_on_hyphen", o.options.wrap_on_hyphen),
+        Object::InputNumber(o) => {
+            require("options.truncate", o.options.truncate);
+            require("options2.real_time_editing", o.options2.real_time_editing);
+        }
+        Object::OutputNumber(o) => require("options.truncate", o.options.truncate),
+        Object::InputList(o) => require("options.real_time_editing", o.options.real_time_editing),
+        Object::FontAttributes(o) => {
+            require("font_size", o.font_size.is_proportional());
+            require("font_type", !is_vt3_font_type(o.font_type));
+            require("font_style.proportional", o.font_style.proportional);
+        }
+        _ => {}
+    }
+    requirements
+}
+
+/// Whether a font type is supported before VT version 4
+fn is_vt3_font_type(font_type: FontType) -> bool {
+    matches!(
+        font_type,
+        FontType::Iso8859_1 | FontType::Iso8859_15 | FontType::Proprietary(0xFF)
+    )
+}
+
+/// Clears the attributes of `object` that `vt_version` does not support
+fn downgrade_attributes(object: &mut Object, vt_version: VtVersion) {
+    if vt_version >= VtVersion::Version4 {
+        return;
+    }
+    match object {
+        Object::Button(o) => {
+            o.options.suppress_border = false;
+            o.options.transparent_background = false;
+            o.options.disabled = false;
+            o.options.no_border = false;
+        }
+        Object::InputString(o) => o.options.wrap_on_hyphen = false,
+        Object::OutputString(o) => o.options.wrap_on_hyphen = false,
+        Object::InputNumber(o) => {
+            o.options.truncate = false;
+            o.options2.real_time_editing = false;
+        }
+        Object::OutputNumber(o) => o.options.truncate = false,
+        Object::InputList(o) => o.options.real_time_editing = false,
+        Object::FontAttributes(o) => {
+            o.font_size = o.font_size.to_fixed();
+            if !is_vt3_font_type(o.font_type) {
+                o.font_type = FontType::Iso8859_1;
+            }
+            o.font_style.proportional = false;
+        }
+        _ => {}
+    }
+}
+
+/// Returns the object that takes the place of `object` on a VT that does not support its type
+///
+/// Objects that only show one of their children are replaced by an object pointer to that child.
+/// Everything else is dropped.
+fn substitute(object: &Object) -> Option<Object> {
+    let value = match object {
+        Object::OutputList(o) => o
+            .list_items
+            .get(o.value as usize)
+            .copied()
+            .unwrap_or_default(),
+        Object::Animation(o) => o
+            .object_refs
+            .get(o.value as usize)
+            .map(|r| r.id.into())
+            .unwrap_or_default(),
+        Object::ExternalObjectPointer(o) => o.default_object_id,
+        Object::ScaledGraphic(o) => o.value,
+        _ => return None,
+    };
+    Some(Object::ObjectPointer(ObjectPointer {
+        id: object.id(),
+        value,
+    }))
+}
+
+impl ObjectPool {
+    /// The lowest VT version that supports every object in the pool
+    pub fn required_vt_version(&self) -> VtVersion {
+        self.objects()
+            .iter()
+            .map(Object::required_vt_version)
+            .fold(VtVersion::Version0, VtVersion::max)
+    }
+
+    /// Lists every object and attribute of the pool that is not supported by all VT versions
+    pub fn vt_version_requirements(&self) -> Vec<VersionRequirement> {
+        let mut requirements = Vec::new();
+        for object in self.objects() {
+            let object_type = object.object_type();
+            if object_type.minimum_vt_version() > VtVersion::Version0 {
+                requirements.push(VersionRequirement {
+                    object: object.id(),
+                    object_type,
+                    attribute: None,
+                    version: object_type.minimum_vt_version(),
+                });
+            }
+            for (attribute, version) in attribute_requirements(object) {
+                requirements.push(VersionRequirement {
+                    object: object.id(),
+                    object_type,
+                    attribute: Some(attribute),
+                    version,
+                });
+            }
+        }
+        requirements
+    }
+
+    /// Creates a copy of the pool that can be loaded by a VT of version `vt_version`
+    ///
+    /// Options a VT of that version does not know are cleared and proportional fonts are replaced
+    /// by the closest fixed font size. Output lists, animations, scaled graphics and external
+    /// object pointers become object pointers to the object they currently show. Other object
+    /// types the VT does not know are dropped, together with all references to them and all
+    /// macro commands acting on them.
+    ///
+    /// Returns the converted pool and the requirements that were not met by `vt_version`. If an
+    /// object that is kept references a dropped object in an attribute that cannot be NULL, the
+    /// converted pool would not load on the VT and a [`ValidationError::MissingObject`] is
+    /// returned for every such reference instead.
+    pub fn convert_to_vt_version(
+        &self,
+        vt_version: VtVersion,
+    ) -> Result<(ObjectPool, Vec<VersionRequirement>), Vec<ValidationError>> {
+        let unmet: Vec<VersionRequirement> = self
+            .vt_version_requirements()
+            .into_iter()
+            .filter(|r| r.version > vt_version)
+            .collect();
+
+        let mut objects = Vec::new();
+        let mut removed = HashSet::new();
+        for object in self.objects() {
+            let converted = if object.object_type().minimum_vt_version() > vt_version {
+                substitute(object)
+            } else {
+                Some(object.clone())
+            };
+            match converted {
+                Some(mut converted) => {
+                    downgrade_attributes(&mut converted, vt_version);
+                    objects.push(converted);
+                }
+                None => {
+                    removed.insert(object.id());
+                }
+            }
+        }
+
+        let mut pool = ObjectPool::new();
+        pool.set_supported_vt_version(vt_version);
+        let mut errors = Vec::new();
+        for mut object in objects {
+            map_references(&mut object, |id| (!removed.contains(&id)).then_some(id));
+            for reference in references(&object) {
+                if removed.contains(&reference.id) {
+                    errors.push(ValidationError::MissingObject {
+                        object: object.id(),
+                        field: reference.field,
+                        reference: reference.id,
+                    });
+                }
+            }
+            pool.add(object);
+        }
+
+        if errors.is_empty() {
+            Ok((pool, unmet))
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::macro_command::MacroCommand;
+    use crate::object_pool::object::{
+        ColourMap, DataMask, FontAttributes, LineAttributes, Macro, OutputLine, OutputList,
+        WorkingSet,
+    };
+    use crate::object_pool::object_attributes::{
+        FontSize, FontStyle, LineArt, LineDirection, ObjectRef, Point,
+    };
+    use crate::object_pool::object_id::NullableObjectId;
+    use crate::object_pool::validation::Field;
+    use crate::object_pool::Colour;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    fn object_ref(object: u16) -> ObjectRef {
+        ObjectRef {
+            id: id(object),
+            offset: Point { x: 0, y: 0 },
+        }
+    }
+
+    /// A pool with an output list (2000) showing an output line (4000) and an object pointer
+    /// (3000) to a colour map (5000)
+    fn vt4_pool() -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        for object in [
+            Object::WorkingSet(WorkingSet {
+                id: id(0),
+                background_colour: Colour::WHITE,
+                selectable: true,
+                active_mask: id(1000),
+                object_refs: vec![],
+                macro_refs: vec![],
+                language_codes: vec![],
+            }),
+            Object::DataMask(DataMask {
+                id: id(1000),
+                background_colour: 1,
+                soft_key_mask: NullableObjectId::NULL,
+                object_refs: vec![object_ref(2000), object_ref(3000)],
+                macro_refs: vec![],
+            }),
+            Object::OutputList(OutputList {
+                id: id(2000),
+                width: 50,
+                height: 50,
+                variable_reference: NullableObjectId::NULL,
+                value: 1,
+                list_items: vec![NullableObjectId::NULL, id(4000).into(), id(5000).into()],
+                macro_refs: vec![],
+            }),
+            Object::ObjectPointer(ObjectPointer {
+                id: id(3000),
+                value: id(5000).into(),
+            }),
+            Object::OutputLine(OutputLine {
+                id: id(4000),
+                line_attributes: id(6000),
+                width: 10,
+                height: 10,
+                line_direction: LineDirection::TopLeftToBottomRight,
+                macro_refs: vec![],
+            }),
+            Object::ColourMap(ColourMap {
+                id: id(5000),
+                colour_map: vec![0, 1],
+            }),
+            Object::LineAttributes(LineAttributes {
+                id: id(6000),
+                line_colour: 0,
+                line_width: 1,
+                line_art: LineArt::SOLID,
+                macro_refs: vec![],
+            }),
+            Object::FontAttributes(FontAttributes {
+                id: id(7000),
+                font_colour: 0,
+                font_size: FontSize::Proportional(40),
+                font_type: FontType::Iso8859_2,
+                font_style: FontStyle::default(),
+                macro_refs: vec![],
+            }),
+        ] {
+            pool.add(object);
+        }
+        pool
+    }
+
+    #[test]
+    fn test_minimum_vt_version() {
+        assert_eq!(
+            ObjectType::DataMask.minimum_vt_version(),
+            VtVersion::Version0
+        );
+        assert_eq!(
+            ObjectType::AuxiliaryInputType2.minimum_vt_version(),
+            VtVersion::Version3
+        );
+        assert_eq!(
+            ObjectType::WindowMask.minimum_vt_version(),
+            VtVersion::Version4
+        );
+        assert_eq!(
+            ObjectType::Animation.minimum_vt_version(),
+            VtVersion::Version5
+        );
+        assert_eq!(
+            ObjectType::ScaledGraphic.minimum_vt_version(),
+            VtVersion::Version6
+        );
+        assert_eq!(ObjectPool::new().required_vt_version(), VtVersion::Version0);
+    }
+
+    #[test]
+    fn test_requirements() {
+        let pool = vt4_pool();
+        assert_eq!(pool.required_vt_version(), VtVersion::Version4);
+
+        let requirement = |object, object_type, attribute| VersionRequirement {
+            object: id(object),
+            object_type,
+            attribute,
+            version: VtVersion::Version4,
+        };
+        assert_eq!(
+            pool.vt_version_requirements(),
+            vec![
+                requirement(2000, ObjectType::OutputList, None),
+                requirement(5000, ObjectType::ColourMap, None),
+                requirement(7000, ObjectType::FontAttributes, Some("font_size")),
+                requirement(7000, ObjectType::FontAttributes, Some("font_type")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convert_to_vt_version() {
+        let pool = vt4_pool();
+
+        let (converted, unmet) = pool.convert_to_vt_version(VtVersion::Version4).unwrap();
+        assert!(unmet.is_empty());
+        assert_eq!(converted.objects(), pool.objects());
+        assert_eq!(converted.supported_vt_version(), VtVersion::Version4);
+
+        let (converted, unmet) = pool.convert_to_vt_version(VtVersion::Version3).unwrap();
+        assert_eq!(unmet.len(), 4);
+        assert_eq!(converted.supported_vt_version(), VtVersion::Version3);
+        assert_eq!(converted.required_vt_version(), VtVersion::Version0);
+        assert!(converted.object_by_id(id(5000)).is_none());
+        assert_eq!(
+            converted.object_by_id(id(2000)),
+            Some(&Object::ObjectPointer(ObjectPointer {
+                id: id(2000),
+                value: id(4000).into(),
+            }))
+        );
+        assert_eq!(
+            converted.object_by_id(id(3000)),
+            Some(&Object::ObjectPointer(ObjectPointer {
+                id: id(3000),
+                value: NullableObjectId::NULL,
+            }))
+        );
+        match converted.object_by_id(id(7000)) {
+            Some(Object::FontAttributes(o)) => {
+                // 32 pixels is the highest fixed font that fits in 40 pixels
+                assert_eq!(o.font_size, FontSize::Size32x32);
+                assert_eq!(o.font_type, FontType::Iso8859_1);
+            }
+            o => panic!("Unexpected object {:?}", o),
+        }
+        assert_eq!(converted.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_convert_dropped_references() {
+        let mut pool = vt4_pool();
+        pool.add(Object::Macro(Macro {
+            id: id(8000),
+            commands: vec![
+                MacroCommand::SelectColourMap { object: id(5000) },
+                MacroCommand::ChangeNumericValue {
+                    object: id(4000),
+                    value: 3,
+                },
+            ],
+        }));
+        let (converted, _) = pool.convert_to_vt_version(VtVersion::Version3).unwrap();
+        // The colour map is dropped, so is the command selecting it
+        assert_eq!(
+            converted.object_by_id(id(8000)),
+            Some(&Object::Macro(Macro {
+                id: id(8000),
+                commands: vec![MacroCommand::ChangeNumericValue {
+                    object: id(4000),
+                    value: 3,
+                }],
+            }))
+        );
+
+        // A reference that cannot be NULL to a dropped object can not be converted
+        if let Some(Object::OutputLine(o)) = pool.object_by_id_mut(id(4000)) {
+            o.line_attributes = id(5000);
+        }
+        assert_eq!(
+            pool.convert_to_vt_version(VtVersion::Version3).err(),
+            Some(vec![ValidationError::MissingObject {
+                object: id(4000),
+                field: Field {
+                    name: "line_attributes",
+                    index: None,
+                },
+                reference: id(5000),
+            }])
+        );
+    }
+}
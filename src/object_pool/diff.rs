@@ -0,0 +1,889 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::macro_command::MacroCommand;
+use crate::object_pool::object::{Object, ValidationType};
+use crate::object_pool::object_attributes::*;
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::traversal::object_refs;
+use crate::object_pool::ObjectPool;
+
+/// The differences between two versions of an object pool, see [`ObjectPool::diff`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PoolDiff {
+    /// Objects only in the new pool
+    pub added: Vec<Object>,
+    /// Objects only in the old pool
+    pub removed: Vec<Object>,
+    /// Objects in both pools that differ
+    pub modified: Vec<ObjectDiff>,
+}
+
+/// An object that differs between two versions of a pool
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectDiff {
+    pub old: Object,
+    pub new: Object,
+    /// The fields that differ, a single `type` field if the object type changed
+    pub fields: Vec<FieldDiff>,
+}
+
+/// A field that differs, with the [`Debug`] representation of the old and new value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// The name of the field, as used in the object structs
+    pub name: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// Changes that turn a pool the VT has into a newer version of it, see [`PoolDiff::patch`]
+#[derive(Debug)]
+pub struct PoolPatch {
+    /// Objects to upload in a partial object pool transfer, they replace the objects with the same
+    /// ID on the VT
+    ///
+    /// Its string values are encoded with the font types of the new pool.
+    pub upload: ObjectPool,
+    /// Commands to send after the upload, encode them with [`ObjectPool::write_command`] of the
+    /// new pool
+    pub commands: Vec<MacroCommand>,
+}
+
+impl ObjectPool {
+    /// Compares this pool with `new`, a newer version of it
+    ///
+    /// Objects are matched by ID. Added and modified objects are listed in the order of `new`,
+    /// removed objects in the order of this pool.
+    pub fn diff(&self, new: &ObjectPool) -> PoolDiff {
+        let mut diff = PoolDiff::default();
+        for object in new.objects() {
+            match self.object_by_id(object.id()) {
+                None => diff.added.push(object.clone()),
+                Some(old) => {
+                    let fields = diff_fields(old, object);
+                    if !fields.is_empty() {
+                        diff.modified.push(ObjectDiff {
+                            old: old.clone(),
+                            new: object.clone(),
+                            fields,
+                        });
+                    }
+                }
+            }
+        }
+        diff.removed = self
+            .objects()
+            .iter()
+            .filter(|object| new.object_by_id(object.id()).is_none())
+            .cloned()
+            .collect();
+        diff
+    }
+}
+
+impl PoolDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Turns the diff into the VT commands that apply the modifications, and a partial pool with
+    /// the objects that have to be uploaded
+    ///
+    /// Modified objects are changed with commands like Change Size or Change Attribute if every
+    /// changed field has a command, otherwise the whole object is uploaded. Added objects are
+    /// always uploaded. There is no command to delete an object, removed objects stay on the VT
+    /// but should not be referenced by any object anymore.
+    ///
+    /// `new` is the pool the diff was made with, the upload is made for its VT version and uses
+    /// its font types.
+    pub fn patch(&self, new: &ObjectPool) -> PoolPatch {
+        let mut upload = ObjectPool::new();
+        upload.set_supported_vt_version(new.supported_vt_version());
+        upload.external_font_types = new.string_font_types();
+        let mut commands = Vec::new();
+        for object in &self.added {
+            upload.add(object.clone());
+        }
+        for diff in &self.modified {
+            match diff.commands() {
+                Some(object_commands) => commands.extend(object_commands),
+                None => upload.add(diff.new.clone()),
+            }
+        }
+        PoolPatch { upload, commands }
+    }
+}
+
+impl ObjectDiff {
+    /// The commands that change the old object into the new one, `None` if a changed field can
+    /// only be changed by uploading the object
+    ///
+    /// String values that got longer are uploaded, Change String Value can't extend the length
+    /// of an object.
+    pub fn commands(&self) -> Option<Vec<MacroCommand>> {
+        let (old, new) = (&self.old, &self.new);
+        let id = new.id();
+        let changed = |name: &str| self.fields.iter().any(|f| f.name == name);
+        let mut commands = Vec::new();
+        let mut handled: Vec<&str> = Vec::new();
+
+        match new {
+            Object::OutputLine(o)
+                if changed("width") || changed("height") || changed("line_direction") =>
+            {
+                commands.push(MacroCommand::ChangeEndPoint {
+                    object: id,
+                    width: o.width,
+                    height: o.height,
+                    line_direction: o.line_direction,
+                });
+                handled.extend(["width", "height", "line_direction"]);
+            }
+            Object::FontAttributes(o)
+                if ["font_colour", "font_size", "font_type", "font_style"]
+                    .iter()
+                    .any(|f| changed(f)) =>
+            {
+                commands.push(MacroCommand::ChangeFontAttributes {
+                    object: id,
+                    colour: o.font_colour,
+                    size: o.font_size,
+                    font_type: o.font_type,
+                    style: o.font_style,
+                });
+                handled.extend(["font_colour", "font_size", "font_type", "font_style"]);
+            }
+            Object::LineAttributes(o)
+                if ["line_colour", "line_width", "line_art"]
+                    .iter()
+                    .any(|f| changed(f)) =>
+            {
+                commands.push(MacroCommand::ChangeLineAttributes {
+                    object: id,
+                    colour: o.line_colour,
+                    width: o.line_width,
+                    line_art: o.line_art,
+                });
+                handled.extend(["line_colour", "line_width", "line_art"]);
+            }
+            Object::FillAttributes(o)
+                if ["fill_type", "fill_colour", "fill_pattern"]
+                    .iter()
+                    .any(|f| changed(f)) =>
+            {
+                commands.push(MacroCommand::ChangeFillAttributes {
+                    object: id,
+                    fill_type: o.fill_type,
+                    colour: o.fill_colour,
+                    pattern: o.fill_pattern,
+                });
+                handled.extend(["fill_type", "fill_colour", "fill_pattern"]);
+            }
+            _ => {}
+        }
+        if let Some((width, height)) = size(new) {
+            if !handled.contains(&"width") && (changed("width") || changed("height")) {
+                commands.push(MacroCommand::ChangeSize {
+                    object: id,
+                    width,
+                    height,
+                });
+                handled.extend(["width", "height"]);
+            }
+        }
+
+        for field in &self.fields {
+            if handled.contains(&field.name) {
+                continue;
+            }
+            match (field.name, new) {
+                ("value", Object::StringVariable(_))
+                | ("value", Object::InputString(_))
+                | ("value", Object::OutputString(_)) => {
+                    let value = string_value(new)?;
+                    // The VT keeps the length the object was uploaded with, longer values are
+                    // rejected
+                    if value.chars().count() > string_value(old)?.chars().count() {
+                        return None;
+                    }
+                    commands.push(MacroCommand::ChangeStringValue {
+                        object: id,
+                        value: value.to_string(),
+                    });
+                }
+                ("value", _) => commands.push(MacroCommand::ChangeNumericValue {
+                    object: id,
+                    value: numeric_value(new)?,
+                }),
+                ("background_colour", _) => {
+                    let (_, colour) = attribute(new, "background_colour")?;
+                    commands.push(MacroCommand::ChangeBackgroundColour {
+                        object: id,
+                        colour: colour as u8,
+                    });
+                }
+                ("hidden", Object::Container(o)) => commands.push(MacroCommand::HideShowObject {
+                    object: id,
+                    show: !o.hidden,
+                }),
+                ("enabled", Object::InputBoolean(_))
+                | ("enabled", Object::InputString(_))
+                | ("enabled", Object::Animation(_)) => {
+                    commands.push(MacroCommand::EnableDisableObject {
+                        object: id,
+                        enable: enabled(new),
+                    })
+                }
+                ("active_mask", Object::WorkingSet(o)) => {
+                    commands.push(MacroCommand::ChangeActiveMask {
+                        working_set: id,
+                        mask: o.active_mask,
+                    })
+                }
+                ("soft_key_mask", Object::DataMask(o)) => {
+                    commands.push(MacroCommand::ChangeSoftKeyMask {
+                        mask_type: 1,
+                        mask: id,
+                        soft_key_mask: o.soft_key_mask,
+                    })
+                }
+                ("soft_key_mask", Object::AlarmMask(o)) => {
+                    commands.push(MacroCommand::ChangeSoftKeyMask {
+                        mask_type: 2,
+                        mask: id,
+                        soft_key_mask: o.soft_key_mask,
+                    })
+                }
+                ("priority", Object::AlarmMask(o)) => commands.push(MacroCommand::ChangePriority {
+                    object: id,
+                    priority: o.priority,
+                }),
+                ("object_refs", _) => commands.extend(child_position_commands(
+                    id,
+                    object_refs(old),
+                    object_refs(new),
+                )?),
+                ("list_items", Object::InputList(o)) => {
+                    commands.extend(list_item_commands(id, list_items(old)?, &o.list_items)?)
+                }
+                ("list_items", Object::OutputList(o)) => {
+                    commands.extend(list_item_commands(id, list_items(old)?, &o.list_items)?)
+                }
+                ("points", Object::OutputPolygon(o)) => {
+                    let Object::OutputPolygon(old) = old else {
+                        return None;
+                    };
+                    if old.points.len() != o.points.len() || o.points.len() > 255 {
+                        return None;
+                    }
+                    for (index, (old, new)) in old.points.iter().zip(&o.points).enumerate() {
+                        if old != new {
+                            commands.push(MacroCommand::ChangePolygonPoint {
+                                object: id,
+                                index: index as u8,
+                                x: new.x,
+                                y: new.y,
+                            });
+                        }
+                    }
+                }
+                (name, _) => {
+                    let (attribute_id, value) = attribute(new, name)?;
+                    commands.push(MacroCommand::ChangeAttribute {
+                        object: id,
+                        attribute_id,
+                        value,
+                    });
+                }
+            }
+        }
+        Some(commands)
+    }
+}
+
+/// Moves the children that moved, if the children are the same and each is in the list once
+fn child_position_commands(
+    parent: ObjectId,
+    old: &[ObjectRef],
+    new: &[ObjectRef],
+) -> Option<Vec<MacroCommand>> {
+    let same_children = old.len() == new.len() && old.iter().zip(new).all(|(o, n)| o.id == n.id);
+    let unique = new
+        .iter()
+        .enumerate()
+        .all(|(index, r)| !new[..index].iter().any(|other| other.id == r.id));
+    if !same_children || !unique {
+        return None;
+    }
+    Some(
+        old.iter()
+            .zip(new)
+            .filter(|(old, new)| old.offset != new.offset)
+            .map(|(_, new)| MacroCommand::ChangeChildPosition {
+                parent,
+                child: new.id,
+                x: new.offset.x,
+                y: new.offset.y,
+            })
+            .collect(),
+    )
+}
+
+fn list_item_commands(
+    object: ObjectId,
+    old: &[NullableObjectId],
+    new: &[NullableObjectId],
+) -> Option<Vec<MacroCommand>> {
+    if old.len() != new.len() || new.len() > 255 {
+        return None;
+    }
+    Some(
+        old.iter()
+            .zip(new)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(index, (_, new))| MacroCommand::ChangeListItem {
+                object,
+                index: index as u8,
+                new_object: *new,
+            })
+            .collect(),
+    )
+}
+
+fn list_items(object: &Object) -> Option<&[NullableObjectId]> {
+    match object {
+        Object::InputList(o) => Some(&o.list_items),
+        Object::OutputList(o) => Some(&o.list_items),
+        _ => None,
+    }
+}
+
+/// The width and height of objects that can be resized with Change Size
+fn size(object: &Object) -> Option<(u16, u16)> {
+    match object {
+        Object::Container(o) => Some((o.width, o.height)),
+        Object::Button(o) => Some((o.width, o.height)),
+        Object::InputString(o) => Some((o.width, o.height)),
+        Object::InputNumber(o) => Some((o.width, o.height)),
+        Object::InputList(o) => Some((o.width, o.height)),
+        Object::OutputString(o) => Some((o.width, o.height)),
+        Object::OutputNumber(o) => Some((o.width, o.height)),
+        Object::OutputList(o) => Some((o.width, o.height)),
+        Object::OutputRectangle(o) => Some((o.width, o.height)),
+        Object::OutputEllipse(o) => Some((o.width, o.height)),
+        Object::OutputLinearBarGraph(o) => Some((o.width, o.height)),
+        Object::OutputArchedBarGraph(o) => Some((o.width, o.height)),
+        Object::Animation(o) => Some((o.width, o.height)),
+        Object::ScaledGraphic(o) => Some((o.width, o.height)),
+        _ => None,
+    }
+}
+
+/// The value of objects that can be changed with Change Numeric Value
+fn numeric_value(object: &Object) -> Option<u32> {
+    match object {
+        Object::NumberVariable(o) => Some(o.value),
+        Object::InputBoolean(o) => Some(o.value.into()),
+        Object::InputNumber(o) => Some(o.value),
+        Object::InputList(o) => Some(o.value.into()),
+        Object::OutputNumber(o) => Some(o.value),
+        Object::OutputList(o) => Some(o.value.into()),
+        Object::OutputMeter(o) => Some(o.value.into()),
+        Object::OutputLinearBarGraph(o) => Some(o.value.into()),
+        Object::OutputArchedBarGraph(o) => Some(o.value.into()),
+        Object::ObjectPointer(o) => Some(u16::from(o.value).into()),
+        Object::Animation(o) => Some(o.value.into()),
+        Object::ScaledGraphic(o) => Some(u16::from(o.value).into()),
+        _ => None,
+    }
+}
+
+fn string_value(object: &Object) -> Option<&str> {
+    match object {
+        Object::StringVariable(o) => Some(&o.value),
+        Object::InputString(o) => Some(&o.value),
+        Object::OutputString(o) => Some(&o.value),
+        _ => None,
+    }
+}
+
+fn enabled(object: &Object) -> bool {
+    match object {
+        Object::InputBoolean(o) => o.enabled,
+        Object::InputString(o) => o.enabled,
+        Object::Animation(o) => o.enabled,
+        _ => false,
+    }
+}
+
+fn diff_fields(old: &Object, new: &Object) -> Vec<FieldDiff> {
+    if old.object_type() != new.object_type() {
+        return vec![FieldDiff {
+            name: "type",
+            old: format!("{:?}", old.object_type()),
+            new: format!("{:?}", new.object_type()),
+        }];
+    }
+    fields(old)
+        .into_iter()
+        .zip(fields(new))
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((name, old), (_, new))| FieldDiff { name, old, new })
+        .collect()
+}
+
+/// The value of an attribute as sent in a Change Attribute command
+trait AttributeValue {
+    fn attribute_value(&self) -> u32;
+}
+
+macro_rules! attribute_value {
+    ($($t:ty => |$v:ident| $e:expr),* $(,)?) => {
+        $(impl AttributeValue for $t {
+            fn attribute_value(&self) -> u32 {
+                let $v = *self;
+                $e
+            }
+        })*
+    };
+}
+
+attribute_value! {
+    u8 => |v| v.into(),
+    u16 => |v| v.into(),
+    u32 => |v| v,
+    i32 => |v| v as u32,
+    f32 => |v| v.to_bits(),
+    bool => |v| v.into(),
+    ObjectId => |v| u16::from(v).into(),
+    NullableObjectId => |v| u16::from(v).into(),
+    FormatType => |v| bool::from(v).into(),
+    Alignment => |v| u8::from(v).into(),
+    LineDirection => |v| u8::from(v).into(),
+    ButtonOptions => |v| u8::from(v).into(),
+    InputStringOptions => |v| u8::from(v).into(),
+    NumberOptions => |v| u8::from(v).into(),
+    InputNumberOptions => |v| u8::from(v).into(),
+    InputListOptions => |v| u8::from(v).into(),
+    OutputStringOptions => |v| u8::from(v).into(),
+    OutputMeterOptions => |v| u8::from(v).into(),
+    OutputLinearBarGraphOptions => |v| u8::from(v).into(),
+    OutputArchedBarGraphOptions => |v| u8::from(v).into(),
+    PictureGraphicOptions => |v| u8::from(v).into(),
+    FontSize => |v| u8::from(v).into(),
+    FontType => |v| u8::from(v).into(),
+    FontStyle => |v| u8::from(v).into(),
+    LineArt => |v| u16::from(v).into(),
+    FillType => |v| u8::from(v).into(),
+    ValidationType => |v| u8::from(v).into(),
+}
+
+/// Defines `attribute`, which returns the attribute ID (AID) and value of the attributes that can
+/// be changed with Change Attribute, see the object definitions in ISO 11783-6 Annex B
+macro_rules! attribute_ids {
+    ($($variant:ident { $($aid:literal => $field:ident),* $(,)? })*) => {
+        fn attribute(object: &Object, field: &str) -> Option<(u8, u32)> {
+            match object {
+                $(Object::$variant(o) => match field {
+                    $(stringify!($field) => Some(($aid, o.$field.attribute_value())),)*
+                    _ => None,
+                },)*
+                _ => None,
+            }
+        }
+    };
+}
+
+attribute_ids! {
+    WorkingSet { 2 => selectable, 3 => active_mask }
+    DataMask { 1 => background_colour, 2 => soft_key_mask }
+    AlarmMask {
+        1 => background_colour, 2 => soft_key_mask, 3 => priority, 4 => acoustic_signal,
+    }
+    Container { 1 => width, 2 => height, 3 => hidden }
+    SoftKeyMask { 1 => background_colour }
+    Key { 1 => background_colour, 2 => key_code }
+    Button {
+        1 => width, 2 => height, 3 => background_colour, 4 => border_colour, 5 => key_code,
+        6 => options,
+    }
+    InputBoolean {
+        1 => background_colour, 2 => width, 3 => foreground_colour, 4 => variable_reference,
+    }
+    InputString {
+        1 => width, 2 => height, 3 => background_colour, 4 => font_attributes,
+        5 => input_attributes, 6 => options, 7 => variable_reference, 8 => justification,
+    }
+    InputNumber {
+        1 => width, 2 => height, 3 => background_colour, 4 => font_attributes, 5 => options,
+        6 => variable_reference, 7 => min_value, 8 => max_value, 9 => offset, 10 => scale,
+        11 => nr_of_decimals, 12 => format, 13 => justification, 14 => options2,
+    }
+    InputList { 1 => width, 2 => height, 3 => variable_reference, 4 => options }
+    OutputString {
+        1 => width, 2 => height, 3 => background_colour, 4 => font_attributes, 5 => options,
+        6 => variable_reference, 7 => justification,
+    }
+    OutputNumber {
+        1 => width, 2 => height, 3 => background_colour, 4 => font_attributes, 5 => options,
+        6 => variable_reference, 7 => offset, 8 => scale, 9 => nr_of_decimals, 10 => format,
+        11 => justification,
+    }
+    OutputList { 1 => width, 2 => height, 3 => variable_reference }
+    OutputLine { 1 => line_attributes, 2 => width, 3 => height, 4 => line_direction }
+    OutputRectangle {
+        1 => line_attributes, 2 => width, 3 => height, 4 => line_suppression,
+        5 => fill_attributes,
+    }
+    OutputEllipse {
+        1 => line_attributes, 2 => width, 3 => height, 4 => ellipse_type, 5 => start_angle,
+        6 => end_angle, 7 => fill_attributes,
+    }
+    OutputPolygon {
+        1 => width, 2 => height, 3 => line_attributes, 4 => fill_attributes, 5 => polygon_type,
+    }
+    OutputMeter {
+        1 => width, 2 => needle_colour, 3 => border_colour, 4 => arc_and_tick_colour,
+        5 => options, 6 => nr_of_ticks, 7 => start_angle, 8 => end_angle, 9 => min_value,
+        10 => max_value, 11 => variable_reference,
+    }
+    OutputLinearBarGraph {
+        1 => width, 2 => height, 3 => colour, 4 => target_line_colour, 5 => options,
+        6 => nr_of_ticks, 7 => min_value, 8 => max_value, 9 => variable_reference,
+        10 => target_value_variable_reference, 11 => target_value,
+    }
+    OutputArchedBarGraph {
+        1 => width, 2 => height, 3 => colour, 4 => target_line_colour, 5 => options,
+        6 => start_angle, 7 => end_angle, 8 => bar_graph_width, 9 => min_value,
+        10 => max_value, 11 => variable_reference, 12 => target_value_variable_reference,
+        13 => target_value,
+    }
+    PictureGraphic { 1 => width, 2 => options, 3 => transparency_colour }
+    FontAttributes { 1 => font_colour, 2 => font_size, 3 => font_type, 4 => font_style }
+    LineAttributes { 1 => line_colour, 2 => line_width, 3 => line_art }
+    FillAttributes { 1 => fill_type, 2 => fill_colour, 3 => fill_pattern }
+    InputAttributes { 1 => validation_type }
+}
+
+/// Defines `fields`, which returns the name and [`Debug`] representation of every field of an
+/// object except its ID
+macro_rules! object_fields {
+    ($($variant:ident { $($field:ident),* $(,)? })*) => {
+        fn fields(object: &Object) -> Vec<(&'static str, String)> {
+            match object {
+                $(Object::$variant(o) => vec![
+                    $((stringify!($field), format!("{:?}", o.$field)),)*
+                ],)*
+            }
+        }
+    };
+}
+
+object_fields! {
+    WorkingSet {
+        background_colour, selectable, active_mask, object_refs, macro_refs, language_codes,
+    }
+    DataMask { background_colour, soft_key_mask, object_refs, macro_refs }
+    AlarmMask {
+        background_colour, soft_key_mask, priority, acoustic_signal, object_refs, macro_refs,
+    }
+    Container { width, height, hidden, object_refs, macro_refs }
+    SoftKeyMask { background_colour, objects, macro_refs }
+    Key { background_colour, key_code, object_refs, macro_refs }
+    Button {
+        width, height, background_colour, border_colour, key_code, options, object_refs,
+        macro_refs,
+    }
+    InputBoolean {
+        background_colour, width, foreground_colour, variable_reference, value, enabled,
+        macro_refs,
+    }
+    InputString {
+        width, height, background_colour, font_attributes, input_attributes, options,
+        variable_reference, justification, value, enabled, macro_refs,
+    }
+    InputNumber {
+        width, height, background_colour, font_attributes, options, variable_reference, value,
+        min_value, max_value, offset, scale, nr_of_decimals, format, justification, options2,
+        macro_refs,
+    }
+    InputList { width, height, variable_reference, value, options, list_items, macro_refs }
+    OutputString {
+        width, height, background_colour, font_attributes, options, variable_reference,
+        justification, value, macro_refs,
+    }
+    OutputNumber {
+        width, height, background_colour, font_attributes, options, variable_reference, value,
+        offset, scale, nr_of_decimals, format, justification, macro_refs,
+    }
+    OutputLine { line_attributes, width, height, line_direction, macro_refs }
+    OutputRectangle {
+        line_attributes, width, height, line_suppression, fill_attributes, macro_refs,
+    }
+    OutputEllipse {
+        line_attributes, width, height, ellipse_type, start_angle, end_angle, fill_attributes,
+        macro_refs,
+    }
+    OutputPolygon {
+        width, height, line_attributes, fill_attributes, polygon_type, points, macro_refs,
+    }
+    OutputMeter {
+        width, needle_colour, border_colour, arc_and_tick_colour, options, nr_of_ticks,
+        start_angle, end_angle, min_value, max_value, variable_reference, value, macro_refs,
+    }
+    OutputLinearBarGraph {
+        width, height, colour, target_line_colour, options, nr_of_ticks, min_value, max_value,
+        variable_reference, value, target_value_variable_reference, target_value, macro_refs,
+    }
+    OutputArchedBarGraph {
+        width, height, colour, target_line_colour, options, start_angle, end_angle,
+        bar_graph_width, min_value, max_value, variable_reference, value,
+        target_value_variable_reference, target_value, macro_refs,
+    }
+    PictureGraphic {
+        width, actual_width, actual_height, format, options, transparency_colour, data,
+        macro_refs,
+    }
+    NumberVariable { value }
+    StringVariable { value }
+    FontAttributes { font_colour, font_size, font_type, font_style, macro_refs }
+    LineAttributes { line_colour, line_width, line_art, macro_refs }
+    FillAttributes { fill_type, fill_colour, fill_pattern, macro_refs }
+    InputAttributes { validation_type, validation_string, macro_refs }
+    ObjectPointer { value }
+    Macro { commands }
+    AuxiliaryFunctionType1 { background_colour, function_type, object_refs }
+    AuxiliaryInputType1 { background_colour, function_type, input_id, object_refs }
+    AuxiliaryFunctionType2 { background_colour, function_attributes, object_refs }
+    AuxiliaryInputType2 { background_colour, function_attributes, object_refs }
+    AuxiliaryControlDesignatorType2 { pointer_type, auxiliary_object_id }
+    WindowMask {
+        cell_format, window_type, background_colour, options, name, window_title, window_icon,
+        objects, object_refs, macro_refs,
+    }
+    KeyGroup { options, name, key_group_icon, objects, macro_refs }
+    GraphicsContext {
+        viewport_width, viewport_height, viewport_x, viewport_y, canvas_width, canvas_height,
+        viewport_zoom, graphics_cursor_x, graphics_cursor_y, foreground_colour,
+        background_colour, font_attributes_object, line_attributes_object,
+        fill_attributes_object, format, options, transparency_colour,
+    }
+    OutputList { width, height, variable_reference, value, list_items, macro_refs }
+    ExtendedInputAttributes { validation_type, code_planes }
+    ColourMap { colour_map }
+    ObjectLabelReferenceList { object_labels }
+    ExternalObjectDefinition { options, name, objects }
+    ExternalReferenceName { options, name }
+    ExternalObjectPointer { default_object_id, external_reference_name_id, external_object_id }
+    Animation {
+        width, height, refresh_interval, value, enabled, first_child_index, last_child_index,
+        default_child_index, options, object_refs, macro_refs,
+    }
+    ColourPalette { options, colours }
+    GraphicData { format, data }
+    WorkingSetSpecialControls { id_of_colour_map, id_of_colour_palette, language_pairs }
+    ScaledGraphic { width, height, scale_type, options, value, macro_refs }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::object::{
+        Container, DataMask, FontAttributes, NumberVariable, OutputList, OutputString, WorkingSet,
+    };
+    use crate::object_pool::ObjectBuilder;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    fn test_pool() -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        pool.add(WorkingSet::builder(id(1000)).build(id(0)));
+        pool.add(DataMask::builder().child(id(2000), 0, 0).build(id(1000)));
+        pool.add(
+            Container::builder()
+                .child(id(3000), 10, 10)
+                .child(id(3001), 10, 40)
+                .build(id(2000)),
+        );
+        pool.add(
+            OutputString::builder(id(4000))
+                .value("a".to_string())
+                .build(id(3000)),
+        );
+        pool.add(OutputList::builder().build(id(3001)));
+        pool.add(FontAttributes::builder().build(id(4000)));
+        pool.add(NumberVariable::builder().build(id(5000)));
+        pool
+    }
+
+    /// The test pool with the changes of `f`
+    fn changed_pool(f: impl FnOnce(&mut ObjectPool)) -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        for object in test_pool().objects() {
+            pool.add(object.clone());
+        }
+        f(&mut pool);
+        pool
+    }
+
+    #[test]
+    fn diff_test() {
+        let old = test_pool();
+        assert!(old.diff(&test_pool()).is_empty());
+
+        let new = changed_pool(|pool| {
+            pool.remove(id(5000));
+            pool.add(NumberVariable::builder().build(id(5001)));
+            let container = pool.container_object_by_id_mut(id(2000)).unwrap();
+            container.width = 150;
+            container.object_refs[1].offset = Point { x: 20, y: 50 };
+            pool.output_string_object_by_id_mut(id(3000)).unwrap().value = "b".to_string();
+        });
+        let diff = old.diff(&new);
+        let ids = |objects: &[Object]| objects.iter().map(Object::id).collect::<Vec<_>>();
+        assert_eq!(ids(&diff.added), vec![id(5001)]);
+        assert_eq!(ids(&diff.removed), vec![id(5000)]);
+
+        let modified: Vec<(ObjectId, Vec<&str>)> = diff
+            .modified
+            .iter()
+            .map(|d| (d.new.id(), d.fields.iter().map(|f| f.name).collect()))
+            .collect();
+        assert_eq!(
+            modified,
+            vec![
+                (id(2000), vec!["width", "object_refs"]),
+                (id(3000), vec!["value"])
+            ]
+        );
+        assert_eq!(
+            diff.modified[1].fields[0],
+            FieldDiff {
+                name: "value",
+                old: "\"a\"".to_string(),
+                new: "\"b\"".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn patch_commands_test() {
+        let new = changed_pool(|pool| {
+            pool.add(NumberVariable::builder().build(id(5001)));
+            let container = pool.container_object_by_id_mut(id(2000)).unwrap();
+            container.width = 150;
+            container.hidden = true;
+            container.object_refs[1].offset = Point { x: 20, y: 50 };
+            let string = pool.output_string_object_by_id_mut(id(3000)).unwrap();
+            string.value = "b".to_string();
+            string.font_attributes = id(4001);
+            let list = pool.output_list_object_by_id_mut(id(3001)).unwrap();
+            list.value = 2;
+            pool.font_attributes_object_by_id_mut(id(4000))
+                .unwrap()
+                .font_size = FontSize::Size12x16;
+        });
+        let patch = test_pool().diff(&new).patch(&new);
+
+        let uploaded: Vec<ObjectId> = patch.upload.objects().iter().map(Object::id).collect();
+        assert_eq!(uploaded, vec![id(5001)]);
+        assert_eq!(
+            patch.commands,
+            vec![
+                MacroCommand::ChangeSize {
+                    object: id(2000),
+                    width: 150,
+                    height: 100
+                },
+                MacroCommand::HideShowObject {
+                    object: id(2000),
+                    show: false
+                },
+                MacroCommand::ChangeChildPosition {
+                    parent: id(2000),
+                    child: id(3001),
+                    x: 20,
+                    y: 50
+                },
+                MacroCommand::ChangeAttribute {
+                    object: id(3000),
+                    attribute_id: 4,
+                    value: 4001
+                },
+                MacroCommand::ChangeStringValue {
+                    object: id(3000),
+                    value: "b".to_string()
+                },
+                MacroCommand::ChangeNumericValue {
+                    object: id(3001),
+                    value: 2
+                },
+                MacroCommand::ChangeFontAttributes {
+                    object: id(4000),
+                    colour: 0,
+                    size: FontSize::Size12x16,
+                    font_type: FontType::Iso8859_1,
+                    style: FontStyle::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn patch_font_types_test() {
+        let greek = |pool: &mut ObjectPool| {
+            pool.font_attributes_object_by_id_mut(id(4000))
+                .unwrap()
+                .font_type = FontType::Iso8859_7;
+        };
+        let old = changed_pool(greek);
+        let new = changed_pool(|pool| {
+            greek(pool);
+            pool.output_string_object_by_id_mut(id(3000)).unwrap().value = "Ωα".to_string();
+        });
+        let patch = old.diff(&new).patch(&new);
+
+        // The font attributes are not uploaded, the value is encoded with them anyway
+        let uploaded: Vec<ObjectId> = patch.upload.objects().iter().map(Object::id).collect();
+        assert_eq!(uploaded, vec![id(3000)]);
+        let iop = patch.upload.as_iop().unwrap();
+        assert!(iop.windows(4).any(|w| w == [0x02, 0x00, 0xD9, 0xE1]));
+    }
+
+    #[test]
+    fn patch_upload_test() {
+        let new = changed_pool(|pool| {
+            // A new child can't be added with a command
+            let mask = pool.data_mask_object_by_id_mut(id(1000)).unwrap();
+            mask.object_refs.push(ObjectRef {
+                id: id(3000),
+                offset: Point { x: 0, y: 0 },
+            });
+            // Neither can a different object type with the same ID
+            pool.replace(OutputString::builder(id(4000)).build(id(3001)));
+            // Only the width and height of a container are attributes
+            pool.container_object_by_id_mut(id(2000))
+                .unwrap()
+                .macro_refs
+                .push(MacroRef {
+                    macro_id: 1,
+                    event_id: 1,
+                });
+            // The length of a string value is fixed
+            pool.output_string_object_by_id_mut(id(3000)).unwrap().value = "ab".to_string();
+        });
+        let diff = test_pool().diff(&new);
+        assert_eq!(diff.modified[3].fields[0].name, "type");
+
+        let patch = diff.patch(&new);
+        assert!(patch.commands.is_empty());
+        let uploaded: Vec<ObjectId> = patch.upload.objects().iter().map(Object::id).collect();
+        assert_eq!(uploaded, vec![id(1000), id(2000), id(3000), id(3001)]);
+        assert_eq!(
+            patch.upload.object_by_id(id(3001)),
+            new.object_by_id(id(3001))
+        );
+    }
+}
@@ -0,0 +1,628 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::macro_command::{GraphicsContextCommand, MacroCommand};
+use crate::object_pool::object::Object;
+use crate::object_pool::object_attributes::FontType;
+use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::{ObjectPool, VtVersion};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// The byte order mark WideStrings start with, the characters follow in UTF-16 little endian
+const BYTE_ORDER_MARK: [u8; 2] = [0xFF, 0xFE];
+
+/// A string value that can't be encoded for the VT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodingError {
+    /// `character` is not in the character set of `font_type`, and WideStrings can't be used
+    /// because the VT does not support them or the writer has no VT version
+    UnrepresentableCharacter {
+        character: char,
+        font_type: FontType,
+    },
+}
+
+impl std::fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for EncodingError {}
+
+impl FontType {
+    /// Decodes a string value shown with this font type
+    ///
+    /// Values that start with the byte order mark 0xFF 0xFE are WideStrings in UTF-16. Bytes that
+    /// are not defined in the character set become U+FFFD.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        if let Some(wide) = bytes.strip_prefix(&BYTE_ORDER_MARK) {
+            let units: Vec<u16> = wide
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect();
+            return String::from_utf16_lossy(&units);
+        }
+        bytes.iter().map(|&byte| self.decode_byte(byte)).collect()
+    }
+
+    /// Encodes text in the 8 bit character set of this font type
+    ///
+    /// Proprietary fonts are treated as ISO 8859-1.
+    pub fn encode(self, text: &str) -> Result<Vec<u8>, EncodingError> {
+        text.chars()
+            .map(|character| {
+                self.encode_char(character)
+                    .ok_or(EncodingError::UnrepresentableCharacter {
+                        character,
+                        font_type: self,
+                    })
+            })
+            .collect()
+    }
+
+    /// Encodes text for a VT of `vt_version`, in the character set of this font type or as
+    /// WideString if the character set does not have all of its characters
+    ///
+    /// Fails if a WideString is needed but the VT is older than version 4.
+    pub fn encode_for_version(
+        self,
+        text: &str,
+        vt_version: VtVersion,
+    ) -> Result<Vec<u8>, EncodingError> {
+        let wide = vt_version >= VtVersion::Version4;
+        match self.encode(text) {
+            // Older VTs don't read the byte order mark as WideString
+            Ok(bytes) if !wide || !bytes.starts_with(&BYTE_ORDER_MARK) => Ok(bytes),
+            Err(error) if !wide => Err(error),
+            _ => Ok(encode_wide(text)),
+        }
+    }
+
+    fn decode_byte(self, byte: u8) -> char {
+        match self.upper_half() {
+            Some(table) if byte >= 0xA0 => table[usize::from(byte - 0xA0)],
+            _ => char::from(byte),
+        }
+    }
+
+    fn encode_char(self, character: char) -> Option<u8> {
+        let code = u32::from(character);
+        match self.upper_half() {
+            // ASCII and the control characters are the same in all character sets
+            _ if code < 0xA0 => Some(code as u8),
+            Some(table) => table
+                .iter()
+                .position(|&c| c == character && c != char::REPLACEMENT_CHARACTER)
+                .map(|position| position as u8 + 0xA0),
+            None => u8::try_from(code).ok(),
+        }
+    }
+
+    /// The characters of the bytes 0xA0 to 0xFF, `None` if they are the ones of ISO 8859-1
+    fn upper_half(self) -> Option<&'static [char; 96]> {
+        match self {
+            FontType::Iso8859_1 | FontType::Proprietary(_) => None,
+            FontType::Iso8859_15 => Some(&ISO_8859_15),
+            FontType::Iso8859_2 => Some(&ISO_8859_2),
+            FontType::Iso8859_4 => Some(&ISO_8859_4),
+            FontType::Iso8859_5 => Some(&ISO_8859_5),
+            FontType::Iso8859_7 => Some(&ISO_8859_7),
+        }
+    }
+}
+
+/// Encodes text as WideString, VT version 4 and later
+pub fn encode_wide(text: &str) -> Vec<u8> {
+    BYTE_ORDER_MARK
+        .into_iter()
+        .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+        .collect()
+}
+
+impl ObjectPool {
+    /// The font type the string value of `id` is shown with
+    ///
+    /// Output and Input Strings use the font type of their font attributes, String Variables the
+    /// one of the first object that shows them. Defaults to ISO 8859-1.
+    pub fn string_font_type(&self, id: ObjectId) -> FontType {
+        self.string_font_types()
+            .get(&id)
+            .copied()
+            .unwrap_or(FontType::Iso8859_1)
+    }
+
+    /// Encodes a string value of `id` the way the VT expects it
+    ///
+    /// Uses the character set of the [font type](ObjectPool::string_font_type) of the object, or a
+    /// WideString if the text has other characters. Fails if the pool is made for a VT that does
+    /// not support WideStrings.
+    pub fn encode_string_value(&self, id: ObjectId, text: &str) -> Result<Vec<u8>, EncodingError> {
+        self.string_font_type(id)
+            .encode_for_version(text, self.supported_vt_version())
+    }
+
+    /// Writes a command to send to the VT, encoding Change String Value and Draw Text commands like
+    /// [`ObjectPool::encode_string_value`]
+    pub fn write_command(&self, command: &MacroCommand) -> Result<Vec<u8>, EncodingError> {
+        let mut data = Vec::new();
+        match command {
+            MacroCommand::ChangeStringValue { object, value } => {
+                let bytes = self.encode_string_value(*object, value)?;
+                MacroCommand::ChangeStringValue {
+                    object: *object,
+                    value: to_latin1(&bytes),
+                }
+                .write(&mut data)?;
+            }
+            MacroCommand::GraphicsContext {
+                object,
+                command: GraphicsContextCommand::DrawText { transparent, text },
+            } => {
+                let bytes = self.encode_string_value(*object, text)?;
+                MacroCommand::GraphicsContext {
+                    object: *object,
+                    command: GraphicsContextCommand::DrawText {
+                        transparent: *transparent,
+                        text: to_latin1(&bytes),
+                    },
+                }
+                .write(&mut data)?;
+            }
+            _ => command.write(&mut data)?,
+        }
+        Ok(data)
+    }
+
+    /// Decodes the string values that were read as ISO 8859-1 with the font type they are shown
+    /// with
+    pub(super) fn decode_strings(&mut self) {
+        let font_types = self.string_font_types();
+        self.update_all(|object| {
+            for (id, value) in string_values(object) {
+                let font_type = font_types.get(&id).copied().unwrap_or(FontType::Iso8859_1);
+                *value = font_type.decode(&from_latin1(value));
+            }
+        });
+    }
+
+    /// The font type of every object that has a string value or is shown as string
+    ///
+    /// Objects whose font attributes are not in the pool keep the font type they have in the
+    /// pool this one is a part of, see [`PoolDiff::patch`](crate::object_pool::PoolDiff::patch).
+    pub(super) fn string_font_types(&self) -> HashMap<ObjectId, FontType> {
+        let font_type = |font_attributes| {
+            self.font_attributes_object_by_id(font_attributes)
+                .map(|font| font.font_type)
+        };
+        let mut font_types = self.external_font_types.clone();
+        for object in self.objects() {
+            let (font_type, variable) = match object {
+                Object::OutputString(o) => (font_type(o.font_attributes), o.variable_reference),
+                Object::InputString(o) => (font_type(o.font_attributes), o.variable_reference),
+                Object::GraphicsContext(o) => {
+                    // Drawn text uses the initial font attributes of the graphics context
+                    if let Some(font_type) = o.font_attributes_object.0.and_then(font_type) {
+                        font_types.insert(o.id, font_type);
+                    }
+                    continue;
+                }
+                Object::ObjectLabelReferenceList(o) => {
+                    for label in &o.object_labels {
+                        if let Some(variable) = label.string_variable_reference.0 {
+                            font_types.entry(variable).or_insert(label.font_type);
+                        }
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            let Some(font_type) = font_type else {
+                continue;
+            };
+            font_types.insert(object.id(), font_type);
+            if let Some(variable) = variable.0 {
+                font_types.entry(variable).or_insert(font_type);
+            }
+        }
+        font_types
+    }
+}
+
+/// Returns `object` with its string values encoded for a VT of `vt_version`, as the ISO 8859-1
+/// text the writer turns back into the same bytes
+pub(super) fn encode_strings<'a>(
+    object: &'a Object,
+    font_types: &HashMap<ObjectId, FontType>,
+    vt_version: VtVersion,
+) -> Result<Cow<'a, Object>, EncodingError> {
+    if !matches!(
+        object,
+        Object::OutputString(_)
+            | Object::InputString(_)
+            | Object::StringVariable(_)
+            | Object::Macro(_)
+    ) {
+        return Ok(Cow::Borrowed(object));
+    }
+    let mut object = object.clone();
+    for (id, value) in string_values(&mut object) {
+        let font_type = font_types.get(&id).copied().unwrap_or(FontType::Iso8859_1);
+        *value = to_latin1(&font_type.encode_for_version(value, vt_version)?);
+    }
+    Ok(Cow::Owned(object))
+}
+
+/// The string values of `object`, with the ID of the object whose font they are shown with
+fn string_values(object: &mut Object) -> Vec<(ObjectId, &mut String)> {
+    match object {
+        Object::OutputString(o) => vec![(o.id, &mut o.value)],
+        Object::InputString(o) => vec![(o.id, &mut o.value)],
+        Object::StringVariable(o) => vec![(o.id, &mut o.value)],
+        Object::Macro(o) => o
+            .commands
+            .iter_mut()
+            .filter_map(|command| match command {
+                MacroCommand::ChangeStringValue { object, value } => Some((*object, value)),
+                MacroCommand::GraphicsContext {
+                    object,
+                    command: GraphicsContextCommand::DrawText { text, .. },
+                } => Some((*object, text)),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// The reader and writer of single objects use ISO 8859-1, one character per byte
+fn to_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| char::from(byte)).collect()
+}
+
+fn from_latin1(value: &str) -> Vec<u8> {
+    value.chars().map(|c| c as u32 as u8).collect()
+}
+
+const ISO_8859_15: [char; 96] = [
+    '\u{00A0}', '\u{00A1}', '\u{00A2}', '\u{00A3}', '\u{20AC}', '\u{00A5}', '\u{0160}', '\u{00A7}',
+    '\u{0161}', '\u{00A9}', '\u{00AA}', '\u{00AB}', '\u{00AC}', '\u{00AD}', '\u{00AE}', '\u{00AF}',
+    '\u{00B0}', '\u{00B1}', '\u{00B2}', '\u{00B3}', '\u{017D}', '\u{00B5}', '\u{00B6}', '\u{00B7}',
+    '\u{017E}', '\u{00B9}', '\u{00BA}', '\u{00BB}', '\u{0152}', '\u{0153}', '\u{0178}', '\u{00BF}',
+    '\u{00C0}', '\u{00C1}', '\u{00C2}', '\u{00C3}', '\u{00C4}', '\u{00C5}', '\u{00C6}', '\u{00C7}',
+    '\u{00C8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00CC}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00D0}', '\u{00D1}', '\u{00D2}', '\u{00D3}', '\u{00D4}', '\u{00D5}', '\u{00D6}', '\u{00D7}',
+    '\u{00D8}', '\u{00D9}', '\u{00DA}', '\u{00DB}', '\u{00DC}', '\u{00DD}', '\u{00DE}', '\u{00DF}',
+    '\u{00E0}', '\u{00E1}', '\u{00E2}', '\u{00E3}', '\u{00E4}', '\u{00E5}', '\u{00E6}', '\u{00E7}',
+    '\u{00E8}', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00EC}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00F0}', '\u{00F1}', '\u{00F2}', '\u{00F3}', '\u{00F4}', '\u{00F5}', '\u{00F6}', '\u{00F7}',
+    '\u{00F8}', '\u{00F9}', '\u{00FA}', '\u{00FB}', '\u{00FC}', '\u{00FD}', '\u{00FE}', '\u{00FF}',
+];
+const ISO_8859_2: [char; 96] = [
+    '\u{00A0}', '\u{0104}', '\u{02D8}', '\u{0141}', '\u{00A4}', '\u{013D}', '\u{015A}', '\u{00A7}',
+    '\u{00A8}', '\u{0160}', '\u{015E}', '\u{0164}', '\u{0179}', '\u{00AD}', '\u{017D}', '\u{017B}',
+    '\u{00B0}', '\u{0105}', '\u{02DB}', '\u{0142}', '\u{00B4}', '\u{013E}', '\u{015B}', '\u{02C7}',
+    '\u{00B8}', '\u{0161}', '\u{015F}', '\u{0165}', '\u{017A}', '\u{02DD}', '\u{017E}', '\u{017C}',
+    '\u{0154}', '\u{00C1}', '\u{00C2}', '\u{0102}', '\u{00C4}', '\u{0139}', '\u{0106}', '\u{00C7}',
+    '\u{010C}', '\u{00C9}', '\u{0118}', '\u{00CB}', '\u{011A}', '\u{00CD}', '\u{00CE}', '\u{010E}',
+    '\u{0110}', '\u{0143}', '\u{0147}', '\u{00D3}', '\u{00D4}', '\u{0150}', '\u{00D6}', '\u{00D7}',
+    '\u{0158}', '\u{016E}', '\u{00DA}', '\u{0170}', '\u{00DC}', '\u{00DD}', '\u{0162}', '\u{00DF}',
+    '\u{0155}', '\u{00E1}', '\u{00E2}', '\u{0103}', '\u{00E4}', '\u{013A}', '\u{0107}', '\u{00E7}',
+    '\u{010D}', '\u{00E9}', '\u{0119}', '\u{00EB}', '\u{011B}', '\u{00ED}', '\u{00EE}', '\u{010F}',
+    '\u{0111}', '\u{0144}', '\u{0148}', '\u{00F3}', '\u{00F4}', '\u{0151}', '\u{00F6}', '\u{00F7}',
+    '\u{0159}', '\u{016F}', '\u{00FA}', '\u{0171}', '\u{00FC}', '\u{00FD}', '\u{0163}', '\u{02D9}',
+];
+const ISO_8859_4: [char; 96] = [
+    '\u{00A0}', '\u{0104}', '\u{0138}', '\u{0156}', '\u{00A4}', '\u{0128}', '\u{013B}', '\u{00A7}',
+    '\u{00A8}', '\u{0160}', '\u{0112}', '\u{0122}', '\u{0166}', '\u{00AD}', '\u{017D}', '\u{00AF}',
+    '\u{00B0}', '\u{0105}', '\u{02DB}', '\u{0157}', '\u{00B4}', '\u{0129}', '\u{013C}', '\u{02C7}',
+    '\u{00B8}', '\u{0161}', '\u{0113}', '\u{0123}', '\u{0167}', '\u{014A}', '\u{017E}', '\u{014B}',
+    '\u{0100}', '\u{00C1}', '\u{00C2}', '\u{00C3}', '\u{00C4}', '\u{00C5}', '\u{00C6}', '\u{012E}',
+    '\u{010C}', '\u{00C9}', '\u{0118}', '\u{00CB}', '\u{0116}', '\u{00CD}', '\u{00CE}', '\u{012A}',
+    '\u{0110}', '\u{0145}', '\u{014C}', '\u{0136}', '\u{00D4}', '\u{00D5}', '\u{00D6}', '\u{00D7}',
+    '\u{00D8}', '\u{0172}', '\u{00DA}', '\u{00DB}', '\u{00DC}', '\u{0168}', '\u{016A}', '\u{00DF}',
+    '\u{0101}', '\u{00E1}', '\u{00E2}', '\u{00E3}', '\u{00E4}', '\u{00E5}', '\u{00E6}', '\u{012F}',
+    '\u{010D}', '\u{00E9}', '\u{0119}', '\u{00EB}', '\u{0117}', '\u{00ED}', '\u{00EE}', '\u{012B}',
+    '\u{0111}', '\u{0146}', '\u{014D}', '\u{0137}', '\u{00F4}', '\u{00F5}', '\u{00F6}', '\u{00F7}',
+    '\u{00F8}', '\u{0173}', '\u{00FA}', '\u{00FB}', '\u{00FC}', '\u{0169}', '\u{016B}', '\u{02D9}',
+];
+const ISO_8859_5: [char; 96] = [
+    '\u{00A0}', '\u{0401}', '\u{0402}', '\u{0403}', '\u{0404}', '\u{0405}', '\u{0406}', '\u{0407}',
+    '\u{0408}', '\u{0409}', '\u{040A}', '\u{040B}', '\u{040C}', '\u{00AD}', '\u{040E}', '\u{040F}',
+    '\u{0410}', '\u{0411}', '\u{0412}', '\u{0413}', '\u{0414}', '\u{0415}', '\u{0416}', '\u{0417}',
+    '\u{0418}', '\u{0419}', '\u{041A}', '\u{041B}', '\u{041C}', '\u{041D}', '\u{041E}', '\u{041F}',
+    '\u{0420}', '\u{0421}', '\u{0422}', '\u{0423}', '\u{0424}', '\u{0425}', '\u{0426}', '\u{0427}',
+    '\u{0428}', '\u{0429}', '\u{042A}', '\u{042B}', '\u{042C}', '\u{042D}', '\u{042E}', '\u{042F}',
+    '\u{0430}', '\u{0431}', '\u{0432}', '\u{0433}', '\u{0434}', '\u{0435}', '\u{0436}', '\u{0437}',
+    '\u{0438}', '\u{0439}', '\u{043A}', '\u{043B}', '\u{043C}', '\u{043D}', '\u{043E}', '\u{043F}',
+    '\u{0440}', '\u{0441}', '\u{0442}', '\u{0443}', '\u{0444}', '\u{0445}', '\u{0446}', '\u{0447}',
+    '\u{0448}', '\u{0449}', '\u{044A}', '\u{044B}', '\u{044C}', '\u{044D}', '\u{044E}', '\u{044F}',
+    '\u{2116}', '\u{0451}', '\u{0452}', '\u{0453}', '\u{0454}', '\u{0455}', '\u{0456}', '\u{0457}',
+    '\u{0458}', '\u{0459}', '\u{045A}', '\u{045B}', '\u{045C}', '\u{00A7}', '\u{045E}', '\u{045F}',
+];
+const ISO_8859_7: [char; 96] = [
+    '\u{00A0}',
+    '\u{2018}',
+    '\u{2019}',
+    '\u{00A3}',
+    '\u{20AC}',
+    '\u{20AF}',
+    '\u{00A6}',
+    '\u{00A7}',
+    '\u{00A8}',
+    '\u{00A9}',
+    '\u{037A}',
+    '\u{00AB}',
+    '\u{00AC}',
+    '\u{00AD}',
+    char::REPLACEMENT_CHARACTER,
+    '\u{2015}',
+    '\u{00B0}',
+    '\u{00B1}',
+    '\u{00B2}',
+    '\u{00B3}',
+    '\u{0384}',
+    '\u{0385}',
+    '\u{0386}',
+    '\u{00B7}',
+    '\u{0388}',
+    '\u{0389}',
+    '\u{038A}',
+    '\u{00BB}',
+    '\u{038C}',
+    '\u{00BD}',
+    '\u{038E}',
+    '\u{038F}',
+    '\u{0390}',
+    '\u{0391}',
+    '\u{0392}',
+    '\u{0393}',
+    '\u{0394}',
+    '\u{0395}',
+    '\u{0396}',
+    '\u{0397}',
+    '\u{0398}',
+    '\u{0399}',
+    '\u{039A}',
+    '\u{039B}',
+    '\u{039C}',
+    '\u{039D}',
+    '\u{039E}',
+    '\u{039F}',
+    '\u{03A0}',
+    '\u{03A1}',
+    char::REPLACEMENT_CHARACTER,
+    '\u{03A3}',
+    '\u{03A4}',
+    '\u{03A5}',
+    '\u{03A6}',
+    '\u{03A7}',
+    '\u{03A8}',
+    '\u{03A9}',
+    '\u{03AA}',
+    '\u{03AB}',
+    '\u{03AC}',
+    '\u{03AD}',
+    '\u{03AE}',
+    '\u{03AF}',
+    '\u{03B0}',
+    '\u{03B1}',
+    '\u{03B2}',
+    '\u{03B3}',
+    '\u{03B4}',
+    '\u{03B5}',
+    '\u{03B6}',
+    '\u{03B7}',
+    '\u{03B8}',
+    '\u{03B9}',
+    '\u{03BA}',
+    '\u{03BB}',
+    '\u{03BC}',
+    '\u{03BD}',
+    '\u{03BE}',
+    '\u{03BF}',
+    '\u{03C0}',
+    '\u{03C1}',
+    '\u{03C2}',
+    '\u{03C3}',
+    '\u{03C4}',
+    '\u{03C5}',
+    '\u{03C6}',
+    '\u{03C7}',
+    '\u{03C8}',
+    '\u{03C9}',
+    '\u{03CA}',
+    '\u{03CB}',
+    '\u{03CC}',
+    '\u{03CD}',
+    '\u{03CE}',
+    char::REPLACEMENT_CHARACTER,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::{
+        FontAttributes, Macro, ObjectBuilder, ObjectLabel, ObjectLabelReferenceList, OutputString,
+        StringVariable,
+    };
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    #[test]
+    fn decode_encode_test() {
+        let polish = "Zażółć gęślą jaźń";
+        let bytes = FontType::Iso8859_2.encode(polish).unwrap();
+        assert_eq!(bytes.len(), polish.chars().count());
+        assert_eq!(bytes[2], 0xBF);
+        assert_eq!(FontType::Iso8859_2.decode(&bytes), polish);
+
+        let greek = "Ταχύτητα";
+        let bytes = FontType::Iso8859_7.encode(greek).unwrap();
+        assert_eq!(FontType::Iso8859_7.decode(&bytes), greek);
+        assert_eq!(
+            FontType::Iso8859_5.decode(&[0xC1, 0xDA, 0xDE, 0xE0]),
+            "Скор"
+        );
+        assert_eq!(
+            FontType::Iso8859_15.encode("5 €").unwrap(),
+            vec![b'5', b' ', 0xA4]
+        );
+        assert_eq!(FontType::Iso8859_1.decode(&[0xE9]), "é");
+
+        assert_eq!(
+            FontType::Iso8859_1.encode("Ωm"),
+            Err(EncodingError::UnrepresentableCharacter {
+                character: 'Ω',
+                font_type: FontType::Iso8859_1
+            })
+        );
+        // Not defined in ISO 8859-7
+        assert_eq!(FontType::Iso8859_7.decode(&[0xAE]), "\u{FFFD}");
+        assert!(FontType::Iso8859_7.encode("\u{FFFD}").is_err());
+    }
+
+    #[test]
+    fn wide_string_test() {
+        let v4 = VtVersion::Version4;
+        let bytes = FontType::Iso8859_1.encode_for_version("Ωm", v4).unwrap();
+        assert_eq!(bytes, vec![0xFF, 0xFE, 0xA9, 0x03, b'm', 0x00]);
+        assert_eq!(FontType::Iso8859_1.decode(&bytes), "Ωm");
+        assert_eq!(
+            FontType::Iso8859_7.encode_for_version("Ωm", v4),
+            Ok(vec![0xD9, b'm'])
+        );
+        // Would be read as WideString
+        assert_eq!(
+            FontType::Iso8859_1.encode_for_version("ÿþ", v4),
+            Ok(encode_wide("ÿþ"))
+        );
+
+        let v3 = VtVersion::Version3;
+        assert!(FontType::Iso8859_1.encode_for_version("Ωm", v3).is_err());
+        assert_eq!(
+            FontType::Iso8859_1.encode_for_version("ÿþ", v3),
+            Ok(vec![0xFF, 0xFE])
+        );
+    }
+
+    fn greek_pool() -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        pool.add(
+            FontAttributes::builder()
+                .font_type(FontType::Iso8859_7)
+                .build(id(1000)),
+        );
+        pool.add(
+            OutputString::builder(id(1000))
+                .variable_reference(id(2000))
+                .value("Ώρα".to_string())
+                .build(id(3000)),
+        );
+        pool.add(Object::StringVariable(StringVariable {
+            id: id(2000),
+            value: "Δ".to_string(),
+        }));
+        pool.add(Object::StringVariable(StringVariable {
+            id: id(2001),
+            value: "Ω".to_string(),
+        }));
+        pool.add(Object::Macro(
+            Macro::builder(id(1))
+                .change_string_value(id(2000), "Γ")
+                .build(),
+        ));
+        pool
+    }
+
+    #[test]
+    fn pool_round_trip_test() {
+        let pool = greek_pool();
+        assert_eq!(pool.string_font_type(id(3000)), FontType::Iso8859_7);
+        assert_eq!(pool.string_font_type(id(2000)), FontType::Iso8859_7);
+        assert_eq!(pool.string_font_type(id(2001)), FontType::Iso8859_1);
+
+        // The string variable without a font needs a WideString
+        assert_eq!(
+            pool.as_iop(),
+            Err(EncodingError::UnrepresentableCharacter {
+                character: 'Ω',
+                font_type: FontType::Iso8859_1
+            })
+        );
+        let mut pool = pool;
+        pool.set_supported_vt_version(VtVersion::Version4);
+
+        let iop = pool.as_iop().unwrap();
+        // The output string value is 3 bytes long, the string variable without a font is wide
+        assert!(iop.windows(5).any(|w| w == [0x03, 0x00, 0xBF, 0xF1, 0xE1]));
+        assert!(iop.windows(4).any(|w| w == [0xFF, 0xFE, 0xA9, 0x03]));
+
+        let read = ObjectPool::from_iop(iop);
+        assert_eq!(read.objects(), pool.objects());
+    }
+
+    #[test]
+    fn encode_string_value_test() {
+        let mut pool = greek_pool();
+        assert_eq!(pool.encode_string_value(id(2000), "Ω"), Ok(vec![0xD9]));
+        assert!(pool.encode_string_value(id(2001), "Ω").is_err());
+        pool.set_supported_vt_version(VtVersion::Version4);
+        assert_eq!(
+            pool.encode_string_value(id(2001), "Ω"),
+            Ok(vec![0xFF, 0xFE, 0xA9, 0x03])
+        );
+
+        let command = MacroCommand::ChangeStringValue {
+            object: id(2000),
+            value: "Ω".to_string(),
+        };
+        assert_eq!(
+            pool.write_command(&command),
+            Ok(vec![0xB3, 0xD0, 0x07, 0x01, 0x00, 0xD9])
+        );
+    }
+
+    #[test]
+    fn single_object_encoding_test() {
+        let variable = Object::StringVariable(StringVariable {
+            id: id(2000),
+            value: "é".to_string(),
+        });
+        assert_eq!(variable.write(), Ok(vec![0xD0, 0x07, 22, 0x01, 0x00, 0xE9]));
+
+        // Without a pool there is no font type or VT version to encode other characters with
+        let variable = Object::StringVariable(StringVariable {
+            id: id(2000),
+            value: "Ω".to_string(),
+        });
+        let error = EncodingError::UnrepresentableCharacter {
+            character: 'Ω',
+            font_type: FontType::Iso8859_1,
+        };
+        assert_eq!(variable.write(), Err(error));
+
+        let mut data = vec![0x01];
+        let command = MacroCommand::ChangeStringValue {
+            object: id(2000),
+            value: "Ω".to_string(),
+        };
+        assert_eq!(command.write(&mut data), Err(error));
+        assert_eq!(data, [0x01]);
+    }
+
+    #[test]
+    fn object_label_font_type_test() {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::ObjectLabelReferenceList(ObjectLabelReferenceList {
+            id: id(1),
+            object_labels: vec![ObjectLabel {
+                id: id(3000),
+                string_variable_reference: id(2000).into(),
+                font_type: FontType::Iso8859_5,
+                graphic_representation: Default::default(),
+            }],
+        }));
+        assert_eq!(pool.string_font_type(id(2000)), FontType::Iso8859_5);
+    }
+}
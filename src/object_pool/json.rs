@@ -0,0 +1,131 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::colour::Colour;
+use crate::object_pool::object::Object;
+use crate::object_pool::object_pool::ObjectPool;
+use crate::object_pool::vt_version::VtVersion;
+use serde::de::Error;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl ObjectPool {
+    /// Serializes the pool as pretty printed JSON
+    ///
+    /// Objects are tagged with their type, like `{"type": "DataMask", "id": 1000, ...}`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// The colour map and palette are only written if they differ from the defaults
+impl Serialize for ObjectPool {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let default = ObjectPool::new();
+        let mut pool = serializer.serialize_struct("ObjectPool", 4)?;
+        pool.serialize_field("supported_vt_version", &self.supported_vt_version())?;
+        if self.colour_map() != default.colour_map() {
+            pool.serialize_field("colour_map", &self.colour_map()[..])?;
+        } else {
+            pool.skip_field("colour_map")?;
+        }
+        if self.colour_palette() != default.colour_palette() {
+            pool.serialize_field("colour_palette", &self.colour_palette()[..])?;
+        } else {
+            pool.skip_field("colour_palette")?;
+        }
+        pool.serialize_field("objects", self.objects())?;
+        pool.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct ObjectPoolData {
+    #[serde(default)]
+    supported_vt_version: VtVersion,
+    colour_map: Option<Vec<u8>>,
+    colour_palette: Option<Vec<Colour>>,
+    objects: Vec<Object>,
+}
+
+impl<'de> Deserialize<'de> for ObjectPool {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ObjectPoolData::deserialize(deserializer)?;
+        let mut pool = ObjectPool::new();
+        pool.set_supported_vt_version(data.supported_vt_version);
+        if let Some(colour_map) = data.colour_map {
+            let colour_map = colour_map
+                .try_into()
+                .map_err(|map: Vec<u8>| D::Error::invalid_length(map.len(), &"256 colours"))?;
+            pool.set_colour_map(colour_map);
+        }
+        if let Some(colour_palette) = data.colour_palette {
+            let colour_palette = colour_palette.try_into().map_err(|palette: Vec<Colour>| {
+                D::Error::invalid_length(palette.len(), &"256 colours")
+            })?;
+            pool.set_colour_palette(colour_palette);
+        }
+        for object in data.objects {
+            pool.add(object);
+        }
+        Ok(pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network_management::name::NAME;
+    use crate::object_pool::object::ExternalReferenceName;
+    use crate::object_pool::object_attributes::ExternalReferenceNameOptions;
+    use crate::object_pool::object_id::ObjectId;
+
+    #[test]
+    fn json_round_trip_test_pool() {
+        let data = include_bytes!("../../resources/test/AgIsoStack-rs-test-pool.iop");
+        let (pool, _) = ObjectPool::from_iop_lenient(data.iter().copied());
+
+        let json = pool.to_json().unwrap();
+        let json_pool = ObjectPool::from_json(&json).unwrap();
+        assert_eq!(json_pool.objects(), pool.objects());
+        assert_eq!(
+            json_pool.supported_vt_version(),
+            pool.supported_vt_version()
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(json.get("colour_map").is_none());
+        assert!(json.get("colour_palette").is_none());
+    }
+
+    #[test]
+    fn json_representation_test() {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::ExternalReferenceName(ExternalReferenceName {
+            id: ObjectId::new(42000).unwrap(),
+            options: ExternalReferenceNameOptions { enabled: true },
+            name: NAME::new(0xA00082000C2FE5A8),
+        }));
+
+        let json: serde_json::Value = serde_json::from_str(&pool.to_json().unwrap()).unwrap();
+        let object = &json["objects"][0];
+        assert_eq!(object["type"], "ExternalReferenceName");
+        assert_eq!(object["id"], 42000);
+        assert_eq!(object["options"]["enabled"], true);
+        assert_eq!(object["name"], "0xA00082000C2FE5A8");
+        assert_eq!(json["supported_vt_version"], "Version3");
+    }
+
+    #[test]
+    fn from_json_errors_test() {
+        // 0xFFFF is the NULL object ID
+        assert!(ObjectPool::from_json(
+            r#"{"objects": [{"type": "NumberVariable", "id": 65535, "value": 0}]}"#
+        )
+        .is_err());
+        assert!(ObjectPool::from_json(r#"{"colour_map": [0], "objects": []}"#).is_err());
+        assert!(ObjectPool::from_json(r#"{"objects": []}"#).is_ok());
+    }
+}
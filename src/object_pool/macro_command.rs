@@ -0,0 +1,1422 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::object::Macro;
+use crate::object_pool::object_attributes::{
+    FillType, FontSize, FontStyle, FontType, LineArt, LineDirection, Point,
+};
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::{EncodingError, ParseError};
+
+/// A VT command that can be part of a macro, see ISO 11783-6 Annex J
+///
+/// Commands are encoded like the corresponding VT command messages: every command except Change
+/// String Value, Change Child Position and some Graphics Context commands is 8 bytes long, padded
+/// with 0xFF.
+///
+/// Strings are read and written in ISO 8859-1, [`ObjectPool::write_command`] encodes them with the
+/// font type of the object they are shown with.
+///
+/// [`ObjectPool::write_command`]: crate::object_pool::ObjectPool::write_command
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MacroCommand {
+    HideShowObject {
+        object: ObjectId,
+        show: bool,
+    },
+    EnableDisableObject {
+        object: ObjectId,
+        enable: bool,
+    },
+    /// Selects an input object, or removes the selection if `object` is NULL
+    SelectInputObject {
+        object: NullableObjectId,
+        /// Opens the object for data input instead of only focusing it
+        open_for_input: bool,
+    },
+    ControlAudioSignal {
+        activations: u8,
+        /// Frequency in Hz
+        frequency: u16,
+        /// On time in ms
+        on_time: u16,
+        /// Off time in ms
+        off_time: u16,
+    },
+    /// Sets the volume in percent
+    SetAudioVolume {
+        volume: u8,
+    },
+    /// Moves a child relative to its current position, 127 means no change
+    ChangeChildLocation {
+        parent: ObjectId,
+        child: ObjectId,
+        x_change: u8,
+        y_change: u8,
+    },
+    ChangeSize {
+        object: ObjectId,
+        width: u16,
+        height: u16,
+    },
+    ChangeBackgroundColour {
+        object: ObjectId,
+        colour: u8,
+    },
+    ChangeNumericValue {
+        object: ObjectId,
+        value: u32,
+    },
+    ChangeEndPoint {
+        object: ObjectId,
+        width: u16,
+        height: u16,
+        line_direction: LineDirection,
+    },
+    ChangeFontAttributes {
+        object: ObjectId,
+        colour: u8,
+        size: FontSize,
+        font_type: FontType,
+        style: FontStyle,
+    },
+    ChangeLineAttributes {
+        object: ObjectId,
+        colour: u8,
+        width: u8,
+        line_art: LineArt,
+    },
+    ChangeFillAttributes {
+        object: ObjectId,
+        fill_type: FillType,
+        colour: u8,
+        pattern: NullableObjectId,
+    },
+    ChangeActiveMask {
+        working_set: ObjectId,
+        mask: ObjectId,
+    },
+    ChangeSoftKeyMask {
+        /// 1 for data masks, 2 for alarm masks
+        mask_type: u8,
+        mask: ObjectId,
+        soft_key_mask: NullableObjectId,
+    },
+    /// Sets the attribute with the attribute ID (AID) `attribute_id` to `value`
+    ChangeAttribute {
+        object: ObjectId,
+        attribute_id: u8,
+        value: u32,
+    },
+    ChangePriority {
+        object: ObjectId,
+        priority: u8,
+    },
+    ChangeListItem {
+        object: ObjectId,
+        index: u8,
+        new_object: NullableObjectId,
+    },
+    ChangeStringValue {
+        object: ObjectId,
+        value: String,
+    },
+    ChangeChildPosition {
+        parent: ObjectId,
+        child: ObjectId,
+        x: i16,
+        y: i16,
+    },
+    ChangeObjectLabel {
+        object: ObjectId,
+        string_variable: NullableObjectId,
+        font_type: FontType,
+        graphic: NullableObjectId,
+    },
+    ChangePolygonPoint {
+        object: ObjectId,
+        index: u8,
+        x: u16,
+        y: u16,
+    },
+    ChangePolygonScale {
+        object: ObjectId,
+        width: u16,
+        height: u16,
+    },
+    /// Draws on the Graphics Context object `object`, VT version 4 and later
+    GraphicsContext {
+        object: ObjectId,
+        command: GraphicsContextCommand,
+    },
+    SelectColourMap {
+        object: ObjectId,
+    },
+    ExecuteExtendedMacro {
+        object: ObjectId,
+    },
+    /// Locks a data mask for at most `timeout` ms, or unlocks it
+    LockUnlockMask {
+        lock: bool,
+        mask: ObjectId,
+        timeout: u16,
+    },
+    ExecuteMacro {
+        macro_id: u8,
+    },
+    /// The remaining bytes of a macro that could not be decoded, like commands from newer versions
+    /// of the standard
+    Unknown(Vec<u8>),
+}
+
+/// A sub-command of the Graphics Context command, see ISO 11783-6 Annex F
+///
+/// Coordinates are in pixels of the canvas, relative to the graphics cursor where the standard
+/// says so.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GraphicsContextCommand {
+    SetGraphicsCursor {
+        x: i16,
+        y: i16,
+    },
+    MoveGraphicsCursor {
+        x: i16,
+        y: i16,
+    },
+    SetForegroundColour {
+        colour: u8,
+    },
+    SetBackgroundColour {
+        colour: u8,
+    },
+    /// Sets the line attributes, NULL draws no lines
+    SetLineAttributesObjectId {
+        object: NullableObjectId,
+    },
+    /// Sets the fill attributes, NULL draws no fill
+    SetFillAttributesObjectId {
+        object: NullableObjectId,
+    },
+    SetFontAttributesObjectId {
+        object: NullableObjectId,
+    },
+    EraseRectangle {
+        width: u16,
+        height: u16,
+    },
+    DrawPoint {
+        x: i16,
+        y: i16,
+    },
+    DrawLine {
+        x: i16,
+        y: i16,
+    },
+    DrawRectangle {
+        width: u16,
+        height: u16,
+    },
+    DrawClosedEllipse {
+        width: u16,
+        height: u16,
+    },
+    DrawPolygon {
+        points: Vec<Point<i16>>,
+    },
+    /// Draws `text` at the graphics cursor, with the background colour unless `transparent`
+    DrawText {
+        transparent: bool,
+        text: String,
+    },
+    PanViewport {
+        x: i16,
+        y: i16,
+    },
+    ZoomViewport {
+        zoom: f32,
+    },
+    PanAndZoomViewport {
+        x: i16,
+        y: i16,
+        zoom: f32,
+    },
+    ChangeViewportSize {
+        width: u16,
+        height: u16,
+    },
+    DrawVtObject {
+        object: ObjectId,
+    },
+    CopyCanvasToPictureGraphic {
+        object: ObjectId,
+    },
+    CopyViewportToPictureGraphic {
+        object: ObjectId,
+    },
+}
+
+impl GraphicsContextCommand {
+    pub const SET_GRAPHICS_CURSOR: u8 = 0x00;
+    pub const MOVE_GRAPHICS_CURSOR: u8 = 0x01;
+    pub const SET_FOREGROUND_COLOUR: u8 = 0x02;
+    pub const SET_BACKGROUND_COLOUR: u8 = 0x03;
+    pub const SET_LINE_ATTRIBUTES_OBJECT_ID: u8 = 0x04;
+    pub const SET_FILL_ATTRIBUTES_OBJECT_ID: u8 = 0x05;
+    pub const SET_FONT_ATTRIBUTES_OBJECT_ID: u8 = 0x06;
+    pub const ERASE_RECTANGLE: u8 = 0x07;
+    pub const DRAW_POINT: u8 = 0x08;
+    pub const DRAW_LINE: u8 = 0x09;
+    pub const DRAW_RECTANGLE: u8 = 0x0A;
+    pub const DRAW_CLOSED_ELLIPSE: u8 = 0x0B;
+    pub const DRAW_POLYGON: u8 = 0x0C;
+    pub const DRAW_TEXT: u8 = 0x0D;
+    pub const PAN_VIEWPORT: u8 = 0x0E;
+    pub const ZOOM_VIEWPORT: u8 = 0x0F;
+    pub const PAN_AND_ZOOM_VIEWPORT: u8 = 0x10;
+    pub const CHANGE_VIEWPORT_SIZE: u8 = 0x11;
+    pub const DRAW_VT_OBJECT: u8 = 0x12;
+    pub const COPY_CANVAS_TO_PICTURE_GRAPHIC: u8 = 0x13;
+    pub const COPY_VIEWPORT_TO_PICTURE_GRAPHIC: u8 = 0x14;
+
+    /// Polygons and text end the command, they are not padded to 8 bytes
+    fn is_variable_length(&self) -> bool {
+        matches!(
+            self,
+            GraphicsContextCommand::DrawPolygon { .. } | GraphicsContextCommand::DrawText { .. }
+        )
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, ParseError> {
+        let command = match r.u8("sub_command")? {
+            Self::SET_GRAPHICS_CURSOR => GraphicsContextCommand::SetGraphicsCursor {
+                x: r.i16("x")?,
+                y: r.i16("y")?,
+            },
+            Self::MOVE_GRAPHICS_CURSOR => GraphicsContextCommand::MoveGraphicsCursor {
+                x: r.i16("x")?,
+                y: r.i16("y")?,
+            },
+            Self::SET_FOREGROUND_COLOUR => GraphicsContextCommand::SetForegroundColour {
+                colour: r.u8("colour")?,
+            },
+            Self::SET_BACKGROUND_COLOUR => GraphicsContextCommand::SetBackgroundColour {
+                colour: r.u8("colour")?,
+            },
+            Self::SET_LINE_ATTRIBUTES_OBJECT_ID => {
+                GraphicsContextCommand::SetLineAttributesObjectId {
+                    object: r.u16("object")?.into(),
+                }
+            }
+            Self::SET_FILL_ATTRIBUTES_OBJECT_ID => {
+                GraphicsContextCommand::SetFillAttributesObjectId {
+                    object: r.u16("object")?.into(),
+                }
+            }
+            Self::SET_FONT_ATTRIBUTES_OBJECT_ID => {
+                GraphicsContextCommand::SetFontAttributesObjectId {
+                    object: r.u16("object")?.into(),
+                }
+            }
+            Self::ERASE_RECTANGLE => GraphicsContextCommand::EraseRectangle {
+                width: r.u16("width")?,
+                height: r.u16("height")?,
+            },
+            Self::DRAW_POINT => GraphicsContextCommand::DrawPoint {
+                x: r.i16("x")?,
+                y: r.i16("y")?,
+            },
+            Self::DRAW_LINE => GraphicsContextCommand::DrawLine {
+                x: r.i16("x")?,
+                y: r.i16("y")?,
+            },
+            Self::DRAW_RECTANGLE => GraphicsContextCommand::DrawRectangle {
+                width: r.u16("width")?,
+                height: r.u16("height")?,
+            },
+            Self::DRAW_CLOSED_ELLIPSE => GraphicsContextCommand::DrawClosedEllipse {
+                width: r.u16("width")?,
+                height: r.u16("height")?,
+            },
+            Self::DRAW_POLYGON => {
+                let count = r.u8("points")?;
+                let points = (0..count)
+                    .map(|_| {
+                        Ok(Point {
+                            x: r.i16("points")?,
+                            y: r.i16("points")?,
+                        })
+                    })
+                    .collect::<Result<_, ParseError>>()?;
+                GraphicsContextCommand::DrawPolygon { points }
+            }
+            Self::DRAW_TEXT => {
+                let transparent = r.bool("transparent")?;
+                let length = r.u8("length")?.into();
+                let text = r
+                    .bytes("text", length)?
+                    .iter()
+                    .map(|&c| c as char)
+                    .collect();
+                GraphicsContextCommand::DrawText { transparent, text }
+            }
+            Self::PAN_VIEWPORT => GraphicsContextCommand::PanViewport {
+                x: r.i16("x")?,
+                y: r.i16("y")?,
+            },
+            Self::ZOOM_VIEWPORT => GraphicsContextCommand::ZoomViewport {
+                zoom: r.f32("zoom")?,
+            },
+            Self::PAN_AND_ZOOM_VIEWPORT => GraphicsContextCommand::PanAndZoomViewport {
+                x: r.i16("x")?,
+                y: r.i16("y")?,
+                zoom: r.f32("zoom")?,
+            },
+            Self::CHANGE_VIEWPORT_SIZE => GraphicsContextCommand::ChangeViewportSize {
+                width: r.u16("width")?,
+                height: r.u16("height")?,
+            },
+            Self::DRAW_VT_OBJECT => GraphicsContextCommand::DrawVtObject {
+                object: r.object_id("object")?,
+            },
+            Self::COPY_CANVAS_TO_PICTURE_GRAPHIC => {
+                GraphicsContextCommand::CopyCanvasToPictureGraphic {
+                    object: r.object_id("object")?,
+                }
+            }
+            Self::COPY_VIEWPORT_TO_PICTURE_GRAPHIC => {
+                GraphicsContextCommand::CopyViewportToPictureGraphic {
+                    object: r.object_id("object")?,
+                }
+            }
+            sub_command => {
+                return Err(ParseError::InvalidValue {
+                    field: "sub_command",
+                    value: sub_command.into(),
+                })
+            }
+        };
+        Ok(command)
+    }
+
+    fn write(&self, w: &mut Writer) -> Result<(), EncodingError> {
+        match self {
+            GraphicsContextCommand::SetGraphicsCursor { x, y } => {
+                w.u8(Self::SET_GRAPHICS_CURSOR);
+                w.i16(*x);
+                w.i16(*y);
+            }
+            GraphicsContextCommand::MoveGraphicsCursor { x, y } => {
+                w.u8(Self::MOVE_GRAPHICS_CURSOR);
+                w.i16(*x);
+                w.i16(*y);
+            }
+            GraphicsContextCommand::SetForegroundColour { colour } => {
+                w.u8(Self::SET_FOREGROUND_COLOUR);
+                w.u8(*colour);
+            }
+            GraphicsContextCommand::SetBackgroundColour { colour } => {
+                w.u8(Self::SET_BACKGROUND_COLOUR);
+                w.u8(*colour);
+            }
+            GraphicsContextCommand::SetLineAttributesObjectId { object } => {
+                w.u8(Self::SET_LINE_ATTRIBUTES_OBJECT_ID);
+                w.u16(*object);
+            }
+            GraphicsContextCommand::SetFillAttributesObjectId { object } => {
+                w.u8(Self::SET_FILL_ATTRIBUTES_OBJECT_ID);
+                w.u16(*object);
+            }
+            GraphicsContextCommand::SetFontAttributesObjectId { object } => {
+                w.u8(Self::SET_FONT_ATTRIBUTES_OBJECT_ID);
+                w.u16(*object);
+            }
+            GraphicsContextCommand::EraseRectangle { width, height } => {
+                w.u8(Self::ERASE_RECTANGLE);
+                w.u16(*width);
+                w.u16(*height);
+            }
+            GraphicsContextCommand::DrawPoint { x, y } => {
+                w.u8(Self::DRAW_POINT);
+                w.i16(*x);
+                w.i16(*y);
+            }
+            GraphicsContextCommand::DrawLine { x, y } => {
+                w.u8(Self::DRAW_LINE);
+                w.i16(*x);
+                w.i16(*y);
+            }
+            GraphicsContextCommand::DrawRectangle { width, height } => {
+                w.u8(Self::DRAW_RECTANGLE);
+                w.u16(*width);
+                w.u16(*height);
+            }
+            GraphicsContextCommand::DrawClosedEllipse { width, height } => {
+                w.u8(Self::DRAW_CLOSED_ELLIPSE);
+                w.u16(*width);
+                w.u16(*height);
+            }
+            GraphicsContextCommand::DrawPolygon { points } => {
+                w.u8(Self::DRAW_POLYGON);
+                w.u8(points.len() as u8);
+                for point in points {
+                    w.i16(point.x);
+                    w.i16(point.y);
+                }
+            }
+            GraphicsContextCommand::DrawText { transparent, text } => {
+                let text = FontType::Iso8859_1.encode(text)?;
+                w.u8(Self::DRAW_TEXT);
+                w.u8(*transparent as u8);
+                w.u8(text.len() as u8);
+                w.0.extend(text);
+            }
+            GraphicsContextCommand::PanViewport { x, y } => {
+                w.u8(Self::PAN_VIEWPORT);
+                w.i16(*x);
+                w.i16(*y);
+            }
+            GraphicsContextCommand::ZoomViewport { zoom } => {
+                w.u8(Self::ZOOM_VIEWPORT);
+                w.u32(zoom.to_bits());
+            }
+            GraphicsContextCommand::PanAndZoomViewport { x, y, zoom } => {
+                w.u8(Self::PAN_AND_ZOOM_VIEWPORT);
+                w.i16(*x);
+                w.i16(*y);
+                w.u32(zoom.to_bits());
+            }
+            GraphicsContextCommand::ChangeViewportSize { width, height } => {
+                w.u8(Self::CHANGE_VIEWPORT_SIZE);
+                w.u16(*width);
+                w.u16(*height);
+            }
+            GraphicsContextCommand::DrawVtObject { object } => {
+                w.u8(Self::DRAW_VT_OBJECT);
+                w.u16(*object);
+            }
+            GraphicsContextCommand::CopyCanvasToPictureGraphic { object } => {
+                w.u8(Self::COPY_CANVAS_TO_PICTURE_GRAPHIC);
+                w.u16(*object);
+            }
+            GraphicsContextCommand::CopyViewportToPictureGraphic { object } => {
+                w.u8(Self::COPY_VIEWPORT_TO_PICTURE_GRAPHIC);
+                w.u16(*object);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MacroCommand {
+    pub const HIDE_SHOW_OBJECT: u8 = 0xA0;
+    pub const ENABLE_DISABLE_OBJECT: u8 = 0xA1;
+    pub const SELECT_INPUT_OBJECT: u8 = 0xA2;
+    pub const CONTROL_AUDIO_SIGNAL: u8 = 0xA3;
+    pub const SET_AUDIO_VOLUME: u8 = 0xA4;
+    pub const CHANGE_CHILD_LOCATION: u8 = 0xA5;
+    pub const CHANGE_SIZE: u8 = 0xA6;
+    pub const CHANGE_BACKGROUND_COLOUR: u8 = 0xA7;
+    pub const CHANGE_NUMERIC_VALUE: u8 = 0xA8;
+    pub const CHANGE_END_POINT: u8 = 0xA9;
+    pub const CHANGE_FONT_ATTRIBUTES: u8 = 0xAA;
+    pub const CHANGE_LINE_ATTRIBUTES: u8 = 0xAB;
+    pub const CHANGE_FILL_ATTRIBUTES: u8 = 0xAC;
+    pub const CHANGE_ACTIVE_MASK: u8 = 0xAD;
+    pub const CHANGE_SOFT_KEY_MASK: u8 = 0xAE;
+    pub const CHANGE_ATTRIBUTE: u8 = 0xAF;
+    pub const CHANGE_PRIORITY: u8 = 0xB0;
+    pub const CHANGE_LIST_ITEM: u8 = 0xB1;
+    pub const CHANGE_STRING_VALUE: u8 = 0xB3;
+    pub const CHANGE_CHILD_POSITION: u8 = 0xB4;
+    pub const CHANGE_OBJECT_LABEL: u8 = 0xB5;
+    pub const CHANGE_POLYGON_POINT: u8 = 0xB6;
+    pub const CHANGE_POLYGON_SCALE: u8 = 0xB7;
+    pub const GRAPHICS_CONTEXT: u8 = 0xB8;
+    pub const SELECT_COLOUR_MAP: u8 = 0xBA;
+    pub const EXECUTE_EXTENDED_MACRO: u8 = 0xBC;
+    pub const LOCK_UNLOCK_MASK: u8 = 0xBD;
+    pub const EXECUTE_MACRO: u8 = 0xBE;
+
+    /// Decodes the command at the start of `data`, returns it with its length in bytes
+    ///
+    /// Never returns [`MacroCommand::Unknown`], unknown commands are an error.
+    pub fn read(data: &[u8]) -> Result<(MacroCommand, usize), ParseError> {
+        let mut r = Reader { data, pos: 0 };
+        let command = match r.u8("command")? {
+            Self::HIDE_SHOW_OBJECT => MacroCommand::HideShowObject {
+                object: r.object_id("object")?,
+                show: r.bool("show")?,
+            },
+            Self::ENABLE_DISABLE_OBJECT => MacroCommand::EnableDisableObject {
+                object: r.object_id("object")?,
+                enable: r.bool("enable")?,
+            },
+            Self::SELECT_INPUT_OBJECT => MacroCommand::SelectInputObject {
+                object: r.u16("object")?.into(),
+                open_for_input: match r.u8("open_for_input")? {
+                    0x00 => true,
+                    0xFF => false,
+                    value => {
+                        return Err(ParseError::InvalidValue {
+                            field: "open_for_input",
+                            value: value.into(),
+                        })
+                    }
+                },
+            },
+            Self::CONTROL_AUDIO_SIGNAL => MacroCommand::ControlAudioSignal {
+                activations: r.u8("activations")?,
+                frequency: r.u16("frequency")?,
+                on_time: r.u16("on_time")?,
+                off_time: r.u16("off_time")?,
+            },
+            Self::SET_AUDIO_VOLUME => MacroCommand::SetAudioVolume {
+                volume: r.u8("volume")?,
+            },
+            Self::CHANGE_CHILD_LOCATION => MacroCommand::ChangeChildLocation {
+                parent: r.object_id("parent")?,
+                child: r.object_id("child")?,
+                x_change: r.u8("x_change")?,
+                y_change: r.u8("y_change")?,
+            },
+            Self::CHANGE_SIZE => MacroCommand::ChangeSize {
+                object: r.object_id("object")?,
+                width: r.u16("width")?,
+                height: r.u16("height")?,
+            },
+            Self::CHANGE_BACKGROUND_COLOUR => MacroCommand::ChangeBackgroundColour {
+                object: r.object_id("object")?,
+                colour: r.u8("colour")?,
+            },
+            Self::CHANGE_NUMERIC_VALUE => {
+                let object = r.object_id("object")?;
+                r.u8("reserved")?;
+                MacroCommand::ChangeNumericValue {
+                    object,
+                    value: r.u32("value")?,
+                }
+            }
+            Self::CHANGE_END_POINT => MacroCommand::ChangeEndPoint {
+                object: r.object_id("object")?,
+                width: r.u16("width")?,
+                height: r.u16("height")?,
+                line_direction: r.u8("line_direction")?.try_into()?,
+            },
+            Self::CHANGE_FONT_ATTRIBUTES => {
+                let object = r.object_id("object")?;
+                let colour = r.u8("colour")?;
+                let size = r.u8("size")?;
+                let font_type = r.u8("font_type")?.try_into()?;
+                let style = r.u8("style")?.into();
+                MacroCommand::ChangeFontAttributes {
+                    object,
+                    colour,
+                    size: FontSize::decode(size, style)?,
+                    font_type,
+                    style,
+                }
+            }
+            Self::CHANGE_LINE_ATTRIBUTES => MacroCommand::ChangeLineAttributes {
+                object: r.object_id("object")?,
+                colour: r.u8("colour")?,
+                width: r.u8("width")?,
+                line_art: r.u16("line_art")?.into(),
+            },
+            Self::CHANGE_FILL_ATTRIBUTES => MacroCommand::ChangeFillAttributes {
+                object: r.object_id("object")?,
+                fill_type: r.u8("fill_type")?.try_into()?,
+                colour: r.u8("colour")?,
+                pattern: r.u16("pattern")?.into(),
+            },
+            Self::CHANGE_ACTIVE_MASK => MacroCommand::ChangeActiveMask {
+                working_set: r.object_id("working_set")?,
+                mask: r.object_id("mask")?,
+            },
+            Self::CHANGE_SOFT_KEY_MASK => MacroCommand::ChangeSoftKeyMask {
+                mask_type: r.u8("mask_type")?,
+                mask: r.object_id("mask")?,
+                soft_key_mask: r.u16("soft_key_mask")?.into(),
+            },
+            Self::CHANGE_ATTRIBUTE => MacroCommand::ChangeAttribute {
+                object: r.object_id("object")?,
+                attribute_id: r.u8("attribute_id")?,
+                value: r.u32("value")?,
+            },
+            Self::CHANGE_PRIORITY => MacroCommand::ChangePriority {
+                object: r.object_id("object")?,
+                priority: r.u8("priority")?,
+            },
+            Self::CHANGE_LIST_ITEM => MacroCommand::ChangeListItem {
+                object: r.object_id("object")?,
+                index: r.u8("index")?,
+                new_object: r.u16("new_object")?.into(),
+            },
+            Self::CHANGE_STRING_VALUE => {
+                let object = r.object_id("object")?;
+                let length = r.u16("length")?.into();
+                let value = r
+                    .bytes("value", length)?
+                    .iter()
+                    .map(|&c| c as char)
+                    .collect();
+                // The string is the end of the command, it has no padding
+                return Ok((MacroCommand::ChangeStringValue { object, value }, r.pos));
+            }
+            Self::CHANGE_CHILD_POSITION => {
+                let command = MacroCommand::ChangeChildPosition {
+                    parent: r.object_id("parent")?,
+                    child: r.object_id("child")?,
+                    x: r.u16("x")? as i16,
+                    y: r.u16("y")? as i16,
+                };
+                return Ok((command, r.pos));
+            }
+            Self::CHANGE_OBJECT_LABEL => MacroCommand::ChangeObjectLabel {
+                object: r.object_id("object")?,
+                string_variable: r.u16("string_variable")?.into(),
+                font_type: r.u8("font_type")?.try_into()?,
+                graphic: r.u16("graphic")?.into(),
+            },
+            Self::CHANGE_POLYGON_POINT => MacroCommand::ChangePolygonPoint {
+                object: r.object_id("object")?,
+                index: r.u8("index")?,
+                x: r.u16("x")?,
+                y: r.u16("y")?,
+            },
+            Self::CHANGE_POLYGON_SCALE => MacroCommand::ChangePolygonScale {
+                object: r.object_id("object")?,
+                width: r.u16("width")?,
+                height: r.u16("height")?,
+            },
+            Self::GRAPHICS_CONTEXT => {
+                let object = r.object_id("object")?;
+                let command = GraphicsContextCommand::read(&mut r)?;
+                if command.is_variable_length() {
+                    return Ok((MacroCommand::GraphicsContext { object, command }, r.pos));
+                }
+                MacroCommand::GraphicsContext { object, command }
+            }
+            Self::SELECT_COLOUR_MAP => MacroCommand::SelectColourMap {
+                object: r.object_id("object")?,
+            },
+            Self::EXECUTE_EXTENDED_MACRO => MacroCommand::ExecuteExtendedMacro {
+                object: r.object_id("object")?,
+            },
+            Self::LOCK_UNLOCK_MASK => MacroCommand::LockUnlockMask {
+                lock: r.bool("lock")?,
+                mask: r.object_id("mask")?,
+                timeout: r.u16("timeout")?,
+            },
+            Self::EXECUTE_MACRO => MacroCommand::ExecuteMacro {
+                macro_id: r.u8("macro_id")?,
+            },
+            command => {
+                return Err(ParseError::InvalidValue {
+                    field: "command",
+                    value: command.into(),
+                })
+            }
+        };
+        // Skip the padding up to the fixed command length
+        r.bytes("padding", 8usize.saturating_sub(r.pos))?;
+        Ok((command, r.pos))
+    }
+
+    /// Decodes all commands of a macro
+    pub fn read_all(data: &[u8]) -> Result<Vec<MacroCommand>, ParseError> {
+        let mut commands = Vec::new();
+        let mut pos = 0;
+        while pos < data.len() {
+            let (command, length) = Self::read(&data[pos..])?;
+            commands.push(command);
+            pos += length;
+        }
+        Ok(commands)
+    }
+
+    /// Decodes all commands of a macro, keeping everything from the first command that can't be
+    /// decoded as [`MacroCommand::Unknown`] so no data is lost
+    pub fn read_all_lenient(data: &[u8]) -> Vec<MacroCommand> {
+        let mut commands = Vec::new();
+        let mut pos = 0;
+        while pos < data.len() {
+            match Self::read(&data[pos..]) {
+                Ok((command, length)) => {
+                    commands.push(command);
+                    pos += length;
+                }
+                Err(_) => {
+                    commands.push(MacroCommand::Unknown(data[pos..].to_vec()));
+                    break;
+                }
+            }
+        }
+        commands
+    }
+
+    /// Encodes the command and appends it to `data`, fails without changing `data` if a string has
+    /// characters that are not in ISO 8859-1
+    pub fn write(&self, data: &mut Vec<u8>) -> Result<(), EncodingError> {
+        let start = data.len();
+        let mut w = Writer(data);
+        match self {
+            MacroCommand::HideShowObject { object, show } => {
+                w.u8(Self::HIDE_SHOW_OBJECT);
+                w.u16(*object);
+                w.u8(*show as u8);
+            }
+            MacroCommand::EnableDisableObject { object, enable } => {
+                w.u8(Self::ENABLE_DISABLE_OBJECT);
+                w.u16(*object);
+                w.u8(*enable as u8);
+            }
+            MacroCommand::SelectInputObject {
+                object,
+                open_for_input,
+            } => {
+                w.u8(Self::SELECT_INPUT_OBJECT);
+                w.u16(*object);
+                w.u8(if *open_for_input { 0x00 } else { 0xFF });
+            }
+            MacroCommand::ControlAudioSignal {
+                activations,
+                frequency,
+                on_time,
+                off_time,
+            } => {
+                w.u8(Self::CONTROL_AUDIO_SIGNAL);
+                w.u8(*activations);
+                w.u16(*frequency);
+                w.u16(*on_time);
+                w.u16(*off_time);
+            }
+            MacroCommand::SetAudioVolume { volume } => {
+                w.u8(Self::SET_AUDIO_VOLUME);
+                w.u8(*volume);
+            }
+            MacroCommand::ChangeChildLocation {
+                parent,
+                child,
+                x_change,
+                y_change,
+            } => {
+                w.u8(Self::CHANGE_CHILD_LOCATION);
+                w.u16(*parent);
+                w.u16(*child);
+                w.u8(*x_change);
+                w.u8(*y_change);
+            }
+            MacroCommand::ChangeSize {
+                object,
+                width,
+                height,
+            } => {
+                w.u8(Self::CHANGE_SIZE);
+                w.u16(*object);
+                w.u16(*width);
+                w.u16(*height);
+            }
+            MacroCommand::ChangeBackgroundColour { object, colour } => {
+                w.u8(Self::CHANGE_BACKGROUND_COLOUR);
+                w.u16(*object);
+                w.u8(*colour);
+            }
+            MacroCommand::ChangeNumericValue { object, value } => {
+                w.u8(Self::CHANGE_NUMERIC_VALUE);
+                w.u16(*object);
+                w.u8(0xFF);
+                w.u32(*value);
+            }
+            MacroCommand::ChangeEndPoint {
+                object,
+                width,
+                height,
+                line_direction,
+            } => {
+                w.u8(Self::CHANGE_END_POINT);
+                w.u16(*object);
+                w.u16(*width);
+                w.u16(*height);
+                w.u8(*line_direction);
+            }
+            MacroCommand::ChangeFontAttributes {
+                object,
+                colour,
+                size,
+                font_type,
+                style,
+            } => {
+                w.u8(Self::CHANGE_FONT_ATTRIBUTES);
+                w.u16(*object);
+                w.u8(*colour);
+                w.u8(*size);
+                w.u8(*font_type);
+                w.u8(size.style(*style));
+            }
+            MacroCommand::ChangeLineAttributes {
+                object,
+                colour,
+                width,
+                line_art,
+            } => {
+                w.u8(Self::CHANGE_LINE_ATTRIBUTES);
+                w.u16(*object);
+                w.u8(*colour);
+                w.u8(*width);
+                w.u16(*line_art);
+            }
+            MacroCommand::ChangeFillAttributes {
+                object,
+                fill_type,
+                colour,
+                pattern,
+            } => {
+                w.u8(Self::CHANGE_FILL_ATTRIBUTES);
+                w.u16(*object);
+                w.u8(*fill_type);
+                w.u8(*colour);
+                w.u16(*pattern);
+            }
+            MacroCommand::ChangeActiveMask { working_set, mask } => {
+                w.u8(Self::CHANGE_ACTIVE_MASK);
+                w.u16(*working_set);
+                w.u16(*mask);
+            }
+            MacroCommand::ChangeSoftKeyMask {
+                mask_type,
+                mask,
+                soft_key_mask,
+            } => {
+                w.u8(Self::CHANGE_SOFT_KEY_MASK);
+                w.u8(*mask_type);
+                w.u16(*mask);
+                w.u16(*soft_key_mask);
+            }
+            MacroCommand::ChangeAttribute {
+                object,
+                attribute_id,
+                value,
+            } => {
+                w.u8(Self::CHANGE_ATTRIBUTE);
+                w.u16(*object);
+                w.u8(*attribute_id);
+                w.u32(*value);
+            }
+            MacroCommand::ChangePriority { object, priority } => {
+                w.u8(Self::CHANGE_PRIORITY);
+                w.u16(*object);
+                w.u8(*priority);
+            }
+            MacroCommand::ChangeListItem {
+                object,
+                index,
+                new_object,
+            } => {
+                w.u8(Self::CHANGE_LIST_ITEM);
+                w.u16(*object);
+                w.u8(*index);
+                w.u16(*new_object);
+            }
+            MacroCommand::ChangeStringValue { object, value } => {
+                let value = FontType::Iso8859_1.encode(value)?;
+                w.u8(Self::CHANGE_STRING_VALUE);
+                w.u16(*object);
+                w.u16(value.len() as u16);
+                w.0.extend(value);
+                return Ok(());
+            }
+            MacroCommand::ChangeChildPosition {
+                parent,
+                child,
+                x,
+                y,
+            } => {
+                w.u8(Self::CHANGE_CHILD_POSITION);
+                w.u16(*parent);
+                w.u16(*child);
+                w.u16(*x as u16);
+                w.u16(*y as u16);
+                return Ok(());
+            }
+            MacroCommand::ChangeObjectLabel {
+                object,
+                string_variable,
+                font_type,
+                graphic,
+            } => {
+                w.u8(Self::CHANGE_OBJECT_LABEL);
+                w.u16(*object);
+                w.u16(*string_variable);
+                w.u8(*font_type);
+                w.u16(*graphic);
+            }
+            MacroCommand::ChangePolygonPoint {
+                object,
+                index,
+                x,
+                y,
+            } => {
+                w.u8(Self::CHANGE_POLYGON_POINT);
+                w.u16(*object);
+                w.u8(*index);
+                w.u16(*x);
+                w.u16(*y);
+            }
+            MacroCommand::ChangePolygonScale {
+                object,
+                width,
+                height,
+            } => {
+                w.u8(Self::CHANGE_POLYGON_SCALE);
+                w.u16(*object);
+                w.u16(*width);
+                w.u16(*height);
+            }
+            MacroCommand::GraphicsContext { object, command } => {
+                let mut command_data = Vec::new();
+                command.write(&mut Writer(&mut command_data))?;
+                w.u8(Self::GRAPHICS_CONTEXT);
+                w.u16(*object);
+                w.0.extend(command_data);
+                if command.is_variable_length() {
+                    return Ok(());
+                }
+            }
+            MacroCommand::SelectColourMap { object } => {
+                w.u8(Self::SELECT_COLOUR_MAP);
+                w.u16(*object);
+            }
+            MacroCommand::ExecuteExtendedMacro { object } => {
+                w.u8(Self::EXECUTE_EXTENDED_MACRO);
+                w.u16(*object);
+            }
+            MacroCommand::LockUnlockMask {
+                lock,
+                mask,
+                timeout,
+            } => {
+                w.u8(Self::LOCK_UNLOCK_MASK);
+                w.u8(*lock as u8);
+                w.u16(*mask);
+                w.u16(*timeout);
+            }
+            MacroCommand::ExecuteMacro { macro_id } => {
+                w.u8(Self::EXECUTE_MACRO);
+                w.u8(*macro_id);
+            }
+            MacroCommand::Unknown(bytes) => {
+                w.0.extend(bytes);
+                return Ok(());
+            }
+        }
+        let padding = (start + 8).saturating_sub(data.len());
+        data.extend(std::iter::repeat_n(0xFF, padding));
+        Ok(())
+    }
+
+    /// Encodes a list of commands as the byte stream stored in a Macro object
+    pub fn write_all(commands: &[MacroCommand]) -> Result<Vec<u8>, EncodingError> {
+        let mut data = Vec::new();
+        for command in commands {
+            command.write(&mut data)?;
+        }
+        Ok(data)
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn bytes(&mut self, field: &'static str, length: usize) -> Result<&[u8], ParseError> {
+        let remaining = self.data.len() - self.pos;
+        if remaining < length {
+            return Err(ParseError::UnexpectedEnd {
+                field,
+                expected: length,
+                found: remaining,
+            });
+        }
+        let bytes = &self.data[self.pos..self.pos + length];
+        self.pos += length;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self, field: &'static str) -> Result<u8, ParseError> {
+        Ok(self.bytes(field, 1)?[0])
+    }
+
+    fn u16(&mut self, field: &'static str) -> Result<u16, ParseError> {
+        let bytes = self.bytes(field, 2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self, field: &'static str) -> Result<u32, ParseError> {
+        let bytes = self.bytes(field, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn i16(&mut self, field: &'static str) -> Result<i16, ParseError> {
+        Ok(self.u16(field)? as i16)
+    }
+
+    fn f32(&mut self, field: &'static str) -> Result<f32, ParseError> {
+        Ok(f32::from_bits(self.u32(field)?))
+    }
+
+    fn bool(&mut self, field: &'static str) -> Result<bool, ParseError> {
+        match self.u8(field)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(ParseError::InvalidValue {
+                field,
+                value: value.into(),
+            }),
+        }
+    }
+
+    fn object_id(&mut self, field: &'static str) -> Result<ObjectId, ParseError> {
+        let id = self.u16(field)?;
+        ObjectId::new(id).map_err(|_| ParseError::InvalidValue {
+            field,
+            value: id.into(),
+        })
+    }
+}
+
+struct Writer<'a>(&'a mut Vec<u8>);
+
+impl Writer<'_> {
+    fn u8(&mut self, value: impl Into<u8>) {
+        self.0.push(value.into());
+    }
+
+    fn u16(&mut self, value: impl Into<u16>) {
+        self.0.extend(value.into().to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.0.extend(value.to_le_bytes());
+    }
+
+    fn i16(&mut self, value: i16) {
+        self.0.extend(value.to_le_bytes());
+    }
+}
+
+/// Builds a [`Macro`] object command by command
+///
+/// ```
+/// # use ag_iso_stack::object_pool::{Macro, ObjectId};
+/// let mask = ObjectId::new(1000).unwrap();
+/// let macro_object = Macro::builder(ObjectId::new(1).unwrap())
+///     .hide_object(ObjectId::new(3000).unwrap())
+///     .change_active_mask(ObjectId::default(), mask)
+///     .build();
+/// assert_eq!(macro_object.commands.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MacroBuilder {
+    id: ObjectId,
+    commands: Vec<MacroCommand>,
+}
+
+impl MacroBuilder {
+    pub fn new(id: ObjectId) -> Self {
+        MacroBuilder {
+            id,
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn command(mut self, command: MacroCommand) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    pub fn hide_object(self, object: ObjectId) -> Self {
+        self.command(MacroCommand::HideShowObject {
+            object,
+            show: false,
+        })
+    }
+
+    pub fn show_object(self, object: ObjectId) -> Self {
+        self.command(MacroCommand::HideShowObject { object, show: true })
+    }
+
+    pub fn enable_object(self, object: ObjectId) -> Self {
+        self.command(MacroCommand::EnableDisableObject {
+            object,
+            enable: true,
+        })
+    }
+
+    pub fn disable_object(self, object: ObjectId) -> Self {
+        self.command(MacroCommand::EnableDisableObject {
+            object,
+            enable: false,
+        })
+    }
+
+    pub fn select_input_object(self, object: ObjectId) -> Self {
+        self.command(MacroCommand::SelectInputObject {
+            object: object.into(),
+            open_for_input: false,
+        })
+    }
+
+    pub fn change_active_mask(self, working_set: ObjectId, mask: ObjectId) -> Self {
+        self.command(MacroCommand::ChangeActiveMask { working_set, mask })
+    }
+
+    pub fn change_numeric_value(self, object: ObjectId, value: u32) -> Self {
+        self.command(MacroCommand::ChangeNumericValue { object, value })
+    }
+
+    pub fn change_string_value(self, object: ObjectId, value: impl Into<String>) -> Self {
+        self.command(MacroCommand::ChangeStringValue {
+            object,
+            value: value.into(),
+        })
+    }
+
+    pub fn change_attribute(self, object: ObjectId, attribute_id: u8, value: u32) -> Self {
+        self.command(MacroCommand::ChangeAttribute {
+            object,
+            attribute_id,
+            value,
+        })
+    }
+
+    pub fn change_size(self, object: ObjectId, width: u16, height: u16) -> Self {
+        self.command(MacroCommand::ChangeSize {
+            object,
+            width,
+            height,
+        })
+    }
+
+    pub fn change_background_colour(self, object: ObjectId, colour: u8) -> Self {
+        self.command(MacroCommand::ChangeBackgroundColour { object, colour })
+    }
+
+    pub fn graphics_context(self, object: ObjectId, command: GraphicsContextCommand) -> Self {
+        self.command(MacroCommand::GraphicsContext { object, command })
+    }
+
+    pub fn execute_macro(self, macro_id: u8) -> Self {
+        self.command(MacroCommand::ExecuteMacro { macro_id })
+    }
+
+    pub fn build(self) -> Macro {
+        Macro {
+            id: self.id,
+            commands: self.commands,
+        }
+    }
+}
+
+impl Macro {
+    pub fn builder(id: ObjectId) -> MacroBuilder {
+        MacroBuilder::new(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    #[test]
+    fn macro_command_round_trip_test() {
+        let commands = vec![
+            MacroCommand::HideShowObject {
+                object: id(3000),
+                show: true,
+            },
+            MacroCommand::SelectInputObject {
+                object: NullableObjectId::NULL,
+                open_for_input: true,
+            },
+            MacroCommand::ChangeNumericValue {
+                object: id(21000),
+                value: 0x12345678,
+            },
+            MacroCommand::ChangeStringValue {
+                object: id(22000),
+                value: "Grüße".to_string(),
+            },
+            MacroCommand::ChangeChildPosition {
+                parent: id(1000),
+                child: id(3000),
+                x: -5,
+                y: 300,
+            },
+            MacroCommand::ChangeFillAttributes {
+                object: id(25000),
+                fill_type: FillType::Pattern,
+                colour: 9,
+                pattern: NullableObjectId::NULL,
+            },
+            MacroCommand::ExecuteMacro { macro_id: 2 },
+        ];
+        let data = MacroCommand::write_all(&commands).unwrap();
+        assert_eq!(data.len(), 8 + 8 + 8 + 10 + 9 + 8 + 8);
+        assert_eq!(MacroCommand::read_all(&data), Ok(commands.clone()));
+        assert_eq!(MacroCommand::read_all_lenient(&data), commands);
+    }
+
+    #[test]
+    fn macro_command_encoding_test() {
+        let (command, length) =
+            MacroCommand::read(&[0xAD, 0x00, 0x00, 0xE8, 0x03, 0xFF, 0xFF, 0xFF]).unwrap();
+        assert_eq!(length, 8);
+        assert_eq!(
+            command,
+            MacroCommand::ChangeActiveMask {
+                working_set: id(0),
+                mask: id(1000)
+            }
+        );
+
+        let mut data = Vec::new();
+        MacroCommand::ChangeSize {
+            object: id(0x1234),
+            width: 100,
+            height: 50,
+        }
+        .write(&mut data)
+        .unwrap();
+        assert_eq!(data, [0xA6, 0x34, 0x12, 100, 0, 50, 0, 0xFF]);
+    }
+
+    #[test]
+    fn graphics_context_command_test() {
+        let commands = vec![
+            MacroCommand::GraphicsContext {
+                object: id(26000),
+                command: GraphicsContextCommand::SetGraphicsCursor { x: -10, y: 20 },
+            },
+            MacroCommand::GraphicsContext {
+                object: id(26000),
+                command: GraphicsContextCommand::SetFillAttributesObjectId {
+                    object: NullableObjectId::NULL,
+                },
+            },
+            MacroCommand::GraphicsContext {
+                object: id(26000),
+                command: GraphicsContextCommand::DrawPolygon {
+                    points: vec![Point { x: 0, y: 0 }, Point { x: 10, y: -5 }],
+                },
+            },
+            MacroCommand::GraphicsContext {
+                object: id(26000),
+                command: GraphicsContextCommand::DrawText {
+                    transparent: true,
+                    text: "Hi".to_string(),
+                },
+            },
+            MacroCommand::GraphicsContext {
+                object: id(26000),
+                command: GraphicsContextCommand::PanAndZoomViewport {
+                    x: 5,
+                    y: 6,
+                    zoom: 1.5,
+                },
+            },
+            MacroCommand::GraphicsContext {
+                object: id(26000),
+                command: GraphicsContextCommand::CopyCanvasToPictureGraphic { object: id(20000) },
+            },
+        ];
+        let data = MacroCommand::write_all(&commands).unwrap();
+        assert_eq!(data.len(), 8 + 8 + 13 + 8 + 12 + 8);
+        assert_eq!(MacroCommand::read_all(&data), Ok(commands));
+
+        let mut data = Vec::new();
+        MacroCommand::GraphicsContext {
+            object: id(0x1234),
+            command: GraphicsContextCommand::DrawLine { x: -1, y: 2 },
+        }
+        .write(&mut data)
+        .unwrap();
+        assert_eq!(data, [0xB8, 0x34, 0x12, 0x09, 0xFF, 0xFF, 0x02, 0x00]);
+    }
+
+    #[test]
+    fn macro_command_errors_test() {
+        assert_eq!(
+            MacroCommand::read(&[0xB9, 0x00, 0x00]),
+            Err(ParseError::InvalidValue {
+                field: "command",
+                value: 0xB9
+            })
+        );
+        assert_eq!(
+            MacroCommand::read(&[0xB8, 0x00, 0x00, 0x15, 0xFF, 0xFF, 0xFF, 0xFF]),
+            Err(ParseError::InvalidValue {
+                field: "sub_command",
+                value: 0x15
+            })
+        );
+        assert_eq!(
+            MacroCommand::read(&[0xA0, 0xFF, 0xFF, 0x01, 0xFF, 0xFF, 0xFF, 0xFF]),
+            Err(ParseError::InvalidValue {
+                field: "object",
+                value: 0xFFFF
+            })
+        );
+        assert_eq!(
+            MacroCommand::read(&[0xA0, 0x00]),
+            Err(ParseError::UnexpectedEnd {
+                field: "object",
+                expected: 2,
+                found: 1
+            })
+        );
+
+        // Everything from the first unknown command is kept as is
+        let data = [
+            0xBE, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xB9, 0x00, 0x00, 0x01,
+        ];
+        let commands = MacroCommand::read_all_lenient(&data);
+        assert_eq!(
+            commands,
+            [
+                MacroCommand::ExecuteMacro { macro_id: 1 },
+                MacroCommand::Unknown(vec![0xB9, 0x00, 0x00, 0x01])
+            ]
+        );
+        assert_eq!(MacroCommand::write_all(&commands), Ok(data.to_vec()));
+    }
+
+    #[test]
+    fn macro_builder_test() {
+        let macro_object = MacroBuilder::new(id(1))
+            .hide_object(id(3000))
+            .change_string_value(id(11000), "Alarm")
+            .execute_macro(2)
+            .build();
+        assert_eq!(macro_object.id, id(1));
+        assert_eq!(
+            macro_object.commands,
+            [
+                MacroCommand::HideShowObject {
+                    object: id(3000),
+                    show: false
+                },
+                MacroCommand::ChangeStringValue {
+                    object: id(11000),
+                    value: "Alarm".to_string()
+                },
+                MacroCommand::ExecuteMacro { macro_id: 2 },
+            ]
+        );
+    }
+}
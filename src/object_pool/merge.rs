@@ -0,0 +1,258 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::builder::FIRST_ALLOCATED_ID;
+use crate::object_pool::object::Object;
+use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::validation::{map_macro_ids, map_references};
+use crate::object_pool::{ObjectPool, ObjectType};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The object IDs that were changed, mapping the old ID to the new one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdRemap {
+    ids: BTreeMap<ObjectId, ObjectId>,
+}
+
+impl IdRemap {
+    pub fn new() -> Self {
+        IdRemap::default()
+    }
+
+    pub fn insert(&mut self, old: ObjectId, new: ObjectId) {
+        self.ids.insert(old, new);
+    }
+
+    /// The new ID of the object with the ID `id`, `id` if it was not changed
+    pub fn get(&self, id: ObjectId) -> ObjectId {
+        self.ids.get(&id).copied().unwrap_or(id)
+    }
+
+    /// The changed IDs as `(old, new)`, sorted by the old ID
+    pub fn iter(&self) -> impl Iterator<Item = (ObjectId, ObjectId)> + '_ {
+        self.ids.iter().map(|(old, new)| (*old, *new))
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeError {
+    /// All object IDs are in use
+    NoFreeObjectId,
+    /// A colliding macro needs a new ID, but all IDs below 256 are in use
+    NoFreeMacroId(ObjectId),
+}
+
+impl std::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for MergeError {}
+
+impl ObjectPool {
+    /// The IDs used by objects in both pools, in the order of `other`
+    pub fn id_collisions(&self, other: &ObjectPool) -> Vec<ObjectId> {
+        other
+            .objects()
+            .iter()
+            .map(Object::id)
+            .filter(|id| self.object_by_id(*id).is_some())
+            .collect()
+    }
+
+    /// Adds the objects of `other` to this pool
+    ///
+    /// Objects of `other` with an ID that is already in use get a free ID, the lowest one from
+    /// 256 up, or below 256 for macros, and all references to them in `other` are changed. The
+    /// objects of this pool keep their IDs. Returns the changed IDs of `other`.
+    ///
+    /// Both pools are added as they are, the result has two Working Set objects if both pools have
+    /// one.
+    pub fn merge(&mut self, other: &ObjectPool) -> Result<IdRemap, MergeError> {
+        let mut used: BTreeSet<ObjectId> = self.objects().iter().map(Object::id).collect();
+        used.extend(other.objects().iter().map(Object::id));
+
+        let mut remap = IdRemap::new();
+        for id in self.id_collisions(other) {
+            let is_macro =
+                other.object_by_id(id).map(Object::object_type) == Some(ObjectType::Macro);
+            let new = if is_macro {
+                free_id(&used, 0..FIRST_ALLOCATED_ID).ok_or(MergeError::NoFreeMacroId(id))?
+            } else {
+                free_id(&used, FIRST_ALLOCATED_ID..u16::MAX).ok_or(MergeError::NoFreeObjectId)?
+            };
+            used.insert(new);
+            remap.insert(id, new);
+        }
+
+        for object in other.objects() {
+            let mut object = object.clone();
+            remap_object(&mut object, &remap);
+            self.add(object);
+        }
+        Ok(remap)
+    }
+
+    /// Changes the IDs of the objects in `remap`, and all references to them
+    pub fn remap_ids(&mut self, remap: &IdRemap) {
+        self.update_all(|object| remap_object(object, remap));
+    }
+}
+
+fn remap_object(object: &mut Object, remap: &IdRemap) {
+    object.set_id(remap.get(object.id()));
+    map_references(object, |id| Some(remap.get(id)));
+    map_macro_ids(object, |macro_id| {
+        ObjectId::new(macro_id.into())
+            .ok()
+            .and_then(|id| u8::try_from(u16::from(remap.get(id))).ok())
+            .unwrap_or(macro_id)
+    });
+}
+
+fn free_id(used: &BTreeSet<ObjectId>, range: std::ops::Range<u16>) -> Option<ObjectId> {
+    range
+        .filter_map(|id| ObjectId::new(id).ok())
+        .find(|id| !used.contains(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::object::{
+        Container, DataMask, FontAttributes, Macro, ObjectPointer, OutputString, WorkingSet,
+    };
+    use crate::object_pool::object_attributes::{FontSize, MacroRef, ObjectRef, Point};
+    use crate::object_pool::{MacroCommand, ObjectBuilder};
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    /// A working set with a data mask, a font and a macro
+    fn product_pool() -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        pool.add(WorkingSet::builder(id(1000)).build(id(0)));
+        pool.add(Object::Macro(Macro::builder(id(1)).build()));
+        pool.add(DataMask::builder().build(id(1000)));
+        pool.add(FontAttributes::builder().build(id(3000)));
+        pool
+    }
+
+    /// A header container using IDs that are in the product pool too
+    fn header_pool() -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::Macro(
+            Macro::builder(id(1))
+                .hide_object(id(1000))
+                .execute_macro(1)
+                .build(),
+        ));
+        pool.add(
+            Container::builder()
+                .child(id(2000), 0, 0)
+                .child(id(2001), 0, 20)
+                .macro_ref(2, 1)
+                .build(id(1000)),
+        );
+        pool.add(OutputString::builder(id(3000)).build(id(2000)));
+        pool.add(ObjectPointer::builder().value(id(1000)).build(id(2001)));
+        pool.add(
+            FontAttributes::builder()
+                .font_size(FontSize::Size16x16)
+                .build(id(3000)),
+        );
+        pool
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut pool = product_pool();
+        let header = header_pool();
+        assert_eq!(pool.id_collisions(&header), vec![id(1), id(1000), id(3000)]);
+
+        let remap = pool.merge(&header).unwrap();
+        let changes: Vec<(ObjectId, ObjectId)> = remap.iter().collect();
+        assert_eq!(
+            changes,
+            vec![(id(1), id(2)), (id(1000), id(256)), (id(3000), id(257))]
+        );
+        assert_eq!(remap.get(id(2000)), id(2000));
+        assert_eq!(pool.objects().len(), 9);
+
+        let container = pool.container_object_by_id(id(256)).unwrap();
+        assert_eq!(container.macro_refs[0].macro_id, 2);
+        let Some(Object::Macro(header_macro)) = pool.object_by_id(id(2)) else {
+            panic!("expected the header macro");
+        };
+        assert_eq!(
+            header_macro.commands,
+            vec![
+                MacroCommand::HideShowObject {
+                    object: id(256),
+                    show: false
+                },
+                MacroCommand::ExecuteMacro { macro_id: 2 },
+            ]
+        );
+        let string = pool.output_string_object_by_id(id(2000)).unwrap();
+        assert_eq!(string.font_attributes, id(257));
+        let Some(Object::ObjectPointer(pointer)) = pool.object_by_id(id(2001)) else {
+            panic!("expected the object pointer");
+        };
+        assert_eq!(pointer.value, id(256).into());
+        // The product pool keeps its IDs
+        assert_eq!(
+            pool.font_attributes_object_by_id(id(3000))
+                .unwrap()
+                .font_size,
+            FontSize::Size6x8
+        );
+
+        pool.data_mask_object_by_id_mut(id(1000))
+            .unwrap()
+            .object_refs
+            .push(ObjectRef {
+                id: remap.get(id(1000)),
+                offset: Point { x: 0, y: 0 },
+            });
+        assert_eq!(pool.validate(), Ok(()));
+    }
+
+    #[test]
+    fn remap_ids_test() {
+        let mut pool = header_pool();
+        let mut remap = IdRemap::new();
+        remap.insert(id(1000), id(5000));
+        remap.insert(id(1), id(7));
+        pool.remap_ids(&remap);
+
+        let ids: Vec<ObjectId> = pool.objects().iter().map(Object::id).collect();
+        assert_eq!(ids, vec![id(7), id(5000), id(2000), id(2001), id(3000)]);
+        assert!(pool.object_by_id(id(1000)).is_none());
+        assert_eq!(
+            pool.container_object_by_id(id(5000)).unwrap().macro_refs,
+            vec![MacroRef {
+                macro_id: 7,
+                event_id: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn merge_errors_test() {
+        let mut pool = ObjectPool::new();
+        let mut macros = ObjectPool::new();
+        for macro_id in 0..FIRST_ALLOCATED_ID {
+            pool.add(Object::Macro(Macro::builder(id(macro_id)).build()));
+            macros.add(Object::Macro(Macro::builder(id(macro_id)).build()));
+        }
+        assert_eq!(pool.merge(&macros), Err(MergeError::NoFreeMacroId(id(0))));
+    }
+}
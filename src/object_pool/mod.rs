@@ -1,22 +1,140 @@
 pub mod colour;
 pub mod reader;
+pub mod render;
 pub mod writer;
 
+mod builder;
+mod compatibility;
+mod diff;
+mod encoding;
+#[cfg(feature = "serde")]
+mod json;
+mod macro_command;
+mod merge;
+mod number_format;
 mod object;
 mod object_attributes;
 mod object_id;
 mod object_pool;
 mod object_type;
+mod picture_graphic;
+mod traversal;
+mod validation;
 mod vt_version;
+#[cfg(feature = "xml")]
+mod xml;
 
 use crate::network_management::name::NAME;
 
+pub use builder::*;
 pub use colour::Colour;
+pub use compatibility::VersionRequirement;
+pub use diff::{FieldDiff, ObjectDiff, PoolDiff, PoolPatch};
+pub use encoding::{encode_wide, EncodingError};
+pub use macro_command::{GraphicsContextCommand, MacroBuilder, MacroCommand};
+pub use merge::{IdRemap, MergeError};
+pub use number_format::{NumberError, NumberFormat};
+pub use object::{
+    AlarmMask, Animation, AuxiliaryControlDesignatorType2, AuxiliaryFunctionType1,
+    AuxiliaryFunctionType2, AuxiliaryInputType1, AuxiliaryInputType2, Button, CharacterRange,
+    CodePlane, ColourMap, ColourPalette, Container, DataMask, ExtendedInputAttributes,
+    ExternalObjectDefinition, ExternalObjectPointer, ExternalReferenceName, FillAttributes,
+    FontAttributes, GraphicData, GraphicsContext, InputAttributes, InputBoolean, InputList,
+    InputNumber, InputString, Key, KeyGroup, LineAttributes, Macro, NumberVariable, Object,
+    ObjectLabelReferenceList, ObjectPointer, OutputArchedBarGraph, OutputEllipse, OutputLine,
+    OutputLinearBarGraph, OutputList, OutputMeter, OutputNumber, OutputPolygon, OutputRectangle,
+    OutputString, PictureGraphic, ScaledGraphic, SoftKeyMask, StringVariable, ValidationType,
+    WindowMask, WorkingSet, WorkingSetSpecialControls,
+};
+pub use object_attributes::{
+    Alignment, AnimationOptions, AnimationSequence, AuxOFunctionType, AuxiliaryFunctionAttributes,
+    AuxiliaryFunctionType, AxisOrientation, BarGraphType, ButtonOptions, ButtonState, ColorFormat,
+    ColorOption, ColourPaletteOptions, DataCodeType, DeflectionDirection, DisabledBehaviour,
+    ExternalObjectDefinitionOptions, ExternalReferenceNameOptions, FillType, FontSize, FontStyle,
+    FontType, FormatType, GraphicsContextOptions, GrowDirection, HorizontalAlignment,
+    InputListOptions, InputNumberOptions, InputStringOptions, KeyGroupOptions, LineArt,
+    LineDirection, MacroRef, NumberOptions, ObjectLabel, ObjectRef, OutputArchedBarGraphOptions,
+    OutputLinearBarGraphOptions, OutputMeterOptions, OutputStringOptions, PictureGraphicFormat,
+    PictureGraphicOptions, Point, ScaledGraphicOptions, VerticalAlignment, WindowMaskCellFormat,
+    WindowMaskOptions, WindowType,
+};
+pub use object_id::{NullableObjectId, ObjectId};
 pub use object_pool::ObjectPool;
 pub use object_type::ObjectType;
+pub use picture_graphic::{PictureGraphicEncoder, PictureGraphicError};
+pub use traversal::Visit;
+pub use validation::{Field, ValidationError};
+pub use vt_version::VtVersion;
+#[cfg(feature = "xml")]
+pub use xml::{NameTable, XmlError};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParseError {
     DataEmpty,
     UnknownObjectType,
+    /// The data ended in the middle of `field`, a value of `expected` bytes of which only `found`
+    /// bytes were left
+    UnexpectedEnd {
+        field: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// An attribute holds a value that is not defined for it
+    InvalidValue {
+        field: &'static str,
+        value: u32,
+    },
 }
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for ParseError {}
+
+/// An error in a binary encoded object pool, with the position it was found at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IopError {
+    /// The position in the data where the error was detected
+    pub offset: usize,
+    /// The position in the data where the object being parsed starts
+    pub object_offset: usize,
+    /// The ID of the object being parsed, if its header could be read
+    pub object_id: Option<u16>,
+    /// The type of the object being parsed, if its header could be read
+    pub object_type: Option<ObjectType>,
+    pub error: ParseError,
+}
+
+impl IopError {
+    /// The name of the attribute that could not be parsed, if known
+    pub fn field(&self) -> Option<&'static str> {
+        match self.error {
+            ParseError::InvalidValue { field, .. } | ParseError::UnexpectedEnd { field, .. } => {
+                Some(field)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for IopError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} at byte {}", self.error, self.offset)?;
+        match (self.object_id, self.object_type) {
+            (Some(id), Some(object_type)) => write!(
+                f,
+                " in {:?} object {} starting at byte {}",
+                object_type, id, self.object_offset
+            ),
+            (Some(id), None) => write!(
+                f,
+                " in object {} starting at byte {}",
+                id, self.object_offset
+            ),
+            _ => write!(f, " in object starting at byte {}", self.object_offset),
+        }
+    }
+}
+impl std::error::Error for IopError {}
@@ -0,0 +1,429 @@
+// Copyright 2023 Raven Industries inc.
+use crate::network_management::language_command::DecimalSymbol;
+use crate::object_pool::object::{InputNumber, Object, OutputNumber};
+use crate::object_pool::object_attributes::{FormatType, NumberOptions};
+use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::ObjectPool;
+
+/// The most decimals a VT displays, larger values are treated as this
+const MAX_DECIMALS: u8 = 7;
+
+/// Margin for values that are meant to be exactly on a digit, but are slightly off because the
+/// scale is a binary floating point number, relative to the value but far below one digit
+const EPSILON: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberError {
+    /// The text is not a decimal or exponential number
+    InvalidText,
+    /// The scale is zero, so every value is displayed the same
+    ZeroScale,
+}
+
+impl std::fmt::Display for NumberError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for NumberError {}
+
+/// How an Output Number or Input Number turns its value into text
+///
+/// The displayed value is `(value + offset) * scale`, rounded or truncated to the number of
+/// decimals. Exponential values are written like C's `%e`, `1.50e+03`. The decimal symbol is the
+/// one of the language command of the VT, a point unless it is a comma.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumberFormat {
+    pub offset: i32,
+    pub scale: f32,
+    pub nr_of_decimals: u8,
+    pub format: FormatType,
+    pub options: NumberOptions,
+}
+
+impl NumberFormat {
+    /// The scaled value before rounding
+    pub fn displayed_value(&self, value: u32) -> f64 {
+        (i64::from(value) + i64::from(self.offset)) as f64 * self.scale()
+    }
+
+    /// The text a VT displays for `value`
+    pub fn format(&self, value: u32, decimal_symbol: DecimalSymbol) -> String {
+        self.format_padded(value, 0, decimal_symbol)
+    }
+
+    /// The text a VT displays for `value` in a field `width` characters wide
+    ///
+    /// With the leading zeros option the number is padded with zeros up to `width`, after the
+    /// sign. Numbers wider than the field are not cut off.
+    pub fn format_padded(&self, value: u32, width: usize, decimal_symbol: DecimalSymbol) -> String {
+        let displayed = self.displayed_value(value);
+        let decimals = self.decimals();
+        let (text, is_zero) = match self.format {
+            FormatType::Decimal => {
+                let rounded = self.round(displayed * 10f64.powi(decimals));
+                let text = format!("{:.*}", decimals as usize, rounded / 10f64.powi(decimals));
+                (text, rounded == 0.0)
+            }
+            FormatType::Exponential => self.exponential(displayed, decimals),
+        };
+        if self.options.display_zero_as_blank && is_zero {
+            return String::new();
+        }
+        let text = text.trim_start_matches('-');
+        let sign = if text.is_empty() || is_zero || displayed >= 0.0 {
+            ""
+        } else {
+            "-"
+        };
+        let zeros = match self.options.display_leading_zeros {
+            true => width.saturating_sub(sign.len() + text.len()),
+            false => 0,
+        };
+        let text = text.replace('.', &separator(decimal_symbol).to_string());
+        format!("{}{}{}", sign, "0".repeat(zeros), text)
+    }
+
+    /// The value that is displayed as the number in `text`, clamped to `min_value..=max_value`
+    ///
+    /// This is the inverse of [`format`](NumberFormat::format), the text is rounded to the
+    /// closest value. Empty text is zero if zero is displayed as blank.
+    pub fn parse(
+        &self,
+        text: &str,
+        min_value: u32,
+        max_value: u32,
+        decimal_symbol: DecimalSymbol,
+    ) -> Result<u32, NumberError> {
+        let text = text.trim();
+        let separator = separator(decimal_symbol);
+        let displayed = if text.is_empty() && self.options.display_zero_as_blank {
+            0.0
+        } else if text
+            .contains(|c: char| c != separator && !matches!(c, '0'..='9' | '+' | '-' | 'e' | 'E'))
+            || !text.contains(|c: char| c.is_ascii_digit())
+        {
+            return Err(NumberError::InvalidText);
+        } else {
+            text.replace(separator, ".")
+                .parse::<f64>()
+                .map_err(|_| NumberError::InvalidText)?
+        };
+        if self.scale() == 0.0 {
+            return Err(NumberError::ZeroScale);
+        }
+        let value = (displayed / self.scale() - f64::from(self.offset)).round();
+        let value = value.clamp(0.0, f64::from(u32::MAX)) as u32;
+        Ok(value.clamp(min_value, max_value.max(min_value)))
+    }
+
+    /// The scale as the decimal number it is written as, so 0.1 is not 0.100000001
+    fn scale(&self) -> f64 {
+        self.scale
+            .to_string()
+            .parse()
+            .unwrap_or(f64::from(self.scale))
+    }
+
+    fn decimals(&self) -> i32 {
+        i32::from(self.nr_of_decimals.min(MAX_DECIMALS))
+    }
+
+    /// Rounds half away from zero, or truncates with the truncate option
+    fn round(&self, value: f64) -> f64 {
+        let nudged = value + value.signum() * (EPSILON * value.abs()).clamp(EPSILON, 1e-6);
+        match self.options.truncate {
+            true => nudged.trunc(),
+            false => nudged.round(),
+        }
+    }
+
+    /// The text and whether it is zero of a value in exponential format
+    fn exponential(&self, value: f64, decimals: i32) -> (String, bool) {
+        let factor = 10f64.powi(decimals);
+        let mut exponent = match value {
+            v if v == 0.0 || !v.is_finite() => 0,
+            v => v.abs().log10().floor() as i32,
+        };
+        let mut mantissa = self.round(value / 10f64.powi(exponent) * factor);
+        if mantissa.abs() >= 10.0 * factor {
+            exponent += 1;
+            mantissa = self.round(value / 10f64.powi(exponent) * factor);
+        }
+        if mantissa == 0.0 {
+            exponent = 0;
+        }
+        let sign = if exponent < 0 { '-' } else { '+' };
+        let text = format!(
+            "{:.*}e{}{:02}",
+            decimals as usize,
+            mantissa / factor,
+            sign,
+            exponent.abs()
+        );
+        (text, mantissa == 0.0)
+    }
+}
+
+/// The character between the integer part and the decimals
+fn separator(decimal_symbol: DecimalSymbol) -> char {
+    match decimal_symbol {
+        DecimalSymbol::Comma => ',',
+        DecimalSymbol::Point | DecimalSymbol::Reserved | DecimalSymbol::NotAvailable => '.',
+    }
+}
+
+impl From<&OutputNumber> for NumberFormat {
+    fn from(value: &OutputNumber) -> Self {
+        NumberFormat {
+            offset: value.offset,
+            scale: value.scale,
+            nr_of_decimals: value.nr_of_decimals,
+            format: value.format,
+            options: value.options,
+        }
+    }
+}
+
+impl From<&InputNumber> for NumberFormat {
+    fn from(value: &InputNumber) -> Self {
+        NumberFormat {
+            offset: value.offset,
+            scale: value.scale,
+            nr_of_decimals: value.nr_of_decimals,
+            format: value.format,
+            options: value.options,
+        }
+    }
+}
+
+impl OutputNumber {
+    /// The text displayed for `value`, see [`NumberFormat::format`]
+    pub fn format_value(&self, value: u32, decimal_symbol: DecimalSymbol) -> String {
+        NumberFormat::from(self).format(value, decimal_symbol)
+    }
+}
+
+impl InputNumber {
+    /// The text displayed for `value`, see [`NumberFormat::format`]
+    pub fn format_value(&self, value: u32, decimal_symbol: DecimalSymbol) -> String {
+        NumberFormat::from(self).format(value, decimal_symbol)
+    }
+
+    /// The value an operator entered as `text`, clamped to the minimum and maximum value
+    pub fn parse_value(
+        &self,
+        text: &str,
+        decimal_symbol: DecimalSymbol,
+    ) -> Result<u32, NumberError> {
+        NumberFormat::from(self).parse(text, self.min_value, self.max_value, decimal_symbol)
+    }
+}
+
+impl ObjectPool {
+    /// The text displayed by the Output Number or Input Number `id`
+    ///
+    /// The value is taken from the referenced Number Variable if there is one. Leading zeros
+    /// fill the width of the object in characters of its font.
+    pub fn number_text(&self, id: ObjectId, decimal_symbol: DecimalSymbol) -> Option<String> {
+        let (format, variable_reference, value, width, font_attributes) =
+            match self.object_by_id(id)? {
+                Object::OutputNumber(o) => (
+                    NumberFormat::from(o),
+                    o.variable_reference,
+                    o.value,
+                    o.width,
+                    o.font_attributes,
+                ),
+                Object::InputNumber(o) => (
+                    NumberFormat::from(o),
+                    o.variable_reference,
+                    o.value,
+                    o.width,
+                    o.font_attributes,
+                ),
+                _ => return None,
+            };
+        let value = variable_reference
+            .0
+            .and_then(|id| self.number_variable_object_by_id(id))
+            .map_or(value, |v| v.value);
+        let char_width = self
+            .font_attributes_object_by_id(font_attributes)
+            .and_then(|f| f.font_size.to_fixed().size())
+            .map_or(6, |size| u16::from(size.x));
+        Some(format.format_padded(value, usize::from(width / char_width), decimal_symbol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::{FontAttributes, FontSize, NumberVariable, ObjectBuilder};
+
+    const POINT: DecimalSymbol = DecimalSymbol::Point;
+
+    fn number_format(offset: i32, scale: f32, nr_of_decimals: u8) -> NumberFormat {
+        NumberFormat {
+            offset,
+            scale,
+            nr_of_decimals,
+            format: FormatType::Decimal,
+            options: NumberOptions {
+                transparent: false,
+                display_leading_zeros: false,
+                display_zero_as_blank: false,
+                truncate: false,
+            },
+        }
+    }
+
+    #[test]
+    fn format_decimal_test() {
+        let format = number_format(-34, 0.1, 1);
+        assert_eq!(format.format(1234, POINT), "120.0");
+        assert_eq!(format.format(0, POINT), "-3.4");
+        assert_eq!(number_format(0, 0.001, 1).format(1999, POINT), "2.0");
+        assert_eq!(number_format(0, 0.1, 0).format(25, POINT), "3");
+        assert_eq!(number_format(-25, 0.1, 0).format(0, POINT), "-3");
+        assert_eq!(number_format(-1, 0.1, 0).format(0, POINT), "0");
+        assert_eq!(number_format(0, 1.0, 9).format(1, POINT), "1.0000000");
+        assert_eq!(
+            number_format(0, 1.0, 0).format(u32::MAX, POINT),
+            "4294967295"
+        );
+
+        let mut truncate = number_format(0, 0.7, 1);
+        truncate.options.truncate = true;
+        assert_eq!(truncate.format(1, POINT), "0.7");
+        truncate.scale = 0.001;
+        assert_eq!(truncate.format(1999, POINT), "1.9");
+        truncate.offset = -2999;
+        assert_eq!(truncate.format(0, POINT), "-2.9");
+    }
+
+    #[test]
+    fn format_options_test() {
+        let mut format = number_format(-10, 1.0, 0);
+        format.options.display_zero_as_blank = true;
+        assert_eq!(format.format(10, POINT), "");
+        assert_eq!(format.format(11, POINT), "1");
+
+        let mut format = number_format(-10, 0.5, 1);
+        format.options.display_leading_zeros = true;
+        assert_eq!(format.format_padded(16, 6, POINT), "0003.0");
+        assert_eq!(format.format_padded(0, 6, POINT), "-005.0");
+        assert_eq!(format.format_padded(2000, 4, POINT), "995.0");
+        assert_eq!(format.format(16, POINT), "3.0");
+    }
+
+    #[test]
+    fn format_exponential_test() {
+        let mut format = number_format(0, 1.0, 2);
+        format.format = FormatType::Exponential;
+        assert_eq!(format.format(1500, POINT), "1.50e+03");
+        assert_eq!(format.format(0, POINT), "0.00e+00");
+        assert_eq!(format.format(9999, POINT), "1.00e+04");
+        format.scale = 0.001;
+        assert_eq!(format.format(15, POINT), "1.50e-02");
+        format.offset = -20;
+        assert_eq!(format.format(15, POINT), "-5.00e-03");
+        format.options.display_zero_as_blank = true;
+        assert_eq!(format.format(20, POINT), "");
+    }
+
+    #[test]
+    fn parse_test() {
+        let format = number_format(-34, 0.1, 1);
+        assert_eq!(format.parse("120.0", 0, u32::MAX, POINT), Ok(1234));
+        assert_eq!(format.parse(" -3.4 ", 0, u32::MAX, POINT), Ok(0));
+        assert_eq!(format.parse("1.2e2", 0, u32::MAX, POINT), Ok(1234));
+        assert_eq!(format.parse("120.04", 0, u32::MAX, POINT), Ok(1234));
+        assert_eq!(format.parse("120", 0, 1000, POINT), Ok(1000));
+        assert_eq!(format.parse("-100", 10, 1000, POINT), Ok(10));
+        assert_eq!(
+            format.parse("", 0, 1000, POINT),
+            Err(NumberError::InvalidText)
+        );
+        assert_eq!(
+            format.parse("12a", 0, 1000, POINT),
+            Err(NumberError::InvalidText)
+        );
+        assert_eq!(
+            format.parse("inf", 0, 1000, POINT),
+            Err(NumberError::InvalidText)
+        );
+        assert_eq!(
+            number_format(0, 0.0, 0).parse("1", 0, 9, POINT),
+            Err(NumberError::ZeroScale)
+        );
+
+        let mut blank = number_format(-10, 1.0, 0);
+        blank.options.display_zero_as_blank = true;
+        assert_eq!(blank.parse("", 0, 1000, POINT), Ok(10));
+
+        // Every value survives a round trip
+        let format = number_format(-500, 0.25, 2);
+        for value in 0..2000 {
+            assert_eq!(
+                format.parse(&format.format(value, POINT), 0, u32::MAX, POINT),
+                Ok(value)
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_comma_test() {
+        let comma = DecimalSymbol::Comma;
+        let format = number_format(-34, 0.1, 1);
+        assert_eq!(format.format(1234, comma), "120,0");
+        assert_eq!(format.parse("120,0", 0, u32::MAX, comma), Ok(1234));
+        assert_eq!(
+            format.parse("120.0", 0, u32::MAX, comma),
+            Err(NumberError::InvalidText)
+        );
+        assert_eq!(format.format(1234, DecimalSymbol::NotAvailable), "120.0");
+
+        let mut format = number_format(0, 1.0, 2);
+        format.format = FormatType::Exponential;
+        assert_eq!(format.format(1500, comma), "1,50e+03");
+        assert_eq!(format.parse("1,5e3", 0, u32::MAX, comma), Ok(1500));
+    }
+
+    #[test]
+    fn number_text_test() {
+        let id = |id| ObjectId::new(id).unwrap();
+        let mut pool = ObjectPool::new();
+        pool.add(
+            FontAttributes::builder()
+                .font_size(FontSize::Size8x12)
+                .build(id(1)),
+        );
+        pool.add(NumberVariable::builder().value(42).build(id(2)));
+        let mut options: NumberOptions = 0.into();
+        options.display_leading_zeros = true;
+        pool.add(
+            OutputNumber::builder(id(1))
+                .width(40)
+                .options(options)
+                .variable_reference(id(2))
+                .value(7)
+                .build(id(3)),
+        );
+        pool.add(
+            InputNumber::builder(id(1))
+                .min_value(5)
+                .max_value(50)
+                .value(7)
+                .build(id(4)),
+        );
+        assert_eq!(pool.number_text(id(3), POINT).as_deref(), Some("00042"));
+        assert_eq!(pool.number_text(id(4), POINT).as_deref(), Some("7"));
+        assert_eq!(pool.number_text(id(1), POINT), None);
+
+        let input = pool.input_number_object_by_id(id(4)).unwrap();
+        assert_eq!(input.parse_value("60", POINT), Ok(50));
+        assert_eq!(input.format_value(12, POINT), "12");
+    }
+}
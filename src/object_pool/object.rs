@@ -1,17 +1,20 @@
 use crate::network_management::name::NAME;
+use crate::object_pool::macro_command::MacroCommand;
 use crate::object_pool::object_attributes::{
-    Alignment, AnimationOptions, ButtonOptions, ColorFormat, ColourPaletteOptions,
-    ExternalObjectDefinitionOptions, ExternalReferenceNameOptions, FormatType,
-    GraphicsContextOptions, InputListOptions, InputNumberOptions, InputStringOptions,
-    KeyGroupOptions, LineDirection, MacroRef, NumberOptions, ObjectLabel, ObjectRef,
-    OutputArchedBarGraphOptions, OutputLinearBarGraphOptions, OutputMeterOptions,
-    OutputStringOptions, PictureGraphicOptions, Point, ScaledGraphicOptions, WindowMaskCellFormat,
-    WindowMaskOptions, WindowType,
+    Alignment, AnimationOptions, AuxiliaryFunctionAttributes, ButtonOptions, ColorFormat,
+    ColourPaletteOptions, ExternalObjectDefinitionOptions, ExternalReferenceNameOptions, FillType,
+    FontSize, FontStyle, FontType, FormatType, GraphicsContextOptions, InputListOptions,
+    InputNumberOptions, InputStringOptions, KeyGroupOptions, LineArt, LineDirection, MacroRef,
+    NumberOptions, ObjectLabel, ObjectRef, OutputArchedBarGraphOptions,
+    OutputLinearBarGraphOptions, OutputMeterOptions, OutputStringOptions, PictureGraphicOptions,
+    Point, ScaledGraphicOptions, WindowMaskCellFormat, WindowMaskOptions, WindowType,
 };
-use crate::object_pool::object_id::ObjectId;
-use crate::object_pool::{Colour, ObjectType};
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::{Colour, ObjectType, ParseError};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
 pub enum Object {
     WorkingSet(WorkingSet),
     DataMask(DataMask),
@@ -119,6 +122,61 @@ impl Object {
         }
     }
 
+    /// Changes the ID of the object, references to it are not changed
+    pub fn set_id(&mut self, id: ObjectId) {
+        match self {
+            Object::WorkingSet(o) => o.id = id,
+            Object::DataMask(o) => o.id = id,
+            Object::AlarmMask(o) => o.id = id,
+            Object::Container(o) => o.id = id,
+            Object::SoftKeyMask(o) => o.id = id,
+            Object::Key(o) => o.id = id,
+            Object::Button(o) => o.id = id,
+            Object::InputBoolean(o) => o.id = id,
+            Object::InputString(o) => o.id = id,
+            Object::InputNumber(o) => o.id = id,
+            Object::InputList(o) => o.id = id,
+            Object::OutputString(o) => o.id = id,
+            Object::OutputNumber(o) => o.id = id,
+            Object::OutputLine(o) => o.id = id,
+            Object::OutputRectangle(o) => o.id = id,
+            Object::OutputEllipse(o) => o.id = id,
+            Object::OutputPolygon(o) => o.id = id,
+            Object::OutputMeter(o) => o.id = id,
+            Object::OutputLinearBarGraph(o) => o.id = id,
+            Object::OutputArchedBarGraph(o) => o.id = id,
+            Object::PictureGraphic(o) => o.id = id,
+            Object::NumberVariable(o) => o.id = id,
+            Object::StringVariable(o) => o.id = id,
+            Object::FontAttributes(o) => o.id = id,
+            Object::LineAttributes(o) => o.id = id,
+            Object::FillAttributes(o) => o.id = id,
+            Object::InputAttributes(o) => o.id = id,
+            Object::ObjectPointer(o) => o.id = id,
+            Object::Macro(o) => o.id = id,
+            Object::AuxiliaryFunctionType1(o) => o.id = id,
+            Object::AuxiliaryInputType1(o) => o.id = id,
+            Object::AuxiliaryFunctionType2(o) => o.id = id,
+            Object::AuxiliaryInputType2(o) => o.id = id,
+            Object::AuxiliaryControlDesignatorType2(o) => o.id = id,
+            Object::WindowMask(o) => o.id = id,
+            Object::KeyGroup(o) => o.id = id,
+            Object::GraphicsContext(o) => o.id = id,
+            Object::OutputList(o) => o.id = id,
+            Object::ExtendedInputAttributes(o) => o.id = id,
+            Object::ColourMap(o) => o.id = id,
+            Object::ObjectLabelReferenceList(o) => o.id = id,
+            Object::ExternalObjectDefinition(o) => o.id = id,
+            Object::ExternalReferenceName(o) => o.id = id,
+            Object::ExternalObjectPointer(o) => o.id = id,
+            Object::Animation(o) => o.id = id,
+            Object::ColourPalette(o) => o.id = id,
+            Object::GraphicData(o) => o.id = id,
+            Object::WorkingSetSpecialControls(o) => o.id = id,
+            Object::ScaledGraphic(o) => o.id = id,
+        }
+    }
+
     pub fn object_type(&self) -> ObjectType {
         match self {
             Object::WorkingSet(_) => ObjectType::WorkingSet,
@@ -176,7 +234,8 @@ impl Object {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorkingSet {
     pub id: ObjectId,
     pub background_colour: Colour,
@@ -187,27 +246,30 @@ pub struct WorkingSet {
     pub language_codes: Vec<String>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataMask {
     pub id: ObjectId,
     pub background_colour: u8,
-    pub soft_key_mask: ObjectId,
+    pub soft_key_mask: NullableObjectId,
     pub object_refs: Vec<ObjectRef>,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AlarmMask {
     pub id: ObjectId,
     pub background_colour: u8,
-    pub soft_key_mask: ObjectId,
+    pub soft_key_mask: NullableObjectId,
     pub priority: u8,
     pub acoustic_signal: u8,
     pub object_refs: Vec<ObjectRef>,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Container {
     pub id: ObjectId,
     pub width: u16,
@@ -217,7 +279,8 @@ pub struct Container {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SoftKeyMask {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -225,7 +288,8 @@ pub struct SoftKeyMask {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Key {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -234,7 +298,8 @@ pub struct Key {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Button {
     pub id: ObjectId,
     pub width: u16,
@@ -247,28 +312,30 @@ pub struct Button {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputBoolean {
     pub id: ObjectId,
     pub background_colour: u8,
     pub width: u16,
     pub foreground_colour: ObjectId,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub value: bool,
     pub enabled: bool,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputString {
     pub id: ObjectId,
     pub width: u16,
     pub height: u16,
     pub background_colour: u8,
     pub font_attributes: ObjectId,
-    pub input_attributes: ObjectId,
+    pub input_attributes: NullableObjectId,
     pub options: InputStringOptions,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub justification: Alignment,
     pub value: String,
     pub enabled: bool,
@@ -276,6 +343,7 @@ pub struct InputString {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputNumber {
     pub id: ObjectId,
     pub width: u16,
@@ -283,7 +351,7 @@ pub struct InputNumber {
     pub background_colour: u8,
     pub font_attributes: ObjectId,
     pub options: NumberOptions,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub value: u32,
     pub min_value: u32,
     pub max_value: u32,
@@ -297,18 +365,20 @@ pub struct InputNumber {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputList {
     pub id: ObjectId,
     pub width: u16,
     pub height: u16,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub value: u8,
     pub options: InputListOptions,
-    pub list_items: Vec<ObjectId>,
+    pub list_items: Vec<NullableObjectId>,
     pub macro_refs: Vec<MacroRef>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputString {
     pub id: ObjectId,
     pub width: u16,
@@ -316,13 +386,14 @@ pub struct OutputString {
     pub background_colour: u8,
     pub font_attributes: ObjectId,
     pub options: OutputStringOptions,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub justification: Alignment,
     pub value: String,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputNumber {
     pub id: ObjectId,
     pub width: u16,
@@ -330,7 +401,7 @@ pub struct OutputNumber {
     pub background_colour: u8,
     pub font_attributes: ObjectId,
     pub options: NumberOptions,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub value: u32,
     pub offset: i32,
     pub scale: f32,
@@ -341,17 +412,19 @@ pub struct OutputNumber {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputList {
     pub id: ObjectId,
     pub width: u16,
     pub height: u16,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub value: u8,
-    pub list_items: Vec<ObjectId>,
+    pub list_items: Vec<NullableObjectId>,
     pub macro_refs: Vec<MacroRef>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputLine {
     pub id: ObjectId,
     pub line_attributes: ObjectId,
@@ -361,18 +434,20 @@ pub struct OutputLine {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputRectangle {
     pub id: ObjectId,
     pub line_attributes: ObjectId,
     pub width: u16,
     pub height: u16,
     pub line_suppression: u8,
-    pub fill_attributes: ObjectId,
+    pub fill_attributes: NullableObjectId,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputEllipse {
     pub id: ObjectId,
     pub line_attributes: ObjectId,
@@ -381,23 +456,25 @@ pub struct OutputEllipse {
     pub ellipse_type: u8,
     pub start_angle: u8,
     pub end_angle: u8,
-    pub fill_attributes: ObjectId,
+    pub fill_attributes: NullableObjectId,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputPolygon {
     pub id: ObjectId,
     pub width: u16,
     pub height: u16,
     pub line_attributes: ObjectId,
-    pub fill_attributes: ObjectId,
+    pub fill_attributes: NullableObjectId,
     pub polygon_type: u8,
     pub points: Vec<Point<u16>>,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputMeter {
     pub id: ObjectId,
     pub width: u16,
@@ -410,12 +487,13 @@ pub struct OutputMeter {
     pub end_angle: u8,
     pub min_value: u16,
     pub max_value: u16,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub value: u16,
     pub macro_refs: Vec<MacroRef>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputLinearBarGraph {
     pub id: ObjectId,
     pub width: u16,
@@ -426,14 +504,15 @@ pub struct OutputLinearBarGraph {
     pub nr_of_ticks: u8,
     pub min_value: u16,
     pub max_value: u16,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub value: u16,
-    pub target_value_variable_reference: ObjectId,
+    pub target_value_variable_reference: NullableObjectId,
     pub target_value: u16,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputArchedBarGraph {
     pub id: ObjectId,
     pub width: u16,
@@ -446,14 +525,15 @@ pub struct OutputArchedBarGraph {
     pub bar_graph_width: u16,
     pub min_value: u16,
     pub max_value: u16,
-    pub variable_reference: ObjectId,
+    pub variable_reference: NullableObjectId,
     pub value: u16,
-    pub target_value_variable_reference: ObjectId,
+    pub target_value_variable_reference: NullableObjectId,
     pub target_value: u16,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PictureGraphic {
     pub id: ObjectId,
     pub width: u16,
@@ -466,55 +546,62 @@ pub struct PictureGraphic {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NumberVariable {
     pub id: ObjectId,
     pub value: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StringVariable {
     pub id: ObjectId,
     pub value: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FontAttributes {
     pub id: ObjectId,
     pub font_colour: u8,
-    pub font_size: u8,
-    pub font_type: u8,
-    pub font_style: u8,
+    pub font_size: FontSize,
+    pub font_type: FontType,
+    pub font_style: FontStyle,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineAttributes {
     pub id: ObjectId,
     pub line_colour: u8,
     pub line_width: u8,
-    pub line_art: u16,
+    pub line_art: LineArt,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FillAttributes {
     pub id: ObjectId,
-    pub fill_type: u8,
+    pub fill_type: FillType,
     pub fill_colour: u8,
-    pub fill_pattern: ObjectId,
+    pub fill_pattern: NullableObjectId,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputAttributes {
     pub id: ObjectId,
-    pub validation_type: u8,
+    pub validation_type: ValidationType,
     pub validation_string: String,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ValidationType {
     ValidCharacters,
     InvalidCharacters,
@@ -529,48 +616,59 @@ impl From<ValidationType> for u8 {
     }
 }
 
-impl From<u8> for ValidationType {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for ValidationType {
+    type Error = ParseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => ValidationType::ValidCharacters,
-            1 => ValidationType::InvalidCharacters,
-            _ => panic!("Invalid validation type"),
+            0 => Ok(ValidationType::ValidCharacters),
+            1 => Ok(ValidationType::InvalidCharacters),
+            _ => Err(ParseError::InvalidValue {
+                field: "validation_type",
+                value: value as u32,
+            }),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterRange {
     pub first_character: u16,
     pub last_character: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CodePlane {
     pub number: u8,
     pub character_ranges: Vec<CharacterRange>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtendedInputAttributes {
     pub id: ObjectId,
     pub validation_type: ValidationType,
     pub code_planes: Vec<CodePlane>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectPointer {
     pub id: ObjectId,
-    pub value: ObjectId,
+    pub value: NullableObjectId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Macro {
     pub id: ObjectId,
-    pub commands: Vec<u8>,
+    pub commands: Vec<MacroCommand>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuxiliaryFunctionType1 {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -578,7 +676,8 @@ pub struct AuxiliaryFunctionType1 {
     pub object_refs: Vec<ObjectRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuxiliaryInputType1 {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -587,7 +686,8 @@ pub struct AuxiliaryInputType1 {
     pub object_refs: Vec<ObjectRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuxiliaryFunctionType2 {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -595,7 +695,14 @@ pub struct AuxiliaryFunctionType2 {
     pub object_refs: Vec<ObjectRef>,
 }
 
-#[derive(Debug)]
+impl AuxiliaryFunctionType2 {
+    pub fn attributes(&self) -> AuxiliaryFunctionAttributes {
+        self.function_attributes.into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuxiliaryInputType2 {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -603,20 +710,29 @@ pub struct AuxiliaryInputType2 {
     pub object_refs: Vec<ObjectRef>,
 }
 
-#[derive(Debug)]
+impl AuxiliaryInputType2 {
+    pub fn attributes(&self) -> AuxiliaryFunctionAttributes {
+        self.function_attributes.into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuxiliaryControlDesignatorType2 {
     pub id: ObjectId,
     pub pointer_type: u8,
-    pub auxiliary_object_id: ObjectId,
+    pub auxiliary_object_id: NullableObjectId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColourMap {
     pub id: ObjectId,
     pub colour_map: Vec<u8>,
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphicsContext {
     pub id: ObjectId,
     pub viewport_width: u16,
@@ -630,46 +746,50 @@ pub struct GraphicsContext {
     pub graphics_cursor_y: i16,
     pub foreground_colour: u8,
     pub background_colour: u8,
-    pub font_attributes_object: ObjectId,
-    pub line_attributes_object: ObjectId,
-    pub fill_attributes_object: ObjectId,
+    pub font_attributes_object: NullableObjectId,
+    pub line_attributes_object: NullableObjectId,
+    pub fill_attributes_object: NullableObjectId,
     pub format: ColorFormat,
     pub options: GraphicsContextOptions,
     pub transparency_colour: u8,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowMask {
     pub id: ObjectId,
     pub cell_format: WindowMaskCellFormat,
     pub window_type: WindowType,
     pub background_colour: u8,
     pub options: WindowMaskOptions,
-    pub name: ObjectId,
-    pub window_title: ObjectId,
-    pub window_icon: ObjectId,
-    pub objects: Vec<ObjectId>,
+    pub name: NullableObjectId,
+    pub window_title: NullableObjectId,
+    pub window_icon: NullableObjectId,
+    pub objects: Vec<NullableObjectId>,
     pub object_refs: Vec<ObjectRef>,
     pub macro_refs: Vec<MacroRef>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyGroup {
     pub id: ObjectId,
     pub options: KeyGroupOptions,
     pub name: ObjectId,
-    pub key_group_icon: ObjectId,
+    pub key_group_icon: NullableObjectId,
     pub objects: Vec<ObjectId>,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectLabelReferenceList {
     pub id: ObjectId,
     pub object_labels: Vec<ObjectLabel>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExternalObjectDefinition {
     pub id: ObjectId,
     pub options: ExternalObjectDefinitionOptions,
@@ -677,22 +797,25 @@ pub struct ExternalObjectDefinition {
     pub objects: Vec<ObjectId>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExternalReferenceName {
     pub id: ObjectId,
     pub options: ExternalReferenceNameOptions,
     pub name: NAME,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExternalObjectPointer {
     pub id: ObjectId,
-    pub default_object_id: ObjectId,
-    pub external_reference_name_id: ObjectId,
-    pub external_object_id: ObjectId,
+    pub default_object_id: NullableObjectId,
+    pub external_reference_name_id: NullableObjectId,
+    pub external_object_id: NullableObjectId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Animation {
     pub id: ObjectId,
     pub width: u16,
@@ -708,35 +831,39 @@ pub struct Animation {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColourPalette {
     pub id: ObjectId,
     pub options: ColourPaletteOptions,
     pub colours: Vec<Colour>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphicData {
     pub id: ObjectId,
     pub format: u8,
     pub data: Vec<u8>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScaledGraphic {
     pub id: ObjectId,
     pub width: u16,
     pub height: u16,
     pub scale_type: u8,
     pub options: ScaledGraphicOptions,
-    pub value: u16,
+    pub value: NullableObjectId,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorkingSetSpecialControls {
     pub id: ObjectId,
-    pub id_of_colour_map: ObjectId,
-    pub id_of_colour_palette: ObjectId,
+    pub id_of_colour_map: NullableObjectId,
+    pub id_of_colour_palette: NullableObjectId,
     pub language_pairs: Vec<(String, String)>,
 }
@@ -1,11 +1,13 @@
-use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::ParseError;
 use bitvec::field::BitField;
-use bitvec::order::{Lsb0, Msb0};
+use bitvec::order::Msb0;
 use bitvec::vec::BitVec;
 use bitvec::view::BitView;
 use strum_macros::FromRepr;
 
 #[derive(FromRepr, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum WindowType {
     FreeForm = 0,
@@ -29,19 +31,25 @@ pub enum WindowType {
     DoubleButton2x1 = 18,
 }
 
-impl From<u8> for WindowType {
-    fn from(value: u8) -> Self {
-        WindowType::from_repr(value).unwrap()
+impl TryFrom<u8> for WindowType {
+    type Error = ParseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        WindowType::from_repr(value).ok_or(ParseError::InvalidValue {
+            field: "window_type",
+            value: value as u32,
+        })
     }
 }
 
 impl From<WindowType> for u8 {
     fn from(value: WindowType) -> Self {
-        value.into()
+        value as u8
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WindowMaskCellFormat {
     CF1x1,
     CF1x2,
@@ -97,7 +105,7 @@ impl WindowMaskCellFormat {
 
 impl From<u16> for WindowMaskCellFormat {
     fn from(value: u16) -> Self {
-        WindowMaskCellFormat::from_size((value << 8) as u8, value as u8)
+        WindowMaskCellFormat::from_size((value >> 8) as u8, value as u8)
     }
 }
 
@@ -109,6 +117,7 @@ impl From<WindowMaskCellFormat> for u16 {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowMaskOptions {
     pub available: bool,
     pub transparent: bool,
@@ -129,12 +138,13 @@ impl From<WindowMaskOptions> for u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
         bit_data.push(value.available);
         bit_data.push(value.transparent);
-        bit_data.extend([0; 6]);
+        bit_data.extend([false; 6]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectRef {
     pub id: ObjectId,
     pub offset: Point<i16>,
@@ -143,12 +153,14 @@ pub struct ObjectRef {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MacroRef {
     pub macro_id: u8,
     pub event_id: u8,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point<T> {
     pub x: T,
     pub y: T,
@@ -165,15 +177,17 @@ impl core::ops::Add<Point<i16>> for Point<u16> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectLabel {
     pub id: ObjectId,
-    pub string_variable_reference: ObjectId,
-    pub font_type: u8,
-    pub graphic_representation: ObjectId,
+    pub string_variable_reference: NullableObjectId,
+    pub font_type: FontType,
+    pub graphic_representation: NullableObjectId,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ButtonOptions {
     pub latchable: bool,
     pub state: ButtonState,
@@ -206,12 +220,13 @@ impl From<ButtonOptions> for u8 {
         bit_data.push(value.transparent_background);
         bit_data.push(value.disabled);
         bit_data.push(value.no_border);
-        bit_data.extend([0; 3]);
+        bit_data.extend([false; 2]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ButtonState {
     Released,
     Latched,
@@ -236,6 +251,7 @@ impl From<bool> for ButtonState {
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputStringOptions {
     pub transparent: bool,
     pub auto_wrap: bool,
@@ -259,12 +275,13 @@ impl From<InputStringOptions> for u8 {
         bit_data.push(value.transparent);
         bit_data.push(value.auto_wrap);
         bit_data.push(value.wrap_on_hyphen);
-        bit_data.extend([0; 5]);
+        bit_data.extend([false; 5]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Alignment {
     pub horizontal: HorizontalAlignment,
     pub vertical: VerticalAlignment,
@@ -272,13 +289,14 @@ pub struct Alignment {
 
 impl From<u8> for Alignment {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
+        let horizontal_low = bit_data.pop().unwrap();
+        let horizontal_high = bit_data.pop().unwrap();
+        let vertical_low = bit_data.pop().unwrap();
+        let vertical_high = bit_data.pop().unwrap();
         Alignment {
-            horizontal: HorizontalAlignment::from([
-                bit_data.pop().unwrap(),
-                bit_data.pop().unwrap(),
-            ]),
-            vertical: VerticalAlignment::from([bit_data.pop().unwrap(), bit_data.pop().unwrap()]),
+            horizontal: HorizontalAlignment::from([horizontal_high, horizontal_low]),
+            vertical: VerticalAlignment::from([vertical_high, vertical_low]),
         }
     }
 }
@@ -289,17 +307,19 @@ impl From<Alignment> for u8 {
         let horizontal_align: [bool; 2] = value.horizontal.into();
         let vertical_align: [bool; 2] = value.vertical.into();
 
-        bit_data.push(horizontal_align[0]);
         bit_data.push(horizontal_align[1]);
+        bit_data.push(horizontal_align[0]);
 
-        bit_data.push(vertical_align[0]);
         bit_data.push(vertical_align[1]);
+        bit_data.push(vertical_align[0]);
 
+        bit_data.extend([false; 4]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HorizontalAlignment {
     Left = 0,
     Middle = 1,
@@ -315,7 +335,7 @@ impl From<[bool; 2]> for HorizontalAlignment {
                 true => HorizontalAlignment::Middle,
             },
             true => match value[1] {
-                false => HorizontalAlignment::Middle,
+                false => HorizontalAlignment::Right,
                 true => HorizontalAlignment::Reserved,
             },
         }
@@ -334,6 +354,7 @@ impl From<HorizontalAlignment> for [bool; 2] {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VerticalAlignment {
     Top = 0,
     Middle = 1,
@@ -368,6 +389,7 @@ impl From<VerticalAlignment> for [bool; 2] {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputNumberOptions {
     pub enabled: bool,
     pub real_time_editing: bool,
@@ -388,12 +410,13 @@ impl From<InputNumberOptions> for u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
         bit_data.push(value.enabled);
         bit_data.push(value.real_time_editing);
-        bit_data.extend([0; 6]);
+        bit_data.extend([false; 6]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FormatType {
     Decimal,
     Exponential,
@@ -418,6 +441,7 @@ impl From<FormatType> for bool {
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputListOptions {
     pub enabled: bool,
     pub real_time_editing: bool,
@@ -438,12 +462,13 @@ impl From<InputListOptions> for u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
         bit_data.push(value.enabled);
         bit_data.push(value.real_time_editing);
-        bit_data.extend([0; 6]);
+        bit_data.extend([false; 6]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputStringOptions {
     pub transparent: bool,
     pub auto_wrap: bool,
@@ -462,6 +487,7 @@ impl From<u8> for OutputStringOptions {
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NumberOptions {
     pub transparent: bool,
     pub display_leading_zeros: bool,
@@ -482,17 +508,23 @@ impl From<u8> for NumberOptions {
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineDirection {
     TopLeftToBottomRight,
     BottomLeftToTopRight,
 }
 
-impl From<u8> for LineDirection {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for LineDirection {
+    type Error = ParseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => LineDirection::TopLeftToBottomRight,
-            1 => LineDirection::BottomLeftToTopRight,
-            _ => panic!("Invalid line direction"),
+            0 => Ok(LineDirection::TopLeftToBottomRight),
+            1 => Ok(LineDirection::BottomLeftToTopRight),
+            _ => Err(ParseError::InvalidValue {
+                field: "line_direction",
+                value: value as u32,
+            }),
         }
     }
 }
@@ -513,7 +545,7 @@ impl From<NumberOptions> for u8 {
         bit_data.push(value.display_leading_zeros);
         bit_data.push(value.display_zero_as_blank);
         bit_data.push(value.truncate);
-        bit_data.extend([0; 4]);
+        bit_data.extend([false; 4]);
         bit_data.load::<u8>()
     }
 }
@@ -524,12 +556,13 @@ impl From<OutputStringOptions> for u8 {
         bit_data.push(value.transparent);
         bit_data.push(value.auto_wrap);
         bit_data.push(value.wrap_on_hyphen);
-        bit_data.extend([0; 5]);
+        bit_data.extend([false; 5]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColorFormat {
     ColorMonochrome,
     Color4Bit,
@@ -546,18 +579,24 @@ impl From<ColorFormat> for u8 {
     }
 }
 
-impl From<u8> for ColorFormat {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for ColorFormat {
+    type Error = ParseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => ColorFormat::ColorMonochrome,
-            1 => ColorFormat::Color4Bit,
-            2 => ColorFormat::Color8Bit,
-            _ => panic!("Invalid color format: {}", value),
+            0 => Ok(ColorFormat::ColorMonochrome),
+            1 => Ok(ColorFormat::Color4Bit),
+            2 => Ok(ColorFormat::Color8Bit),
+            _ => Err(ParseError::InvalidValue {
+                field: "format",
+                value: value as u32,
+            }),
         }
     }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColorOption {
     ForegroundBackground,
     LineFontFill,
@@ -582,6 +621,7 @@ impl From<ColorOption> for bool {
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphicsContextOptions {
     pub transparent: bool,
     pub color: ColorOption,
@@ -589,7 +629,7 @@ pub struct GraphicsContextOptions {
 
 impl From<u8> for GraphicsContextOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         GraphicsContextOptions {
             transparent: bit_data.pop().unwrap(),
             color: bit_data.pop().unwrap().into(),
@@ -602,12 +642,13 @@ impl From<GraphicsContextOptions> for u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
         bit_data.push(value.transparent);
         bit_data.push(value.color.into());
-        bit_data.extend([0; 6]);
+        bit_data.extend([false; 6]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyGroupOptions {
     pub available: bool,
     pub transparent: bool,
@@ -615,7 +656,7 @@ pub struct KeyGroupOptions {
 
 impl From<u8> for KeyGroupOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         KeyGroupOptions {
             available: bit_data.pop().unwrap(),
             transparent: bit_data.pop().unwrap(),
@@ -628,12 +669,13 @@ impl From<KeyGroupOptions> for u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
         bit_data.push(value.available);
         bit_data.push(value.transparent);
-        bit_data.extend([0; 6]);
+        bit_data.extend([false; 6]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeflectionDirection {
     AntiClockwise,
     Clockwise,
@@ -658,6 +700,7 @@ impl From<DeflectionDirection> for bool {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputMeterOptions {
     pub draw_arc: bool,
     pub draw_border: bool,
@@ -667,7 +710,7 @@ pub struct OutputMeterOptions {
 
 impl From<u8> for OutputMeterOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         OutputMeterOptions {
             draw_arc: bit_data.pop().unwrap(),
             draw_border: bit_data.pop().unwrap(),
@@ -684,12 +727,13 @@ impl From<OutputMeterOptions> for u8 {
         bit_data.push(value.draw_border);
         bit_data.push(value.draw_ticks);
         bit_data.push(value.deflection_direction.into());
-        bit_data.extend([0; 4]);
+        bit_data.extend([false; 4]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BarGraphType {
     Filled,
     NotFilled,
@@ -714,6 +758,7 @@ impl From<BarGraphType> for bool {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AxisOrientation {
     Vertical,
     Horizontal,
@@ -738,6 +783,7 @@ impl From<AxisOrientation> for bool {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GrowDirection {
     GrowLeftDown,
     GrowRightUp,
@@ -762,6 +808,7 @@ impl From<GrowDirection> for bool {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputLinearBarGraphOptions {
     pub draw_border: bool,
     pub draw_target_line: bool,
@@ -773,7 +820,7 @@ pub struct OutputLinearBarGraphOptions {
 
 impl From<u8> for OutputLinearBarGraphOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         OutputLinearBarGraphOptions {
             draw_border: bit_data.pop().unwrap(),
             draw_target_line: bit_data.pop().unwrap(),
@@ -794,12 +841,13 @@ impl From<OutputLinearBarGraphOptions> for u8 {
         bit_data.push(value.bar_graph_type.into());
         bit_data.push(value.axis_orientation.into());
         bit_data.push(value.grow_direction.into());
-        bit_data.extend([0; 2]);
+        bit_data.extend([false; 2]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputArchedBarGraphOptions {
     pub draw_border: bool,
     pub draw_target_line: bool,
@@ -811,7 +859,7 @@ pub struct OutputArchedBarGraphOptions {
 
 impl From<u8> for OutputArchedBarGraphOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         let draw_border = bit_data.pop().unwrap();
         let draw_target_line = bit_data.pop().unwrap();
         bit_data.pop(); //undefined bit
@@ -837,12 +885,46 @@ impl From<OutputArchedBarGraphOptions> for u8 {
         bit_data.push(value.axis_orientation.into());
         bit_data.push(value.grow_direction.into());
         bit_data.push(value.deflection_direction.into());
-        bit_data.extend([0; 1]);
+        bit_data.extend([false; 1]);
         bit_data.load::<u8>()
     }
 }
 
+#[derive(FromRepr, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum PictureGraphicFormat {
+    /// 1 bit per pixel, 8 pixels per byte with the leftmost pixel in the most significant bit
+    Monochrome = 0,
+    /// 4 bits per pixel, 2 pixels per byte with the leftmost pixel in the high nibble
+    FourBitColour = 1,
+    /// 8 bits per pixel
+    EightBitColour = 2,
+}
+
+impl PictureGraphicFormat {
+    pub fn bits_per_pixel(&self) -> usize {
+        match self {
+            PictureGraphicFormat::Monochrome => 1,
+            PictureGraphicFormat::FourBitColour => 4,
+            PictureGraphicFormat::EightBitColour => 8,
+        }
+    }
+
+    /// The number of colours of the VT palette that can be used in this format
+    pub fn colours(&self) -> usize {
+        1 << self.bits_per_pixel()
+    }
+}
+
+impl From<PictureGraphicFormat> for u8 {
+    fn from(value: PictureGraphicFormat) -> Self {
+        value as u8
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataCodeType {
     Raw,
     RunLength,
@@ -867,6 +949,7 @@ impl From<DataCodeType> for bool {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PictureGraphicOptions {
     pub transparent: bool,
     pub flashing: bool,
@@ -875,7 +958,7 @@ pub struct PictureGraphicOptions {
 
 impl From<u8> for PictureGraphicOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         PictureGraphicOptions {
             transparent: bit_data.pop().unwrap(),
             flashing: bit_data.pop().unwrap(),
@@ -890,19 +973,20 @@ impl From<PictureGraphicOptions> for u8 {
         bit_data.push(value.transparent);
         bit_data.push(value.flashing);
         bit_data.push(value.data_code_type.into());
-        bit_data.extend([0; 5]);
+        bit_data.extend([false; 5]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExternalObjectDefinitionOptions {
     pub enabled: bool,
 }
 
 impl From<u8> for ExternalObjectDefinitionOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         ExternalObjectDefinitionOptions {
             enabled: bit_data.pop().unwrap(),
         }
@@ -913,19 +997,20 @@ impl From<ExternalObjectDefinitionOptions> for u8 {
     fn from(value: ExternalObjectDefinitionOptions) -> u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
         bit_data.push(value.enabled);
-        bit_data.extend([0; 7]);
+        bit_data.extend([false; 7]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExternalReferenceNameOptions {
     pub enabled: bool,
 }
 
 impl From<u8> for ExternalReferenceNameOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         ExternalReferenceNameOptions {
             enabled: bit_data.pop().unwrap(),
         }
@@ -936,12 +1021,13 @@ impl From<ExternalReferenceNameOptions> for u8 {
     fn from(value: ExternalReferenceNameOptions) -> u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
         bit_data.push(value.enabled);
-        bit_data.extend([0; 7]);
+        bit_data.extend([false; 7]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnimationSequence {
     SingleShot,
     Loop,
@@ -966,6 +1052,7 @@ impl From<AnimationSequence> for bool {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DisabledBehaviour {
     Pause,
     ResetToFirst,
@@ -996,6 +1083,7 @@ impl From<DisabledBehaviour> for [bool; 2] {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnimationOptions {
     pub animation_sequence: AnimationSequence,
     pub disabled_behaviour: DisabledBehaviour,
@@ -1003,7 +1091,7 @@ pub struct AnimationOptions {
 
 impl From<u8> for AnimationOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         AnimationOptions {
             animation_sequence: bit_data.pop().unwrap().into(),
             disabled_behaviour: DisabledBehaviour::from([
@@ -1021,17 +1109,18 @@ impl From<AnimationOptions> for u8 {
         let disabled_behaviour: [bool; 2] = value.disabled_behaviour.into();
         bit_data.push(disabled_behaviour[0]);
         bit_data.push(disabled_behaviour[1]);
-        bit_data.extend([0; 5]);
+        bit_data.extend([false; 5]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColourPaletteOptions {}
 
 impl From<u8> for ColourPaletteOptions {
     fn from(value: u8) -> Self {
-        let mut _bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut _bit_data = value.view_bits::<Msb0>().to_bitvec();
         ColourPaletteOptions {}
     }
 }
@@ -1039,19 +1128,20 @@ impl From<u8> for ColourPaletteOptions {
 impl From<ColourPaletteOptions> for u8 {
     fn from(_value: ColourPaletteOptions) -> u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
-        bit_data.extend([0; 8]);
+        bit_data.extend([false; 8]);
         bit_data.load::<u8>()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScaledGraphicOptions {
     pub flashing: bool,
 }
 
 impl From<u8> for ScaledGraphicOptions {
     fn from(value: u8) -> Self {
-        let mut bit_data = value.view_bits::<Lsb0>().to_bitvec();
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
         ScaledGraphicOptions {
             flashing: bit_data.pop().unwrap(),
         }
@@ -1062,7 +1152,559 @@ impl From<ScaledGraphicOptions> for u8 {
     fn from(value: ScaledGraphicOptions) -> u8 {
         let mut bit_data: BitVec<u8> = BitVec::new();
         bit_data.push(value.flashing);
-        bit_data.extend([0; 7]);
+        bit_data.extend([false; 7]);
+        bit_data.load::<u8>()
+    }
+}
+
+/// The size of a font, the fixed sizes are named after their character cell in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontSize {
+    Size6x8,
+    Size8x8,
+    Size8x12,
+    Size12x16,
+    Size16x16,
+    Size16x24,
+    Size24x32,
+    Size32x32,
+    Size32x48,
+    Size48x64,
+    Size64x64,
+    Size64x96,
+    Size96x128,
+    Size128x128,
+    Size128x192,
+    /// A proportional font with a height of 8 to 255 pixels, VT version 4 and later
+    Proportional(u8),
+}
+
+impl FontSize {
+    /// The fixed font sizes, ordered by their value
+    pub const FIXED: [FontSize; 15] = [
+        FontSize::Size6x8,
+        FontSize::Size8x8,
+        FontSize::Size8x12,
+        FontSize::Size12x16,
+        FontSize::Size16x16,
+        FontSize::Size16x24,
+        FontSize::Size24x32,
+        FontSize::Size32x32,
+        FontSize::Size32x48,
+        FontSize::Size48x64,
+        FontSize::Size64x64,
+        FontSize::Size64x96,
+        FontSize::Size96x128,
+        FontSize::Size128x128,
+        FontSize::Size128x192,
+    ];
+
+    /// The character cell of a fixed font size, `None` for proportional fonts
+    pub const fn size(self) -> Option<Point<u8>> {
+        let (x, y) = match self {
+            FontSize::Size6x8 => (6, 8),
+            FontSize::Size8x8 => (8, 8),
+            FontSize::Size8x12 => (8, 12),
+            FontSize::Size12x16 => (12, 16),
+            FontSize::Size16x16 => (16, 16),
+            FontSize::Size16x24 => (16, 24),
+            FontSize::Size24x32 => (24, 32),
+            FontSize::Size32x32 => (32, 32),
+            FontSize::Size32x48 => (32, 48),
+            FontSize::Size48x64 => (48, 64),
+            FontSize::Size64x64 => (64, 64),
+            FontSize::Size64x96 => (64, 96),
+            FontSize::Size96x128 => (96, 128),
+            FontSize::Size128x128 => (128, 128),
+            FontSize::Size128x192 => (128, 192),
+            FontSize::Proportional(_) => return None,
+        };
+        Some(Point { x, y })
+    }
+
+    /// The height of a character in pixels
+    pub const fn height(self) -> u8 {
+        match (self, self.size()) {
+            (_, Some(size)) => size.y,
+            (FontSize::Proportional(height), None) => height,
+            (_, None) => 0,
+        }
+    }
+
+    pub const fn is_proportional(self) -> bool {
+        matches!(self, FontSize::Proportional(_))
+    }
+
+    /// The largest fixed font size that is not higher than this size, for VTs that don't
+    /// support proportional fonts
+    pub fn to_fixed(self) -> FontSize {
+        match self {
+            FontSize::Proportional(height) => FontSize::FIXED
+                .into_iter()
+                .rev()
+                .find(|size| size.height() <= height)
+                .unwrap_or(FontSize::Size6x8),
+            fixed => fixed,
+        }
+    }
+}
+
+impl FontSize {
+    /// Decodes the font size byte, which depends on the proportional bit of the font style
+    ///
+    /// A fixed font size is an index of [`FontSize::FIXED`], a proportional font size is the
+    /// height of the font in pixels.
+    pub fn decode(value: u8, style: FontStyle) -> Result<Self, ParseError> {
+        match (style.proportional, value) {
+            (false, 0..=14) => Ok(FontSize::FIXED[value as usize]),
+            (true, 8..=255) => Ok(FontSize::Proportional(value)),
+            _ => Err(ParseError::InvalidValue {
+                field: "font_size",
+                value: value as u32,
+            }),
+        }
+    }
+
+    /// The font style with the proportional bit matching this size
+    pub const fn style(self, style: FontStyle) -> FontStyle {
+        FontStyle {
+            proportional: self.is_proportional(),
+            ..style
+        }
+    }
+}
+
+impl From<FontSize> for u8 {
+    fn from(value: FontSize) -> Self {
+        match value {
+            FontSize::Proportional(height) => height,
+            _ => FontSize::FIXED
+                .iter()
+                .position(|size| *size == value)
+                .unwrap_or_default() as u8,
+        }
+    }
+}
+
+/// The character set of a font
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontType {
+    /// ISO 8859-1 (Latin 1)
+    Iso8859_1,
+    /// ISO 8859-15 (Latin 9)
+    Iso8859_15,
+    /// ISO 8859-2 (Latin 2), VT version 4 and later
+    Iso8859_2,
+    /// ISO 8859-4 (Latin 4), VT version 4 and later
+    Iso8859_4,
+    /// ISO 8859-5 (Cyrillic), VT version 4 and later
+    Iso8859_5,
+    /// ISO 8859-7 (Greek), VT version 4 and later
+    Iso8859_7,
+    /// A manufacturer specific font, 240 to 255
+    Proprietary(u8),
+}
+
+impl TryFrom<u8> for FontType {
+    type Error = ParseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FontType::Iso8859_1),
+            1 => Ok(FontType::Iso8859_15),
+            2 => Ok(FontType::Iso8859_2),
+            4 => Ok(FontType::Iso8859_4),
+            5 => Ok(FontType::Iso8859_5),
+            7 => Ok(FontType::Iso8859_7),
+            240..=255 => Ok(FontType::Proprietary(value)),
+            _ => Err(ParseError::InvalidValue {
+                field: "font_type",
+                value: value as u32,
+            }),
+        }
+    }
+}
+
+impl From<FontType> for u8 {
+    fn from(value: FontType) -> Self {
+        match value {
+            FontType::Iso8859_1 => 0,
+            FontType::Iso8859_15 => 1,
+            FontType::Iso8859_2 => 2,
+            FontType::Iso8859_4 => 4,
+            FontType::Iso8859_5 => 5,
+            FontType::Iso8859_7 => 7,
+            FontType::Proprietary(value) => value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontStyle {
+    pub bold: bool,
+    pub crossed_out: bool,
+    pub underlined: bool,
+    pub italic: bool,
+    pub inverted: bool,
+    /// Alternates between normal and inverted
+    pub flashing_inverted: bool,
+    /// Alternates between shown and hidden
+    pub flashing_hidden: bool,
+    /// Proportional font rendering, VT version 4 and later
+    ///
+    /// Selects how the font size byte is read, the writer sets it from [`FontSize`].
+    pub proportional: bool,
+}
+
+impl From<u8> for FontStyle {
+    fn from(value: u8) -> Self {
+        let mut bit_data = value.view_bits::<Msb0>().to_bitvec();
+        FontStyle {
+            bold: bit_data.pop().unwrap(),
+            crossed_out: bit_data.pop().unwrap(),
+            underlined: bit_data.pop().unwrap(),
+            italic: bit_data.pop().unwrap(),
+            inverted: bit_data.pop().unwrap(),
+            flashing_inverted: bit_data.pop().unwrap(),
+            flashing_hidden: bit_data.pop().unwrap(),
+            proportional: bit_data.pop().unwrap(),
+        }
+    }
+}
+
+impl From<FontStyle> for u8 {
+    fn from(value: FontStyle) -> u8 {
+        let mut bit_data: BitVec<u8> = BitVec::new();
+        bit_data.push(value.bold);
+        bit_data.push(value.crossed_out);
+        bit_data.push(value.underlined);
+        bit_data.push(value.italic);
+        bit_data.push(value.inverted);
+        bit_data.push(value.flashing_inverted);
+        bit_data.push(value.flashing_hidden);
+        bit_data.push(value.proportional);
         bit_data.load::<u8>()
     }
 }
+
+/// The pattern a line is drawn with
+///
+/// Each bit represents one pixel along the line, starting with the most significant bit. A
+/// cleared bit leaves the pixel unpainted, the pattern repeats every 16 pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineArt(pub u16);
+
+impl LineArt {
+    pub const SOLID: LineArt = LineArt(0xFFFF);
+
+    /// Whether the pixel `step` pixels from the start of the line is painted
+    pub const fn is_painted(self, step: usize) -> bool {
+        self.0 & (0x8000 >> (step % 16)) != 0
+    }
+}
+
+impl Default for LineArt {
+    fn default() -> Self {
+        LineArt::SOLID
+    }
+}
+
+impl From<u16> for LineArt {
+    fn from(value: u16) -> Self {
+        LineArt(value)
+    }
+}
+
+impl From<LineArt> for u16 {
+    fn from(value: LineArt) -> Self {
+        value.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillType {
+    NoFill,
+    /// Fills with the colour of the line attributes of the shape
+    LineColour,
+    FillColour,
+    /// Fills with the picture graphic referenced as fill pattern
+    Pattern,
+}
+
+impl TryFrom<u8> for FillType {
+    type Error = ParseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FillType::NoFill),
+            1 => Ok(FillType::LineColour),
+            2 => Ok(FillType::FillColour),
+            3 => Ok(FillType::Pattern),
+            _ => Err(ParseError::InvalidValue {
+                field: "fill_type",
+                value: value as u32,
+            }),
+        }
+    }
+}
+
+impl From<FillType> for u8 {
+    fn from(value: FillType) -> Self {
+        match value {
+            FillType::NoFill => 0,
+            FillType::LineColour => 1,
+            FillType::FillColour => 2,
+            FillType::Pattern => 3,
+        }
+    }
+}
+
+/// The type of an auxiliary function or input (AUX-N), what an input reports in its values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuxiliaryFunctionType {
+    BooleanLatching,
+    Analogue,
+    BooleanNonLatching,
+    AnalogueReturnToCentre,
+    AnalogueReturnToZero,
+    DualBooleanLatching,
+    DualBooleanNonLatching,
+    DualBooleanLatchingUp,
+    DualBooleanLatchingDown,
+    CombinedAnalogueReturnWithDualBooleanLatching,
+    CombinedAnalogueWithDualBooleanLatching,
+    QuadratureBooleanNonLatching,
+    QuadratureAnalogue,
+    QuadratureAnalogueReturnToCentre,
+    BidirectionalEncoder,
+    Reserved(u8),
+    /// Used in the assignment command to remove an assignment
+    RemoveAssignment,
+}
+
+impl AuxiliaryFunctionType {
+    /// Whether the values are on/off states rather than positions
+    pub fn is_boolean(self) -> bool {
+        matches!(
+            self,
+            AuxiliaryFunctionType::BooleanLatching
+                | AuxiliaryFunctionType::BooleanNonLatching
+                | AuxiliaryFunctionType::DualBooleanLatching
+                | AuxiliaryFunctionType::DualBooleanNonLatching
+                | AuxiliaryFunctionType::DualBooleanLatchingUp
+                | AuxiliaryFunctionType::DualBooleanLatchingDown
+                | AuxiliaryFunctionType::QuadratureBooleanNonLatching
+        )
+    }
+}
+
+impl From<u8> for AuxiliaryFunctionType {
+    fn from(value: u8) -> Self {
+        match value & 0x1F {
+            0 => AuxiliaryFunctionType::BooleanLatching,
+            1 => AuxiliaryFunctionType::Analogue,
+            2 => AuxiliaryFunctionType::BooleanNonLatching,
+            3 => AuxiliaryFunctionType::AnalogueReturnToCentre,
+            4 => AuxiliaryFunctionType::AnalogueReturnToZero,
+            5 => AuxiliaryFunctionType::DualBooleanLatching,
+            6 => AuxiliaryFunctionType::DualBooleanNonLatching,
+            7 => AuxiliaryFunctionType::DualBooleanLatchingUp,
+            8 => AuxiliaryFunctionType::DualBooleanLatchingDown,
+            9 => AuxiliaryFunctionType::CombinedAnalogueReturnWithDualBooleanLatching,
+            10 => AuxiliaryFunctionType::CombinedAnalogueWithDualBooleanLatching,
+            11 => AuxiliaryFunctionType::QuadratureBooleanNonLatching,
+            12 => AuxiliaryFunctionType::QuadratureAnalogue,
+            13 => AuxiliaryFunctionType::QuadratureAnalogueReturnToCentre,
+            14 => AuxiliaryFunctionType::BidirectionalEncoder,
+            31 => AuxiliaryFunctionType::RemoveAssignment,
+            value => AuxiliaryFunctionType::Reserved(value),
+        }
+    }
+}
+
+impl From<AuxiliaryFunctionType> for u8 {
+    fn from(value: AuxiliaryFunctionType) -> Self {
+        match value {
+            AuxiliaryFunctionType::BooleanLatching => 0,
+            AuxiliaryFunctionType::Analogue => 1,
+            AuxiliaryFunctionType::BooleanNonLatching => 2,
+            AuxiliaryFunctionType::AnalogueReturnToCentre => 3,
+            AuxiliaryFunctionType::AnalogueReturnToZero => 4,
+            AuxiliaryFunctionType::DualBooleanLatching => 5,
+            AuxiliaryFunctionType::DualBooleanNonLatching => 6,
+            AuxiliaryFunctionType::DualBooleanLatchingUp => 7,
+            AuxiliaryFunctionType::DualBooleanLatchingDown => 8,
+            AuxiliaryFunctionType::CombinedAnalogueReturnWithDualBooleanLatching => 9,
+            AuxiliaryFunctionType::CombinedAnalogueWithDualBooleanLatching => 10,
+            AuxiliaryFunctionType::QuadratureBooleanNonLatching => 11,
+            AuxiliaryFunctionType::QuadratureAnalogue => 12,
+            AuxiliaryFunctionType::QuadratureAnalogueReturnToCentre => 13,
+            AuxiliaryFunctionType::BidirectionalEncoder => 14,
+            AuxiliaryFunctionType::Reserved(value) => value & 0x1F,
+            AuxiliaryFunctionType::RemoveAssignment => 31,
+        }
+    }
+}
+
+/// The function attributes of an Auxiliary Function Type 2 or Auxiliary Input Type 2 object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuxiliaryFunctionAttributes {
+    pub function_type: AuxiliaryFunctionType,
+    /// The function needs, or the input provides, critical control
+    pub critical_control: bool,
+    /// Functions only: may only be assigned to inputs of the same type
+    pub assignment_restriction: bool,
+    /// May only be assigned to a single input or function
+    pub single_assignment: bool,
+}
+
+impl From<u8> for AuxiliaryFunctionAttributes {
+    fn from(value: u8) -> Self {
+        AuxiliaryFunctionAttributes {
+            function_type: value.into(),
+            critical_control: value & 0x20 != 0,
+            assignment_restriction: value & 0x40 != 0,
+            single_assignment: value & 0x80 != 0,
+        }
+    }
+}
+
+impl From<AuxiliaryFunctionAttributes> for u8 {
+    fn from(value: AuxiliaryFunctionAttributes) -> Self {
+        u8::from(value.function_type)
+            | u8::from(value.critical_control) << 5
+            | u8::from(value.assignment_restriction) << 6
+            | u8::from(value.single_assignment) << 7
+    }
+}
+
+/// The function type of an Auxiliary Function Type 1 or Auxiliary Input Type 1 object (AUX-O)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuxOFunctionType {
+    /// Each press of the input toggles the function on or off
+    LatchingBoolean,
+    Analogue,
+    /// The function is on while the input is pressed
+    NonLatchingBoolean,
+    Reserved(u8),
+}
+
+impl From<u8> for AuxOFunctionType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AuxOFunctionType::LatchingBoolean,
+            1 => AuxOFunctionType::Analogue,
+            2 => AuxOFunctionType::NonLatchingBoolean,
+            value => AuxOFunctionType::Reserved(value),
+        }
+    }
+}
+
+impl From<AuxOFunctionType> for u8 {
+    fn from(value: AuxOFunctionType) -> Self {
+        match value {
+            AuxOFunctionType::LatchingBoolean => 0,
+            AuxOFunctionType::Analogue => 1,
+            AuxOFunctionType::NonLatchingBoolean => 2,
+            AuxOFunctionType::Reserved(value) => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn option_bit_order_test() {
+        // Bit 0 is the least significant bit of the option byte in ISO 11783-6
+        let button = ButtonOptions::from(0b0010_0010);
+        assert!(!button.latchable);
+        assert_eq!(button.state, ButtonState::Latched);
+        assert!(button.no_border);
+        assert!(!button.disabled);
+        assert_eq!(u8::from(button), 0b0010_0010);
+
+        let window_mask = WindowMaskOptions::from(0x01);
+        assert!(window_mask.available);
+        assert!(!window_mask.transparent);
+        assert_eq!(u8::from(window_mask), 0x01);
+
+        let string = OutputStringOptions::from(0x06);
+        assert!(!string.transparent);
+        assert!(string.auto_wrap);
+        assert!(string.wrap_on_hyphen);
+        assert_eq!(u8::from(string), 0x06);
+
+        let number = NumberOptions::from(0x08);
+        assert!(number.truncate);
+        assert!(!number.transparent);
+        assert_eq!(u8::from(number), 0x08);
+
+        let input_number = InputNumberOptions::from(0x02);
+        assert!(!input_number.enabled);
+        assert!(input_number.real_time_editing);
+        assert_eq!(u8::from(input_number), 0x02);
+
+        let meter = OutputMeterOptions::from(0x09);
+        assert!(meter.draw_arc);
+        assert!(!meter.draw_ticks);
+        assert_eq!(meter.deflection_direction, DeflectionDirection::Clockwise);
+        assert_eq!(u8::from(meter), 0x09);
+
+        let linear = OutputLinearBarGraphOptions::from(0x28);
+        assert_eq!(linear.bar_graph_type, BarGraphType::NotFilled);
+        assert_eq!(linear.grow_direction, GrowDirection::GrowRightUp);
+        assert!(!linear.draw_border);
+        assert_eq!(u8::from(linear), 0x28);
+
+        // Bit 2 of the arched bar graph options is undefined
+        let arched = OutputArchedBarGraphOptions::from(0x44);
+        assert_eq!(arched.deflection_direction, DeflectionDirection::Clockwise);
+        assert_eq!(arched.bar_graph_type, BarGraphType::Filled);
+        assert_eq!(u8::from(arched), 0x40);
+
+        let picture = PictureGraphicOptions::from(0x05);
+        assert!(picture.transparent);
+        assert!(!picture.flashing);
+        assert_eq!(picture.data_code_type, DataCodeType::RunLength);
+        assert_eq!(u8::from(picture), 0x05);
+
+        let context = GraphicsContextOptions::from(0x02);
+        assert!(!context.transparent);
+        assert_eq!(context.color, ColorOption::LineFontFill);
+        assert_eq!(u8::from(context), 0x02);
+
+        let style = FontStyle::from(0x81);
+        assert!(style.bold);
+        assert!(style.proportional);
+        assert!(!style.italic);
+        assert_eq!(u8::from(style), 0x81);
+    }
+
+    #[test]
+    fn alignment_bit_order_test() {
+        // Bits 0-1 hold the horizontal and bits 2-3 the vertical justification
+        let alignment = Alignment::from(0x06);
+        assert_eq!(alignment.horizontal, HorizontalAlignment::Right);
+        assert_eq!(alignment.vertical, VerticalAlignment::Middle);
+        assert_eq!(u8::from(alignment), 0x06);
+
+        let alignment = Alignment::from(0x09);
+        assert_eq!(alignment.horizontal, HorizontalAlignment::Middle);
+        assert_eq!(alignment.vertical, VerticalAlignment::Bottom);
+        assert_eq!(u8::from(alignment), 0x09);
+
+        for value in 0..16u8 {
+            assert_eq!(u8::from(Alignment::from(value)), value);
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::object_pool::ParseError;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u16", into = "u16"))]
 pub struct ObjectId {
     id: u16,
 }
@@ -15,9 +17,23 @@ impl ObjectId {
             Ok(ObjectId { id })
         }
     }
+
+    /// Creates an object ID in a const context
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is the NULL object ID (0xFFFF), at compile time when used in a constant.
+    pub const fn new_const(id: u16) -> Self {
+        assert!(id != Self::NULL.id, "0xFFFF is the NULL object ID");
+        ObjectId { id }
+    }
 }
 
-pub struct NullableObjectId(Option<ObjectId>);
+/// A reference to an object that may be NULL (0xFFFF)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct NullableObjectId(pub Option<ObjectId>);
 
 impl NullableObjectId {
     pub const NULL: NullableObjectId = NullableObjectId(None);
@@ -25,9 +41,13 @@ impl NullableObjectId {
         if id == ObjectId::NULL.id {
             NullableObjectId(None)
         } else {
-            NullableObjectId(Some(ObjectId::new(id).unwrap()))
+            NullableObjectId(Some(ObjectId { id }))
         }
     }
+
+    pub fn is_null(&self) -> bool {
+        self.0.is_none()
+    }
 }
 
 impl Default for NullableObjectId {
@@ -1,20 +1,30 @@
 use crate::object_pool::colour::Colour;
+use crate::object_pool::encoding::encode_strings;
 use crate::object_pool::object::{
-    AlarmMask, Button, Container, DataMask, GraphicsContext, InputBoolean, InputList, InputNumber,
-    InputString, Key, KeyGroup, LineAttributes, Object, OutputLine, OutputList, OutputNumber,
-    OutputString, PictureGraphic, SoftKeyMask, WindowMask, WorkingSet,
+    AlarmMask, Button, Container, DataMask, FillAttributes, FontAttributes, GraphicsContext,
+    InputBoolean, InputList, InputNumber, InputString, Key, KeyGroup, LineAttributes,
+    NumberVariable, Object, OutputLine, OutputList, OutputNumber, OutputString, PictureGraphic,
+    SoftKeyMask, StringVariable, WindowMask, WorkingSet,
 };
+use crate::object_pool::object_attributes::FontType;
 use crate::object_pool::object_id::ObjectId;
 use crate::object_pool::vt_version::VtVersion;
-use crate::object_pool::ObjectType;
+use crate::object_pool::EncodingError;
+use crate::object_pool::{IopError, ObjectType, ParseError};
 use core::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug)]
 pub struct ObjectPool {
     objects: Vec<Object>,
+    /// The position in `objects` of the first object with each ID
+    index: BTreeMap<ObjectId, usize>,
     colour_map: [u8; 256],
     colour_palette: [Colour; 256],
-    _supported_vt_version: VtVersion,
+    supported_vt_version: VtVersion,
+    /// The font types of string values in a partial pool whose font attributes are only in the
+    /// full pool
+    pub(super) external_font_types: HashMap<ObjectId, FontType>,
 
     size_cache: Cell<Option<usize>>,
 }
@@ -23,25 +33,30 @@ impl ObjectPool {
     pub fn new() -> Self {
         // Setup the default colour map
         let mut colour_map = [0xFFu8; 256];
-        for i in 0..(colour_map.len() as u8) {
-            colour_map[i as usize] = i;
+        for (i, colour) in colour_map.iter_mut().enumerate() {
+            *colour = i as u8;
         }
 
         ObjectPool {
             objects: Vec::new(),
+            index: BTreeMap::new(),
             colour_map,
             colour_palette: Colour::COLOUR_PALETTE,
-            _supported_vt_version: VtVersion::default(),
+            supported_vt_version: VtVersion::default(),
+            external_font_types: HashMap::new(),
 
             size_cache: Cell::new(None),
         }
     }
 
-    pub fn size(&self) -> usize {
-        if self.size_cache.get().is_none() {
-            self.size_cache.set(Some(self.as_iop().len()));
+    /// The size of the pool in binary format, fails like [`ObjectPool::as_iop`]
+    pub fn size(&self) -> Result<usize, EncodingError> {
+        if let Some(size) = self.size_cache.get() {
+            return Ok(size);
         }
-        self.size_cache.get().unwrap_or_default()
+        let size = self.as_iop()?.len();
+        self.size_cache.set(Some(size));
+        Ok(size)
     }
 
     ///
@@ -49,6 +64,12 @@ impl ObjectPool {
     /// with ISO 11783-6 Annex B (object definitions) and returns the
     /// parsed [`ObjectPool`].
     ///
+    /// Parsing stops at the first object that cannot be parsed, see
+    /// [`ObjectPool::try_from_iop`] and [`ObjectPool::from_iop_lenient`] to find out why.
+    ///
+    /// String values are decoded with the [font type](ObjectPool::string_font_type) they are
+    /// shown with.
+    ///
     /// # Arguments
     ///
     /// * `data` - A buffer containing the binary encoded object pool
@@ -60,7 +81,10 @@ impl ObjectPool {
     /// use std::path::Path;
     /// use ag_iso_stack::object_pool::ObjectPool;
     ///
-    /// let example_path = Path::new("C:/project/resources/test/AgIsoStack-rs-test-pool.iop");
+    /// let example_path = Path::new(concat!(
+    ///     env!("CARGO_MANIFEST_DIR"),
+    ///     "/resources/test/AgIsoStack-rs-test-pool.iop"
+    /// ));
     /// let mut pool_file = match File::open(example_path) {
     ///             Err(why) => panic!("couldn't open {:?}: {}", example_path.to_str(), why),
     ///             Ok(file) => file,
@@ -84,28 +108,238 @@ impl ObjectPool {
         let mut op = Self::new();
 
         while let Ok(o) = Object::read(&mut data) {
-            op.objects.push(o);
+            op.add(o);
         }
+        op.decode_strings();
 
         op
     }
 
-    pub fn as_iop(&self) -> Vec<u8> {
+    /// Loads a binary encoded object pool like [`ObjectPool::from_iop`], but fails with the
+    /// position and context of the first object that cannot be parsed instead of returning the
+    /// objects before it.
+    pub fn try_from_iop<I>(data: I) -> Result<Self, IopError>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let data: Vec<u8> = data.into_iter().collect();
+
+        let mut op = Self::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let (object, next) = read_object_at(&data, offset)?;
+            op.add(object);
+            offset = next;
+        }
+        op.decode_strings();
+
+        Ok(op)
+    }
+
+    /// Loads a binary encoded object pool, skipping objects that cannot be parsed
+    ///
+    /// Objects don't encode their length, so after an error the data is searched for the next
+    /// position where the following objects parse without errors and use IDs that are not in the
+    /// pool yet. Everything in between is skipped. Returns the parsed pool and an error for every
+    /// part of the data that was skipped.
+    ///
+    /// Each candidate position is only checked against a limited window of the data following
+    /// it, so the search takes linear time in the length of the data. An object that is larger
+    /// than that window and directly follows a skipped part may be skipped as well.
+    pub fn from_iop_lenient<I>(data: I) -> (Self, Vec<IopError>)
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let data: Vec<u8> = data.into_iter().collect();
+
+        let mut op = Self::new();
+        let mut warnings = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            match read_object_at(&data, offset) {
+                Ok((object, next)) => {
+                    op.add(object);
+                    offset = next;
+                }
+                Err(error) => {
+                    warnings.push(error);
+                    offset = (offset + 1..data.len())
+                        .find(|&o| op.is_plausible_object_start(&data, o))
+                        .unwrap_or(data.len());
+                }
+            }
+        }
+        op.decode_strings();
+
+        (op, warnings)
+    }
+
+    /// Whether the objects starting at `offset` look like a continuation of this pool
+    ///
+    /// Only the next [`PROBE_LENGTH`] bytes are parsed. If at least one object was read
+    /// completely, an object that runs past the end of that window is accepted.
+    fn is_plausible_object_start(&self, data: &[u8], mut offset: usize) -> bool {
+        const OBJECTS_TO_CHECK: usize = 4;
+
+        let end = data.len().min(offset + PROBE_LENGTH);
+        let probe = &data[..end];
+        let mut ids = Vec::new();
+        while ids.len() < OBJECTS_TO_CHECK && offset < end {
+            match read_object_at(probe, offset) {
+                Ok((object, next)) => {
+                    if ids.contains(&object.id()) || self.object_by_id(object.id()).is_some() {
+                        return false;
+                    }
+                    ids.push(object.id());
+                    offset = next;
+                }
+                Err(IopError {
+                    error: ParseError::UnexpectedEnd { .. },
+                    ..
+                }) if end < data.len() => return !ids.is_empty(),
+                Err(_) => return false,
+            }
+        }
+        true
+    }
+
+    /// Writes the pool in binary format
+    ///
+    /// String values are encoded in the character set of their font, or as WideString if they
+    /// have other characters, see [`ObjectPool::encode_string_value`]. Fails if a string needs a
+    /// WideString but the pool is made for a VT older than version 4.
+    pub fn as_iop(&self) -> Result<Vec<u8>, EncodingError> {
         let mut data = Vec::new();
 
+        let font_types = self.string_font_types();
         for obj in &self.objects {
-            data.extend(obj.write());
+            data.extend(encode_strings(obj, &font_types, self.supported_vt_version)?.write()?);
         }
 
-        data
+        Ok(data)
     }
 
+    /// Adds an object to the end of the pool
+    ///
+    /// An object with an ID that is already in the pool is added too, but can't be looked up by
+    /// ID until the first one is removed, [`ObjectPool::validate`] reports it.
     pub fn add(&mut self, obj: Object) {
+        let position = self.objects.len();
+        self.index.entry(obj.id()).or_insert(position);
         self.objects.push(obj);
+        self.size_cache.set(None);
+    }
+
+    /// Removes the object with this ID, the objects after it keep their order
+    pub fn remove(&mut self, id: ObjectId) -> Option<Object> {
+        let position = self.index.remove(&id)?;
+        let object = self.objects.remove(position);
+        for other in self.index.values_mut() {
+            if *other > position {
+                *other -= 1;
+            }
+        }
+        // A later object with the same ID can be looked up now
+        if let Some(duplicate) = self.objects[position..].iter().position(|o| o.id() == id) {
+            self.index.insert(id, position + duplicate);
+        }
+        self.size_cache.set(None);
+        Some(object)
+    }
+
+    /// Replaces the object with the same ID, in its position, and returns the replaced object
+    ///
+    /// Adds the object if there is no object with its ID.
+    pub fn replace(&mut self, obj: Object) -> Option<Object> {
+        match self.index.get(&obj.id()) {
+            Some(&position) => {
+                self.size_cache.set(None);
+                Some(std::mem::replace(&mut self.objects[position], obj))
+            }
+            None => {
+                self.add(obj);
+                None
+            }
+        }
+    }
+
+    /// Keeps only the objects `f` returns `true` for, in their order
+    pub fn retain(&mut self, f: impl FnMut(&Object) -> bool) {
+        self.objects.retain(f);
+        self.reindex();
+        self.size_cache.set(None);
+    }
+
+    /// Calls `f` on every object, which may change the object IDs
+    pub(super) fn update_all(&mut self, f: impl FnMut(&mut Object)) {
+        self.objects.iter_mut().for_each(f);
+        self.reindex();
+        self.size_cache.set(None);
+    }
+
+    /// Changes the ID of the object with ID `id` to `new_id`
+    ///
+    /// References to the object are not changed, see [`ObjectPool::remap_ids`] to change them
+    /// as well. Returns `false` if there is no object with ID `id` or `new_id` is already used.
+    pub fn change_id(&mut self, id: ObjectId, new_id: ObjectId) -> bool {
+        if self.index.contains_key(&new_id) {
+            return false;
+        }
+        let Some(position) = self.index.remove(&id) else {
+            return false;
+        };
+        self.objects[position].set_id(new_id);
+        self.index.insert(new_id, position);
+        // A later object with the old ID can be looked up now
+        if let Some(duplicate) = self.objects.iter().position(|o| o.id() == id) {
+            self.index.insert(id, duplicate);
+        }
+        true
+    }
+
+    /// Rebuilds the index after the positions or IDs of objects changed
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (position, object) in self.objects.iter().enumerate() {
+            self.index.entry(object.id()).or_insert(position);
+        }
+    }
+
+    /// The VT version this pool is made for
+    pub fn supported_vt_version(&self) -> VtVersion {
+        self.supported_vt_version
+    }
+
+    pub fn set_supported_vt_version(&mut self, vt_version: VtVersion) {
+        self.supported_vt_version = vt_version;
+        // The strings may be encoded differently
+        self.size_cache.set(None);
+    }
+
+    /// All objects in the pool, in the order they were added
+    pub fn objects(&self) -> &[Object] {
+        &self.objects
+    }
+
+    /// All objects in the pool, sorted by ID
+    pub fn objects_in_id_order(&self) -> impl Iterator<Item = &Object> {
+        self.index.values().map(|&position| &self.objects[position])
     }
 
     pub fn object_by_id(&self, id: ObjectId) -> Option<&Object> {
-        self.objects.iter().find(|&o| o.id() == id)
+        let position = *self.index.get(&id)?;
+        Some(&self.objects[position])
+    }
+
+    /// The object with this ID, for changes at runtime
+    ///
+    /// The pool is indexed by the object IDs, so the ID must not be changed through the returned
+    /// object, use [`ObjectPool::change_id`] instead.
+    pub fn object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut Object> {
+        let position = *self.index.get(&id)?;
+        // The size can change with the object
+        self.size_cache.set(None);
+        Some(&mut self.objects[position])
     }
 
     pub fn objects_by_type(&self, object_type: ObjectType) -> Vec<&Object> {
@@ -276,9 +510,266 @@ impl ObjectPool {
         }
     }
 
+    pub fn picture_graphic_object_by_id(&self, id: ObjectId) -> Option<&PictureGraphic> {
+        match &self.object_by_id(id) {
+            Some(Object::PictureGraphic(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn number_variable_object_by_id(&self, id: ObjectId) -> Option<&NumberVariable> {
+        match &self.object_by_id(id) {
+            Some(Object::NumberVariable(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn string_variable_object_by_id(&self, id: ObjectId) -> Option<&StringVariable> {
+        match &self.object_by_id(id) {
+            Some(Object::StringVariable(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn font_attributes_object_by_id(&self, id: ObjectId) -> Option<&FontAttributes> {
+        match &self.object_by_id(id) {
+            Some(Object::FontAttributes(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn fill_attributes_object_by_id(&self, id: ObjectId) -> Option<&FillAttributes> {
+        match &self.object_by_id(id) {
+            Some(Object::FillAttributes(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    // Get mutable typed objects by id
+
+    pub fn data_mask_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut DataMask> {
+        match self.object_by_id_mut(id) {
+            Some(Object::DataMask(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn alarm_mask_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut AlarmMask> {
+        match self.object_by_id_mut(id) {
+            Some(Object::AlarmMask(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn soft_key_mask_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut SoftKeyMask> {
+        match self.object_by_id_mut(id) {
+            Some(Object::SoftKeyMask(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn key_group_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut KeyGroup> {
+        match self.object_by_id_mut(id) {
+            Some(Object::KeyGroup(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn window_mask_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut WindowMask> {
+        match self.object_by_id_mut(id) {
+            Some(Object::WindowMask(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn container_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut Container> {
+        match self.object_by_id_mut(id) {
+            Some(Object::Container(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn key_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut Key> {
+        match self.object_by_id_mut(id) {
+            Some(Object::Key(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn button_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut Button> {
+        match self.object_by_id_mut(id) {
+            Some(Object::Button(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn input_boolean_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut InputBoolean> {
+        match self.object_by_id_mut(id) {
+            Some(Object::InputBoolean(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn input_string_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut InputString> {
+        match self.object_by_id_mut(id) {
+            Some(Object::InputString(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn input_number_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut InputNumber> {
+        match self.object_by_id_mut(id) {
+            Some(Object::InputNumber(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn input_list_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut InputList> {
+        match self.object_by_id_mut(id) {
+            Some(Object::InputList(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn output_string_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut OutputString> {
+        match self.object_by_id_mut(id) {
+            Some(Object::OutputString(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn output_number_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut OutputNumber> {
+        match self.object_by_id_mut(id) {
+            Some(Object::OutputNumber(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn output_list_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut OutputList> {
+        match self.object_by_id_mut(id) {
+            Some(Object::OutputList(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn output_line_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut OutputLine> {
+        match self.object_by_id_mut(id) {
+            Some(Object::OutputLine(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn line_attributes_object_by_id_mut(
+        &mut self,
+        id: ObjectId,
+    ) -> Option<&mut LineAttributes> {
+        match self.object_by_id_mut(id) {
+            Some(Object::LineAttributes(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn graphics_context_object_by_id_mut(
+        &mut self,
+        id: ObjectId,
+    ) -> Option<&mut GraphicsContext> {
+        match self.object_by_id_mut(id) {
+            Some(Object::GraphicsContext(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn picture_graphic_object_by_id_mut(
+        &mut self,
+        id: ObjectId,
+    ) -> Option<&mut PictureGraphic> {
+        match self.object_by_id_mut(id) {
+            Some(Object::PictureGraphic(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn number_variable_object_by_id_mut(
+        &mut self,
+        id: ObjectId,
+    ) -> Option<&mut NumberVariable> {
+        match self.object_by_id_mut(id) {
+            Some(Object::NumberVariable(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn string_variable_object_by_id_mut(
+        &mut self,
+        id: ObjectId,
+    ) -> Option<&mut StringVariable> {
+        match self.object_by_id_mut(id) {
+            Some(Object::StringVariable(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn font_attributes_object_by_id_mut(
+        &mut self,
+        id: ObjectId,
+    ) -> Option<&mut FontAttributes> {
+        match self.object_by_id_mut(id) {
+            Some(Object::FontAttributes(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn fill_attributes_object_by_id_mut(
+        &mut self,
+        id: ObjectId,
+    ) -> Option<&mut FillAttributes> {
+        match self.object_by_id_mut(id) {
+            Some(Object::FillAttributes(o)) => Some(o),
+            _ => None,
+        }
+    }
+
     pub fn color_by_index(&self, index: u8) -> Colour {
         self.colour_palette[self.colour_map[index as usize] as usize]
     }
+
+    /// The palette index each colour index is mapped to
+    pub fn colour_map(&self) -> &[u8; 256] {
+        &self.colour_map
+    }
+
+    pub fn set_colour_map(&mut self, colour_map: [u8; 256]) {
+        self.colour_map = colour_map;
+    }
+
+    pub fn colour_palette(&self) -> &[Colour; 256] {
+        &self.colour_palette
+    }
+
+    pub fn set_colour_palette(&mut self, colour_palette: [Colour; 256]) {
+        self.colour_palette = colour_palette;
+    }
+}
+
+/// Parses the object starting at `offset`, returns it with the offset of the next object
+/// The number of bytes [`ObjectPool::from_iop_lenient`] parses to check a resync position
+const PROBE_LENGTH: usize = 1024;
+
+fn read_object_at(data: &[u8], offset: usize) -> Result<(Object, usize), IopError> {
+    let mut iter = data[offset..].iter().copied();
+    match Object::read(&mut iter) {
+        Ok(object) => Ok((object, data.len() - iter.len())),
+        Err(error) => {
+            let header = &data[offset..];
+            Err(IopError {
+                offset: data.len() - iter.len(),
+                object_offset: offset,
+                object_id: (header.len() >= 2).then(|| u16::from_le_bytes([header[0], header[1]])),
+                object_type: header.get(2).and_then(|t| ObjectType::try_from(*t).ok()),
+                error,
+            })
+        }
+    }
 }
 
 impl Default for ObjectPool {
@@ -287,41 +778,145 @@ impl Default for ObjectPool {
     }
 }
 
-/* !todo: implement tests / fix tests
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::Read;
-    use std::path::Path;
-
-    fn get_pool_path() -> Box<Path> {
-        Box::from(Path::new(&format!(
-            "{}/resources/test/AgIsoStack-rs-test-pool.iop",
-            match std::env::var("CARGO_MANIFEST_DIR") {
-                Err(_why) =>
-                    panic!("could not find environment variable 'CARGO_MANIFEST_DIR': {_why}!"),
-                Ok(path) => path,
-            }
-        )))
+    use crate::object_pool::object::ObjectPointer;
+    use crate::object_pool::NullableObjectId;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
     }
 
-    fn get_pool_file() -> File {
-        match File::open(get_pool_path()) {
-            Err(why) => panic!("couldn't open {:?}: {}", get_pool_path().to_str(), why),
-            Ok(file) => file,
-        }
+    fn number_variable(object_id: u16, value: u32) -> Object {
+        Object::NumberVariable(NumberVariable {
+            id: id(object_id),
+            value,
+        })
     }
 
     #[test]
     fn test_from_iop() {
-        let mut buffer = Vec::new();
-        match get_pool_file().read_to_end(&mut buffer) {
-            Ok(size) => size,
-            Err(why) => panic!("Could not read object pool file: {why}"),
-        };
+        let data = include_bytes!("../../resources/test/AgIsoStack-rs-test-pool.iop");
+        let pool = ObjectPool::from_iop(data.iter().copied());
+        for object in pool.objects() {
+            assert!(pool.object_by_id(object.id()).is_some());
+        }
+    }
+
+    #[test]
+    fn test_remove_and_replace() {
+        let mut pool = ObjectPool::new();
+        pool.add(number_variable(3, 0));
+        pool.add(number_variable(1, 1));
+        pool.add(number_variable(2, 2));
+
+        let ids: Vec<u16> = pool.objects_in_id_order().map(|o| o.id().into()).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        assert_eq!(pool.remove(id(1)), Some(number_variable(1, 1)));
+        assert_eq!(pool.remove(id(1)), None);
+        assert_eq!(
+            pool.objects(),
+            &[number_variable(3, 0), number_variable(2, 2)]
+        );
+        assert_eq!(pool.object_by_id(id(2)), Some(&number_variable(2, 2)));
+
+        assert_eq!(
+            pool.replace(number_variable(3, 30)),
+            Some(number_variable(3, 0))
+        );
+        assert_eq!(pool.replace(number_variable(4, 40)), None);
+        assert_eq!(
+            pool.objects(),
+            &[
+                number_variable(3, 30),
+                number_variable(2, 2),
+                number_variable(4, 40)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let mut pool = ObjectPool::new();
+        pool.add(number_variable(1, 1));
+        pool.add(number_variable(1, 2));
+        assert_eq!(pool.objects().len(), 2);
+        assert_eq!(pool.object_by_id(id(1)), Some(&number_variable(1, 1)));
+
+        // Removing the first object makes the duplicate visible
+        pool.remove(id(1));
+        assert_eq!(pool.object_by_id(id(1)), Some(&number_variable(1, 2)));
+    }
+
+    #[test]
+    fn test_mutable_access() {
+        let mut pool = ObjectPool::new();
+        pool.add(number_variable(1, 1));
+        pool.add(Object::ObjectPointer(ObjectPointer {
+            id: id(2),
+            value: NullableObjectId::NULL,
+        }));
+
+        pool.number_variable_object_by_id_mut(id(1)).unwrap().value = 10;
+        assert_eq!(pool.number_variable_object_by_id(id(1)).unwrap().value, 10);
+        assert!(pool.number_variable_object_by_id_mut(id(2)).is_none());
+        assert!(pool.object_by_id_mut(id(3)).is_none());
+    }
+
+    #[test]
+    fn test_change_id() {
+        let mut pool = ObjectPool::new();
+        pool.add(number_variable(1, 1));
+        pool.add(number_variable(2, 2));
+        pool.add(number_variable(1, 3));
+
+        assert!(pool.change_id(id(2), id(5)));
+        assert_eq!(pool.object_by_id(id(2)), None);
+        assert_eq!(pool.object_by_id(id(5)), Some(&number_variable(5, 2)));
+        assert!(!pool.change_id(id(2), id(6)));
+        assert!(!pool.change_id(id(5), id(1)));
 
-        let _object_pool = ObjectPool::from_iop(buffer);
+        // The duplicate is found once the first object with the ID is renamed
+        assert!(pool.change_id(id(1), id(4)));
+        assert_eq!(pool.object_by_id(id(1)), Some(&number_variable(1, 3)));
+        let ids: Vec<u16> = pool.objects_in_id_order().map(|o| o.id().into()).collect();
+        assert_eq!(ids, vec![1, 4, 5]);
+
+        assert_eq!(pool.remove(id(4)), Some(number_variable(4, 1)));
+        assert_eq!(pool.object_by_id(id(5)), Some(&number_variable(5, 2)));
+        assert_eq!(pool.object_by_id(id(1)), Some(&number_variable(1, 3)));
+    }
+
+    #[test]
+    fn test_size_cache() {
+        let mut pool = ObjectPool::new();
+        pool.add(number_variable(1, 1));
+        let size = pool.size().unwrap();
+        assert_eq!(size, pool.as_iop().unwrap().len());
+
+        pool.add(number_variable(2, 2));
+        assert_eq!(pool.size().unwrap(), pool.as_iop().unwrap().len());
+        pool.remove(id(2));
+        assert_eq!(pool.size().unwrap(), size);
+
+        pool.replace(Object::StringVariable(StringVariable {
+            id: id(1),
+            value: "a".to_string(),
+        }));
+        assert_eq!(pool.size().unwrap(), pool.as_iop().unwrap().len());
+        if let Some(Object::StringVariable(o)) = pool.object_by_id_mut(id(1)) {
+            o.value = "a longer value".to_string();
+        }
+        assert_eq!(pool.size().unwrap(), pool.as_iop().unwrap().len());
+
+        // A VT version 3 pool can't encode the value
+        if let Some(Object::StringVariable(o)) = pool.object_by_id_mut(id(1)) {
+            o.value = "Ω".to_string();
+        }
+        assert!(pool.size().is_err());
+        pool.set_supported_vt_version(VtVersion::Version4);
+        assert_eq!(pool.size().unwrap(), pool.as_iop().unwrap().len());
     }
 }
-*/
@@ -0,0 +1,543 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::object::PictureGraphic;
+use crate::object_pool::object_attributes::{
+    DataCodeType, PictureGraphicFormat, PictureGraphicOptions,
+};
+use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::render::RgbaImage;
+use crate::object_pool::{Colour, ObjectPool};
+
+/// Pixels with an alpha value below this are treated as transparent when encoding
+const ALPHA_THRESHOLD: u8 = 0x80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PictureGraphicError {
+    /// The format byte does not name a known pixel format
+    UnknownFormat(u8),
+    /// Run length encoded data must consist of (count, value) pairs
+    InvalidRunLengthData,
+    /// The (decoded) data holds fewer bytes than the dimensions of the picture require
+    NotEnoughData { expected: usize, actual: usize },
+    /// The image to encode is empty or larger than a picture graphic can hold
+    InvalidDimensions { width: u32, height: u32 },
+    /// The transparency colour is not one of the colours the pixel format can hold
+    InvalidTransparencyColour {
+        colour: u8,
+        format: PictureGraphicFormat,
+    },
+}
+
+impl std::fmt::Display for PictureGraphicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for PictureGraphicError {}
+
+impl PictureGraphic {
+    pub fn pixel_format(&self) -> Result<PictureGraphicFormat, PictureGraphicError> {
+        PictureGraphicFormat::from_repr(self.format)
+            .ok_or(PictureGraphicError::UnknownFormat(self.format))
+    }
+
+    /// Returns the pixel data with any run length encoding removed
+    ///
+    /// Each row of pixels starts on a new byte, unused bits at the end of a row are padding.
+    pub fn raw_data(&self) -> Result<Vec<u8>, PictureGraphicError> {
+        match self.options.data_code_type {
+            DataCodeType::Raw => Ok(self.data.clone()),
+            DataCodeType::RunLength => {
+                if !self.data.len().is_multiple_of(2) {
+                    return Err(PictureGraphicError::InvalidRunLengthData);
+                }
+                Ok(self
+                    .data
+                    .chunks_exact(2)
+                    .flat_map(|run| std::iter::repeat_n(run[1], run[0] as usize))
+                    .collect())
+            }
+        }
+    }
+
+    /// Decodes the picture into one colour index per pixel, row by row
+    pub fn colour_indices(&self) -> Result<Vec<u8>, PictureGraphicError> {
+        let format = self.pixel_format()?;
+        let raw = self.raw_data()?;
+        let width = self.actual_width as usize;
+        let height = self.actual_height as usize;
+        let bits_per_pixel = format.bits_per_pixel();
+        let bytes_per_row = row_size(width, format);
+
+        let expected = bytes_per_row * height;
+        if raw.len() < expected {
+            return Err(PictureGraphicError::NotEnoughData {
+                expected,
+                actual: raw.len(),
+            });
+        }
+
+        let pixels_per_byte = 8 / bits_per_pixel;
+        let mask = ((1u16 << bits_per_pixel) - 1) as u8;
+        let mut indices = Vec::with_capacity(width * height);
+        for row in raw.chunks(bytes_per_row.max(1)).take(height) {
+            for column in 0..width {
+                let byte = row[column / pixels_per_byte];
+                let shift = 8 - bits_per_pixel * (column % pixels_per_byte + 1);
+                indices.push((byte >> shift) & mask);
+            }
+        }
+        Ok(indices)
+    }
+
+    /// Decodes the picture at its actual size using the colour palette of `pool`
+    ///
+    /// If the picture is transparent, pixels of the transparency colour are fully transparent.
+    pub fn to_rgba(&self, pool: &ObjectPool) -> Result<RgbaImage, PictureGraphicError> {
+        let indices = self.colour_indices()?;
+        let mut image = RgbaImage::new(self.actual_width as u32, self.actual_height as u32);
+        let width = self.actual_width.max(1) as usize;
+        for (i, index) in indices.iter().enumerate() {
+            if self.options.transparent && *index == self.transparency_colour {
+                continue;
+            }
+            let (x, y) = (i % width, i / width);
+            image.put_pixel(x as i32, y as i32, pool.color_by_index(*index).as_rgba());
+        }
+        Ok(image)
+    }
+}
+
+/// Creates [`PictureGraphic`] objects from RGBA images
+///
+/// The image is quantised to the standard VT colour palette, optionally using Floyd-Steinberg
+/// dithering, and run length encoded if that makes the data smaller.
+///
+/// # Examples
+/// ```
+/// use ag_iso_stack::object_pool::render::RgbaImage;
+/// use ag_iso_stack::object_pool::{Colour, ObjectId, PictureGraphicEncoder, PictureGraphicFormat};
+///
+/// let image = RgbaImage::filled(64, 32, Colour::RED);
+/// let picture = PictureGraphicEncoder::new(PictureGraphicFormat::FourBitColour)
+///     .with_dithering(true)
+///     .encode(ObjectId::new(1000).unwrap(), &image)
+///     .unwrap();
+///
+/// assert_eq!(picture.colour_indices().unwrap(), vec![12; 64 * 32]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PictureGraphicEncoder {
+    format: PictureGraphicFormat,
+    dithering: bool,
+    width: Option<u16>,
+    transparency_colour: Option<u8>,
+}
+
+impl PictureGraphicEncoder {
+    pub fn new(format: PictureGraphicFormat) -> Self {
+        PictureGraphicEncoder {
+            format,
+            dithering: false,
+            width: None,
+            transparency_colour: None,
+        }
+    }
+
+    /// Diffuse the quantisation error over neighbouring pixels
+    pub fn with_dithering(mut self, dithering: bool) -> Self {
+        self.dithering = dithering;
+        self
+    }
+
+    /// The width the picture is displayed at, defaults to the width of the image
+    pub fn with_width(mut self, width: u16) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Encode transparent pixels as `colour` and mark the picture as transparent
+    ///
+    /// Opaque pixels are never quantised to the transparency colour. The colour must fit the
+    /// format, i.e. be 0 or 1 for monochrome and at most 15 for 4 bit pictures, otherwise
+    /// [`PictureGraphicEncoder::encode`] fails.
+    pub fn with_transparency_colour(mut self, colour: u8) -> Self {
+        self.transparency_colour = Some(colour);
+        self
+    }
+
+    pub fn encode(
+        &self,
+        id: ObjectId,
+        image: &RgbaImage,
+    ) -> Result<PictureGraphic, PictureGraphicError> {
+        let (width, height) = (image.width(), image.height());
+        if width == 0 || height == 0 || width > u16::MAX as u32 || height > u16::MAX as u32 {
+            return Err(PictureGraphicError::InvalidDimensions { width, height });
+        }
+        if let Some(colour) = self.transparency_colour {
+            if colour as usize >= self.format.colours() {
+                return Err(PictureGraphicError::InvalidTransparencyColour {
+                    colour,
+                    format: self.format,
+                });
+            }
+        }
+
+        let indices = self.quantise(image);
+        let raw = pack(&indices, width as usize, self.format);
+        let run_length = run_length_encode(&raw);
+        let (data_code_type, data) = if run_length.len() < raw.len() {
+            (DataCodeType::RunLength, run_length)
+        } else {
+            (DataCodeType::Raw, raw)
+        };
+
+        Ok(PictureGraphic {
+            id,
+            width: self.width.unwrap_or(width as u16),
+            actual_width: width as u16,
+            actual_height: height as u16,
+            format: self.format.into(),
+            options: PictureGraphicOptions {
+                transparent: self.transparency_colour.is_some(),
+                flashing: false,
+                data_code_type,
+            },
+            transparency_colour: self.transparency_colour.unwrap_or_default(),
+            data,
+            macro_refs: vec![],
+        })
+    }
+
+    /// Maps every pixel of the image to the index of a colour in the VT palette
+    fn quantise(&self, image: &RgbaImage) -> Vec<u8> {
+        let palette: Vec<Colour> = Colour::COLOUR_PALETTE[..self.format.colours()]
+            .iter()
+            .filter(|c| Some(c.id) != self.transparency_colour)
+            .copied()
+            .collect();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+
+        // The quantisation error diffused to pixels that have not been processed yet
+        let mut error = vec![[0f32; 3]; width * height];
+        let mut indices = Vec::with_capacity(width * height);
+        for (i, pixel) in image.as_raw().chunks_exact(4).enumerate() {
+            if let Some(transparency_colour) = self.transparency_colour {
+                if pixel[3] < ALPHA_THRESHOLD {
+                    indices.push(transparency_colour);
+                    continue;
+                }
+            }
+
+            let target = [0, 1, 2].map(|c| (pixel[c] as f32 + error[i][c]).clamp(0.0, 255.0));
+            let colour = nearest_colour(&palette, target);
+            indices.push(colour.id);
+
+            if self.dithering {
+                let (x, y) = (i % width, i / width);
+                let delta = [
+                    target[0] - colour.r as f32,
+                    target[1] - colour.g as f32,
+                    target[2] - colour.b as f32,
+                ];
+                let neighbours = [(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)];
+                for (dx, dy, weight) in neighbours {
+                    let (nx, ny) = (x as isize + dx, y + dy);
+                    if nx < 0 || nx as usize >= width || ny >= height {
+                        continue;
+                    }
+                    let neighbour = &mut error[ny * width + nx as usize];
+                    for c in 0..3 {
+                        neighbour[c] += delta[c] * weight / 16.0;
+                    }
+                }
+            }
+        }
+        indices
+    }
+}
+
+fn nearest_colour(palette: &[Colour], target: [f32; 3]) -> Colour {
+    let distance = |c: &Colour| {
+        let dr = c.r as f32 - target[0];
+        let dg = c.g as f32 - target[1];
+        let db = c.b as f32 - target[2];
+        dr * dr + dg * dg + db * db
+    };
+    palette
+        .iter()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .copied()
+        .unwrap_or(Colour::BLACK)
+}
+
+fn row_size(width: usize, format: PictureGraphicFormat) -> usize {
+    (width * format.bits_per_pixel()).div_ceil(8)
+}
+
+/// Packs colour indices into bytes, every row starting on a new byte
+fn pack(indices: &[u8], width: usize, format: PictureGraphicFormat) -> Vec<u8> {
+    let bits_per_pixel = format.bits_per_pixel();
+    let pixels_per_byte = 8 / bits_per_pixel;
+    let bytes_per_row = row_size(width, format);
+    let mask = ((1u16 << bits_per_pixel) - 1) as u8;
+
+    let mut data = Vec::with_capacity(bytes_per_row * indices.len() / width);
+    for row in indices.chunks(width) {
+        let mut packed = vec![0u8; bytes_per_row];
+        for (column, index) in row.iter().enumerate() {
+            let shift = 8 - bits_per_pixel * (column % pixels_per_byte + 1);
+            packed[column / pixels_per_byte] |= (index & mask) << shift;
+        }
+        data.extend(packed);
+    }
+    data
+}
+
+/// Encodes data as (count, value) pairs, runs are at most 255 bytes long
+fn run_length_encode(data: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    let mut bytes = data.iter().peekable();
+    while let Some(value) = bytes.next() {
+        let mut count = 1u8;
+        while count < u8::MAX && bytes.peek() == Some(&value) {
+            bytes.next();
+            count += 1;
+        }
+        encoded.push(count);
+        encoded.push(*value);
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id() -> ObjectId {
+        ObjectId::new(1).unwrap()
+    }
+
+    fn picture(
+        format: PictureGraphicFormat,
+        width: u16,
+        height: u16,
+        data: Vec<u8>,
+    ) -> PictureGraphic {
+        PictureGraphic {
+            id: id(),
+            width,
+            actual_width: width,
+            actual_height: height,
+            format: format.into(),
+            options: PictureGraphicOptions {
+                transparent: false,
+                flashing: false,
+                data_code_type: DataCodeType::Raw,
+            },
+            transparency_colour: 0,
+            data,
+            macro_refs: vec![],
+        }
+    }
+
+    #[test]
+    fn test_decode_formats() {
+        let mono = picture(
+            PictureGraphicFormat::Monochrome,
+            10,
+            2,
+            vec![0xA0, 0x40, 0xFF, 0xC0],
+        );
+        assert_eq!(
+            mono.colour_indices().unwrap(),
+            vec![1, 0, 1, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]
+        );
+
+        let four_bit = picture(
+            PictureGraphicFormat::FourBitColour,
+            3,
+            2,
+            vec![0x12, 0x30, 0x45, 0x60],
+        );
+        assert_eq!(four_bit.colour_indices().unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+        let eight_bit = picture(PictureGraphicFormat::EightBitColour, 2, 1, vec![200, 17]);
+        assert_eq!(eight_bit.colour_indices().unwrap(), vec![200, 17]);
+    }
+
+    #[test]
+    fn test_decode_run_length() {
+        let mut mono = picture(
+            PictureGraphicFormat::Monochrome,
+            3,
+            2,
+            vec![1, 0b1010_0000, 1, 0b0100_0000],
+        );
+        mono.options.data_code_type = DataCodeType::RunLength;
+        assert_eq!(mono.colour_indices().unwrap(), vec![1, 0, 1, 0, 1, 0]);
+
+        let mut eight_bit = picture(
+            PictureGraphicFormat::EightBitColour,
+            4,
+            2,
+            vec![5, 9, 3, 12],
+        );
+        eight_bit.options.data_code_type = DataCodeType::RunLength;
+        assert_eq!(
+            eight_bit.colour_indices().unwrap(),
+            vec![9, 9, 9, 9, 9, 12, 12, 12]
+        );
+
+        eight_bit.data.pop();
+        assert_eq!(
+            eight_bit.colour_indices(),
+            Err(PictureGraphicError::InvalidRunLengthData)
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let short = picture(PictureGraphicFormat::EightBitColour, 2, 2, vec![1, 2, 3]);
+        assert_eq!(
+            short.colour_indices(),
+            Err(PictureGraphicError::NotEnoughData {
+                expected: 4,
+                actual: 3
+            })
+        );
+
+        let mut unknown = picture(PictureGraphicFormat::EightBitColour, 1, 1, vec![1]);
+        unknown.format = 3;
+        assert_eq!(
+            unknown.colour_indices(),
+            Err(PictureGraphicError::UnknownFormat(3))
+        );
+    }
+
+    #[test]
+    fn test_to_rgba() {
+        let pool = ObjectPool::new();
+        let mut four_bit = picture(PictureGraphicFormat::FourBitColour, 2, 1, vec![0x9C]);
+        four_bit.options.transparent = true;
+        four_bit.transparency_colour = 12;
+
+        let image = four_bit.to_rgba(&pool).unwrap();
+        assert_eq!(image.pixel(0, 0), Some(Colour::BLUE.as_rgba()));
+        assert_eq!(image.pixel(1, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let mut image = RgbaImage::filled(5, 3, Colour::WHITE);
+        image.put_pixel(1, 1, Colour::NAVY.as_rgba());
+        image.put_pixel(4, 2, Colour::new_by_id(100).as_rgba());
+
+        for format in [
+            PictureGraphicFormat::Monochrome,
+            PictureGraphicFormat::FourBitColour,
+            PictureGraphicFormat::EightBitColour,
+        ] {
+            let picture = PictureGraphicEncoder::new(format)
+                .encode(id(), &image)
+                .unwrap();
+            let indices = picture.colour_indices().unwrap();
+            assert_eq!(indices.len(), 15);
+            assert_eq!(indices[0], 1);
+            match format {
+                PictureGraphicFormat::Monochrome => assert_eq!(indices[6], 0),
+                PictureGraphicFormat::FourBitColour => assert_eq!(indices[6], 15),
+                PictureGraphicFormat::EightBitColour => {
+                    assert_eq!(indices[6], 15);
+                    assert_eq!(indices[14], 100);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_chooses_smallest_coding() {
+        let plain = RgbaImage::filled(100, 10, Colour::GREEN);
+        let picture = PictureGraphicEncoder::new(PictureGraphicFormat::EightBitColour)
+            .encode(id(), &plain)
+            .unwrap();
+        assert_eq!(picture.options.data_code_type, DataCodeType::RunLength);
+        assert_eq!(picture.data.len(), 8);
+
+        let mut noisy = RgbaImage::new(4, 1);
+        for x in 0..4 {
+            noisy.put_pixel(x, 0, Colour::new_by_id(x as u8 + 40).as_rgba());
+        }
+        let picture = PictureGraphicEncoder::new(PictureGraphicFormat::EightBitColour)
+            .encode(id(), &noisy)
+            .unwrap();
+        assert_eq!(picture.options.data_code_type, DataCodeType::Raw);
+        assert_eq!(picture.data, vec![40, 41, 42, 43]);
+    }
+
+    #[test]
+    fn test_encode_transparency_and_dithering() {
+        let mut image = RgbaImage::filled(4, 4, Colour::new_by_rgb(0x80, 0x80, 0x80));
+        image.put_pixel(0, 0, [0, 0, 0, 0]);
+        let picture = PictureGraphicEncoder::new(PictureGraphicFormat::Monochrome)
+            .with_dithering(true)
+            .with_transparency_colour(1)
+            .with_width(8)
+            .encode(id(), &image)
+            .unwrap();
+        assert_eq!(picture.width, 8);
+        assert!(picture.options.transparent);
+        // White is reserved for transparency, everything opaque must be black
+        let indices = picture.colour_indices().unwrap();
+        assert_eq!(indices[0], 1);
+        assert!(indices[1..].iter().all(|i| *i == 0));
+
+        // Grey (0x99) dithered to black and white is about 60% white
+        let picture = PictureGraphicEncoder::new(PictureGraphicFormat::Monochrome)
+            .with_dithering(true)
+            .encode(id(), &RgbaImage::filled(8, 8, Colour::new_by_id(8)))
+            .unwrap();
+        let white = picture
+            .colour_indices()
+            .unwrap()
+            .iter()
+            .filter(|i| **i == 1)
+            .count();
+        assert!((32..=45).contains(&white), "{white} white pixels");
+    }
+
+    #[test]
+    fn test_encode_invalid_transparency_colour() {
+        let image = RgbaImage::filled(2, 2, Colour::BLACK);
+        assert_eq!(
+            PictureGraphicEncoder::new(PictureGraphicFormat::Monochrome)
+                .with_transparency_colour(2)
+                .encode(id(), &image),
+            Err(PictureGraphicError::InvalidTransparencyColour {
+                colour: 2,
+                format: PictureGraphicFormat::Monochrome,
+            })
+        );
+        assert!(
+            PictureGraphicEncoder::new(PictureGraphicFormat::FourBitColour)
+                .with_transparency_colour(16)
+                .encode(id(), &image)
+                .is_err()
+        );
+        assert!(
+            PictureGraphicEncoder::new(PictureGraphicFormat::FourBitColour)
+                .with_transparency_colour(15)
+                .encode(id(), &image)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_run_length_encode() {
+        assert_eq!(run_length_encode(&[]), Vec::<u8>::new());
+        assert_eq!(run_length_encode(&[1, 1, 2]), vec![2, 1, 1, 2]);
+        let long = vec![7; 300];
+        assert_eq!(run_length_encode(&long), vec![255, 7, 45, 7]);
+    }
+}
@@ -2,12 +2,28 @@ use super::*;
 use crate::object_pool::colour::Colour;
 use crate::object_pool::object::*;
 use crate::object_pool::object_attributes::{MacroRef, ObjectLabel, ObjectRef, Point};
-use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
 
 impl Object {
     pub fn read(data: &mut dyn Iterator<Item = u8>) -> Result<Self, ParseError> {
-        let id = Self::read_u16(data)?.try_into()?;
-        let object_type = Self::read_u8(data)?.try_into()?;
+        // Running out of data before an object starts is the regular end of a pool
+        let first = data.next().ok_or(ParseError::DataEmpty)?;
+        let second = data.next().ok_or(ParseError::UnexpectedEnd {
+            field: "object_id",
+            expected: 2,
+            found: 1,
+        })?;
+        let id = u16::from_le_bytes([first, second]);
+        let id = ObjectId::new(id).map_err(|_| ParseError::InvalidValue {
+            field: "object_id",
+            value: id as u32,
+        })?;
+        let object_type = Self::read_u8("object_type", data)?;
+        let object_type =
+            ObjectType::try_from(object_type).map_err(|_| ParseError::InvalidValue {
+                field: "object_type",
+                value: object_type as u32,
+            })?;
 
         match object_type {
             ObjectType::WorkingSet => Self::read_working_set(id, data),
@@ -71,119 +87,143 @@ impl Object {
     /* READ COMMON TYPES */
 
     fn read_objects(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
         nr_of_objects: usize,
     ) -> Result<Vec<ObjectId>, ParseError> {
         let mut objs = Vec::new();
         for _ in 0..nr_of_objects {
-            objs.push(Self::read_u16(data)?.try_into()?);
+            objs.push(Self::read_object_id(field, data)?);
+        }
+        Ok(objs)
+    }
+
+    fn read_nullable_objects(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+        nr_of_objects: usize,
+    ) -> Result<Vec<NullableObjectId>, ParseError> {
+        let mut objs = Vec::new();
+        for _ in 0..nr_of_objects {
+            objs.push(Self::read_u16(field, data)?.into());
         }
         Ok(objs)
     }
 
     fn read_object_refs(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
         nr_of_objects: usize,
     ) -> Result<Vec<ObjectRef>, ParseError> {
         let mut refs = Vec::new();
         for _ in 0..nr_of_objects {
             refs.push(ObjectRef {
-                id: Self::read_u16(data)?.try_into()?,
+                id: Self::read_object_id(field, data)?,
                 offset: Point {
-                    x: Self::read_i16(data)?,
-                    y: Self::read_i16(data)?,
+                    x: Self::read_i16(field, data)?,
+                    y: Self::read_i16(field, data)?,
                 },
             })
         }
         Ok(refs)
     }
     fn read_macro_refs(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
         nr_of_macros: usize,
     ) -> Result<Vec<MacroRef>, ParseError> {
         let mut refs = Vec::new();
         for _ in 0..nr_of_macros {
             refs.push(MacroRef {
-                event_id: Self::read_u8(data)?,
-                macro_id: Self::read_u8(data)?,
+                event_id: Self::read_u8(field, data)?,
+                macro_id: Self::read_u8(field, data)?,
             })
         }
         Ok(refs)
     }
     fn read_bytes(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
         nr_of_bytes: usize,
     ) -> Result<Vec<u8>, ParseError> {
         let mut objs = Vec::new();
         for _ in 0..nr_of_bytes {
-            objs.push(Self::read_u8(data)?)
+            objs.push(Self::read_u8(field, data)?)
         }
         Ok(objs)
     }
     fn read_points(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
         nr_of_points: usize,
     ) -> Result<Vec<Point<u16>>, ParseError> {
         let mut objs = Vec::new();
         for _ in 0..nr_of_points {
             objs.push(Point {
-                x: Self::read_u16(data)?,
-                y: Self::read_u16(data)?,
+                x: Self::read_u16(field, data)?,
+                y: Self::read_u16(field, data)?,
             })
         }
         Ok(objs)
     }
     fn read_colours(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
         nr_of_colours: usize,
     ) -> Result<Vec<Colour>, ParseError> {
         let mut objs = Vec::new();
         for _ in 0..nr_of_colours {
-            let b = Self::read_u8(data)?;
-            let g = Self::read_u8(data)?;
-            let r = Self::read_u8(data)?;
-            let a = Self::read_u8(data)?;
+            let b = Self::read_u8(field, data)?;
+            let g = Self::read_u8(field, data)?;
+            let r = Self::read_u8(field, data)?;
+            let a = Self::read_u8(field, data)?;
 
             objs.push(Colour::new_by_rgba(r, g, b, a))
         }
         Ok(objs)
     }
     fn read_object_labels(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
         nr_of_objects: usize,
     ) -> Result<Vec<ObjectLabel>, ParseError> {
         let mut objs = Vec::new();
         for _ in 0..nr_of_objects {
             objs.push(ObjectLabel {
-                id: Self::read_u16(data)?.try_into()?,
-                string_variable_reference: Self::read_u16(data)?.try_into()?,
-                font_type: Self::read_u8(data)?,
-                graphic_representation: Self::read_u16(data)?.try_into()?,
+                id: Self::read_object_id(field, data)?,
+                string_variable_reference: Self::read_u16(field, data)?.into(),
+                font_type: Self::read_u8(field, data)?.try_into()?,
+                graphic_representation: Self::read_u16(field, data)?.into(),
             })
         }
         Ok(objs)
     }
     fn read_language_pairs(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
         nr_of_objects: usize,
     ) -> Result<Vec<(String, String)>, ParseError> {
         let mut objs = Vec::new();
         for _ in 0..nr_of_objects {
-            objs.push((Self::read_string(2, data)?, Self::read_string(2, data)?))
+            objs.push((
+                Self::read_string(field, 2, data)?,
+                Self::read_string(field, 2, data)?,
+            ))
         }
         Ok(objs)
     }
 
     fn read_character_ranges(
+        field: &'static str,
         data: &mut dyn Iterator<Item = u8>,
     ) -> Result<Vec<CharacterRange>, ParseError> {
-        let nr_of_character_ranges = Self::read_u8(data)? as usize;
+        let nr_of_character_ranges = Self::read_u8(field, data)? as usize;
         let mut character_ranges = Vec::new();
 
         for _ in 0..nr_of_character_ranges {
             let character_range = CharacterRange {
-                first_character: Self::read_u16(data)?,
-                last_character: Self::read_u16(data)?,
+                first_character: Self::read_u16(field, data)?,
+                last_character: Self::read_u16(field, data)?,
             };
             character_ranges.push(character_range);
         }
@@ -191,13 +231,16 @@ impl Object {
         Ok(character_ranges)
     }
 
-    fn read_code_planes(data: &mut dyn Iterator<Item = u8>) -> Result<Vec<CodePlane>, ParseError> {
+    fn read_code_planes(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<Vec<CodePlane>, ParseError> {
         let mut code_planes = Vec::new();
-        let nr_of_code_planes = Self::read_u8(data)? as usize;
+        let nr_of_code_planes = Self::read_u8(field, data)? as usize;
 
         for _ in 0..nr_of_code_planes {
-            let number = Self::read_u8(data)?;
-            let character_ranges = Self::read_character_ranges(data)?;
+            let number = Self::read_u8(field, data)?;
+            let character_ranges = Self::read_character_ranges(field, data)?;
             let code_plane = CodePlane {
                 number,
                 character_ranges,
@@ -209,123 +252,106 @@ impl Object {
         Ok(code_planes)
     }
 
-    fn read_bool(data: &mut dyn Iterator<Item = u8>) -> Result<bool, ParseError> {
-        match data.next() {
-            Some(d) => {
-                if d == 0 || d == 1 {
-                    Ok(d == 1)
-                } else {
-                    Err(ParseError::UnknownObjectType)
-                }
-            }
-            None => Err(ParseError::DataEmpty),
+    fn read_bool(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<bool, ParseError> {
+        match Self::read_u8(field, data)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(ParseError::InvalidValue {
+                field,
+                value: value as u32,
+            }),
         }
     }
-    fn read_u8(data: &mut dyn Iterator<Item = u8>) -> Result<u8, ParseError> {
-        match data.next() {
-            Some(d) => Ok(d),
-            None => Err(ParseError::DataEmpty),
+    /// Reads a reference that cannot be NULL
+    fn read_object_id(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ObjectId, ParseError> {
+        let id = Self::read_u16(field, data)?;
+        ObjectId::new(id).map_err(|_| ParseError::InvalidValue {
+            field,
+            value: id as u32,
+        })
+    }
+    /// Reads `N` bytes, failing with the number of bytes that were left if there are not enough
+    fn read_array<const N: usize>(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<[u8; N], ParseError> {
+        let mut bytes = [0u8; N];
+        for (found, byte) in bytes.iter_mut().enumerate() {
+            *byte = data.next().ok_or(ParseError::UnexpectedEnd {
+                field,
+                expected: N,
+                found,
+            })?;
         }
+        Ok(bytes)
     }
-    fn read_u16(data: &mut dyn Iterator<Item = u8>) -> Result<u16, ParseError> {
-        let a: Option<u8> = data.next();
-        let b: Option<u8> = data.next();
-
-        if a.is_none() || b.is_none() {
-            return Err(ParseError::DataEmpty);
-        }
-
-        Ok(u16::from_le_bytes([a.unwrap(), b.unwrap()]))
+    fn read_u8(field: &'static str, data: &mut dyn Iterator<Item = u8>) -> Result<u8, ParseError> {
+        Ok(Self::read_array::<1>(field, data)?[0])
     }
-    fn read_i16(data: &mut dyn Iterator<Item = u8>) -> Result<i16, ParseError> {
-        let a: Option<u8> = data.next();
-        let b: Option<u8> = data.next();
-
-        if a.is_none() || b.is_none() {
-            return Err(ParseError::DataEmpty);
-        }
-
-        Ok(i16::from_le_bytes([a.unwrap(), b.unwrap()]))
+    fn read_u16(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<u16, ParseError> {
+        Ok(u16::from_le_bytes(Self::read_array(field, data)?))
     }
-    fn read_u32(data: &mut dyn Iterator<Item = u8>) -> Result<u32, ParseError> {
-        let a: Option<u8> = data.next();
-        let b: Option<u8> = data.next();
-        let c: Option<u8> = data.next();
-        let d: Option<u8> = data.next();
-
-        if a.is_none() || b.is_none() || c.is_none() || d.is_none() {
-            return Err(ParseError::DataEmpty);
-        }
-
-        Ok(u32::from_le_bytes([
-            a.unwrap(),
-            b.unwrap(),
-            c.unwrap(),
-            d.unwrap(),
-        ]))
-    }
-    fn read_i32(data: &mut dyn Iterator<Item = u8>) -> Result<i32, ParseError> {
-        let a: Option<u8> = data.next();
-        let b: Option<u8> = data.next();
-        let c: Option<u8> = data.next();
-        let d: Option<u8> = data.next();
-
-        if a.is_none() || b.is_none() || c.is_none() || d.is_none() {
-            return Err(ParseError::DataEmpty);
-        }
-
-        Ok(i32::from_le_bytes([
-            a.unwrap(),
-            b.unwrap(),
-            c.unwrap(),
-            d.unwrap(),
-        ]))
-    }
-    fn read_f32(data: &mut dyn Iterator<Item = u8>) -> Result<f32, ParseError> {
-        let a: Option<u8> = data.next();
-        let b: Option<u8> = data.next();
-        let c: Option<u8> = data.next();
-        let d: Option<u8> = data.next();
-
-        if a.is_none() || b.is_none() || c.is_none() || d.is_none() {
-            return Err(ParseError::DataEmpty);
-        }
-
-        Ok(f32::from_le_bytes([
-            a.unwrap(),
-            b.unwrap(),
-            c.unwrap(),
-            d.unwrap(),
-        ]))
+    fn read_i16(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<i16, ParseError> {
+        Ok(i16::from_le_bytes(Self::read_array(field, data)?))
     }
-    fn read_string(len: usize, data: &mut dyn Iterator<Item = u8>) -> Result<String, ParseError> {
+    fn read_u32(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<u32, ParseError> {
+        Ok(u32::from_le_bytes(Self::read_array(field, data)?))
+    }
+    fn read_i32(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<i32, ParseError> {
+        Ok(i32::from_le_bytes(Self::read_array(field, data)?))
+    }
+    fn read_f32(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<f32, ParseError> {
+        Ok(f32::from_le_bytes(Self::read_array(field, data)?))
+    }
+    /// Reads one character per byte, [`ObjectPool`] decodes string values with their font type
+    fn read_string(
+        field: &'static str,
+        len: usize,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<String, ParseError> {
         let mut s = String::new();
-        for _ in 0..len {
-            if let Some(c) = data.next() {
-                s.push(c as char);
-            } else {
-                return Err(ParseError::DataEmpty);
-            };
+        for found in 0..len {
+            match data.next() {
+                Some(c) => s.push(c as char),
+                None => {
+                    return Err(ParseError::UnexpectedEnd {
+                        field,
+                        expected: len,
+                        found,
+                    })
+                }
+            }
         }
         Ok(s)
     }
-    fn read_name(data: &mut dyn Iterator<Item = u8>) -> Result<NAME, ParseError> {
-        let name: [Option<u8>; 8] = [
-            data.next(),
-            data.next(),
-            data.next(),
-            data.next(),
-            data.next(),
-            data.next(),
-            data.next(),
-            data.next(),
-        ];
-
-        if name.contains(&None) {
-            return Err(ParseError::DataEmpty);
-        }
-
-        Ok(NAME::new(u64::from_le_bytes(name.map(|v| v.unwrap()))))
+    fn read_name(
+        field: &'static str,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<NAME, ParseError> {
+        Ok(NAME::new(u64::from_le_bytes(Self::read_array(
+            field, data,
+        )?)))
     }
 
     /* READ ISOBUS OBJECTS */
@@ -336,21 +362,28 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = WorkingSet {
             id,
-            background_colour: Self::read_u8(data)?.into(),
-            selectable: Self::read_bool(data)?,
-            active_mask: Self::read_u16(data)?.try_into()?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            language_codes: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?.into(),
+            selectable: Self::read_bool("selectable", data)?,
+            active_mask: Self::read_object_id("active_mask", data)?,
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
+            language_codes: Vec::with_capacity(Self::read_u8("language_codes", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         for _ in 0..o.language_codes.capacity() {
-            o.language_codes.push(Self::read_string(2, data)?)
+            o.language_codes
+                .push(Self::read_string("language_codes", 2, data)?)
         }
 
         Ok(Object::WorkingSet(o))
@@ -362,16 +395,22 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = DataMask {
             id,
-            background_colour: Self::read_u8(data)?,
-            soft_key_mask: Self::read_u16(data)?.try_into()?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            soft_key_mask: Self::read_u16("soft_key_mask", data)?.into(),
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::DataMask(o))
     }
@@ -382,18 +421,24 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = AlarmMask {
             id,
-            background_colour: Self::read_u8(data)?,
-            soft_key_mask: Self::read_u16(data)?.try_into()?,
-            priority: Self::read_u8(data)?,
-            acoustic_signal: Self::read_u8(data)?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            soft_key_mask: Self::read_u16("soft_key_mask", data)?.into(),
+            priority: Self::read_u8("priority", data)?,
+            acoustic_signal: Self::read_u8("acoustic_signal", data)?,
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::AlarmMask(o))
     }
@@ -404,17 +449,23 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = Container {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            hidden: Self::read_bool(data)?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            hidden: Self::read_bool("hidden", data)?,
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::Container(o))
     }
@@ -425,15 +476,18 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = SoftKeyMask {
             id,
-            background_colour: Self::read_u8(data)?,
-            objects: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            objects: Vec::with_capacity(Self::read_u8("objects", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
         o.objects
-            .extend(Self::read_objects(data, o.objects.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+            .extend(Self::read_objects("objects", data, o.objects.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::SoftKeyMask(o))
     }
@@ -441,16 +495,22 @@ impl Object {
     fn read_key(id: ObjectId, data: &mut dyn Iterator<Item = u8>) -> Result<Self, ParseError> {
         let mut o = Key {
             id,
-            background_colour: Self::read_u8(data)?,
-            key_code: Self::read_u8(data)?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            key_code: Self::read_u8("key_code", data)?,
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::Key(o))
     }
@@ -458,20 +518,26 @@ impl Object {
     fn read_button(id: ObjectId, data: &mut dyn Iterator<Item = u8>) -> Result<Self, ParseError> {
         let mut o = Button {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            background_colour: Self::read_u8(data)?,
-            border_colour: Self::read_u8(data)?,
-            key_code: Self::read_u8(data)?,
-            options: Self::read_u8(data)?.into(),
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            background_colour: Self::read_u8("background_colour", data)?,
+            border_colour: Self::read_u8("border_colour", data)?,
+            key_code: Self::read_u8("key_code", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::Button(o))
     }
@@ -482,17 +548,20 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = InputBoolean {
             id,
-            background_colour: Self::read_u8(data)?,
-            width: Self::read_u16(data)?,
-            foreground_colour: Self::read_u16(data)?.try_into()?,
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            value: Self::read_bool(data)?,
-            enabled: Self::read_bool(data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            width: Self::read_u16("width", data)?,
+            foreground_colour: Self::read_object_id("foreground_colour", data)?,
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            value: Self::read_bool("value", data)?,
+            enabled: Self::read_bool("enabled", data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::InputBoolean(o))
     }
@@ -503,21 +572,24 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = InputString {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            background_colour: Self::read_u8(data)?,
-            font_attributes: Self::read_u16(data)?.try_into()?,
-            input_attributes: Self::read_u16(data)?.try_into()?,
-            options: Self::read_u8(data)?.into(),
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            justification: Self::read_u8(data)?.into(),
-            value: Self::read_string(Self::read_u8(data)?.into(), data)?,
-            enabled: Self::read_bool(data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            background_colour: Self::read_u8("background_colour", data)?,
+            font_attributes: Self::read_object_id("font_attributes", data)?,
+            input_attributes: Self::read_u16("input_attributes", data)?.into(),
+            options: Self::read_u8("options", data)?.into(),
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            justification: Self::read_u8("justification", data)?.into(),
+            value: Self::read_string("value", Self::read_u8("value", data)?.into(), data)?,
+            enabled: Self::read_bool("enabled", data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::InputString(o))
     }
@@ -528,26 +600,29 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = InputNumber {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            background_colour: Self::read_u8(data)?,
-            font_attributes: Self::read_u16(data)?.try_into()?,
-            options: Self::read_u8(data)?.into(),
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            value: Self::read_u32(data)?,
-            min_value: Self::read_u32(data)?,
-            max_value: Self::read_u32(data)?,
-            offset: Self::read_i32(data)?,
-            scale: Self::read_f32(data)?,
-            nr_of_decimals: Self::read_u8(data)?,
-            format: Self::read_bool(data)?.into(),
-            justification: Self::read_u8(data)?.into(),
-            options2: Self::read_u8(data)?.into(),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            background_colour: Self::read_u8("background_colour", data)?,
+            font_attributes: Self::read_object_id("font_attributes", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            value: Self::read_u32("value", data)?,
+            min_value: Self::read_u32("min_value", data)?,
+            max_value: Self::read_u32("max_value", data)?,
+            offset: Self::read_i32("offset", data)?,
+            scale: Self::read_f32("scale", data)?,
+            nr_of_decimals: Self::read_u8("nr_of_decimals", data)?,
+            format: Self::read_bool("format", data)?.into(),
+            justification: Self::read_u8("justification", data)?.into(),
+            options2: Self::read_u8("options2", data)?.into(),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::InputNumber(o))
     }
@@ -558,19 +633,25 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = InputList {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            value: Self::read_u8(data)?,
-            list_items: Vec::with_capacity(Self::read_u8(data)?.into()),
-            options: Self::read_u8(data)?.into(),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            value: Self::read_u8("value", data)?,
+            list_items: Vec::with_capacity(Self::read_u8("list_items", data)?.into()),
+            options: Self::read_u8("options", data)?.into(),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.list_items
-            .extend(Self::read_objects(data, o.list_items.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.list_items.extend(Self::read_nullable_objects(
+            "list_items",
+            data,
+            o.list_items.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::InputList(o))
     }
@@ -581,19 +662,22 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputString {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            background_colour: Self::read_u8(data)?,
-            font_attributes: Self::read_u16(data)?.try_into()?,
-            options: Self::read_u8(data)?.into(),
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            justification: Self::read_u8(data)?.into(),
-            value: Self::read_string(Self::read_u16(data)?.into(), data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            background_colour: Self::read_u8("background_colour", data)?,
+            font_attributes: Self::read_object_id("font_attributes", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            justification: Self::read_u8("justification", data)?.into(),
+            value: Self::read_string("value", Self::read_u16("value", data)?.into(), data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputString(o))
     }
@@ -604,23 +688,26 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputNumber {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            background_colour: Self::read_u8(data)?,
-            font_attributes: Self::read_u16(data)?.try_into()?,
-            options: Self::read_u8(data)?.into(),
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            value: Self::read_u32(data)?,
-            offset: Self::read_i32(data)?,
-            scale: Self::read_f32(data)?,
-            nr_of_decimals: Self::read_u8(data)?,
-            format: Self::read_bool(data)?.into(),
-            justification: Self::read_u8(data)?.into(),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            background_colour: Self::read_u8("background_colour", data)?,
+            font_attributes: Self::read_object_id("font_attributes", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            value: Self::read_u32("value", data)?,
+            offset: Self::read_i32("offset", data)?,
+            scale: Self::read_f32("scale", data)?,
+            nr_of_decimals: Self::read_u8("nr_of_decimals", data)?,
+            format: Self::read_bool("format", data)?.into(),
+            justification: Self::read_u8("justification", data)?.into(),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputNumber(o))
     }
@@ -631,15 +718,18 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputLine {
             id,
-            line_attributes: Self::read_u16(data)?.try_into()?,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            line_direction: Self::read_u8(data)?.into(),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            line_attributes: Self::read_object_id("line_attributes", data)?,
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            line_direction: Self::read_u8("line_direction", data)?.try_into()?,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputLine(o))
     }
@@ -650,16 +740,19 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputRectangle {
             id,
-            line_attributes: Self::read_u16(data)?.try_into()?,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            line_suppression: Self::read_u8(data)?,
-            fill_attributes: Self::read_u16(data)?.try_into()?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            line_attributes: Self::read_object_id("line_attributes", data)?,
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            line_suppression: Self::read_u8("line_suppression", data)?,
+            fill_attributes: Self::read_u16("fill_attributes", data)?.into(),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputRectangle(o))
     }
@@ -670,18 +763,21 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputEllipse {
             id,
-            line_attributes: Self::read_u16(data)?.try_into()?,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            ellipse_type: Self::read_u8(data)?,
-            start_angle: Self::read_u8(data)?,
-            end_angle: Self::read_u8(data)?,
-            fill_attributes: Self::read_u16(data)?.try_into()?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            line_attributes: Self::read_object_id("line_attributes", data)?,
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            ellipse_type: Self::read_u8("ellipse_type", data)?,
+            start_angle: Self::read_u8("start_angle", data)?,
+            end_angle: Self::read_u8("end_angle", data)?,
+            fill_attributes: Self::read_u16("fill_attributes", data)?.into(),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputEllipse(o))
     }
@@ -692,19 +788,22 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputPolygon {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            line_attributes: Self::read_u16(data)?.try_into()?,
-            fill_attributes: Self::read_u16(data)?.try_into()?,
-            polygon_type: Self::read_u8(data)?,
-            points: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            line_attributes: Self::read_object_id("line_attributes", data)?,
+            fill_attributes: Self::read_u16("fill_attributes", data)?.into(),
+            polygon_type: Self::read_u8("polygon_type", data)?,
+            points: Vec::with_capacity(Self::read_u8("points", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
         o.points
-            .extend(Self::read_points(data, o.points.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+            .extend(Self::read_points("points", data, o.points.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputPolygon(o))
     }
@@ -715,23 +814,26 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputMeter {
             id,
-            width: Self::read_u16(data)?,
-            needle_colour: Self::read_u8(data)?,
-            border_colour: Self::read_u8(data)?,
-            arc_and_tick_colour: Self::read_u8(data)?,
-            options: Self::read_u8(data)?.into(),
-            nr_of_ticks: Self::read_u8(data)?,
-            start_angle: Self::read_u8(data)?,
-            end_angle: Self::read_u8(data)?,
-            min_value: Self::read_u16(data)?,
-            max_value: Self::read_u16(data)?,
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            value: Self::read_u16(data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            needle_colour: Self::read_u8("needle_colour", data)?,
+            border_colour: Self::read_u8("border_colour", data)?,
+            arc_and_tick_colour: Self::read_u8("arc_and_tick_colour", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            nr_of_ticks: Self::read_u8("nr_of_ticks", data)?,
+            start_angle: Self::read_u8("start_angle", data)?,
+            end_angle: Self::read_u8("end_angle", data)?,
+            min_value: Self::read_u16("min_value", data)?,
+            max_value: Self::read_u16("max_value", data)?,
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            value: Self::read_u16("value", data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputMeter(o))
     }
@@ -742,23 +844,30 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputLinearBarGraph {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            colour: Self::read_u8(data)?,
-            target_line_colour: Self::read_u8(data)?,
-            options: Self::read_u8(data)?.into(),
-            nr_of_ticks: Self::read_u8(data)?,
-            min_value: Self::read_u16(data)?,
-            max_value: Self::read_u16(data)?,
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            value: Self::read_u16(data)?,
-            target_value_variable_reference: Self::read_u16(data)?.try_into()?,
-            target_value: Self::read_u16(data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            colour: Self::read_u8("colour", data)?,
+            target_line_colour: Self::read_u8("target_line_colour", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            nr_of_ticks: Self::read_u8("nr_of_ticks", data)?,
+            min_value: Self::read_u16("min_value", data)?,
+            max_value: Self::read_u16("max_value", data)?,
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            value: Self::read_u16("value", data)?,
+            target_value_variable_reference: Self::read_u16(
+                "target_value_variable_reference",
+                data,
+            )?
+            .into(),
+            target_value: Self::read_u16("target_value", data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputLinearBarGraph(o))
     }
@@ -769,25 +878,32 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputArchedBarGraph {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            colour: Self::read_u8(data)?,
-            target_line_colour: Self::read_u8(data)?,
-            options: Self::read_u8(data)?.into(),
-            start_angle: Self::read_u8(data)?,
-            end_angle: Self::read_u8(data)?,
-            bar_graph_width: Self::read_u16(data)?,
-            min_value: Self::read_u16(data)?,
-            max_value: Self::read_u16(data)?,
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            value: Self::read_u16(data)?,
-            target_value_variable_reference: Self::read_u16(data)?.try_into()?,
-            target_value: Self::read_u16(data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            colour: Self::read_u8("colour", data)?,
+            target_line_colour: Self::read_u8("target_line_colour", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            start_angle: Self::read_u8("start_angle", data)?,
+            end_angle: Self::read_u8("end_angle", data)?,
+            bar_graph_width: Self::read_u16("bar_graph_width", data)?,
+            min_value: Self::read_u16("min_value", data)?,
+            max_value: Self::read_u16("max_value", data)?,
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            value: Self::read_u16("value", data)?,
+            target_value_variable_reference: Self::read_u16(
+                "target_value_variable_reference",
+                data,
+            )?
+            .into(),
+            target_value: Self::read_u16("target_value", data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputArchedBarGraph(o))
     }
@@ -796,23 +912,27 @@ impl Object {
         id: ObjectId,
         data: &mut dyn Iterator<Item = u8>,
     ) -> Result<Self, ParseError> {
-        let mut o = PictureGraphic {
+        let width = Self::read_u16("width", data)?;
+        let actual_width = Self::read_u16("actual_width", data)?;
+        let actual_height = Self::read_u16("actual_height", data)?;
+        let format = Self::read_u8("format", data)?;
+        let options = Self::read_u8("options", data)?.into();
+        let transparency_colour = Self::read_u8("transparency_colour", data)?;
+        // The length is not trusted to allocate the data up front, it can be up to 4 GiB
+        let nr_of_bytes = Self::read_u32("nr_of_bytes", data)? as usize;
+        let nr_of_macros = Self::read_u8("nr_of_macros", data)?.into();
+
+        Ok(Object::PictureGraphic(PictureGraphic {
             id,
-            width: Self::read_u16(data)?,
-            actual_width: Self::read_u16(data)?,
-            actual_height: Self::read_u16(data)?,
-            format: Self::read_u8(data)?,
-            options: Self::read_u8(data)?.into(),
-            transparency_colour: Self::read_u8(data)?,
-            data: Vec::with_capacity(Self::read_u32(data)? as usize),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-        };
-
-        o.data.extend(Self::read_bytes(data, o.data.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-        Ok(Object::PictureGraphic(o))
+            width,
+            actual_width,
+            actual_height,
+            format,
+            options,
+            transparency_colour,
+            data: Self::read_bytes("data", data, nr_of_bytes)?,
+            macro_refs: Self::read_macro_refs("macro_refs", data, nr_of_macros)?,
+        }))
     }
 
     fn read_number_variable(
@@ -821,7 +941,7 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let o = NumberVariable {
             id,
-            value: Self::read_u32(data)?,
+            value: Self::read_u32("value", data)?,
         };
 
         Ok(Object::NumberVariable(o))
@@ -833,7 +953,7 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let o = StringVariable {
             id,
-            value: Self::read_string(Self::read_u16(data)?.into(), data)?,
+            value: Self::read_string("value", Self::read_u16("value", data)?.into(), data)?,
         };
 
         Ok(Object::StringVariable(o))
@@ -843,17 +963,24 @@ impl Object {
         id: ObjectId,
         data: &mut dyn Iterator<Item = u8>,
     ) -> Result<Self, ParseError> {
+        let font_colour = Self::read_u8("font_colour", data)?;
+        let font_size = Self::read_u8("font_size", data)?;
+        let font_type = Self::read_u8("font_type", data)?.try_into()?;
+        let font_style = Self::read_u8("font_style", data)?.into();
         let mut o = FontAttributes {
             id,
-            font_colour: Self::read_u8(data)?,
-            font_size: Self::read_u8(data)?,
-            font_type: Self::read_u8(data)?,
-            font_style: Self::read_u8(data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            font_colour,
+            font_size: FontSize::decode(font_size, font_style)?,
+            font_type,
+            font_style,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::FontAttributes(o))
     }
@@ -864,14 +991,17 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = LineAttributes {
             id,
-            line_colour: Self::read_u8(data)?,
-            line_width: Self::read_u8(data)?,
-            line_art: Self::read_u16(data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            line_colour: Self::read_u8("line_colour", data)?,
+            line_width: Self::read_u8("line_width", data)?,
+            line_art: Self::read_u16("line_art", data)?.into(),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::LineAttributes(o))
     }
@@ -882,14 +1012,17 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = FillAttributes {
             id,
-            fill_type: Self::read_u8(data)?,
-            fill_colour: Self::read_u8(data)?,
-            fill_pattern: Self::read_u16(data)?.try_into()?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            fill_type: Self::read_u8("fill_type", data)?.try_into()?,
+            fill_colour: Self::read_u8("fill_colour", data)?,
+            fill_pattern: Self::read_u16("fill_pattern", data)?.into(),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::FillAttributes(o))
     }
@@ -900,13 +1033,20 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = InputAttributes {
             id,
-            validation_type: Self::read_u8(data)?,
-            validation_string: Self::read_string(Self::read_u8(data)?.into(), data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            validation_type: Self::read_u8("validation_type", data)?.try_into()?,
+            validation_string: Self::read_string(
+                "validation_string",
+                Self::read_u8("validation_string", data)?.into(),
+                data,
+            )?,
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::InputAttributes(o))
     }
@@ -917,21 +1057,19 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let o = ObjectPointer {
             id,
-            value: Self::read_u16(data)?.try_into()?,
+            value: Self::read_u16("value", data)?.into(),
         };
 
         Ok(Object::ObjectPointer(o))
     }
 
     fn read_macro(id: ObjectId, data: &mut dyn Iterator<Item = u8>) -> Result<Self, ParseError> {
-        let mut o = Macro {
+        let length = Self::read_u16("length", data)?.into();
+        let o = Macro {
             id,
-            commands: Vec::with_capacity(Self::read_u16(data)?.into()),
+            commands: MacroCommand::read_all_lenient(&Self::read_bytes("commands", data, length)?),
         };
 
-        o.commands
-            .extend(Self::read_bytes(data, o.commands.capacity())?);
-
         Ok(Object::Macro(o))
     }
 
@@ -941,13 +1079,16 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = AuxiliaryFunctionType1 {
             id,
-            background_colour: Self::read_u8(data)?,
-            function_type: Self::read_u8(data)?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            function_type: Self::read_u8("function_type", data)?,
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
 
         Ok(Object::AuxiliaryFunctionType1(o))
     }
@@ -958,14 +1099,17 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = AuxiliaryInputType1 {
             id,
-            background_colour: Self::read_u8(data)?,
-            function_type: Self::read_u8(data)?,
-            input_id: Self::read_u8(data)?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            function_type: Self::read_u8("function_type", data)?,
+            input_id: Self::read_u8("input_id", data)?,
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
 
         Ok(Object::AuxiliaryInputType1(o))
     }
@@ -976,13 +1120,16 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = AuxiliaryFunctionType2 {
             id,
-            background_colour: Self::read_u8(data)?,
-            function_attributes: Self::read_u8(data)?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            function_attributes: Self::read_u8("function_attributes", data)?,
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
 
         Ok(Object::AuxiliaryFunctionType2(o))
     }
@@ -993,13 +1140,16 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = AuxiliaryInputType2 {
             id,
-            background_colour: Self::read_u8(data)?,
-            function_attributes: Self::read_u8(data)?,
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            background_colour: Self::read_u8("background_colour", data)?,
+            function_attributes: Self::read_u8("function_attributes", data)?,
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
 
         Ok(Object::AuxiliaryInputType2(o))
     }
@@ -1010,8 +1160,8 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let o = AuxiliaryControlDesignatorType2 {
             id,
-            pointer_type: Self::read_u8(data)?,
-            auxiliary_object_id: Self::read_u16(data)?.try_into()?,
+            pointer_type: Self::read_u8("pointer_type", data)?,
+            auxiliary_object_id: Self::read_u16("auxiliary_object_id", data)?.into(),
         };
 
         Ok(Object::AuxiliaryControlDesignatorType2(o))
@@ -1023,24 +1173,33 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = WindowMask {
             id,
-            cell_format: Self::read_u16(data)?.into(),
-            window_type: Self::read_u8(data)?.into(),
-            background_colour: Self::read_u8(data)?,
-            options: Self::read_u8(data)?.into(),
-            name: Self::read_u16(data)?.try_into()?,
-            window_title: Self::read_u16(data)?.try_into()?,
-            window_icon: Self::read_u16(data)?.try_into()?,
-            objects: Vec::with_capacity(Self::read_u8(data)?.into()),
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            cell_format: u16::from_be_bytes(Self::read_array("cell_format", data)?).into(),
+            window_type: Self::read_u8("window_type", data)?.try_into()?,
+            background_colour: Self::read_u8("background_colour", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            name: Self::read_u16("name", data)?.into(),
+            window_title: Self::read_u16("window_title", data)?.into(),
+            window_icon: Self::read_u16("window_icon", data)?.into(),
+            objects: Vec::with_capacity(Self::read_u8("objects", data)?.into()),
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.objects
-            .extend(Self::read_objects(data, o.objects.capacity())?);
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.objects.extend(Self::read_nullable_objects(
+            "objects",
+            data,
+            o.objects.capacity(),
+        )?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::WindowMask(o))
     }
@@ -1051,17 +1210,20 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = KeyGroup {
             id,
-            options: Self::read_u8(data)?.into(),
-            name: Self::read_u16(data)?.try_into()?,
-            key_group_icon: Self::read_u16(data)?.try_into()?,
-            objects: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            options: Self::read_u8("options", data)?.into(),
+            name: Self::read_object_id("name", data)?,
+            key_group_icon: Self::read_u16("key_group_icon", data)?.into(),
+            objects: Vec::with_capacity(Self::read_u8("objects", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
         o.objects
-            .extend(Self::read_objects(data, o.objects.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+            .extend(Self::read_objects("objects", data, o.objects.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::KeyGroup(o))
     }
@@ -1072,23 +1234,23 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let o = GraphicsContext {
             id,
-            viewport_width: Self::read_u16(data)?,
-            viewport_height: Self::read_u16(data)?,
-            viewport_x: Self::read_i16(data)?,
-            viewport_y: Self::read_i16(data)?,
-            canvas_width: Self::read_u16(data)?,
-            canvas_height: Self::read_u16(data)?,
-            viewport_zoom: Self::read_f32(data)?,
-            graphics_cursor_x: Self::read_i16(data)?,
-            graphics_cursor_y: Self::read_i16(data)?,
-            foreground_colour: Self::read_u8(data)?,
-            background_colour: Self::read_u8(data)?,
-            font_attributes_object: Self::read_u16(data)?.try_into()?,
-            line_attributes_object: Self::read_u16(data)?.try_into()?,
-            fill_attributes_object: Self::read_u16(data)?.try_into()?,
-            format: Self::read_u8(data)?.into(),
-            options: Self::read_u8(data)?.into(),
-            transparency_colour: Self::read_u8(data)?,
+            viewport_width: Self::read_u16("viewport_width", data)?,
+            viewport_height: Self::read_u16("viewport_height", data)?,
+            viewport_x: Self::read_i16("viewport_x", data)?,
+            viewport_y: Self::read_i16("viewport_y", data)?,
+            canvas_width: Self::read_u16("canvas_width", data)?,
+            canvas_height: Self::read_u16("canvas_height", data)?,
+            viewport_zoom: Self::read_f32("viewport_zoom", data)?,
+            graphics_cursor_x: Self::read_i16("graphics_cursor_x", data)?,
+            graphics_cursor_y: Self::read_i16("graphics_cursor_y", data)?,
+            foreground_colour: Self::read_u8("foreground_colour", data)?,
+            background_colour: Self::read_u8("background_colour", data)?,
+            font_attributes_object: Self::read_u16("font_attributes_object", data)?.into(),
+            line_attributes_object: Self::read_u16("line_attributes_object", data)?.into(),
+            fill_attributes_object: Self::read_u16("fill_attributes_object", data)?.into(),
+            format: Self::read_u8("format", data)?.try_into()?,
+            options: Self::read_u8("options", data)?.into(),
+            transparency_colour: Self::read_u8("transparency_colour", data)?,
         };
         Ok(Object::GraphicsContext(o))
     }
@@ -1099,18 +1261,24 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = OutputList {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            variable_reference: Self::read_u16(data)?.try_into()?,
-            value: Self::read_u8(data)?,
-            list_items: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            variable_reference: Self::read_u16("variable_reference", data)?.into(),
+            value: Self::read_u8("value", data)?,
+            list_items: Vec::with_capacity(Self::read_u8("list_items", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.list_items
-            .extend(Self::read_objects(data, o.list_items.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.list_items.extend(Self::read_nullable_objects(
+            "list_items",
+            data,
+            o.list_items.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::OutputList(o))
     }
@@ -1121,8 +1289,8 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let o = ExtendedInputAttributes {
             id,
-            validation_type: Self::read_u8(data)?.into(),
-            code_planes: Self::read_code_planes(data)?,
+            validation_type: Self::read_u8("validation_type", data)?.try_into()?,
+            code_planes: Self::read_code_planes("code_planes", data)?,
         };
 
         Ok(Object::ExtendedInputAttributes(o))
@@ -1134,11 +1302,14 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = ColourMap {
             id,
-            colour_map: Vec::with_capacity(Self::read_u16(data)?.into()),
+            colour_map: Vec::with_capacity(Self::read_u16("colour_map", data)?.into()),
         };
 
-        o.colour_map
-            .extend(Self::read_bytes(data, o.colour_map.capacity())?);
+        o.colour_map.extend(Self::read_bytes(
+            "colour_map",
+            data,
+            o.colour_map.capacity(),
+        )?);
 
         Ok(Object::ColourMap(o))
     }
@@ -1149,11 +1320,14 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = ObjectLabelReferenceList {
             id,
-            object_labels: Vec::with_capacity(Self::read_u16(data)?.into()),
+            object_labels: Vec::with_capacity(Self::read_u16("object_labels", data)?.into()),
         };
 
-        o.object_labels
-            .extend(Self::read_object_labels(data, o.object_labels.capacity())?);
+        o.object_labels.extend(Self::read_object_labels(
+            "object_labels",
+            data,
+            o.object_labels.capacity(),
+        )?);
 
         Ok(Object::ObjectLabelReferenceList(o))
     }
@@ -1164,13 +1338,13 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = ExternalObjectDefinition {
             id,
-            options: Self::read_u8(data)?.into(),
-            name: Self::read_name(data)?,
-            objects: Vec::with_capacity(Self::read_u8(data)?.into()),
+            options: Self::read_u8("options", data)?.into(),
+            name: Self::read_name("name", data)?,
+            objects: Vec::with_capacity(Self::read_u8("objects", data)?.into()),
         };
 
         o.objects
-            .extend(Self::read_objects(data, o.objects.capacity())?);
+            .extend(Self::read_objects("objects", data, o.objects.capacity())?);
 
         Ok(Object::ExternalObjectDefinition(o))
     }
@@ -1181,8 +1355,8 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let o = ExternalReferenceName {
             id,
-            options: Self::read_u8(data)?.into(),
-            name: Self::read_name(data)?,
+            options: Self::read_u8("options", data)?.into(),
+            name: Self::read_name("name", data)?,
         };
 
         Ok(Object::ExternalReferenceName(o))
@@ -1194,9 +1368,9 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let o = ExternalObjectPointer {
             id,
-            default_object_id: Self::read_u16(data)?.try_into()?,
-            external_reference_name_id: Self::read_u16(data)?.try_into()?,
-            external_object_id: Self::read_u16(data)?.try_into()?,
+            default_object_id: Self::read_u16("default_object_id", data)?.into(),
+            external_reference_name_id: Self::read_u16("external_reference_name_id", data)?.into(),
+            external_object_id: Self::read_u16("external_object_id", data)?.into(),
         };
 
         Ok(Object::ExternalObjectPointer(o))
@@ -1208,23 +1382,29 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = Animation {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            refresh_interval: Self::read_u16(data)?,
-            value: Self::read_u8(data)?,
-            enabled: Self::read_bool(data)?,
-            first_child_index: Self::read_u8(data)?,
-            last_child_index: Self::read_u8(data)?,
-            default_child_index: Self::read_u8(data)?,
-            options: Self::read_u8(data)?.into(),
-            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            refresh_interval: Self::read_u16("refresh_interval", data)?,
+            value: Self::read_u8("value", data)?,
+            enabled: Self::read_bool("enabled", data)?,
+            first_child_index: Self::read_u8("first_child_index", data)?,
+            last_child_index: Self::read_u8("last_child_index", data)?,
+            default_child_index: Self::read_u8("default_child_index", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            object_refs: Vec::with_capacity(Self::read_u8("object_refs", data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.object_refs
-            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.object_refs.extend(Self::read_object_refs(
+            "object_refs",
+            data,
+            o.object_refs.capacity(),
+        )?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::Animation(o))
     }
@@ -1235,12 +1415,12 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = ColourPalette {
             id,
-            options: Self::read_u8(data)?.into(),
-            colours: Vec::with_capacity(Self::read_u16(data)?.into()),
+            options: Self::read_u8("options", data)?.into(),
+            colours: Vec::with_capacity(Self::read_u16("colours", data)?.into()),
         };
 
         o.colours
-            .extend(Self::read_colours(data, o.colours.capacity())?);
+            .extend(Self::read_colours("colours", data, o.colours.capacity())?);
 
         Ok(Object::ColourPalette(o))
     }
@@ -1249,15 +1429,14 @@ impl Object {
         id: ObjectId,
         data: &mut dyn Iterator<Item = u8>,
     ) -> Result<Self, ParseError> {
-        let mut o = GraphicData {
-            id,
-            format: Self::read_u8(data)?,
-            data: Vec::with_capacity(Self::read_u32(data)?.try_into().unwrap()),
-        };
-
-        o.data.extend(Self::read_bytes(data, o.data.capacity())?);
+        let format = Self::read_u8("format", data)?;
+        let nr_of_bytes = Self::read_u32("nr_of_bytes", data)? as usize;
 
-        Ok(Object::GraphicData(o))
+        Ok(Object::GraphicData(GraphicData {
+            id,
+            format,
+            data: Self::read_bytes("data", data, nr_of_bytes)?,
+        }))
     }
 
     fn read_working_set_special_controls(
@@ -1266,12 +1445,13 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = WorkingSetSpecialControls {
             id,
-            id_of_colour_map: Self::read_u16(data)?.try_into()?,
-            id_of_colour_palette: Self::read_u16(data)?.try_into()?,
-            language_pairs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            id_of_colour_map: Self::read_u16("id_of_colour_map", data)?.into(),
+            id_of_colour_palette: Self::read_u16("id_of_colour_palette", data)?.into(),
+            language_pairs: Vec::with_capacity(Self::read_u8("language_pairs", data)?.into()),
         };
 
         o.language_pairs.extend(Self::read_language_pairs(
+            "language_pairs",
             data,
             o.language_pairs.capacity(),
         )?);
@@ -1285,16 +1465,19 @@ impl Object {
     ) -> Result<Self, ParseError> {
         let mut o = ScaledGraphic {
             id,
-            width: Self::read_u16(data)?,
-            height: Self::read_u16(data)?,
-            scale_type: Self::read_u8(data)?,
-            options: Self::read_u8(data)?.into(),
-            value: Self::read_u16(data)?,
-            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            width: Self::read_u16("width", data)?,
+            height: Self::read_u16("height", data)?,
+            scale_type: Self::read_u8("scale_type", data)?,
+            options: Self::read_u8("options", data)?.into(),
+            value: Self::read_u16("value", data)?.into(),
+            macro_refs: Vec::with_capacity(Self::read_u8("macro_refs", data)?.into()),
         };
 
-        o.macro_refs
-            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+        o.macro_refs.extend(Self::read_macro_refs(
+            "macro_refs",
+            data,
+            o.macro_refs.capacity(),
+        )?);
 
         Ok(Object::ScaledGraphic(o))
     }
@@ -1305,21 +1488,17 @@ mod tests {
     use crate::object_pool::object::{Object, WorkingSet};
     use crate::object_pool::object_attributes::{ObjectRef, Point};
     use crate::object_pool::object_id::ObjectId;
-    use crate::object_pool::{Colour, ObjectPool, ObjectType};
+    use crate::object_pool::{Colour, IopError, ObjectPool, ObjectType, ParseError};
     use std::vec::IntoIter;
 
-    fn read_id_type(data: &mut dyn Iterator<Item = u8>) -> ObjectId {
-        let id = Object::read_u16(data)
-            .unwrap_or_else(|_| panic!("Failed to read object ID",))
-            .try_into()
-            .unwrap_or_else(|why| panic!("Failed to convert object ID: {:#?}", why));
-
-        let _object_type: ObjectType = Object::read_u8(data)
-            .unwrap_or_else(|_| panic!("Failed to read object type",))
-            .try_into()
-            .unwrap_or_else(|_| panic!("Failed to read object type",));
-
-        id
+    fn read_id_type(data: &mut dyn Iterator<Item = u8>) -> Result<ObjectId, ParseError> {
+        let id = Object::read_object_id("object_id", data)?;
+        let object_type = Object::read_u8("object_type", data)?;
+        ObjectType::try_from(object_type).map_err(|_| ParseError::InvalidValue {
+            field: "object_type",
+            value: object_type as u32,
+        })?;
+        Ok(id)
     }
 
     #[test]
@@ -1348,7 +1527,7 @@ mod tests {
         ]
         .into_iter();
 
-        let id = read_id_type(&mut data);
+        let id = read_id_type(&mut data).unwrap();
 
         let mut pool = ObjectPool::new();
         pool.add(
@@ -1381,4 +1560,234 @@ mod tests {
 
         assert_eq!(*_working_set_act, _working_set_exp);
     }
+
+    fn number_variable(id: u8) -> Vec<u8> {
+        vec![
+            id, 0x00, //Object ID
+            0x15, //Type
+            0x78, 0x56, 0x34, 0x12, //Value
+        ]
+    }
+
+    #[test]
+    fn try_from_iop_errors_test() {
+        let mut data = number_variable(1);
+        data.truncate(5);
+        assert_eq!(
+            ObjectPool::try_from_iop(data).unwrap_err(),
+            IopError {
+                offset: 5,
+                object_offset: 0,
+                object_id: Some(1),
+                object_type: Some(ObjectType::NumberVariable),
+                error: ParseError::UnexpectedEnd {
+                    field: "value",
+                    expected: 4,
+                    found: 2
+                },
+            }
+        );
+        assert_eq!(
+            ObjectPool::try_from_iop(data_with_null_reference())
+                .unwrap_err()
+                .field(),
+            Some("active_mask")
+        );
+
+        let mut data = number_variable(1);
+        data.extend([0x02, 0x00, 0x99]);
+        let error = ObjectPool::try_from_iop(data).unwrap_err();
+        assert_eq!(
+            error,
+            IopError {
+                offset: 10,
+                object_offset: 7,
+                object_id: Some(2),
+                object_type: None,
+                error: ParseError::InvalidValue {
+                    field: "object_type",
+                    value: 0x99
+                },
+            }
+        );
+        assert_eq!(error.field(), Some("object_type"));
+
+        let pool = ObjectPool::try_from_iop(number_variable(1)).unwrap();
+        assert_eq!(pool.objects().len(), 1);
+    }
+
+    #[test]
+    fn from_iop_lenient_test() {
+        let mut data = number_variable(1);
+        data.extend([0x02, 0x00, 0x99, 0xAA]);
+        data.extend(number_variable(3));
+        data.extend(number_variable(4));
+
+        let (pool, warnings) = ObjectPool::from_iop_lenient(data);
+        let ids: Vec<u16> = pool.objects().iter().map(|o| o.id().into()).collect();
+        assert_eq!(ids, vec![1, 3, 4]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].object_offset, 7);
+    }
+
+    /// A working set whose active mask is the NULL object ID
+    fn data_with_null_reference() -> Vec<u8> {
+        vec![
+            0x01, 0x00, 0x00, 0x00, 0x01, 0xFF, 0xFF, 0x00, 0x00, 0x00, //Working set
+        ]
+    }
+
+    #[test]
+    fn from_iop_lenient_test_pool() {
+        let data = include_bytes!("../../resources/test/AgIsoStack-rs-test-pool.iop");
+        let pool = ObjectPool::from_iop(data.iter().copied());
+        let objects = pool.objects();
+        let middle = objects.len() / 2;
+
+        // Start of the object in the middle of the pool
+        let mut front = ObjectPool::new();
+        for object in &objects[..middle] {
+            front.add(object.clone());
+        }
+        let middle_offset = front.as_iop().unwrap().len();
+        let valid = pool.as_iop().unwrap();
+        assert_eq!(
+            ObjectPool::try_from_iop(valid.clone()).unwrap().objects(),
+            objects
+        );
+
+        // An unknown object type in the middle of the pool
+        let mut data = valid.clone();
+        data[middle_offset + 2] = 0x99;
+        let error = ObjectPool::try_from_iop(data.clone()).unwrap_err();
+        assert_eq!(error.object_offset, middle_offset);
+        assert_eq!(error.field(), Some("object_type"));
+
+        let (lenient, warnings) = ObjectPool::from_iop_lenient(data);
+        assert_eq!(warnings, vec![error]);
+        assert_eq!(lenient.objects().len(), objects.len() - 1);
+        assert!(lenient.object_by_id(objects[middle].id()).is_none());
+        assert!(lenient.object_by_id(objects[middle + 1].id()).is_some());
+
+        // Garbage between two objects is skipped, no object is lost
+        let mut data = valid[..middle_offset].to_vec();
+        data.extend([0xFF; 37]);
+        data.extend(&valid[middle_offset..]);
+        let (lenient, warnings) = ObjectPool::from_iop_lenient(data);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].field(), Some("object_id"));
+        assert_eq!(lenient.objects(), objects);
+    }
+
+    #[test]
+    fn read_invalid_value_test() {
+        // An output line with line direction 2
+        let mut data = vec![
+            0x01, 0x00, 0x0D, 0x02, 0x00, 0x0A, 0x00, 0x0A, 0x00, 0x02, 0x00,
+        ];
+        assert_eq!(
+            Object::read(&mut data.clone().into_iter()),
+            Err(ParseError::InvalidValue {
+                field: "line_direction",
+                value: 2
+            })
+        );
+        data[9] = 0x01;
+        assert!(Object::read(&mut data.into_iter()).is_ok());
+
+        // An input boolean that is enabled with a value of 2
+        let data = vec![
+            0x01, 0x00, 0x07, 0x00, 0x10, 0x00, 0x02, 0x00, 0xFF, 0xFF, 0x00, 0x02, 0x00,
+        ];
+        assert_eq!(
+            Object::read(&mut data.into_iter()),
+            Err(ParseError::InvalidValue {
+                field: "enabled",
+                value: 2
+            })
+        );
+
+        // A picture graphic claiming 4 GiB of data
+        let data = vec![
+            0x01, 0x00, 0x14, 0x0A, 0x00, 0x0A, 0x00, 0x0A, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF,
+            0xFF, 0xFF, 0x00, 0x01,
+        ];
+        assert!(matches!(
+            Object::read(&mut data.into_iter()),
+            Err(ParseError::UnexpectedEnd { .. })
+        ));
+    }
+
+    #[test]
+    fn read_font_attributes_test() {
+        use crate::object_pool::{FontSize, FontStyle, FontType};
+
+        // Colour, proportional size of 148 pixels, ISO 8859-5, bold, inverted and
+        // proportional, no macros
+        let data = vec![0x01, 0x00, 0x17, 0x05, 0x94, 0x05, 0x91, 0x00];
+        let Ok(Object::FontAttributes(font)) = Object::read(&mut data.clone().into_iter()) else {
+            panic!("expected font attributes");
+        };
+        assert_eq!(font.font_size, FontSize::Proportional(148));
+        assert_eq!(font.font_size.to_fixed(), FontSize::Size128x128);
+        assert_eq!(font.font_type, FontType::Iso8859_5);
+        assert_eq!(
+            font.font_style,
+            FontStyle {
+                bold: true,
+                inverted: true,
+                proportional: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(Object::FontAttributes(font).write(), Ok(data));
+
+        // Without the proportional bit the size byte is an index of the fixed sizes
+        let data = vec![0x01, 0x00, 0x17, 0x05, 0x04, 0x05, 0x11, 0x00];
+        let Ok(Object::FontAttributes(font)) = Object::read(&mut data.into_iter()) else {
+            panic!("expected font attributes");
+        };
+        assert_eq!(font.font_size, FontSize::Size16x16);
+
+        for (size, style) in [(0x94, 0x11), (0x0F, 0x11), (0x07, 0x91)] {
+            let data = vec![0x01, 0x00, 0x17, 0x05, size, 0x05, style, 0x00];
+            assert_eq!(
+                Object::read(&mut data.into_iter()),
+                Err(ParseError::InvalidValue {
+                    field: "font_size",
+                    value: size as u32
+                })
+            );
+        }
+    }
+
+    /// Feeds random and corrupted objects to the reader, see `fuzz/` for the coverage guided
+    /// version of this test
+    #[test]
+    fn read_never_panics_test() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(11783);
+        let pool = include_bytes!("../../resources/test/AgIsoStack-rs-test-pool.iop");
+
+        for _ in 0..20_000 {
+            // Random objects of every type
+            let object_type = rng.gen_range(0..=48u8);
+            let mut data = vec![rng.gen(), rng.gen(), object_type];
+            let len = rng.gen_range(0..64);
+            data.extend((0..len).map(|_| rng.gen::<u8>()));
+            let _ = Object::read(&mut data.into_iter());
+
+            // The test pool with a few corrupted bytes
+            let mut data = pool.to_vec();
+            for _ in 0..rng.gen_range(1..8) {
+                let i = rng.gen_range(0..data.len());
+                data[i] = rng.gen();
+            }
+            data.truncate(rng.gen_range(0..=data.len()));
+            let mut data = data.into_iter();
+            while Object::read(&mut data).is_ok() {}
+        }
+    }
 }
@@ -0,0 +1,227 @@
+// Copyright 2023 Raven Industries inc.
+use super::image::RgbaImage;
+use crate::object_pool::{Colour, LineArt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Rect {
+            x,
+            y,
+            width: width.max(0),
+            height: height.max(0),
+        }
+    }
+
+    pub fn intersect(&self, other: &Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+        Rect::new(x, y, right - x, bottom - y)
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.x + self.width && y < self.y + self.height
+    }
+}
+
+/// An [`RgbaImage`] with a stack of clipping rectangles, all drawing primitives are clipped to the
+/// top most rectangle on the stack
+pub(super) struct Canvas {
+    image: RgbaImage,
+    clip: Vec<Rect>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32, background: Colour) -> Self {
+        Canvas {
+            image: RgbaImage::filled(width, height, background),
+            clip: vec![Rect::new(0, 0, width as i32, height as i32)],
+        }
+    }
+
+    pub fn into_image(self) -> RgbaImage {
+        self.image
+    }
+
+    pub fn push_clip(&mut self, rect: Rect) {
+        let clip = self.current_clip().intersect(&rect);
+        self.clip.push(clip);
+    }
+
+    pub fn pop_clip(&mut self) {
+        if self.clip.len() > 1 {
+            self.clip.pop();
+        }
+    }
+
+    fn current_clip(&self) -> Rect {
+        // The stack always holds at least the image bounds
+        self.clip[self.clip.len() - 1]
+    }
+
+    pub fn put(&mut self, x: i32, y: i32, colour: Colour) {
+        if self.current_clip().contains(x, y) {
+            self.image.put_pixel(x, y, colour.as_rgba());
+        }
+    }
+
+    pub fn fill_rect(&mut self, rect: Rect, colour: Colour) {
+        let rect = self.current_clip().intersect(&rect);
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                self.image.put_pixel(x, y, colour.as_rgba());
+            }
+        }
+    }
+
+    /// Paints every pixel inside `bounds` for which `inside` returns true
+    ///
+    /// The predicate receives the pixel centre relative to the centre of `bounds`.
+    pub fn fill_where(&mut self, bounds: Rect, colour: Colour, inside: impl Fn(f64, f64) -> bool) {
+        let cx = bounds.x as f64 + bounds.width as f64 / 2.0;
+        let cy = bounds.y as f64 + bounds.height as f64 / 2.0;
+        let rect = self.current_clip().intersect(&bounds);
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                if inside(x as f64 + 0.5 - cx, y as f64 + 0.5 - cy) {
+                    self.image.put_pixel(x, y, colour.as_rgba());
+                }
+            }
+        }
+    }
+
+    /// Draws a line using a square brush of `width` pixels
+    ///
+    /// Each bit of `line_art` represents one brush position along the line, see [`LineArt`].
+    pub fn draw_line(
+        &mut self,
+        from: (i32, i32),
+        to: (i32, i32),
+        width: u8,
+        colour: Colour,
+        line_art: LineArt,
+    ) {
+        if width == 0 {
+            return;
+        }
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let sx = if x < to.0 { 1 } else { -1 };
+        let sy = if y < to.1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        let brush_offset = (width as i32 - 1) / 2;
+
+        for step in 0.. {
+            if line_art.is_painted(step) {
+                self.fill_rect(
+                    Rect::new(
+                        x - brush_offset,
+                        y - brush_offset,
+                        width as i32,
+                        width as i32,
+                    ),
+                    colour,
+                );
+            }
+            if x == to.0 && y == to.1 {
+                break;
+            }
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Fills a polygon using the even-odd rule
+    pub fn fill_polygon(&mut self, points: &[(i32, i32)], colour: Colour) {
+        if points.len() < 3 {
+            return;
+        }
+        let min_y = points.iter().map(|p| p.1).min().unwrap_or_default();
+        let max_y = points.iter().map(|p| p.1).max().unwrap_or_default();
+
+        let mut crossings = Vec::new();
+        for y in min_y..=max_y {
+            let scan_y = y as f64 + 0.5;
+            crossings.clear();
+            for (i, a) in points.iter().enumerate() {
+                let b = points[(i + 1) % points.len()];
+                let (ay, by) = (a.1 as f64, b.1 as f64);
+                if (ay <= scan_y && by > scan_y) || (by <= scan_y && ay > scan_y) {
+                    let t = (scan_y - ay) / (by - ay);
+                    crossings.push(a.0 as f64 + t * (b.0 - a.0) as f64);
+                }
+            }
+            crossings.sort_by(|a, b| a.total_cmp(b));
+            for pair in crossings.chunks_exact(2) {
+                let start = (pair[0] - 0.5).ceil() as i32;
+                let end = (pair[1] - 0.5).floor() as i32;
+                if end >= start {
+                    self.fill_rect(Rect::new(start, y, end - start + 1, 1), colour);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn painted(canvas: Canvas, colour: Colour) -> usize {
+        canvas
+            .into_image()
+            .as_raw()
+            .chunks_exact(4)
+            .filter(|p| *p == colour.as_rgba())
+            .count()
+    }
+
+    #[test]
+    fn test_clip_stack() {
+        let mut canvas = Canvas::new(10, 10, Colour::BLACK);
+        canvas.push_clip(Rect::new(2, 2, 4, 4));
+        canvas.push_clip(Rect::new(4, 4, 10, 10));
+        canvas.fill_rect(Rect::new(0, 0, 10, 10), Colour::RED);
+        canvas.pop_clip();
+        canvas.pop_clip();
+        canvas.put(9, 9, Colour::RED);
+
+        assert_eq!(painted(canvas, Colour::RED), 5);
+    }
+
+    #[test]
+    fn test_line_art() {
+        let mut canvas = Canvas::new(16, 1, Colour::BLACK);
+        canvas.draw_line((0, 0), (15, 0), 1, Colour::WHITE, LineArt(0xAAAA));
+        let image = canvas.into_image();
+
+        assert_eq!(image.pixel(0, 0), Some(Colour::WHITE.as_rgba()));
+        assert_eq!(image.pixel(1, 0), Some(Colour::BLACK.as_rgba()));
+        assert_eq!(image.pixel(14, 0), Some(Colour::WHITE.as_rgba()));
+    }
+
+    #[test]
+    fn test_fill_polygon() {
+        let mut canvas = Canvas::new(10, 10, Colour::BLACK);
+        canvas.fill_polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)], Colour::WHITE);
+
+        assert_eq!(painted(canvas, Colour::WHITE), 16);
+    }
+}
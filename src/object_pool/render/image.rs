@@ -0,0 +1,154 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::Colour;
+
+/// A simple RGBA image buffer with 8 bits per channel, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Creates a fully transparent image
+    pub fn new(width: u32, height: u32) -> Self {
+        RgbaImage {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Creates an image with every pixel set to `colour`
+    pub fn filled(width: u32, height: u32, colour: Colour) -> Self {
+        let mut image = Self::new(width, height);
+        for pixel in image.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&colour.as_rgba());
+        }
+        image
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The raw pixel data, 4 bytes (R, G, B, A) per pixel
+    pub fn as_raw(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn into_raw(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// Returns the pixel at the given position, or `None` if it is outside of the image
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = self.index(x, y);
+        Some([
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ])
+    }
+
+    /// Sets the pixel at the given position, positions outside of the image are ignored
+    pub fn put_pixel(&mut self, x: i32, y: i32, rgba: [u8; 4]) {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return;
+        }
+        let i = self.index(x as u32, y as u32);
+        self.pixels[i..i + 4].copy_from_slice(&rgba);
+    }
+
+    /// Copies `other` into this image with its top left corner at (`x`, `y`)
+    ///
+    /// Fully transparent pixels of `other` are skipped, all other pixels overwrite the destination.
+    pub fn blit(&mut self, other: &RgbaImage, x: i32, y: i32) {
+        for oy in 0..other.height {
+            for ox in 0..other.width {
+                let i = other.index(ox, oy);
+                if other.pixels[i + 3] == 0 {
+                    continue;
+                }
+                let mut rgba = [0; 4];
+                rgba.copy_from_slice(&other.pixels[i..i + 4]);
+                self.put_pixel(x + ox as i32, y + oy as i32, rgba);
+            }
+        }
+    }
+
+    /// Encodes the image as PNG into `writer`
+    #[cfg(feature = "png")]
+    pub fn write_png<W: std::io::Write>(&self, writer: W) -> Result<(), png::EncodingError> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()
+    }
+
+    /// Encodes the image as PNG and writes it to the file at `path`
+    #[cfg(feature = "png")]
+    pub fn save_png<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), png::EncodingError> {
+        let file = std::fs::File::create(path)?;
+        self.write_png(std::io::BufWriter::new(file))
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_pixel_clips_to_image() {
+        let mut image = RgbaImage::new(2, 2);
+        image.put_pixel(1, 1, [1, 2, 3, 4]);
+        image.put_pixel(-1, 0, [9, 9, 9, 9]);
+        image.put_pixel(2, 0, [9, 9, 9, 9]);
+
+        assert_eq!(image.pixel(1, 1), Some([1, 2, 3, 4]));
+        assert_eq!(image.pixel(0, 0), Some([0, 0, 0, 0]));
+        assert_eq!(image.pixel(2, 0), None);
+    }
+
+    #[test]
+    fn test_blit_skips_transparent_pixels() {
+        let mut image = RgbaImage::filled(3, 3, Colour::WHITE);
+        let mut other = RgbaImage::new(2, 1);
+        other.put_pixel(1, 0, Colour::RED.as_rgba());
+
+        image.blit(&other, 1, 1);
+
+        assert_eq!(image.pixel(1, 1), Some(Colour::WHITE.as_rgba()));
+        assert_eq!(image.pixel(2, 1), Some(Colour::RED.as_rgba()));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_write_png() {
+        let image = RgbaImage::filled(4, 3, Colour::BLUE);
+        let mut data = Vec::new();
+        image.write_png(&mut data).unwrap();
+
+        let decoder = png::Decoder::new(data.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).unwrap();
+
+        assert_eq!((info.width, info.height), (4, 3));
+        assert_eq!(&buffer[..info.buffer_size()], image.as_raw());
+    }
+}
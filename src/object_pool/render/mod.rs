@@ -0,0 +1,21 @@
+// Copyright 2023 Raven Industries inc.
+
+//! Software rendering of object pools
+//!
+//! The [`Renderer`] draws the masks of an [`ObjectPool`](crate::object_pool::ObjectPool) into an
+//! [`RgbaImage`] approximating what a Virtual Terminal would display, optionally together with
+//! the soft keys laid out as described by a [`SoftKeyLayout`]. This is intended for previewing and
+//! testing object pools without a VT at hand, it is not a pixel exact reproduction of any
+//! particular terminal.
+//!
+//! Enable the `png` feature to save rendered images as PNG files.
+
+mod canvas;
+mod font;
+mod image;
+mod renderer;
+mod soft_keys;
+
+pub use image::RgbaImage;
+pub use renderer::{RenderError, Renderer};
+pub use soft_keys::SoftKeyLayout;
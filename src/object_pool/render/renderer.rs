@@ -0,0 +1,1158 @@
+// Copyright 2023 Raven Industries inc.
+use super::canvas::{Canvas, Rect};
+use super::font::{self, TextStyle};
+use super::image::RgbaImage;
+use super::soft_keys::SoftKeyLayout;
+use crate::network_management::language_command::DecimalSymbol;
+use crate::object_pool::object::{
+    Button, Container, InputBoolean, Object, OutputArchedBarGraph, OutputEllipse, OutputLine,
+    OutputLinearBarGraph, OutputMeter, OutputPolygon, OutputRectangle, PictureGraphic,
+};
+use crate::object_pool::object_attributes::{
+    Alignment, AxisOrientation, BarGraphType, DeflectionDirection, FillType, FontSize, FontStyle,
+    GrowDirection, HorizontalAlignment, LineArt, LineDirection, ObjectRef, Point,
+    VerticalAlignment,
+};
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::{Colour, ObjectPool, ObjectType};
+use std::f64::consts::PI;
+
+/// Object pointers can form cycles, stop descending into the object tree after this many levels
+const MAX_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderError {
+    /// The requested object does not exist in the object pool
+    ObjectNotFound(ObjectId),
+    /// The requested object exists, but is not of a type that can be rendered on its own
+    UnsupportedObject(ObjectId, ObjectType),
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to render object: {:?}", self)
+    }
+}
+impl std::error::Error for RenderError {}
+
+/// Renders masks of an [`ObjectPool`] into [`RgbaImage`]s the way a VT would display them
+///
+/// # Examples
+/// ```
+/// use ag_iso_stack::object_pool::render::Renderer;
+/// use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+///
+/// let pool = ObjectPool::new();
+/// let renderer = Renderer::new(&pool, 480);
+/// // The pool is empty, so there is no data mask to render
+/// assert!(renderer.render_data_mask(ObjectId::new(1000).unwrap()).is_err());
+/// ```
+pub struct Renderer<'a> {
+    pub(super) pool: &'a ObjectPool,
+    data_mask_size: u16,
+    pub(super) soft_key_layout: SoftKeyLayout,
+    decimal_symbol: DecimalSymbol,
+}
+
+impl<'a> Renderer<'a> {
+    /// Creates a renderer for a VT with a square data mask area of `data_mask_size` pixels
+    pub fn new(pool: &'a ObjectPool, data_mask_size: u16) -> Self {
+        Renderer {
+            pool,
+            data_mask_size,
+            soft_key_layout: SoftKeyLayout::default(),
+            decimal_symbol: DecimalSymbol::Point,
+        }
+    }
+
+    /// Sets the soft key area of the VT, used when rendering soft key masks
+    pub fn with_soft_key_layout(mut self, soft_key_layout: SoftKeyLayout) -> Self {
+        self.soft_key_layout = soft_key_layout;
+        self
+    }
+
+    /// Sets the decimal symbol of the language command of the VT, used for numbers
+    pub fn with_decimal_symbol(mut self, decimal_symbol: DecimalSymbol) -> Self {
+        self.decimal_symbol = decimal_symbol;
+        self
+    }
+
+    pub fn data_mask_size(&self) -> u16 {
+        self.data_mask_size
+    }
+
+    pub fn soft_key_layout(&self) -> SoftKeyLayout {
+        self.soft_key_layout
+    }
+
+    /// Renders a data mask or alarm mask and all of its children
+    pub fn render_data_mask(&self, mask: ObjectId) -> Result<RgbaImage, RenderError> {
+        let (background_colour, object_refs, _) = self.mask(mask)?;
+
+        let size = self.data_mask_size as u32;
+        let mut canvas = Canvas::new(size, size, self.colour(background_colour));
+        self.draw_children(&mut canvas, object_refs, 0, 0, 0);
+        Ok(canvas.into_image())
+    }
+
+    /// Renders a data mask or alarm mask with its soft key mask next to it, like a VT would show it
+    ///
+    /// `page` selects which soft keys are shown if the soft key mask holds more keys than the VT has
+    /// physical soft keys, see [`Renderer::soft_key_pages`]. A data mask without a soft key mask is
+    /// shown with an empty soft key area.
+    pub fn render_screen(&self, mask: ObjectId, page: usize) -> Result<RgbaImage, RenderError> {
+        let (background_colour, _, soft_key_mask) = self.mask(mask)?;
+        let data_mask = self.render_data_mask(mask)?;
+        let soft_keys = match soft_key_mask.0 {
+            Some(id) if matches!(self.pool.object_by_id(id), Some(Object::SoftKeyMask(_))) => {
+                self.render_soft_key_mask(id, page)?
+            }
+            _ => {
+                let (width, height) = self.soft_key_layout.area_size();
+                RgbaImage::filled(width, height, self.colour(background_colour))
+            }
+        };
+
+        let mut screen = RgbaImage::filled(
+            data_mask.width() + soft_keys.width(),
+            data_mask.height().max(soft_keys.height()),
+            Colour::BLACK,
+        );
+        screen.blit(&data_mask, 0, 0);
+        screen.blit(&soft_keys, data_mask.width() as i32, 0);
+        Ok(screen)
+    }
+
+    /// Returns the background colour, children and soft key mask of a data mask or alarm mask
+    fn mask(&self, mask: ObjectId) -> Result<(u8, &'a [ObjectRef], NullableObjectId), RenderError> {
+        match self.pool.object_by_id(mask) {
+            Some(Object::DataMask(o)) => Ok((o.background_colour, &o.object_refs, o.soft_key_mask)),
+            Some(Object::AlarmMask(o)) => {
+                Ok((o.background_colour, &o.object_refs, o.soft_key_mask))
+            }
+            Some(o) => Err(RenderError::UnsupportedObject(mask, o.object_type())),
+            None => Err(RenderError::ObjectNotFound(mask)),
+        }
+    }
+
+    pub(super) fn colour(&self, index: u8) -> Colour {
+        self.pool.color_by_index(index)
+    }
+
+    pub(super) fn draw_children(
+        &self,
+        canvas: &mut Canvas,
+        object_refs: &[ObjectRef],
+        x: i32,
+        y: i32,
+        depth: usize,
+    ) {
+        for object_ref in object_refs {
+            self.draw_object(
+                canvas,
+                object_ref.id,
+                x + object_ref.offset.x as i32,
+                y + object_ref.offset.y as i32,
+                depth + 1,
+            );
+        }
+    }
+
+    pub(super) fn draw_object(
+        &self,
+        canvas: &mut Canvas,
+        id: ObjectId,
+        x: i32,
+        y: i32,
+        depth: usize,
+    ) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let object = match self.pool.object_by_id(id) {
+            Some(o) => o,
+            None => return,
+        };
+
+        match object {
+            Object::Container(o) => self.draw_container(canvas, o, x, y, depth),
+            Object::Button(o) => self.draw_button(canvas, o, x, y, depth),
+            Object::InputBoolean(o) => self.draw_input_boolean(canvas, o, x, y),
+            Object::InputString(o) => {
+                let text = self.string_value(o.variable_reference, &o.value);
+                let background = (!o.options.transparent).then_some(o.background_colour);
+                self.draw_text(
+                    canvas,
+                    Rect::new(x, y, o.width as i32, o.height as i32),
+                    text,
+                    o.font_attributes,
+                    o.justification,
+                    o.options.auto_wrap,
+                    background,
+                );
+            }
+            Object::OutputString(o) => {
+                let text = self.string_value(o.variable_reference, &o.value);
+                let background = (!o.options.transparent).then_some(o.background_colour);
+                self.draw_text(
+                    canvas,
+                    Rect::new(x, y, o.width as i32, o.height as i32),
+                    text,
+                    o.font_attributes,
+                    o.justification,
+                    o.options.auto_wrap,
+                    background,
+                );
+            }
+            Object::InputNumber(o) => {
+                let text = self
+                    .pool
+                    .number_text(o.id, self.decimal_symbol)
+                    .unwrap_or_default();
+                let background = (!o.options.transparent).then_some(o.background_colour);
+                self.draw_text(
+                    canvas,
+                    Rect::new(x, y, o.width as i32, o.height as i32),
+                    &text,
+                    o.font_attributes,
+                    o.justification,
+                    false,
+                    background,
+                );
+            }
+            Object::OutputNumber(o) => {
+                let text = self
+                    .pool
+                    .number_text(o.id, self.decimal_symbol)
+                    .unwrap_or_default();
+                let background = (!o.options.transparent).then_some(o.background_colour);
+                self.draw_text(
+                    canvas,
+                    Rect::new(x, y, o.width as i32, o.height as i32),
+                    &text,
+                    o.font_attributes,
+                    o.justification,
+                    false,
+                    background,
+                );
+            }
+            Object::InputList(o) => {
+                let index = self.number_value(o.variable_reference, o.value as u32);
+                self.draw_list_item(
+                    canvas,
+                    &o.list_items,
+                    index,
+                    (x, y, o.width, o.height),
+                    depth,
+                );
+            }
+            Object::OutputList(o) => {
+                let index = self.number_value(o.variable_reference, o.value as u32);
+                self.draw_list_item(
+                    canvas,
+                    &o.list_items,
+                    index,
+                    (x, y, o.width, o.height),
+                    depth,
+                );
+            }
+            Object::OutputLine(o) => self.draw_output_line(canvas, o, x, y),
+            Object::OutputRectangle(o) => self.draw_output_rectangle(canvas, o, x, y),
+            Object::OutputEllipse(o) => self.draw_output_ellipse(canvas, o, x, y),
+            Object::OutputPolygon(o) => self.draw_output_polygon(canvas, o, x, y),
+            Object::OutputMeter(o) => self.draw_output_meter(canvas, o, x, y),
+            Object::OutputLinearBarGraph(o) => self.draw_linear_bar_graph(canvas, o, x, y),
+            Object::OutputArchedBarGraph(o) => self.draw_arched_bar_graph(canvas, o, x, y),
+            Object::PictureGraphic(o) => self.draw_picture_graphic(canvas, o, x, y),
+            Object::ObjectPointer(o) => {
+                if let Some(value) = o.value.0 {
+                    self.draw_object(canvas, value, x, y, depth + 1)
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn number_value(&self, variable_reference: NullableObjectId, value: u32) -> u32 {
+        variable_reference
+            .0
+            .and_then(|id| self.pool.number_variable_object_by_id(id))
+            .map_or(value, |v| v.value)
+    }
+
+    fn string_value<'b>(&'b self, variable_reference: NullableObjectId, value: &'b str) -> &'b str {
+        variable_reference
+            .0
+            .and_then(|id| self.pool.string_variable_object_by_id(id))
+            .map_or(value, |v| v.value.as_str())
+    }
+
+    /// Returns the colour, width and line art of a line attributes object
+    fn line(&self, line_attributes: ObjectId) -> Option<(Colour, u8, LineArt)> {
+        self.pool
+            .line_attributes_object_by_id(line_attributes)
+            .map(|l| (self.colour(l.line_colour), l.line_width, l.line_art))
+    }
+
+    /// Returns the colour used to fill a shape, if it should be filled at all
+    fn fill(
+        &self,
+        fill_attributes: NullableObjectId,
+        line_colour: Option<Colour>,
+    ) -> Option<Colour> {
+        let fill = self.pool.fill_attributes_object_by_id(fill_attributes.0?)?;
+        match fill.fill_type {
+            FillType::LineColour => line_colour,
+            // Pattern fills are approximated with the fill colour
+            FillType::FillColour | FillType::Pattern => Some(self.colour(fill.fill_colour)),
+            FillType::NoFill => None,
+        }
+    }
+
+    fn draw_container(&self, canvas: &mut Canvas, o: &Container, x: i32, y: i32, depth: usize) {
+        if o.hidden {
+            return;
+        }
+        canvas.push_clip(Rect::new(x, y, o.width as i32, o.height as i32));
+        self.draw_children(canvas, &o.object_refs, x, y, depth);
+        canvas.pop_clip();
+    }
+
+    fn draw_button(&self, canvas: &mut Canvas, o: &Button, x: i32, y: i32, depth: usize) {
+        let bounds = Rect::new(x, y, o.width as i32, o.height as i32);
+        if !o.options.transparent_background {
+            canvas.fill_rect(bounds, self.colour(o.background_colour));
+        }
+
+        let border = if o.options.no_border { 0 } else { 2 };
+        if border > 0 && !o.options.suppress_border {
+            let colour = self.colour(o.border_colour);
+            let (w, h) = (o.width as i32, o.height as i32);
+            canvas.fill_rect(Rect::new(x, y, w, border), colour);
+            canvas.fill_rect(Rect::new(x, y + h - border, w, border), colour);
+            canvas.fill_rect(Rect::new(x, y, border, h), colour);
+            canvas.fill_rect(Rect::new(x + w - border, y, border, h), colour);
+        }
+
+        canvas.push_clip(Rect::new(
+            x + border,
+            y + border,
+            o.width as i32 - 2 * border,
+            o.height as i32 - 2 * border,
+        ));
+        self.draw_children(canvas, &o.object_refs, x + border, y + border, depth);
+        canvas.pop_clip();
+    }
+
+    fn draw_input_boolean(&self, canvas: &mut Canvas, o: &InputBoolean, x: i32, y: i32) {
+        let size = o.width as i32;
+        let foreground = self
+            .pool
+            .font_attributes_object_by_id(o.foreground_colour)
+            .map_or(Colour::BLACK, |f| self.colour(f.font_colour));
+
+        canvas.fill_rect(
+            Rect::new(x, y, size, size),
+            self.colour(o.background_colour),
+        );
+        let corners = [
+            (x, y),
+            (x + size - 1, y),
+            (x + size - 1, y + size - 1),
+            (x, y + size - 1),
+        ];
+        for i in 0..corners.len() {
+            canvas.draw_line(
+                corners[i],
+                corners[(i + 1) % corners.len()],
+                1,
+                foreground,
+                LineArt::SOLID,
+            );
+        }
+
+        if self.number_value(o.variable_reference, o.value as u32) != 0 {
+            let width = (size / 8).clamp(1, u8::MAX as i32) as u8;
+            canvas.draw_line(
+                (x + size / 5, y + size / 2),
+                (x + size * 2 / 5, y + size * 3 / 4),
+                width,
+                foreground,
+                LineArt::SOLID,
+            );
+            canvas.draw_line(
+                (x + size * 2 / 5, y + size * 3 / 4),
+                (x + size * 4 / 5, y + size / 4),
+                width,
+                foreground,
+                LineArt::SOLID,
+            );
+        }
+    }
+
+    fn draw_list_item(
+        &self,
+        canvas: &mut Canvas,
+        list_items: &[NullableObjectId],
+        index: u32,
+        (x, y, width, height): (i32, i32, u16, u16),
+        depth: usize,
+    ) {
+        if let Some(NullableObjectId(Some(item))) = list_items.get(index as usize) {
+            canvas.push_clip(Rect::new(x, y, width as i32, height as i32));
+            self.draw_object(canvas, *item, x, y, depth + 1);
+            canvas.pop_clip();
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text(
+        &self,
+        canvas: &mut Canvas,
+        bounds: Rect,
+        text: &str,
+        font_attributes: ObjectId,
+        justification: Alignment,
+        auto_wrap: bool,
+        background_colour: Option<u8>,
+    ) {
+        let font = self.pool.font_attributes_object_by_id(font_attributes);
+        let (char_width, char_height) = font.map_or((6, 8), |f| font_size(f.font_size));
+        let mut colour = font.map_or(Colour::BLACK, |f| self.colour(f.font_colour));
+        let font_style = font.map_or(FontStyle::default(), |f| f.font_style);
+        let style = TextStyle {
+            bold: font_style.bold,
+            crossed_out: font_style.crossed_out,
+            underlined: font_style.underlined,
+            italic: font_style.italic,
+        };
+        let inverted = font_style.inverted;
+
+        let mut background = background_colour.map(|c| self.colour(c));
+        if inverted {
+            let inverted_background = colour;
+            colour = background.unwrap_or(Colour::WHITE);
+            background = Some(inverted_background);
+        }
+
+        canvas.push_clip(bounds);
+        if let Some(background) = background {
+            canvas.fill_rect(bounds, background);
+        }
+
+        let max_chars = (bounds.width as u32 / char_width) as usize;
+        let lines = layout_text(text, max_chars, auto_wrap);
+        let text_height = (lines.len() as u32 * char_height) as i32;
+        let mut line_y = match justification.vertical {
+            VerticalAlignment::Middle => bounds.y + (bounds.height - text_height) / 2,
+            VerticalAlignment::Bottom => bounds.y + bounds.height - text_height,
+            _ => bounds.y,
+        };
+
+        for line in lines {
+            let line_width = (line.chars().count() as u32 * char_width) as i32;
+            let mut char_x = match justification.horizontal {
+                HorizontalAlignment::Middle => bounds.x + (bounds.width - line_width) / 2,
+                HorizontalAlignment::Right => bounds.x + bounds.width - line_width,
+                _ => bounds.x,
+            };
+            for c in line.chars() {
+                font::draw_char(
+                    canvas,
+                    c,
+                    char_x,
+                    line_y,
+                    (char_width, char_height),
+                    colour,
+                    style,
+                );
+                char_x += char_width as i32;
+            }
+            line_y += char_height as i32;
+        }
+        canvas.pop_clip();
+    }
+
+    fn draw_output_line(&self, canvas: &mut Canvas, o: &OutputLine, x: i32, y: i32) {
+        let (colour, width, line_art) = match self.line(o.line_attributes) {
+            Some(line) => line,
+            None => return,
+        };
+        let right = x + (o.width as i32 - 1).max(0);
+        let bottom = y + (o.height as i32 - 1).max(0);
+        let (from, to) = match o.line_direction {
+            LineDirection::TopLeftToBottomRight => ((x, y), (right, bottom)),
+            LineDirection::BottomLeftToTopRight => ((x, bottom), (right, y)),
+        };
+        canvas.draw_line(from, to, width, colour, line_art);
+    }
+
+    fn draw_output_rectangle(&self, canvas: &mut Canvas, o: &OutputRectangle, x: i32, y: i32) {
+        let line = self.line(o.line_attributes);
+        let (w, h) = (o.width as i32, o.height as i32);
+
+        if let Some(fill) = self.fill(o.fill_attributes, line.map(|l| l.0)) {
+            canvas.fill_rect(Rect::new(x, y, w, h), fill);
+        }
+
+        if let Some((colour, width, line_art)) = line {
+            let inset = (width as i32 - 1) / 2;
+            let (left, top) = (x + inset, y + inset);
+            let (right, bottom) = (x + w - 1 - (width as i32 - 1 - inset), y + h - 1 - inset);
+            let edges = [
+                ((left, top), (right, top)),
+                ((right, top), (right, bottom)),
+                ((right, bottom), (left, bottom)),
+                ((left, bottom), (left, top)),
+            ];
+            for (i, (from, to)) in edges.iter().enumerate() {
+                if o.line_suppression & (1 << i) == 0 {
+                    canvas.draw_line(*from, *to, width, colour, line_art);
+                }
+            }
+        }
+    }
+
+    fn draw_output_ellipse(&self, canvas: &mut Canvas, o: &OutputEllipse, x: i32, y: i32) {
+        let bounds = Rect::new(x, y, o.width as i32, o.height as i32);
+        let line = self.line(o.line_attributes);
+        let (rx, ry) = (o.width as f64 / 2.0, o.height as f64 / 2.0);
+        let start = o.start_angle as f64 * 2.0;
+        let end = o.end_angle as f64 * 2.0;
+        let sweep = sweep(start, end, DeflectionDirection::AntiClockwise);
+
+        // Closed segments are cut off by the chord between the start and end point
+        let chord = {
+            let (sx, sy) = point_on_ellipse(rx, ry, start);
+            let (ex, ey) = point_on_ellipse(rx, ry, end);
+            let (mx, my) = point_on_ellipse(rx, ry, start + sweep / 2.0);
+            let side = move |px: f64, py: f64| (ex - sx) * (py - sy) - (ey - sy) * (px - sx);
+            let arc_side = side(mx, my).signum();
+            move |px: f64, py: f64| side(px, py) * arc_side >= 0.0
+        };
+        let in_sweep = |px: f64, py: f64| in_sweep(angle_of(px, py), start, sweep);
+
+        let line_colour = line.map(|l| l.0);
+        if o.ellipse_type != 1 {
+            if let Some(fill) = self.fill(o.fill_attributes, line_colour) {
+                canvas.fill_where(bounds, fill, |px, py| {
+                    inside_ellipse(px, py, rx, ry)
+                        && match o.ellipse_type {
+                            2 => chord(px, py),
+                            3 => in_sweep(px, py),
+                            _ => true,
+                        }
+                });
+            }
+        }
+
+        if let Some((colour, width, _)) = line {
+            let width = width as f64;
+            canvas.fill_where(bounds, colour, |px, py| {
+                inside_ellipse(px, py, rx, ry)
+                    && !inside_ellipse(px, py, rx - width, ry - width)
+                    && (o.ellipse_type == 0 || in_sweep(px, py))
+            });
+
+            let centre = (x as f64 + rx, y as f64 + ry);
+            let to_canvas = |(px, py): (f64, f64)| ((centre.0 + px) as i32, (centre.1 + py) as i32);
+            let start_point = to_canvas(point_on_ellipse(rx, ry, start));
+            let end_point = to_canvas(point_on_ellipse(rx, ry, end));
+            let centre_point = to_canvas((0.0, 0.0));
+            match o.ellipse_type {
+                2 => canvas.draw_line(start_point, end_point, width as u8, colour, LineArt::SOLID),
+                3 => {
+                    canvas.draw_line(
+                        centre_point,
+                        start_point,
+                        width as u8,
+                        colour,
+                        LineArt::SOLID,
+                    );
+                    canvas.draw_line(centre_point, end_point, width as u8, colour, LineArt::SOLID);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn draw_output_polygon(&self, canvas: &mut Canvas, o: &OutputPolygon, x: i32, y: i32) {
+        let points: Vec<(i32, i32)> = o
+            .points
+            .iter()
+            .map(|p| (x + p.x as i32, y + p.y as i32))
+            .collect();
+        let line = self.line(o.line_attributes);
+        // Type 3 is an open polygon, it has no inside to fill and its outline is not closed
+        let open = o.polygon_type == 3;
+
+        if !open {
+            if let Some(fill) = self.fill(o.fill_attributes, line.map(|l| l.0)) {
+                canvas.fill_polygon(&points, fill);
+            }
+        }
+
+        if let Some((colour, width, line_art)) = line {
+            let segments = if open {
+                points.len().saturating_sub(1)
+            } else {
+                points.len()
+            };
+            for i in 0..segments {
+                canvas.draw_line(
+                    points[i],
+                    points[(i + 1) % points.len()],
+                    width,
+                    colour,
+                    line_art,
+                );
+            }
+        }
+    }
+
+    fn draw_output_meter(&self, canvas: &mut Canvas, o: &OutputMeter, x: i32, y: i32) {
+        let bounds = Rect::new(x, y, o.width as i32, o.width as i32);
+        let radius = o.width as f64 / 2.0;
+        let centre = (x as f64 + radius, y as f64 + radius);
+        let start = o.start_angle as f64 * 2.0;
+        let end = o.end_angle as f64 * 2.0;
+        let direction = o.options.deflection_direction;
+        let sweep = sweep(start, end, direction);
+        let at = |angle: f64, r: f64| {
+            let (px, py) = point_on_ellipse(r, r, angle);
+            ((centre.0 + px) as i32, (centre.1 + py) as i32)
+        };
+
+        if o.options.draw_border {
+            canvas.fill_where(bounds, self.colour(o.border_colour), |px, py| {
+                inside_ellipse(px, py, radius, radius)
+                    && !inside_ellipse(px, py, radius - 1.0, radius - 1.0)
+            });
+        }
+
+        let arc_radius = radius - 2.0;
+        let arc_colour = self.colour(o.arc_and_tick_colour);
+        if o.options.draw_arc {
+            canvas.fill_where(bounds, arc_colour, |px, py| {
+                inside_ellipse(px, py, arc_radius, arc_radius)
+                    && !inside_ellipse(px, py, arc_radius - 1.0, arc_radius - 1.0)
+                    && in_sweep_directed(angle_of(px, py), start, sweep, direction)
+            });
+        }
+
+        if o.options.draw_ticks && o.nr_of_ticks > 0 {
+            let tick_length = (radius / 8.0).max(2.0);
+            for tick in 0..o.nr_of_ticks {
+                let fraction = if o.nr_of_ticks > 1 {
+                    tick as f64 / (o.nr_of_ticks - 1) as f64
+                } else {
+                    0.0
+                };
+                let angle = directed_angle(start, fraction * sweep, direction);
+                canvas.draw_line(
+                    at(angle, arc_radius),
+                    at(angle, arc_radius - tick_length),
+                    1,
+                    arc_colour,
+                    LineArt::SOLID,
+                );
+            }
+        }
+
+        let value = self.number_value(o.variable_reference, o.value as u32);
+        let fraction = value_fraction(value, o.min_value as u32, o.max_value as u32);
+        let angle = directed_angle(start, fraction * sweep, direction);
+        canvas.draw_line(
+            at(0.0, 0.0),
+            at(angle, arc_radius - 1.0),
+            1,
+            self.colour(o.needle_colour),
+            LineArt::SOLID,
+        );
+    }
+
+    fn draw_linear_bar_graph(&self, canvas: &mut Canvas, o: &OutputLinearBarGraph, x: i32, y: i32) {
+        let (w, h) = (o.width as i32, o.height as i32);
+        let colour = self.colour(o.colour);
+        let horizontal = o.options.axis_orientation == AxisOrientation::Horizontal;
+        let length = if horizontal { w } else { h };
+
+        // Maps a value to a rectangle covering the bar from its origin up to that value
+        let bar_up_to = |value: u32| {
+            let fraction = value_fraction(value, o.min_value as u32, o.max_value as u32);
+            let extent = (fraction * length as f64).round() as i32;
+            match (horizontal, o.options.grow_direction) {
+                (true, GrowDirection::GrowRightUp) => Rect::new(x, y, extent, h),
+                (true, GrowDirection::GrowLeftDown) => Rect::new(x + w - extent, y, extent, h),
+                (false, GrowDirection::GrowRightUp) => Rect::new(x, y + h - extent, w, extent),
+                (false, GrowDirection::GrowLeftDown) => Rect::new(x, y, w, extent),
+            }
+        };
+        // The edge of a bar that moves with the value
+        let value_edge = |bar: Rect| match (horizontal, o.options.grow_direction) {
+            (true, GrowDirection::GrowRightUp) => Rect::new(bar.x + bar.width - 1, y, 1, h),
+            (true, GrowDirection::GrowLeftDown) => Rect::new(bar.x, y, 1, h),
+            (false, GrowDirection::GrowRightUp) => Rect::new(x, bar.y, w, 1),
+            (false, GrowDirection::GrowLeftDown) => Rect::new(x, bar.y + bar.height - 1, w, 1),
+        };
+
+        let value = self.number_value(o.variable_reference, o.value as u32);
+        let bar = bar_up_to(value);
+        match o.options.bar_graph_type {
+            BarGraphType::Filled => canvas.fill_rect(bar, colour),
+            BarGraphType::NotFilled => canvas.fill_rect(value_edge(bar), colour),
+        }
+
+        if o.options.draw_target_line {
+            let target =
+                self.number_value(o.target_value_variable_reference, o.target_value as u32);
+            canvas.fill_rect(
+                value_edge(bar_up_to(target)),
+                self.colour(o.target_line_colour),
+            );
+        }
+
+        if o.options.draw_ticks && o.nr_of_ticks > 0 {
+            let tick_size = if horizontal { h / 4 } else { w / 4 }.max(1);
+            for tick in 0..o.nr_of_ticks as i32 {
+                let offset = if o.nr_of_ticks > 1 {
+                    tick * (length - 1) / (o.nr_of_ticks as i32 - 1)
+                } else {
+                    0
+                };
+                let rect = if horizontal {
+                    Rect::new(x + offset, y + h - tick_size, 1, tick_size)
+                } else {
+                    Rect::new(x + w - tick_size, y + offset, tick_size, 1)
+                };
+                canvas.fill_rect(rect, colour);
+            }
+        }
+
+        if o.options.draw_border {
+            canvas.fill_rect(Rect::new(x, y, w, 1), colour);
+            canvas.fill_rect(Rect::new(x, y + h - 1, w, 1), colour);
+            canvas.fill_rect(Rect::new(x, y, 1, h), colour);
+            canvas.fill_rect(Rect::new(x + w - 1, y, 1, h), colour);
+        }
+    }
+
+    fn draw_arched_bar_graph(&self, canvas: &mut Canvas, o: &OutputArchedBarGraph, x: i32, y: i32) {
+        let bounds = Rect::new(x, y, o.width as i32, o.height as i32);
+        let colour = self.colour(o.colour);
+        let (rx, ry) = (o.width as f64 / 2.0, o.height as f64 / 2.0);
+        let bar_width = o.bar_graph_width as f64;
+        let start = o.start_angle as f64 * 2.0;
+        let end = o.end_angle as f64 * 2.0;
+        let direction = o.options.deflection_direction;
+        let sweep = sweep(start, end, direction);
+        let in_band = |px: f64, py: f64| {
+            inside_ellipse(px, py, rx, ry)
+                && !inside_ellipse(px, py, rx - bar_width, ry - bar_width)
+        };
+
+        let value = self.number_value(o.variable_reference, o.value as u32);
+        let value_sweep = value_fraction(value, o.min_value as u32, o.max_value as u32) * sweep;
+        match o.options.bar_graph_type {
+            BarGraphType::Filled => canvas.fill_where(bounds, colour, |px, py| {
+                in_band(px, py)
+                    && in_sweep_directed(angle_of(px, py), start, value_sweep, direction)
+            }),
+            BarGraphType::NotFilled => {
+                let angle = directed_angle(start, value_sweep, direction);
+                self.draw_radial_line(canvas, bounds, angle, bar_width, colour);
+            }
+        }
+
+        if o.options.draw_target_line {
+            let target =
+                self.number_value(o.target_value_variable_reference, o.target_value as u32);
+            let target_sweep =
+                value_fraction(target, o.min_value as u32, o.max_value as u32) * sweep;
+            let angle = directed_angle(start, target_sweep, direction);
+            self.draw_radial_line(
+                canvas,
+                bounds,
+                angle,
+                bar_width,
+                self.colour(o.target_line_colour),
+            );
+        }
+
+        if o.options.draw_border {
+            canvas.fill_where(bounds, colour, |px, py| {
+                let outer_edge =
+                    inside_ellipse(px, py, rx, ry) && !inside_ellipse(px, py, rx - 1.0, ry - 1.0);
+                let inner_edge = inside_ellipse(px, py, rx - bar_width + 1.0, ry - bar_width + 1.0)
+                    && !inside_ellipse(px, py, rx - bar_width, ry - bar_width);
+                (outer_edge || inner_edge)
+                    && in_sweep_directed(angle_of(px, py), start, sweep, direction)
+            });
+            self.draw_radial_line(canvas, bounds, start, bar_width, colour);
+            self.draw_radial_line(
+                canvas,
+                bounds,
+                directed_angle(start, sweep, direction),
+                bar_width,
+                colour,
+            );
+        }
+    }
+
+    /// Draws a line across the band of an arched bar graph at the given angle
+    fn draw_radial_line(
+        &self,
+        canvas: &mut Canvas,
+        bounds: Rect,
+        angle: f64,
+        bar_width: f64,
+        colour: Colour,
+    ) {
+        let (rx, ry) = (bounds.width as f64 / 2.0, bounds.height as f64 / 2.0);
+        let centre = (bounds.x as f64 + rx, bounds.y as f64 + ry);
+        let (ox, oy) = point_on_ellipse(rx - 1.0, ry - 1.0, angle);
+        let (ix, iy) = point_on_ellipse(rx - bar_width, ry - bar_width, angle);
+        canvas.draw_line(
+            ((centre.0 + ox) as i32, (centre.1 + oy) as i32),
+            ((centre.0 + ix) as i32, (centre.1 + iy) as i32),
+            1,
+            colour,
+            LineArt::SOLID,
+        );
+    }
+
+    fn draw_picture_graphic(&self, canvas: &mut Canvas, o: &PictureGraphic, x: i32, y: i32) {
+        if o.actual_width == 0 || o.actual_height == 0 {
+            return;
+        }
+        let indices = match o.colour_indices() {
+            Ok(indices) => indices,
+            Err(_) => return,
+        };
+        let width = o.width as u32;
+        let height = o.actual_height as u32 * width / o.actual_width as u32;
+
+        for ty in 0..height {
+            let sy = ty * o.actual_height as u32 / height;
+            for tx in 0..width {
+                let sx = tx * o.actual_width as u32 / width;
+                let index = match indices.get((sy * o.actual_width as u32 + sx) as usize) {
+                    Some(index) => *index,
+                    None => continue,
+                };
+                if o.options.transparent && index == o.transparency_colour {
+                    continue;
+                }
+                canvas.put(x + tx as i32, y + ty as i32, self.colour(index));
+            }
+        }
+    }
+}
+
+/// Returns the character cell size of a VT font size, proportional fonts are drawn with the
+/// fixed font of the same height
+fn font_size(font_size: FontSize) -> (u32, u32) {
+    let size = font_size.to_fixed().size().unwrap_or(Point { x: 6, y: 8 });
+    (size.x.into(), size.y.into())
+}
+
+/// Splits text into the lines a VT would display, wrapping on whitespace if requested
+fn layout_text(text: &str, max_chars: usize, auto_wrap: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split("\r\n").flat_map(|l| l.split(['\r', '\n'])) {
+        if !auto_wrap || max_chars == 0 || paragraph.chars().count() <= max_chars {
+            lines.push(paragraph.to_string());
+            continue;
+        }
+
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let line_length = line.chars().count();
+            let word_length = word.chars().count();
+            if line_length > 0 && line_length + 1 + word_length > max_chars {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+            // Break words that do not fit on a line on their own
+            while line.chars().count() > max_chars {
+                let split = line
+                    .char_indices()
+                    .nth(max_chars)
+                    .map_or(line.len(), |(i, _)| i);
+                let rest = line.split_off(split);
+                lines.push(std::mem::replace(&mut line, rest));
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn inside_ellipse(px: f64, py: f64, rx: f64, ry: f64) -> bool {
+    rx > 0.0 && ry > 0.0 && (px * px) / (rx * rx) + (py * py) / (ry * ry) <= 1.0
+}
+
+/// The angle of a point relative to the centre in degrees, anticlockwise from the positive x axis
+fn angle_of(px: f64, py: f64) -> f64 {
+    (-py).atan2(px).to_degrees().rem_euclid(360.0)
+}
+
+/// A point on an ellipse centred on the origin, in screen coordinates
+fn point_on_ellipse(rx: f64, ry: f64, angle: f64) -> (f64, f64) {
+    let radians = angle * PI / 180.0;
+    (rx * radians.cos(), -ry * radians.sin())
+}
+
+/// The number of degrees between two angles when moving in `direction`, a full circle if equal
+fn sweep(start: f64, end: f64, direction: DeflectionDirection) -> f64 {
+    let sweep = match direction {
+        DeflectionDirection::AntiClockwise => (end - start).rem_euclid(360.0),
+        DeflectionDirection::Clockwise => (start - end).rem_euclid(360.0),
+    };
+    if sweep == 0.0 {
+        360.0
+    } else {
+        sweep
+    }
+}
+
+fn directed_angle(start: f64, sweep: f64, direction: DeflectionDirection) -> f64 {
+    match direction {
+        DeflectionDirection::AntiClockwise => start + sweep,
+        DeflectionDirection::Clockwise => start - sweep,
+    }
+}
+
+fn in_sweep(angle: f64, start: f64, sweep: f64) -> bool {
+    (angle - start).rem_euclid(360.0) <= sweep
+}
+
+fn in_sweep_directed(angle: f64, start: f64, sweep: f64, direction: DeflectionDirection) -> bool {
+    match direction {
+        DeflectionDirection::AntiClockwise => in_sweep(angle, start, sweep),
+        DeflectionDirection::Clockwise => (start - angle).rem_euclid(360.0) <= sweep,
+    }
+}
+
+fn value_fraction(value: u32, min: u32, max: u32) -> f64 {
+    if max <= min {
+        return 0.0;
+    }
+    (value.clamp(min, max) - min) as f64 / (max - min) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::object::{
+        DataMask, FillAttributes, FontAttributes, LineAttributes, NumberVariable, OutputString,
+    };
+    use crate::object_pool::object_attributes::{
+        DataCodeType, FontType, OutputStringOptions, PictureGraphicOptions, Point,
+    };
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    fn object_ref(object: u16, x: i16, y: i16) -> ObjectRef {
+        ObjectRef {
+            id: id(object),
+            offset: Point { x, y },
+        }
+    }
+
+    fn pool_with_mask(object_refs: Vec<ObjectRef>, objects: Vec<Object>) -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::DataMask(DataMask {
+            id: id(1000),
+            background_colour: 1,
+            soft_key_mask: NullableObjectId::NULL,
+            object_refs,
+            macro_refs: vec![],
+        }));
+        for object in objects {
+            pool.add(object);
+        }
+        pool
+    }
+
+    fn pixel(image: &RgbaImage, x: u32, y: u32) -> Colour {
+        let rgba = image.pixel(x, y).unwrap();
+        Colour::new_by_rgb(rgba[0], rgba[1], rgba[2])
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let pool = pool_with_mask(vec![], vec![]);
+        let renderer = Renderer::new(&pool, 100);
+
+        assert_eq!(
+            renderer.render_data_mask(id(1)),
+            Err(RenderError::ObjectNotFound(id(1)))
+        );
+
+        let mut pool = pool_with_mask(vec![], vec![]);
+        pool.add(Object::NumberVariable(NumberVariable {
+            id: id(1),
+            value: 0,
+        }));
+        let renderer = Renderer::new(&pool, 100);
+        assert_eq!(
+            renderer.render_data_mask(id(1)),
+            Err(RenderError::UnsupportedObject(
+                id(1),
+                ObjectType::NumberVariable
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render_background_and_rectangle() {
+        let pool = pool_with_mask(
+            vec![object_ref(1, 10, 20)],
+            vec![
+                Object::OutputRectangle(OutputRectangle {
+                    id: id(1),
+                    line_attributes: id(2),
+                    width: 30,
+                    height: 10,
+                    line_suppression: 0,
+                    fill_attributes: id(3).into(),
+                    macro_refs: vec![],
+                }),
+                Object::LineAttributes(LineAttributes {
+                    id: id(2),
+                    line_colour: 12,
+                    line_width: 1,
+                    line_art: LineArt::SOLID,
+                    macro_refs: vec![],
+                }),
+                Object::FillAttributes(FillAttributes {
+                    id: id(3),
+                    fill_type: FillType::FillColour,
+                    fill_colour: 9,
+                    fill_pattern: NullableObjectId::NULL,
+                    macro_refs: vec![],
+                }),
+            ],
+        );
+        let image = Renderer::new(&pool, 100)
+            .render_data_mask(id(1000))
+            .unwrap();
+
+        assert_eq!((image.width(), image.height()), (100, 100));
+        assert_eq!(pixel(&image, 0, 0), Colour::WHITE);
+        assert_eq!(pixel(&image, 10, 20), Colour::RED);
+        assert_eq!(pixel(&image, 39, 29), Colour::RED);
+        assert_eq!(pixel(&image, 20, 25), Colour::BLUE);
+        assert_eq!(pixel(&image, 40, 25), Colour::WHITE);
+    }
+
+    #[test]
+    fn test_render_output_string_from_variable() {
+        let pool = pool_with_mask(
+            vec![object_ref(1, 0, 0)],
+            vec![
+                Object::OutputString(OutputString {
+                    id: id(1),
+                    width: 60,
+                    height: 8,
+                    background_colour: 14,
+                    font_attributes: id(2),
+                    options: OutputStringOptions {
+                        transparent: false,
+                        auto_wrap: false,
+                        wrap_on_hyphen: false,
+                    },
+                    variable_reference: id(3).into(),
+                    justification: 0.into(),
+                    value: String::new(),
+                    macro_refs: vec![],
+                }),
+                Object::FontAttributes(FontAttributes {
+                    id: id(2),
+                    font_colour: 0,
+                    font_size: FontSize::Size6x8,
+                    font_type: FontType::Iso8859_1,
+                    font_style: FontStyle::default(),
+                    macro_refs: vec![],
+                }),
+                Object::StringVariable(crate::object_pool::object::StringVariable {
+                    id: id(3),
+                    value: "|".to_string(),
+                }),
+            ],
+        );
+        let image = Renderer::new(&pool, 80).render_data_mask(id(1000)).unwrap();
+
+        // The vertical bar of '|' is in the middle column of the glyph
+        assert_eq!(pixel(&image, 2, 0), Colour::BLACK);
+        assert_eq!(pixel(&image, 1, 0), Colour::YELLOW);
+        assert_eq!(pixel(&image, 59, 7), Colour::YELLOW);
+        assert_eq!(pixel(&image, 60, 0), Colour::WHITE);
+    }
+
+    #[test]
+    fn test_render_scaled_picture_graphic() {
+        let pool = pool_with_mask(
+            vec![object_ref(1, 0, 0)],
+            vec![Object::PictureGraphic(PictureGraphic {
+                id: id(1),
+                width: 4,
+                actual_width: 2,
+                actual_height: 1,
+                format: 2,
+                options: PictureGraphicOptions {
+                    transparent: true,
+                    flashing: false,
+                    data_code_type: DataCodeType::Raw,
+                },
+                transparency_colour: 12,
+                data: vec![9, 12],
+                macro_refs: vec![],
+            })],
+        );
+        let image = Renderer::new(&pool, 10).render_data_mask(id(1000)).unwrap();
+
+        assert_eq!(pixel(&image, 0, 0), Colour::BLUE);
+        assert_eq!(pixel(&image, 1, 1), Colour::BLUE);
+        assert_eq!(pixel(&image, 2, 0), Colour::WHITE);
+        assert_eq!(pixel(&image, 0, 2), Colour::WHITE);
+    }
+
+    #[test]
+    fn test_layout_text() {
+        assert_eq!(layout_text("a\r\nb\nc", 10, false), vec!["a", "b", "c"]);
+        assert_eq!(
+            layout_text("hello big world", 9, true),
+            vec!["hello big", "world"]
+        );
+        assert_eq!(layout_text("abcdefgh", 3, true), vec!["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn test_angles() {
+        assert_eq!(sweep(0.0, 90.0, DeflectionDirection::AntiClockwise), 90.0);
+        assert_eq!(sweep(0.0, 90.0, DeflectionDirection::Clockwise), 270.0);
+        assert_eq!(sweep(10.0, 10.0, DeflectionDirection::Clockwise), 360.0);
+        assert_eq!(angle_of(0.0, -1.0), 90.0);
+        assert!(in_sweep_directed(
+            350.0,
+            10.0,
+            30.0,
+            DeflectionDirection::Clockwise
+        ));
+        assert!(!in_sweep_directed(
+            350.0,
+            10.0,
+            30.0,
+            DeflectionDirection::AntiClockwise
+        ));
+        assert_eq!(value_fraction(150, 100, 200), 0.5);
+        assert_eq!(value_fraction(50, 100, 200), 0.0);
+    }
+}
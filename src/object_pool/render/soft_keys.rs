@@ -0,0 +1,327 @@
+// Copyright 2023 Raven Industries inc.
+use super::canvas::{Canvas, Rect};
+use super::image::RgbaImage;
+use super::renderer::{RenderError, Renderer};
+use crate::object_pool::object::{Key, Object};
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::Colour;
+
+/// The soft key area of a VT, as reported in its Get Soft Key Capabilities response
+///
+/// The physical soft keys are arranged in `columns` columns to the right of the data mask, filled
+/// top to bottom. If a soft key mask holds more keys than there are physical soft keys, the keys
+/// are split into pages and the last physical soft key navigates to the next page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftKeyLayout {
+    /// The number of physical soft keys, a VT has at least 6
+    pub physical_keys: u8,
+    /// The number of columns the physical soft keys are arranged in
+    pub columns: u8,
+    /// The width of a soft key designator in pixels
+    pub designator_width: u16,
+    /// The height of a soft key designator in pixels
+    pub designator_height: u16,
+}
+
+impl SoftKeyLayout {
+    /// The number of physical soft keys stacked in each column
+    pub fn keys_per_column(&self) -> u8 {
+        let columns = self.columns.max(1);
+        self.physical_keys.max(1).div_ceil(columns)
+    }
+
+    /// The size of the soft key area in pixels
+    pub fn area_size(&self) -> (u32, u32) {
+        (
+            self.columns.max(1) as u32 * self.designator_width as u32,
+            self.keys_per_column() as u32 * self.designator_height as u32,
+        )
+    }
+
+    /// The number of keys shown on each page when `key_count` keys are laid out
+    fn keys_per_page(&self, key_count: usize) -> usize {
+        let physical_keys = self.physical_keys.max(1) as usize;
+        if key_count <= physical_keys || physical_keys == 1 {
+            physical_keys
+        } else {
+            // The last physical key is used to switch pages
+            physical_keys - 1
+        }
+    }
+
+    /// The position of a physical soft key in the soft key area
+    fn designator(&self, position: usize) -> Rect {
+        let keys_per_column = self.keys_per_column() as usize;
+        let column = position / keys_per_column;
+        let row = position % keys_per_column;
+        Rect::new(
+            column as i32 * self.designator_width as i32,
+            row as i32 * self.designator_height as i32,
+            self.designator_width as i32,
+            self.designator_height as i32,
+        )
+    }
+}
+
+impl Default for SoftKeyLayout {
+    fn default() -> Self {
+        SoftKeyLayout {
+            physical_keys: 6,
+            columns: 1,
+            designator_width: 60,
+            designator_height: 60,
+        }
+    }
+}
+
+impl<'a> Renderer<'a> {
+    /// Returns the number of pages needed to show all keys of a soft key mask
+    pub fn soft_key_pages(&self, soft_key_mask: ObjectId) -> Result<usize, RenderError> {
+        let keys = self.soft_keys(soft_key_mask)?;
+        let keys_per_page = self.soft_key_layout.keys_per_page(keys.len());
+        Ok(keys.len().div_ceil(keys_per_page).max(1))
+    }
+
+    /// Renders one page of a soft key mask, laid out according to the [`SoftKeyLayout`]
+    ///
+    /// Pages past the last one are rendered as the last page.
+    pub fn render_soft_key_mask(
+        &self,
+        soft_key_mask: ObjectId,
+        page: usize,
+    ) -> Result<RgbaImage, RenderError> {
+        let keys = self.soft_keys(soft_key_mask)?;
+        let background_colour = match self.pool.object_by_id(soft_key_mask) {
+            Some(Object::SoftKeyMask(o)) => self.colour(o.background_colour),
+            _ => Colour::BLACK,
+        };
+        let layout = self.soft_key_layout;
+        let (width, height) = layout.area_size();
+        let mut canvas = Canvas::new(width, height, background_colour);
+
+        let keys_per_page = layout.keys_per_page(keys.len());
+        let pages = keys.len().div_ceil(keys_per_page).max(1);
+        let first = page.min(pages - 1) * keys_per_page;
+        for (position, key) in keys.iter().skip(first).take(keys_per_page).enumerate() {
+            if let Some(key) = key {
+                let designator = layout.designator(position);
+                canvas.push_clip(designator);
+                canvas.fill_rect(designator, self.colour(key.background_colour));
+                self.draw_children(&mut canvas, &key.object_refs, designator.x, designator.y, 0);
+                canvas.pop_clip();
+            }
+        }
+
+        if pages > 1 {
+            self.draw_more_key(&mut canvas, layout.designator(keys_per_page));
+        }
+        Ok(canvas.into_image())
+    }
+
+    /// Resolves the keys of a soft key mask in the order they are shown
+    ///
+    /// Object pointers that do not point to a key still take up a position, but show nothing.
+    fn soft_keys(&self, soft_key_mask: ObjectId) -> Result<Vec<Option<&'a Key>>, RenderError> {
+        let objects = match self.pool.object_by_id(soft_key_mask) {
+            Some(Object::SoftKeyMask(o)) => &o.objects,
+            Some(o) => {
+                return Err(RenderError::UnsupportedObject(
+                    soft_key_mask,
+                    o.object_type(),
+                ))
+            }
+            None => return Err(RenderError::ObjectNotFound(soft_key_mask)),
+        };
+
+        let mut keys = Vec::new();
+        for id in objects {
+            match self.pool.object_by_id(*id) {
+                Some(Object::Key(key)) => keys.push(Some(key)),
+                Some(Object::ObjectPointer(o)) => keys.push(self.key(o.value)),
+                Some(Object::ExternalObjectPointer(o)) => keys.push(self.key(o.default_object_id)),
+                Some(Object::KeyGroup(o)) if o.options.available => {
+                    for id in &o.objects {
+                        match self.pool.object_by_id(*id) {
+                            Some(Object::ObjectPointer(o)) => keys.push(self.key(o.value)),
+                            _ => keys.push(self.key((*id).into())),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(keys)
+    }
+
+    fn key(&self, id: NullableObjectId) -> Option<&'a Key> {
+        match self.pool.object_by_id(id.0?) {
+            Some(Object::Key(key)) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Draws the key used to switch to the next page of soft keys
+    fn draw_more_key(&self, canvas: &mut Canvas, designator: Rect) {
+        canvas.fill_rect(designator, Colour::SILVER);
+        let (w, h) = (designator.width, designator.height);
+        let (x, y) = (designator.x, designator.y);
+        canvas.fill_polygon(
+            &[
+                (x + w / 3, y + h / 4),
+                (x + w * 2 / 3, y + h / 2),
+                (x + w / 3, y + h * 3 / 4),
+            ],
+            Colour::BLACK,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::object::{DataMask, ObjectPointer, SoftKeyMask};
+    use crate::object_pool::ObjectPool;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    /// A pool with a data mask (1000) and a soft key mask (2000) holding `key_count` keys, the
+    /// key at index `i` has colour `i + 2` as background colour
+    fn pool_with_keys(key_count: u16) -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::DataMask(DataMask {
+            id: id(1000),
+            background_colour: 1,
+            soft_key_mask: id(2000).into(),
+            object_refs: vec![],
+            macro_refs: vec![],
+        }));
+        pool.add(Object::SoftKeyMask(SoftKeyMask {
+            id: id(2000),
+            background_colour: 0,
+            objects: (0..key_count).map(|i| id(3000 + i)).collect(),
+            macro_refs: vec![],
+        }));
+        for i in 0..key_count {
+            pool.add(Object::Key(Key {
+                id: id(3000 + i),
+                background_colour: i as u8 + 2,
+                key_code: i as u8,
+                object_refs: vec![],
+                macro_refs: vec![],
+            }));
+        }
+        pool
+    }
+
+    fn pixel(image: &RgbaImage, x: u32, y: u32) -> Colour {
+        let rgba = image.pixel(x, y).unwrap();
+        Colour::new_by_rgb(rgba[0], rgba[1], rgba[2])
+    }
+
+    #[test]
+    fn test_layout() {
+        let layout = SoftKeyLayout {
+            physical_keys: 12,
+            columns: 2,
+            designator_width: 80,
+            designator_height: 40,
+        };
+        assert_eq!(layout.keys_per_column(), 6);
+        assert_eq!(layout.area_size(), (160, 240));
+        assert_eq!(layout.designator(7), Rect::new(80, 40, 80, 40));
+        assert_eq!(layout.keys_per_page(12), 12);
+        assert_eq!(layout.keys_per_page(13), 11);
+    }
+
+    #[test]
+    fn test_soft_key_pages() {
+        let pool = pool_with_keys(6);
+        let renderer = Renderer::new(&pool, 100);
+        assert_eq!(renderer.soft_key_pages(id(2000)), Ok(1));
+
+        let pool = pool_with_keys(11);
+        let renderer = Renderer::new(&pool, 100);
+        assert_eq!(renderer.soft_key_pages(id(2000)), Ok(3));
+        assert_eq!(
+            renderer.soft_key_pages(id(1000)),
+            Err(RenderError::UnsupportedObject(
+                id(1000),
+                crate::object_pool::ObjectType::DataMask
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render_soft_key_pages() {
+        let pool = pool_with_keys(7);
+        let renderer = Renderer::new(&pool, 100);
+
+        let first = renderer.render_soft_key_mask(id(2000), 0).unwrap();
+        assert_eq!((first.width(), first.height()), (60, 360));
+        assert_eq!(pixel(&first, 0, 0), Colour::new_by_id(2));
+        assert_eq!(pixel(&first, 0, 240), Colour::new_by_id(6));
+        // The last physical key switches to the next page
+        assert_eq!(pixel(&first, 0, 300), Colour::SILVER);
+        assert_eq!(pixel(&first, 25, 330), Colour::BLACK);
+
+        let second = renderer.render_soft_key_mask(id(2000), 1).unwrap();
+        assert_eq!(pixel(&second, 0, 0), Colour::new_by_id(7));
+        assert_eq!(pixel(&second, 0, 60), Colour::new_by_id(8));
+        assert_eq!(pixel(&second, 0, 120), Colour::BLACK);
+    }
+
+    #[test]
+    fn test_empty_object_pointer_keeps_position() {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::SoftKeyMask(SoftKeyMask {
+            id: id(2000),
+            background_colour: 0,
+            objects: vec![id(3000), id(3001)],
+            macro_refs: vec![],
+        }));
+        for object in [
+            Object::ObjectPointer(ObjectPointer {
+                id: id(3000),
+                value: NullableObjectId::NULL,
+            }),
+            Object::ObjectPointer(ObjectPointer {
+                id: id(3001),
+                value: id(3002).into(),
+            }),
+            Object::Key(Key {
+                id: id(3002),
+                background_colour: 12,
+                key_code: 0,
+                object_refs: vec![],
+                macro_refs: vec![],
+            }),
+        ] {
+            pool.add(object);
+        }
+
+        let renderer = Renderer::new(&pool, 100);
+        let image = renderer.render_soft_key_mask(id(2000), 0).unwrap();
+        assert_eq!(pixel(&image, 0, 0), Colour::BLACK);
+        assert_eq!(pixel(&image, 0, 60), Colour::RED);
+    }
+
+    #[test]
+    fn test_render_screen() {
+        let pool = pool_with_keys(2);
+        let renderer = Renderer::new(&pool, 100).with_soft_key_layout(SoftKeyLayout {
+            physical_keys: 4,
+            columns: 2,
+            designator_width: 30,
+            designator_height: 20,
+        });
+        let screen = renderer.render_screen(id(1000), 0).unwrap();
+
+        assert_eq!((screen.width(), screen.height()), (160, 100));
+        assert_eq!(pixel(&screen, 99, 99), Colour::WHITE);
+        assert_eq!(pixel(&screen, 100, 0), Colour::new_by_id(2));
+        assert_eq!(pixel(&screen, 100, 20), Colour::new_by_id(3));
+        assert_eq!(pixel(&screen, 130, 0), Colour::BLACK);
+    }
+}
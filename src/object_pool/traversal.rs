@@ -0,0 +1,314 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::object::Object;
+use crate::object_pool::object_attributes::{ObjectRef, Point};
+use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::validation::{macro_ids, references};
+use crate::object_pool::{ObjectPool, ObjectType};
+use std::collections::BTreeSet;
+
+/// Objects the VT shows or uses without another object referencing them
+const ROOT_TYPES: &[ObjectType] = &[
+    ObjectType::WorkingSet,
+    ObjectType::DataMask,
+    ObjectType::AlarmMask,
+    ObjectType::WindowMask,
+    ObjectType::KeyGroup,
+    ObjectType::AuxiliaryFunctionType1,
+    ObjectType::AuxiliaryInputType1,
+    ObjectType::AuxiliaryFunctionType2,
+    ObjectType::AuxiliaryInputType2,
+    ObjectType::WorkingSetSpecialControls,
+    ObjectType::ObjectLabelReferenceList,
+    ObjectType::ExternalObjectDefinition,
+];
+
+/// An object reached by [`ObjectPool::walk`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Visit<'a> {
+    pub object: &'a Object,
+    /// The object that references this object, `None` for the root
+    pub parent: Option<ObjectId>,
+    /// The position of the object relative to the root
+    ///
+    /// Objects placed with an [`ObjectRef`] add its offset to the position of their parent, all
+    /// other children, like the value of an Object Pointer, are at the position of their parent.
+    pub position: Point<i32>,
+    /// The number of objects between the root and this object, 0 for the root
+    pub depth: usize,
+}
+
+impl ObjectPool {
+    /// The objects `id` references, in the order of its fields, including the macros it runs
+    ///
+    /// Each child is listed once, even if it is referenced more than once. References to objects
+    /// that are not in the pool are included.
+    pub fn children(&self, id: ObjectId) -> Vec<ObjectId> {
+        self.object_by_id(id).map(children).unwrap_or_default()
+    }
+
+    /// The objects that reference `id`, in pool order
+    pub fn parents(&self, id: ObjectId) -> Vec<ObjectId> {
+        self.objects()
+            .iter()
+            .filter(|object| children(object).contains(&id))
+            .map(Object::id)
+            .collect()
+    }
+
+    /// Visits `root` and all its descendants depth first, parents before their children
+    ///
+    /// An object referenced from several places is visited once for every place. References
+    /// back to an object being visited and references to objects that are not in the pool are
+    /// skipped.
+    pub fn walk<F>(&self, root: ObjectId, mut visitor: F)
+    where
+        F: FnMut(&Visit),
+    {
+        if let Some(object) = self.object_by_id(root) {
+            let visit = Visit {
+                object,
+                parent: None,
+                position: Point { x: 0, y: 0 },
+                depth: 0,
+            };
+            self.walk_from(visit, &mut vec![root], &mut visitor);
+        }
+    }
+
+    fn walk_from<F>(&self, visit: Visit, path: &mut Vec<ObjectId>, visitor: &mut F)
+    where
+        F: FnMut(&Visit),
+    {
+        visitor(&visit);
+        let offsets = object_refs(visit.object);
+        for reference in references(visit.object) {
+            if path.contains(&reference.id) {
+                continue;
+            }
+            let Some(child) = self.object_by_id(reference.id) else {
+                continue;
+            };
+            let offset = match (reference.field.name, reference.field.index) {
+                ("object_refs", Some(index)) => offsets[index].offset,
+                _ => Point { x: 0, y: 0 },
+            };
+            let child_visit = Visit {
+                object: child,
+                parent: Some(visit.object.id()),
+                position: Point {
+                    x: visit.position.x + i32::from(offset.x),
+                    y: visit.position.y + i32::from(offset.y),
+                },
+                depth: visit.depth + 1,
+            };
+            path.push(reference.id);
+            self.walk_from(child_visit, path, visitor);
+            path.pop();
+        }
+    }
+
+    /// The objects reachable from `roots` through references and macros, including the roots
+    pub fn reachable_from(&self, roots: &[ObjectId]) -> BTreeSet<ObjectId> {
+        let mut reachable = BTreeSet::new();
+        let mut pending: Vec<ObjectId> = roots.to_vec();
+        while let Some(id) = pending.pop() {
+            if let Some(object) = self.object_by_id(id) {
+                if reachable.insert(id) {
+                    pending.extend(children(object));
+                }
+            }
+        }
+        reachable
+    }
+
+    /// The objects that can't be reached from an object the VT uses on its own
+    ///
+    /// Those are the Working Set, masks, Key Groups, auxiliary objects and the other objects that
+    /// are not referenced by other objects by design. Objects the application only uses through
+    /// commands at runtime are unreachable too.
+    pub fn unreachable_objects(&self) -> Vec<ObjectId> {
+        let reachable = self.reachable_from(&self.roots());
+        self.objects()
+            .iter()
+            .map(Object::id)
+            .filter(|id| !reachable.contains(id))
+            .collect()
+    }
+
+    /// Removes the [unreachable objects](ObjectPool::unreachable_objects), returns them
+    ///
+    /// The objects in `keep`, like the ones the application only uses through commands at
+    /// runtime, and everything they reference are kept too.
+    pub fn prune_unused(&mut self, keep: &[ObjectId]) -> Vec<Object> {
+        let mut roots = self.roots();
+        roots.extend_from_slice(keep);
+        let reachable = self.reachable_from(&roots);
+        let removed = self
+            .objects()
+            .iter()
+            .filter(|object| !reachable.contains(&object.id()))
+            .cloned()
+            .collect();
+        self.retain(|object| reachable.contains(&object.id()));
+        removed
+    }
+
+    fn roots(&self) -> Vec<ObjectId> {
+        self.objects()
+            .iter()
+            .filter(|object| ROOT_TYPES.contains(&object.object_type()))
+            .map(Object::id)
+            .collect()
+    }
+}
+
+fn children(object: &Object) -> Vec<ObjectId> {
+    let referenced = references(object).into_iter().map(|r| r.id);
+    let macros = macro_ids(object)
+        .into_iter()
+        .filter_map(|(_, macro_id)| ObjectId::new(u16::from(macro_id)).ok());
+    let mut children = Vec::new();
+    for id in referenced.chain(macros) {
+        if !children.contains(&id) {
+            children.push(id);
+        }
+    }
+    children
+}
+
+/// The children of `object` that are placed with an offset
+pub(super) fn object_refs(object: &Object) -> &[ObjectRef] {
+    match object {
+        Object::WorkingSet(o) => &o.object_refs,
+        Object::DataMask(o) => &o.object_refs,
+        Object::AlarmMask(o) => &o.object_refs,
+        Object::Container(o) => &o.object_refs,
+        Object::Key(o) => &o.object_refs,
+        Object::Button(o) => &o.object_refs,
+        Object::AuxiliaryFunctionType1(o) => &o.object_refs,
+        Object::AuxiliaryInputType1(o) => &o.object_refs,
+        Object::AuxiliaryFunctionType2(o) => &o.object_refs,
+        Object::AuxiliaryInputType2(o) => &o.object_refs,
+        Object::WindowMask(o) => &o.object_refs,
+        Object::Animation(o) => &o.object_refs,
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::object::{
+        Container, DataMask, FontAttributes, NumberVariable, ObjectPointer, OutputString,
+    };
+    use crate::object_pool::{Macro, ObjectBuilder, ObjectPoolBuilder, WorkingSet};
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    /// A working set with a mask, a container in it showing a string twice, and a pointer to
+    /// the container
+    fn test_pool() -> ObjectPool {
+        let mut builder = ObjectPoolBuilder::new();
+        builder.add_with_id(id(0), WorkingSet::builder(id(1000)).macro_ref(1, 1));
+        builder.add_object(Object::Macro(
+            Macro::builder(id(1)).hide_object(id(300)).build(),
+        ));
+        builder.add_with_id(
+            id(1000),
+            DataMask::builder()
+                .child(id(200), 10, 20)
+                .child(id(400), 0, 0),
+        );
+        builder.add_with_id(
+            id(200),
+            Container::builder()
+                .child(id(300), 5, 5)
+                .child(id(300), -5, 30),
+        );
+        builder.add_with_id(id(300), OutputString::builder(id(500)));
+        builder.add_with_id(id(400), ObjectPointer::builder().value(id(200)));
+        builder.add_with_id(id(500), FontAttributes::builder());
+        builder.add_with_id(id(600), NumberVariable::builder());
+        builder.add_with_id(id(700), FontAttributes::builder());
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn children_and_parents_test() {
+        let pool = test_pool();
+        assert_eq!(pool.children(id(0)), vec![id(1000), id(1)]);
+        assert_eq!(pool.children(id(1)), vec![id(300)]);
+        assert_eq!(pool.children(id(200)), vec![id(300)]);
+        assert_eq!(pool.children(id(500)), vec![]);
+        assert_eq!(pool.children(id(9999)), vec![]);
+
+        assert_eq!(pool.parents(id(300)), vec![id(1), id(200)]);
+        assert_eq!(pool.parents(id(200)), vec![id(1000), id(400)]);
+        assert_eq!(pool.parents(id(0)), vec![]);
+    }
+
+    #[test]
+    fn walk_test() {
+        let pool = test_pool();
+        let mut visits = Vec::new();
+        pool.walk(id(1000), |visit| {
+            visits.push((
+                u16::from(visit.object.id()),
+                visit.parent.map(u16::from),
+                visit.position.x,
+                visit.position.y,
+                visit.depth,
+            ))
+        });
+        assert_eq!(
+            visits,
+            vec![
+                (1000, None, 0, 0, 0),
+                (200, Some(1000), 10, 20, 1),
+                (300, Some(200), 15, 25, 2),
+                (500, Some(300), 15, 25, 3),
+                (300, Some(200), 5, 50, 2),
+                (500, Some(300), 5, 50, 3),
+                (400, Some(1000), 0, 0, 1),
+                (200, Some(400), 0, 0, 2),
+                (300, Some(200), 5, 5, 3),
+                (500, Some(300), 5, 5, 4),
+                (300, Some(200), -5, 30, 3),
+                (500, Some(300), -5, 30, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_cycle_test() {
+        let mut pool = ObjectPool::new();
+        pool.add(ObjectPointer::builder().value(id(2)).build(id(1)));
+        pool.add(ObjectPointer::builder().value(id(1)).build(id(2)));
+        let mut visited = Vec::new();
+        pool.walk(id(1), |visit| visited.push(visit.object.id()));
+        assert_eq!(visited, vec![id(1), id(2)]);
+    }
+
+    #[test]
+    fn prune_unused_test() {
+        let mut pool = test_pool();
+        assert_eq!(pool.unreachable_objects(), vec![id(600), id(700)]);
+
+        let removed: Vec<ObjectId> = pool
+            .prune_unused(&[id(600)])
+            .iter()
+            .map(Object::id)
+            .collect();
+        assert_eq!(removed, vec![id(700)]);
+        assert_eq!(pool.unreachable_objects(), vec![id(600)]);
+
+        let removed: Vec<ObjectId> = pool.prune_unused(&[]).iter().map(Object::id).collect();
+        assert_eq!(removed, vec![id(600)]);
+        assert!(pool.unreachable_objects().is_empty());
+        assert!(pool.object_by_id(id(600)).is_none());
+        assert_eq!(pool.objects().len(), 7);
+        assert!(pool.validate().is_ok());
+    }
+}
@@ -0,0 +1,1339 @@
+// Copyright 2023 Raven Industries inc.
+use crate::object_pool::macro_command::{GraphicsContextCommand, MacroCommand};
+use crate::object_pool::object::Object;
+use crate::object_pool::object_attributes::{FillType, ObjectRef};
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::{ObjectPool, ObjectType};
+use std::collections::HashMap;
+
+/// Objects that only display something, allowed in every object that has children
+const OUTPUT_OBJECTS: &[ObjectType] = &[
+    ObjectType::Container,
+    ObjectType::OutputString,
+    ObjectType::OutputNumber,
+    ObjectType::OutputList,
+    ObjectType::OutputLine,
+    ObjectType::OutputRectangle,
+    ObjectType::OutputEllipse,
+    ObjectType::OutputPolygon,
+    ObjectType::OutputMeter,
+    ObjectType::OutputLinearBarGraph,
+    ObjectType::OutputArchedBarGraph,
+    ObjectType::PictureGraphic,
+    ObjectType::GraphicsContext,
+    ObjectType::Animation,
+    ObjectType::ScaledGraphic,
+    ObjectType::ObjectPointer,
+    ObjectType::ExternalObjectPointer,
+];
+
+/// Objects allowed on data masks, alarm masks and window masks
+const MASK_OBJECTS: &[ObjectType] = &[
+    ObjectType::Container,
+    ObjectType::Button,
+    ObjectType::InputBoolean,
+    ObjectType::InputString,
+    ObjectType::InputNumber,
+    ObjectType::InputList,
+    ObjectType::OutputString,
+    ObjectType::OutputNumber,
+    ObjectType::OutputList,
+    ObjectType::OutputLine,
+    ObjectType::OutputRectangle,
+    ObjectType::OutputEllipse,
+    ObjectType::OutputPolygon,
+    ObjectType::OutputMeter,
+    ObjectType::OutputLinearBarGraph,
+    ObjectType::OutputArchedBarGraph,
+    ObjectType::PictureGraphic,
+    ObjectType::GraphicsContext,
+    ObjectType::Animation,
+    ObjectType::ScaledGraphic,
+    ObjectType::ObjectPointer,
+    ObjectType::ExternalObjectPointer,
+];
+
+/// Objects an object pointer may point at, which depends on the parent of the pointer
+const POINTER_TARGETS: &[ObjectType] = &[
+    ObjectType::Container,
+    ObjectType::Key,
+    ObjectType::Button,
+    ObjectType::InputBoolean,
+    ObjectType::InputString,
+    ObjectType::InputNumber,
+    ObjectType::InputList,
+    ObjectType::OutputString,
+    ObjectType::OutputNumber,
+    ObjectType::OutputList,
+    ObjectType::OutputLine,
+    ObjectType::OutputRectangle,
+    ObjectType::OutputEllipse,
+    ObjectType::OutputPolygon,
+    ObjectType::OutputMeter,
+    ObjectType::OutputLinearBarGraph,
+    ObjectType::OutputArchedBarGraph,
+    ObjectType::PictureGraphic,
+    ObjectType::GraphicsContext,
+    ObjectType::Animation,
+    ObjectType::ScaledGraphic,
+    ObjectType::ObjectPointer,
+    ObjectType::ExternalObjectPointer,
+];
+
+const KEYS: &[ObjectType] = &[
+    ObjectType::Key,
+    ObjectType::ObjectPointer,
+    ObjectType::ExternalObjectPointer,
+];
+const MASKS: &[ObjectType] = &[ObjectType::DataMask, ObjectType::AlarmMask];
+const SOFT_KEY_MASK: &[ObjectType] = &[ObjectType::SoftKeyMask];
+const STRING_LABEL: &[ObjectType] = &[ObjectType::OutputString, ObjectType::ObjectPointer];
+const FONT_ATTRIBUTES: &[ObjectType] = &[ObjectType::FontAttributes];
+const LINE_ATTRIBUTES: &[ObjectType] = &[ObjectType::LineAttributes];
+const FILL_ATTRIBUTES: &[ObjectType] = &[ObjectType::FillAttributes];
+const INPUT_ATTRIBUTES: &[ObjectType] = &[
+    ObjectType::InputAttributes,
+    ObjectType::ExtendedInputAttributes,
+];
+const NUMBER_VARIABLE: &[ObjectType] = &[ObjectType::NumberVariable];
+const STRING_VARIABLE: &[ObjectType] = &[ObjectType::StringVariable];
+const PICTURE_GRAPHIC: &[ObjectType] = &[ObjectType::PictureGraphic];
+const SCALABLE_GRAPHICS: &[ObjectType] = &[ObjectType::PictureGraphic, ObjectType::GraphicData];
+const AUXILIARY_OBJECTS: &[ObjectType] = &[
+    ObjectType::AuxiliaryFunctionType2,
+    ObjectType::AuxiliaryInputType2,
+];
+const EXTERNAL_REFERENCE_NAME: &[ObjectType] = &[ObjectType::ExternalReferenceName];
+const COLOUR_MAP: &[ObjectType] = &[ObjectType::ColourMap];
+const COLOUR_PALETTE: &[ObjectType] = &[ObjectType::ColourPalette];
+const WORKING_SET: &[ObjectType] = &[ObjectType::WorkingSet];
+const DATA_MASK: &[ObjectType] = &[ObjectType::DataMask];
+const ALARM_MASK: &[ObjectType] = &[ObjectType::AlarmMask];
+const MACRO: &[ObjectType] = &[ObjectType::Macro];
+const GRAPHICS_CONTEXT: &[ObjectType] = &[ObjectType::GraphicsContext];
+
+/// The attribute of an object that holds an invalid reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    /// The name of the attribute, as used in the object structs
+    pub name: &'static str,
+    /// The position in the list, for attributes that hold a list of references
+    pub index: Option<usize>,
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{}[{}]", self.name, index),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The pool does not contain a Working Set object
+    MissingWorkingSet,
+    /// The pool contains more than one Working Set object
+    MultipleWorkingSets(Vec<ObjectId>),
+    /// More than one object in the pool uses this ID
+    DuplicateObjectId(ObjectId),
+    /// A reference points at an object that is not in the pool
+    MissingObject {
+        object: ObjectId,
+        field: Field,
+        reference: ObjectId,
+    },
+    /// A reference points at an object of a type that is not allowed in that place
+    InvalidObjectType {
+        object: ObjectId,
+        field: Field,
+        reference: ObjectId,
+        object_type: ObjectType,
+    },
+    /// A macro reference points at a macro that is not in the pool
+    MissingMacro {
+        object: ObjectId,
+        field: Field,
+        macro_id: u8,
+    },
+    /// A fill attributes object fills with a pattern, but does not reference one
+    MissingFillPattern(ObjectId),
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::MissingWorkingSet => write!(f, "The pool has no Working Set object"),
+            ValidationError::MultipleWorkingSets(ids) => {
+                write!(f, "The pool has {} Working Set objects", ids.len())
+            }
+            ValidationError::DuplicateObjectId(id) => {
+                write!(f, "Object ID {} is used more than once", u16::from(*id))
+            }
+            ValidationError::MissingObject {
+                object,
+                field,
+                reference,
+            } => write!(
+                f,
+                "{} of object {} references object {}, which does not exist",
+                field,
+                u16::from(*object),
+                u16::from(*reference)
+            ),
+            ValidationError::InvalidObjectType {
+                object,
+                field,
+                reference,
+                object_type,
+            } => write!(
+                f,
+                "{} of object {} references object {}, a {:?} is not allowed there",
+                field,
+                u16::from(*object),
+                u16::from(*reference),
+                object_type
+            ),
+            ValidationError::MissingMacro {
+                object,
+                field,
+                macro_id,
+            } => write!(
+                f,
+                "{} of object {} references macro {}, which does not exist",
+                field,
+                u16::from(*object),
+                macro_id
+            ),
+            ValidationError::MissingFillPattern(id) => write!(
+                f,
+                "Object {} fills with a pattern, but has no fill pattern",
+                u16::from(*id)
+            ),
+        }
+    }
+}
+impl std::error::Error for ValidationError {}
+
+/// A reference from one object to another
+pub(super) struct Reference {
+    pub field: Field,
+    pub id: ObjectId,
+    /// The object types allowed as target, `None` if any type is allowed
+    pub allowed: Option<&'static [ObjectType]>,
+}
+
+#[derive(Default)]
+struct References(Vec<Reference>);
+
+impl References {
+    fn one(
+        &mut self,
+        name: &'static str,
+        id: impl Into<NullableObjectId>,
+        allowed: Option<&'static [ObjectType]>,
+    ) {
+        if let NullableObjectId(Some(id)) = id.into() {
+            self.0.push(Reference {
+                field: Field { name, index: None },
+                id,
+                allowed,
+            });
+        }
+    }
+
+    fn list<T: Into<NullableObjectId> + Copy>(
+        &mut self,
+        name: &'static str,
+        ids: &[T],
+        allowed: Option<&'static [ObjectType]>,
+    ) {
+        for (index, id) in ids.iter().enumerate() {
+            if let NullableObjectId(Some(id)) = (*id).into() {
+                self.0.push(Reference {
+                    field: Field {
+                        name,
+                        index: Some(index),
+                    },
+                    id,
+                    allowed,
+                });
+            }
+        }
+    }
+
+    fn object_refs(&mut self, object_refs: &[ObjectRef], allowed: &'static [ObjectType]) {
+        let ids: Vec<ObjectId> = object_refs.iter().map(|r| r.id).collect();
+        self.list("object_refs", &ids, Some(allowed));
+    }
+
+    /// Adds the references of the command at `index`, all of them are reported as `commands[index]`
+    fn command(&mut self, index: usize, command: &MacroCommand) {
+        let mut add = |id: NullableObjectId, allowed: Option<&'static [ObjectType]>| {
+            if let NullableObjectId(Some(id)) = id {
+                self.0.push(Reference {
+                    field: Field {
+                        name: "commands",
+                        index: Some(index),
+                    },
+                    id,
+                    allowed,
+                });
+            }
+        };
+        match command {
+            MacroCommand::HideShowObject { object, .. }
+            | MacroCommand::EnableDisableObject { object, .. }
+            | MacroCommand::ChangeSize { object, .. }
+            | MacroCommand::ChangeBackgroundColour { object, .. }
+            | MacroCommand::ChangeNumericValue { object, .. }
+            | MacroCommand::ChangeEndPoint { object, .. }
+            | MacroCommand::ChangeAttribute { object, .. }
+            | MacroCommand::ChangeStringValue { object, .. }
+            | MacroCommand::ChangePolygonPoint { object, .. }
+            | MacroCommand::ChangePolygonScale { object, .. } => add((*object).into(), None),
+            MacroCommand::SelectInputObject { object, .. } => add(*object, None),
+            MacroCommand::ChangeChildLocation { parent, child, .. }
+            | MacroCommand::ChangeChildPosition { parent, child, .. } => {
+                add((*parent).into(), None);
+                add((*child).into(), None);
+            }
+            MacroCommand::ChangeFontAttributes { object, .. } => {
+                add((*object).into(), Some(FONT_ATTRIBUTES))
+            }
+            MacroCommand::ChangeLineAttributes { object, .. } => {
+                add((*object).into(), Some(LINE_ATTRIBUTES))
+            }
+            MacroCommand::ChangeFillAttributes {
+                object, pattern, ..
+            } => {
+                add((*object).into(), Some(FILL_ATTRIBUTES));
+                add(*pattern, Some(PICTURE_GRAPHIC));
+            }
+            MacroCommand::ChangeActiveMask { working_set, mask } => {
+                add((*working_set).into(), Some(WORKING_SET));
+                add((*mask).into(), Some(MASKS));
+            }
+            MacroCommand::ChangeSoftKeyMask {
+                mask,
+                soft_key_mask,
+                ..
+            } => {
+                add((*mask).into(), Some(MASKS));
+                add(*soft_key_mask, Some(SOFT_KEY_MASK));
+            }
+            MacroCommand::ChangePriority { object, .. } => add((*object).into(), Some(ALARM_MASK)),
+            MacroCommand::ChangeListItem {
+                object, new_object, ..
+            } => {
+                add((*object).into(), None);
+                add(*new_object, None);
+            }
+            MacroCommand::ChangeObjectLabel {
+                object,
+                string_variable,
+                graphic,
+                ..
+            } => {
+                add((*object).into(), None);
+                add(*string_variable, Some(STRING_VARIABLE));
+                add(*graphic, None);
+            }
+            MacroCommand::GraphicsContext { object, command } => {
+                add((*object).into(), Some(GRAPHICS_CONTEXT));
+                match command {
+                    GraphicsContextCommand::SetLineAttributesObjectId { object } => {
+                        add(*object, Some(LINE_ATTRIBUTES))
+                    }
+                    GraphicsContextCommand::SetFillAttributesObjectId { object } => {
+                        add(*object, Some(FILL_ATTRIBUTES))
+                    }
+                    GraphicsContextCommand::SetFontAttributesObjectId { object } => {
+                        add(*object, Some(FONT_ATTRIBUTES))
+                    }
+                    GraphicsContextCommand::DrawVtObject { object } => add((*object).into(), None),
+                    GraphicsContextCommand::CopyCanvasToPictureGraphic { object }
+                    | GraphicsContextCommand::CopyViewportToPictureGraphic { object } => {
+                        add((*object).into(), Some(PICTURE_GRAPHIC))
+                    }
+                    _ => {}
+                }
+            }
+            MacroCommand::SelectColourMap { object } => add((*object).into(), Some(COLOUR_MAP)),
+            MacroCommand::ExecuteExtendedMacro { object } => add((*object).into(), Some(MACRO)),
+            MacroCommand::LockUnlockMask { mask, .. } => add((*mask).into(), Some(DATA_MASK)),
+            MacroCommand::ControlAudioSignal { .. }
+            | MacroCommand::SetAudioVolume { .. }
+            | MacroCommand::ExecuteMacro { .. }
+            | MacroCommand::Unknown(_) => {}
+        }
+    }
+}
+
+/// Returns every object reference held by `object`
+pub(super) fn references(object: &Object) -> Vec<Reference> {
+    let mut r = References::default();
+    match object {
+        Object::WorkingSet(o) => {
+            r.one("active_mask", o.active_mask, Some(MASKS));
+            r.object_refs(&o.object_refs, OUTPUT_OBJECTS);
+        }
+        Object::DataMask(o) => {
+            r.one("soft_key_mask", o.soft_key_mask, Some(SOFT_KEY_MASK));
+            r.object_refs(&o.object_refs, MASK_OBJECTS);
+        }
+        Object::AlarmMask(o) => {
+            r.one("soft_key_mask", o.soft_key_mask, Some(SOFT_KEY_MASK));
+            r.object_refs(&o.object_refs, MASK_OBJECTS);
+        }
+        Object::Container(o) => r.object_refs(&o.object_refs, MASK_OBJECTS),
+        Object::SoftKeyMask(o) => r.list("objects", &o.objects, Some(KEYS)),
+        Object::Key(o) => r.object_refs(&o.object_refs, OUTPUT_OBJECTS),
+        Object::Button(o) => r.object_refs(&o.object_refs, OUTPUT_OBJECTS),
+        Object::InputBoolean(o) => {
+            r.one(
+                "foreground_colour",
+                o.foreground_colour,
+                Some(FONT_ATTRIBUTES),
+            );
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+        }
+        Object::InputString(o) => {
+            r.one("font_attributes", o.font_attributes, Some(FONT_ATTRIBUTES));
+            r.one(
+                "input_attributes",
+                o.input_attributes,
+                Some(INPUT_ATTRIBUTES),
+            );
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(STRING_VARIABLE),
+            );
+        }
+        Object::InputNumber(o) => {
+            r.one("font_attributes", o.font_attributes, Some(FONT_ATTRIBUTES));
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+        }
+        Object::InputList(o) => {
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+            r.list("list_items", &o.list_items, Some(OUTPUT_OBJECTS));
+        }
+        Object::OutputString(o) => {
+            r.one("font_attributes", o.font_attributes, Some(FONT_ATTRIBUTES));
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(STRING_VARIABLE),
+            );
+        }
+        Object::OutputNumber(o) => {
+            r.one("font_attributes", o.font_attributes, Some(FONT_ATTRIBUTES));
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+        }
+        Object::OutputList(o) => {
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+            r.list("list_items", &o.list_items, Some(OUTPUT_OBJECTS));
+        }
+        Object::OutputLine(o) => {
+            r.one("line_attributes", o.line_attributes, Some(LINE_ATTRIBUTES));
+        }
+        Object::OutputRectangle(o) => {
+            r.one("line_attributes", o.line_attributes, Some(LINE_ATTRIBUTES));
+            r.one("fill_attributes", o.fill_attributes, Some(FILL_ATTRIBUTES));
+        }
+        Object::OutputEllipse(o) => {
+            r.one("line_attributes", o.line_attributes, Some(LINE_ATTRIBUTES));
+            r.one("fill_attributes", o.fill_attributes, Some(FILL_ATTRIBUTES));
+        }
+        Object::OutputPolygon(o) => {
+            r.one("line_attributes", o.line_attributes, Some(LINE_ATTRIBUTES));
+            r.one("fill_attributes", o.fill_attributes, Some(FILL_ATTRIBUTES));
+        }
+        Object::OutputMeter(o) => {
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+        }
+        Object::OutputLinearBarGraph(o) => {
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+            r.one(
+                "target_value_variable_reference",
+                o.target_value_variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+        }
+        Object::OutputArchedBarGraph(o) => {
+            r.one(
+                "variable_reference",
+                o.variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+            r.one(
+                "target_value_variable_reference",
+                o.target_value_variable_reference,
+                Some(NUMBER_VARIABLE),
+            );
+        }
+        Object::FillAttributes(o) => {
+            r.one("fill_pattern", o.fill_pattern, Some(PICTURE_GRAPHIC));
+        }
+        Object::ObjectPointer(o) => r.one("value", o.value, Some(POINTER_TARGETS)),
+        Object::AuxiliaryFunctionType1(o) => r.object_refs(&o.object_refs, OUTPUT_OBJECTS),
+        Object::AuxiliaryInputType1(o) => r.object_refs(&o.object_refs, OUTPUT_OBJECTS),
+        Object::AuxiliaryFunctionType2(o) => r.object_refs(&o.object_refs, OUTPUT_OBJECTS),
+        Object::AuxiliaryInputType2(o) => r.object_refs(&o.object_refs, OUTPUT_OBJECTS),
+        Object::AuxiliaryControlDesignatorType2(o) => {
+            // Only pointer type 0 references an auxiliary object of this pool directly
+            if o.pointer_type == 0 {
+                r.one(
+                    "auxiliary_object_id",
+                    o.auxiliary_object_id,
+                    Some(AUXILIARY_OBJECTS),
+                );
+            }
+        }
+        Object::WindowMask(o) => {
+            r.one("name", o.name, Some(STRING_LABEL));
+            r.one("window_title", o.window_title, Some(STRING_LABEL));
+            r.one("window_icon", o.window_icon, Some(OUTPUT_OBJECTS));
+            r.list("objects", &o.objects, Some(MASK_OBJECTS));
+            r.object_refs(&o.object_refs, MASK_OBJECTS);
+        }
+        Object::KeyGroup(o) => {
+            r.one("name", o.name, Some(STRING_LABEL));
+            r.one("key_group_icon", o.key_group_icon, Some(OUTPUT_OBJECTS));
+            r.list("objects", &o.objects, Some(KEYS));
+        }
+        Object::GraphicsContext(o) => {
+            r.one(
+                "font_attributes_object",
+                o.font_attributes_object,
+                Some(FONT_ATTRIBUTES),
+            );
+            r.one(
+                "line_attributes_object",
+                o.line_attributes_object,
+                Some(LINE_ATTRIBUTES),
+            );
+            r.one(
+                "fill_attributes_object",
+                o.fill_attributes_object,
+                Some(FILL_ATTRIBUTES),
+            );
+        }
+        Object::ObjectLabelReferenceList(o) => {
+            for (index, label) in o.object_labels.iter().enumerate() {
+                let field = |name| Field {
+                    name,
+                    index: Some(index),
+                };
+                r.0.push(Reference {
+                    field: field("object_labels.id"),
+                    id: label.id,
+                    allowed: None,
+                });
+                if let NullableObjectId(Some(id)) = label.string_variable_reference {
+                    r.0.push(Reference {
+                        field: field("object_labels.string_variable_reference"),
+                        id,
+                        allowed: Some(STRING_VARIABLE),
+                    });
+                }
+                if let NullableObjectId(Some(id)) = label.graphic_representation {
+                    r.0.push(Reference {
+                        field: field("object_labels.graphic_representation"),
+                        id,
+                        allowed: Some(OUTPUT_OBJECTS),
+                    });
+                }
+            }
+        }
+        Object::ExternalObjectDefinition(o) => r.list("objects", &o.objects, None),
+        Object::ExternalObjectPointer(o) => {
+            r.one(
+                "default_object_id",
+                o.default_object_id,
+                Some(POINTER_TARGETS),
+            );
+            r.one(
+                "external_reference_name_id",
+                o.external_reference_name_id,
+                Some(EXTERNAL_REFERENCE_NAME),
+            );
+            // The external object lives in the pool of another working set
+        }
+        Object::Animation(o) => r.object_refs(&o.object_refs, MASK_OBJECTS),
+        Object::WorkingSetSpecialControls(o) => {
+            r.one("id_of_colour_map", o.id_of_colour_map, Some(COLOUR_MAP));
+            r.one(
+                "id_of_colour_palette",
+                o.id_of_colour_palette,
+                Some(COLOUR_PALETTE),
+            );
+        }
+        Object::ScaledGraphic(o) => r.one("value", o.value, Some(SCALABLE_GRAPHICS)),
+        Object::Macro(o) => {
+            for (index, command) in o.commands.iter().enumerate() {
+                r.command(index, command);
+            }
+        }
+        Object::PictureGraphic(_)
+        | Object::NumberVariable(_)
+        | Object::StringVariable(_)
+        | Object::FontAttributes(_)
+        | Object::LineAttributes(_)
+        | Object::InputAttributes(_)
+        | Object::ExtendedInputAttributes(_)
+        | Object::ColourMap(_)
+        | Object::ExternalReferenceName(_)
+        | Object::ColourPalette(_)
+        | Object::GraphicData(_) => {}
+    }
+    r.0
+}
+
+/// Replaces every object reference held by `object` with the result of `f`
+///
+/// If `f` returns `None` the reference is removed: NULL references are set to NULL and references
+/// in lists are removed from the list, except for lists where the position of an entry matters.
+/// Those entries are set to NULL instead. Macro commands that act on a removed object are removed
+/// from the macro. Other references that cannot be NULL are left unchanged.
+pub(super) fn map_references<F>(object: &mut Object, mut f: F)
+where
+    F: FnMut(ObjectId) -> Option<ObjectId>,
+{
+    let f = &mut f;
+    match object {
+        Object::WorkingSet(o) => {
+            map_required(&mut o.active_mask, f);
+            map_object_refs(&mut o.object_refs, f);
+        }
+        Object::DataMask(o) => {
+            map_one(&mut o.soft_key_mask, f);
+            map_object_refs(&mut o.object_refs, f);
+        }
+        Object::AlarmMask(o) => {
+            map_one(&mut o.soft_key_mask, f);
+            map_object_refs(&mut o.object_refs, f);
+        }
+        Object::Container(o) => map_object_refs(&mut o.object_refs, f),
+        Object::SoftKeyMask(o) => map_list(&mut o.objects, f),
+        Object::Key(o) => map_object_refs(&mut o.object_refs, f),
+        Object::Button(o) => map_object_refs(&mut o.object_refs, f),
+        Object::InputBoolean(o) => {
+            map_required(&mut o.foreground_colour, f);
+            map_one(&mut o.variable_reference, f);
+        }
+        Object::InputString(o) => {
+            map_required(&mut o.font_attributes, f);
+            map_one(&mut o.input_attributes, f);
+            map_one(&mut o.variable_reference, f);
+        }
+        Object::InputNumber(o) => {
+            map_required(&mut o.font_attributes, f);
+            map_one(&mut o.variable_reference, f);
+        }
+        Object::InputList(o) => {
+            map_one(&mut o.variable_reference, f);
+            map_nullable_list(&mut o.list_items, f);
+        }
+        Object::OutputString(o) => {
+            map_required(&mut o.font_attributes, f);
+            map_one(&mut o.variable_reference, f);
+        }
+        Object::OutputNumber(o) => {
+            map_required(&mut o.font_attributes, f);
+            map_one(&mut o.variable_reference, f);
+        }
+        Object::OutputList(o) => {
+            map_one(&mut o.variable_reference, f);
+            map_nullable_list(&mut o.list_items, f);
+        }
+        Object::OutputLine(o) => {
+            map_required(&mut o.line_attributes, f);
+        }
+        Object::OutputRectangle(o) => {
+            map_required(&mut o.line_attributes, f);
+            map_one(&mut o.fill_attributes, f);
+        }
+        Object::OutputEllipse(o) => {
+            map_required(&mut o.line_attributes, f);
+            map_one(&mut o.fill_attributes, f);
+        }
+        Object::OutputPolygon(o) => {
+            map_required(&mut o.line_attributes, f);
+            map_one(&mut o.fill_attributes, f);
+        }
+        Object::OutputMeter(o) => map_one(&mut o.variable_reference, f),
+        Object::OutputLinearBarGraph(o) => {
+            map_one(&mut o.variable_reference, f);
+            map_one(&mut o.target_value_variable_reference, f);
+        }
+        Object::OutputArchedBarGraph(o) => {
+            map_one(&mut o.variable_reference, f);
+            map_one(&mut o.target_value_variable_reference, f);
+        }
+        Object::FillAttributes(o) => map_one(&mut o.fill_pattern, f),
+        Object::ObjectPointer(o) => map_one(&mut o.value, f),
+        Object::AuxiliaryFunctionType1(o) => map_object_refs(&mut o.object_refs, f),
+        Object::AuxiliaryInputType1(o) => map_object_refs(&mut o.object_refs, f),
+        Object::AuxiliaryFunctionType2(o) => map_object_refs(&mut o.object_refs, f),
+        Object::AuxiliaryInputType2(o) => map_object_refs(&mut o.object_refs, f),
+        Object::AuxiliaryControlDesignatorType2(o) => {
+            if o.pointer_type == 0 {
+                map_one(&mut o.auxiliary_object_id, f);
+            }
+        }
+        Object::WindowMask(o) => {
+            map_one(&mut o.name, f);
+            map_one(&mut o.window_title, f);
+            map_one(&mut o.window_icon, f);
+            map_nullable_list(&mut o.objects, f);
+            map_object_refs(&mut o.object_refs, f);
+        }
+        Object::KeyGroup(o) => {
+            map_required(&mut o.name, f);
+            map_one(&mut o.key_group_icon, f);
+            map_list(&mut o.objects, f);
+        }
+        Object::GraphicsContext(o) => {
+            map_one(&mut o.font_attributes_object, f);
+            map_one(&mut o.line_attributes_object, f);
+            map_one(&mut o.fill_attributes_object, f);
+        }
+        Object::ObjectLabelReferenceList(o) => {
+            o.object_labels.retain_mut(|label| match f(label.id) {
+                Some(id) => {
+                    label.id = id;
+                    map_one(&mut label.string_variable_reference, f);
+                    map_one(&mut label.graphic_representation, f);
+                    true
+                }
+                None => false,
+            });
+        }
+        Object::ExternalObjectDefinition(o) => map_list(&mut o.objects, f),
+        Object::ExternalObjectPointer(o) => {
+            map_one(&mut o.default_object_id, f);
+            map_one(&mut o.external_reference_name_id, f);
+        }
+        Object::Animation(o) => map_object_refs(&mut o.object_refs, f),
+        Object::WorkingSetSpecialControls(o) => {
+            map_one(&mut o.id_of_colour_map, f);
+            map_one(&mut o.id_of_colour_palette, f);
+        }
+        Object::ScaledGraphic(o) => map_one(&mut o.value, f),
+        Object::Macro(o) => o.commands.retain_mut(|command| map_command(command, f)),
+        Object::PictureGraphic(_)
+        | Object::NumberVariable(_)
+        | Object::StringVariable(_)
+        | Object::FontAttributes(_)
+        | Object::LineAttributes(_)
+        | Object::InputAttributes(_)
+        | Object::ExtendedInputAttributes(_)
+        | Object::ColourMap(_)
+        | Object::ExternalReferenceName(_)
+        | Object::ColourPalette(_)
+        | Object::GraphicData(_) => {}
+    }
+}
+
+type MapFn<'a> = &'a mut dyn FnMut(ObjectId) -> Option<ObjectId>;
+
+fn map_one(id: &mut NullableObjectId, f: MapFn) {
+    if let Some(old) = id.0 {
+        id.0 = f(old);
+    }
+}
+
+/// Returns `false` if `f` removed the reference, which is then left unchanged
+fn map_required(id: &mut ObjectId, f: MapFn) -> bool {
+    match f(*id) {
+        Some(new) => {
+            *id = new;
+            true
+        }
+        None => false,
+    }
+}
+
+fn map_list(ids: &mut Vec<ObjectId>, f: MapFn) {
+    ids.retain_mut(|id| match f(*id) {
+        Some(new) => {
+            *id = new;
+            true
+        }
+        None => false,
+    });
+}
+
+fn map_nullable_list(ids: &mut [NullableObjectId], f: MapFn) {
+    for id in ids {
+        map_one(id, f);
+    }
+}
+
+/// Returns `false` if `f` removed one of the objects the command requires
+fn map_command(command: &mut MacroCommand, f: MapFn) -> bool {
+    match command {
+        MacroCommand::HideShowObject { object, .. }
+        | MacroCommand::EnableDisableObject { object, .. }
+        | MacroCommand::ChangeSize { object, .. }
+        | MacroCommand::ChangeBackgroundColour { object, .. }
+        | MacroCommand::ChangeNumericValue { object, .. }
+        | MacroCommand::ChangeEndPoint { object, .. }
+        | MacroCommand::ChangeFontAttributes { object, .. }
+        | MacroCommand::ChangeLineAttributes { object, .. }
+        | MacroCommand::ChangeAttribute { object, .. }
+        | MacroCommand::ChangePriority { object, .. }
+        | MacroCommand::ChangeStringValue { object, .. }
+        | MacroCommand::ChangePolygonPoint { object, .. }
+        | MacroCommand::ChangePolygonScale { object, .. }
+        | MacroCommand::SelectColourMap { object }
+        | MacroCommand::ExecuteExtendedMacro { object } => map_required(object, f),
+        MacroCommand::SelectInputObject { object, .. } => {
+            map_one(object, f);
+            true
+        }
+        MacroCommand::ChangeChildLocation { parent, child, .. }
+        | MacroCommand::ChangeChildPosition { parent, child, .. } => {
+            map_required(parent, f) & map_required(child, f)
+        }
+        MacroCommand::ChangeFillAttributes {
+            object, pattern, ..
+        } => {
+            map_one(pattern, f);
+            map_required(object, f)
+        }
+        MacroCommand::ChangeActiveMask { working_set, mask } => {
+            map_required(working_set, f) & map_required(mask, f)
+        }
+        MacroCommand::ChangeSoftKeyMask {
+            mask,
+            soft_key_mask,
+            ..
+        } => {
+            map_one(soft_key_mask, f);
+            map_required(mask, f)
+        }
+        MacroCommand::ChangeListItem {
+            object, new_object, ..
+        } => {
+            map_one(new_object, f);
+            map_required(object, f)
+        }
+        MacroCommand::ChangeObjectLabel {
+            object,
+            string_variable,
+            graphic,
+            ..
+        } => {
+            map_one(string_variable, f);
+            map_one(graphic, f);
+            map_required(object, f)
+        }
+        MacroCommand::GraphicsContext { object, command } => {
+            let command = match command {
+                GraphicsContextCommand::SetLineAttributesObjectId { object }
+                | GraphicsContextCommand::SetFillAttributesObjectId { object }
+                | GraphicsContextCommand::SetFontAttributesObjectId { object } => {
+                    map_one(object, f);
+                    true
+                }
+                GraphicsContextCommand::DrawVtObject { object }
+                | GraphicsContextCommand::CopyCanvasToPictureGraphic { object }
+                | GraphicsContextCommand::CopyViewportToPictureGraphic { object } => {
+                    map_required(object, f)
+                }
+                _ => true,
+            };
+            map_required(object, f) & command
+        }
+        MacroCommand::LockUnlockMask { mask, .. } => map_required(mask, f),
+        MacroCommand::ControlAudioSignal { .. }
+        | MacroCommand::SetAudioVolume { .. }
+        | MacroCommand::ExecuteMacro { .. }
+        | MacroCommand::Unknown(_) => true,
+    }
+}
+
+fn map_object_refs(object_refs: &mut Vec<ObjectRef>, f: MapFn) {
+    object_refs.retain_mut(|r| match f(r.id) {
+        Some(new) => {
+            r.id = new;
+            true
+        }
+        None => false,
+    });
+}
+
+/// Returns the macros `object` references, with the field that references them
+pub(super) fn macro_ids(object: &Object) -> Vec<(Field, u8)> {
+    let macro_refs = match object {
+        Object::WorkingSet(o) => &o.macro_refs,
+        Object::DataMask(o) => &o.macro_refs,
+        Object::AlarmMask(o) => &o.macro_refs,
+        Object::Container(o) => &o.macro_refs,
+        Object::SoftKeyMask(o) => &o.macro_refs,
+        Object::Key(o) => &o.macro_refs,
+        Object::Button(o) => &o.macro_refs,
+        Object::InputBoolean(o) => &o.macro_refs,
+        Object::InputString(o) => &o.macro_refs,
+        Object::InputNumber(o) => &o.macro_refs,
+        Object::InputList(o) => &o.macro_refs,
+        Object::OutputString(o) => &o.macro_refs,
+        Object::OutputNumber(o) => &o.macro_refs,
+        Object::OutputList(o) => &o.macro_refs,
+        Object::OutputLine(o) => &o.macro_refs,
+        Object::OutputRectangle(o) => &o.macro_refs,
+        Object::OutputEllipse(o) => &o.macro_refs,
+        Object::OutputPolygon(o) => &o.macro_refs,
+        Object::OutputMeter(o) => &o.macro_refs,
+        Object::OutputLinearBarGraph(o) => &o.macro_refs,
+        Object::OutputArchedBarGraph(o) => &o.macro_refs,
+        Object::PictureGraphic(o) => &o.macro_refs,
+        Object::FontAttributes(o) => &o.macro_refs,
+        Object::LineAttributes(o) => &o.macro_refs,
+        Object::FillAttributes(o) => &o.macro_refs,
+        Object::InputAttributes(o) => &o.macro_refs,
+        Object::WindowMask(o) => &o.macro_refs,
+        Object::KeyGroup(o) => &o.macro_refs,
+        Object::Animation(o) => &o.macro_refs,
+        Object::ScaledGraphic(o) => &o.macro_refs,
+        Object::Macro(o) => {
+            return o
+                .commands
+                .iter()
+                .enumerate()
+                .filter_map(|(index, command)| match command {
+                    MacroCommand::ExecuteMacro { macro_id } => Some((
+                        Field {
+                            name: "commands",
+                            index: Some(index),
+                        },
+                        *macro_id,
+                    )),
+                    _ => None,
+                })
+                .collect()
+        }
+        _ => return vec![],
+    };
+    macro_refs
+        .iter()
+        .enumerate()
+        .map(|(index, m)| {
+            let field = Field {
+                name: "macro_refs",
+                index: Some(index),
+            };
+            (field, m.macro_id)
+        })
+        .collect()
+}
+
+/// Replaces every macro ID `object` references with the result of `f`
+pub(super) fn map_macro_ids<F>(object: &mut Object, mut f: F)
+where
+    F: FnMut(u8) -> u8,
+{
+    let macro_refs = match object {
+        Object::WorkingSet(o) => &mut o.macro_refs,
+        Object::DataMask(o) => &mut o.macro_refs,
+        Object::AlarmMask(o) => &mut o.macro_refs,
+        Object::Container(o) => &mut o.macro_refs,
+        Object::SoftKeyMask(o) => &mut o.macro_refs,
+        Object::Key(o) => &mut o.macro_refs,
+        Object::Button(o) => &mut o.macro_refs,
+        Object::InputBoolean(o) => &mut o.macro_refs,
+        Object::InputString(o) => &mut o.macro_refs,
+        Object::InputNumber(o) => &mut o.macro_refs,
+        Object::InputList(o) => &mut o.macro_refs,
+        Object::OutputString(o) => &mut o.macro_refs,
+        Object::OutputNumber(o) => &mut o.macro_refs,
+        Object::OutputList(o) => &mut o.macro_refs,
+        Object::OutputLine(o) => &mut o.macro_refs,
+        Object::OutputRectangle(o) => &mut o.macro_refs,
+        Object::OutputEllipse(o) => &mut o.macro_refs,
+        Object::OutputPolygon(o) => &mut o.macro_refs,
+        Object::OutputMeter(o) => &mut o.macro_refs,
+        Object::OutputLinearBarGraph(o) => &mut o.macro_refs,
+        Object::OutputArchedBarGraph(o) => &mut o.macro_refs,
+        Object::PictureGraphic(o) => &mut o.macro_refs,
+        Object::FontAttributes(o) => &mut o.macro_refs,
+        Object::LineAttributes(o) => &mut o.macro_refs,
+        Object::FillAttributes(o) => &mut o.macro_refs,
+        Object::InputAttributes(o) => &mut o.macro_refs,
+        Object::WindowMask(o) => &mut o.macro_refs,
+        Object::KeyGroup(o) => &mut o.macro_refs,
+        Object::Animation(o) => &mut o.macro_refs,
+        Object::ScaledGraphic(o) => &mut o.macro_refs,
+        Object::Macro(o) => {
+            for command in &mut o.commands {
+                if let MacroCommand::ExecuteMacro { macro_id } = command {
+                    *macro_id = f(*macro_id);
+                }
+            }
+            return;
+        }
+        _ => return,
+    };
+    for macro_ref in macro_refs {
+        macro_ref.macro_id = f(macro_ref.macro_id);
+    }
+}
+
+impl ObjectPool {
+    /// Checks the pool for problems a VT would reject it for
+    ///
+    /// This checks that there is exactly one Working Set object, that every object ID is unique
+    /// and that every reference points at an existing object of a type allowed in that place, as
+    /// listed in ISO 11783-6 Annex B. All problems found are returned, not just the first one.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        let working_sets: Vec<ObjectId> = self
+            .objects_by_type(ObjectType::WorkingSet)
+            .iter()
+            .map(|o| o.id())
+            .collect();
+        match working_sets.len() {
+            0 => errors.push(ValidationError::MissingWorkingSet),
+            1 => {}
+            _ => errors.push(ValidationError::MultipleWorkingSets(working_sets)),
+        }
+
+        let mut types = HashMap::new();
+        for object in self.objects() {
+            if types.insert(object.id(), object.object_type()).is_some() {
+                let error = ValidationError::DuplicateObjectId(object.id());
+                if !errors.contains(&error) {
+                    errors.push(error);
+                }
+            }
+        }
+
+        for object in self.objects() {
+            for reference in references(object) {
+                match types.get(&reference.id) {
+                    None => errors.push(ValidationError::MissingObject {
+                        object: object.id(),
+                        field: reference.field,
+                        reference: reference.id,
+                    }),
+                    Some(object_type) => {
+                        if let Some(allowed) = reference.allowed {
+                            if !allowed.contains(object_type) {
+                                errors.push(ValidationError::InvalidObjectType {
+                                    object: object.id(),
+                                    field: reference.field,
+                                    reference: reference.id,
+                                    object_type: *object_type,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+
+            for (field, macro_id) in macro_ids(object) {
+                let is_macro = ObjectId::new(macro_id as u16)
+                    .map(|id| types.get(&id) == Some(&ObjectType::Macro))
+                    .unwrap_or(false);
+                if !is_macro {
+                    errors.push(ValidationError::MissingMacro {
+                        object: object.id(),
+                        field,
+                        macro_id,
+                    });
+                }
+            }
+
+            if let Object::FillAttributes(o) = object {
+                if o.fill_type == FillType::Pattern && o.fill_pattern.0.is_none() {
+                    errors.push(ValidationError::MissingFillPattern(o.id));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::object::{
+        DataMask, Macro, ObjectPointer, OutputLine, SoftKeyMask, WorkingSet,
+    };
+    use crate::object_pool::object_attributes::{LineDirection, MacroRef, Point};
+    use crate::object_pool::Colour;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    fn working_set(object_id: u16, active_mask: u16) -> Object {
+        Object::WorkingSet(WorkingSet {
+            id: id(object_id),
+            background_colour: Colour::WHITE,
+            selectable: true,
+            active_mask: id(active_mask),
+            object_refs: vec![],
+            macro_refs: vec![],
+            language_codes: vec![],
+        })
+    }
+
+    fn data_mask(object_id: u16, soft_key_mask: NullableObjectId, children: &[u16]) -> Object {
+        Object::DataMask(DataMask {
+            id: id(object_id),
+            background_colour: 1,
+            soft_key_mask,
+            object_refs: children
+                .iter()
+                .map(|c| ObjectRef {
+                    id: id(*c),
+                    offset: Point { x: 0, y: 0 },
+                })
+                .collect(),
+            macro_refs: vec![],
+        })
+    }
+
+    fn pool(objects: Vec<Object>) -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        for object in objects {
+            pool.add(object);
+        }
+        pool
+    }
+
+    #[test]
+    fn test_valid_pool() {
+        let pool = pool(vec![
+            working_set(0, 1000),
+            data_mask(1000, NullableObjectId::NULL, &[]),
+        ]);
+        assert_eq!(pool.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_working_sets() {
+        let pool = pool(vec![data_mask(1000, NullableObjectId::NULL, &[])]);
+        assert_eq!(
+            pool.validate(),
+            Err(vec![ValidationError::MissingWorkingSet])
+        );
+
+        let pool = super::tests::pool(vec![
+            working_set(0, 1000),
+            working_set(1, 1000),
+            data_mask(1000, NullableObjectId::NULL, &[]),
+        ]);
+        assert_eq!(
+            pool.validate(),
+            Err(vec![ValidationError::MultipleWorkingSets(vec![
+                id(0),
+                id(1)
+            ])])
+        );
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let pool = pool(vec![
+            working_set(0, 1000),
+            data_mask(1000, NullableObjectId::NULL, &[]),
+            data_mask(1000, NullableObjectId::NULL, &[]),
+            data_mask(1000, NullableObjectId::NULL, &[]),
+        ]);
+        assert_eq!(
+            pool.validate(),
+            Err(vec![ValidationError::DuplicateObjectId(id(1000))])
+        );
+    }
+
+    #[test]
+    fn test_fill_pattern() {
+        use crate::object_pool::{FillAttributes, ObjectBuilder};
+
+        let fill = FillAttributes::builder().fill_type(FillType::Pattern);
+        let pool = pool(vec![
+            working_set(0, 1000),
+            data_mask(1000, NullableObjectId::NULL, &[]),
+            fill.build(id(2000)),
+        ]);
+        assert_eq!(
+            pool.validate(),
+            Err(vec![ValidationError::MissingFillPattern(id(2000))])
+        );
+    }
+
+    #[test]
+    fn test_reference_errors() {
+        let pool = pool(vec![
+            working_set(0, 1000),
+            data_mask(1000, id(2000).into(), &[3000, 4000]),
+            Object::SoftKeyMask(SoftKeyMask {
+                id: id(2000),
+                background_colour: 0,
+                objects: vec![id(3000)],
+                macro_refs: vec![],
+            }),
+            Object::ObjectPointer(ObjectPointer {
+                id: id(3000),
+                value: NullableObjectId::NULL,
+            }),
+            Object::OutputLine(OutputLine {
+                id: id(4000),
+                line_attributes: id(2000),
+                width: 10,
+                height: 10,
+                line_direction: LineDirection::TopLeftToBottomRight,
+                macro_refs: vec![MacroRef {
+                    macro_id: 7,
+                    event_id: 1,
+                }],
+            }),
+        ]);
+
+        assert_eq!(
+            pool.validate(),
+            Err(vec![
+                ValidationError::InvalidObjectType {
+                    object: id(4000),
+                    field: Field {
+                        name: "line_attributes",
+                        index: None
+                    },
+                    reference: id(2000),
+                    object_type: ObjectType::SoftKeyMask,
+                },
+                ValidationError::MissingMacro {
+                    object: id(4000),
+                    field: Field {
+                        name: "macro_refs",
+                        index: Some(0)
+                    },
+                    macro_id: 7,
+                },
+            ])
+        );
+
+        let pool = super::tests::pool(vec![
+            working_set(0, 1000),
+            data_mask(1000, id(2000).into(), &[1000]),
+            Object::Macro(Macro {
+                id: id(7),
+                commands: vec![],
+            }),
+        ]);
+        let errors = pool.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::MissingObject {
+                    object: id(1000),
+                    field: Field {
+                        name: "soft_key_mask",
+                        index: None
+                    },
+                    reference: id(2000),
+                },
+                ValidationError::InvalidObjectType {
+                    object: id(1000),
+                    field: Field {
+                        name: "object_refs",
+                        index: Some(0)
+                    },
+                    reference: id(1000),
+                    object_type: ObjectType::DataMask,
+                },
+            ]
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "object_refs[0] of object 1000 references object 1000, a DataMask is not allowed there"
+        );
+    }
+
+    #[test]
+    fn test_macro_command_references() {
+        let pool = pool(vec![
+            working_set(0, 1000),
+            data_mask(1000, NullableObjectId::NULL, &[]),
+            Object::Macro(
+                Macro::builder(id(1))
+                    .change_active_mask(id(0), id(1000))
+                    .hide_object(id(3000))
+                    .change_active_mask(id(0), id(1))
+                    .execute_macro(2)
+                    .build(),
+            ),
+        ]);
+        assert_eq!(
+            pool.validate(),
+            Err(vec![
+                ValidationError::MissingObject {
+                    object: id(1),
+                    field: Field {
+                        name: "commands",
+                        index: Some(1)
+                    },
+                    reference: id(3000),
+                },
+                ValidationError::InvalidObjectType {
+                    object: id(1),
+                    field: Field {
+                        name: "commands",
+                        index: Some(2)
+                    },
+                    reference: id(1),
+                    object_type: ObjectType::Macro,
+                },
+                ValidationError::MissingMacro {
+                    object: id(1),
+                    field: Field {
+                        name: "commands",
+                        index: Some(3)
+                    },
+                    macro_id: 2,
+                },
+            ])
+        );
+    }
+}
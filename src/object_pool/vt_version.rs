@@ -1,7 +1,8 @@
 use crate::object_pool::ParseError;
 use crate::object_pool::ParseError::UnknownObjectType;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VtVersion {
     Version0,
     Version1,
@@ -12,15 +12,18 @@ use crate::object_pool::object::{
     OutputString, PictureGraphic, ScaledGraphic, SoftKeyMask, StringVariable, WindowMask,
     WorkingSet, WorkingSetSpecialControls,
 };
-use crate::object_pool::object_attributes::{MacroRef, ObjectLabel, ObjectRef, Point};
-use crate::object_pool::object_id::ObjectId;
+use crate::object_pool::object_attributes::{FontType, MacroRef, ObjectLabel, ObjectRef, Point};
 
 impl Object {
-    pub fn write(&self) -> Vec<u8> {
+    /// Encodes the object in binary format, see ISO 11783-6 Annex B
+    ///
+    /// Strings are written in ISO 8859-1, characters outside of it are an error.
+    /// [`ObjectPool::as_iop`] encodes string values with the font type they are shown with.
+    pub fn write(&self) -> Result<Vec<u8>, EncodingError> {
         let mut data = Vec::new();
 
         match self {
-            Object::WorkingSet(o) => Self::write_working_set(&mut data, o),
+            Object::WorkingSet(o) => Self::write_working_set(&mut data, o)?,
             Object::DataMask(o) => Self::write_data_mask(&mut data, o),
             Object::AlarmMask(o) => Self::write_alarm_mask(&mut data, o),
             Object::Container(o) => Self::write_container(&mut data, o),
@@ -28,10 +31,10 @@ impl Object {
             Object::Key(o) => Self::write_key(&mut data, o),
             Object::Button(o) => Self::write_button(&mut data, o),
             Object::InputBoolean(o) => Self::write_input_boolean(&mut data, o),
-            Object::InputString(o) => Self::write_input_string(&mut data, o),
+            Object::InputString(o) => Self::write_input_string(&mut data, o)?,
             Object::InputNumber(o) => Self::write_input_number(&mut data, o),
             Object::InputList(o) => Self::write_input_list(&mut data, o),
-            Object::OutputString(o) => Self::write_output_string(&mut data, o),
+            Object::OutputString(o) => Self::write_output_string(&mut data, o)?,
             Object::OutputNumber(o) => Self::write_output_number(&mut data, o),
             Object::OutputLine(o) => Self::write_output_line(&mut data, o),
             Object::OutputRectangle(o) => Self::write_output_rectangle(&mut data, o),
@@ -42,13 +45,13 @@ impl Object {
             Object::OutputArchedBarGraph(o) => Self::write_output_arched_bar_graph(&mut data, o),
             Object::PictureGraphic(o) => Self::write_picture_graphic(&mut data, o),
             Object::NumberVariable(o) => Self::write_number_variable(&mut data, o),
-            Object::StringVariable(o) => Self::write_string_variable(&mut data, o),
+            Object::StringVariable(o) => Self::write_string_variable(&mut data, o)?,
             Object::FontAttributes(o) => Self::write_font_attributes(&mut data, o),
             Object::LineAttributes(o) => Self::write_line_attributes(&mut data, o),
             Object::FillAttributes(o) => Self::write_fill_attributes(&mut data, o),
-            Object::InputAttributes(o) => Self::write_input_attributes(&mut data, o),
+            Object::InputAttributes(o) => Self::write_input_attributes(&mut data, o)?,
             Object::ObjectPointer(o) => Self::write_object_pointer(&mut data, o),
-            Object::Macro(o) => Self::write_macro(&mut data, o),
+            Object::Macro(o) => Self::write_macro(&mut data, o)?,
             Object::AuxiliaryFunctionType1(o) => Self::write_auxiliary_function_type1(&mut data, o),
             Object::AuxiliaryInputType1(o) => Self::write_auxiliary_input_type1(&mut data, o),
             Object::AuxiliaryFunctionType2(o) => Self::write_auxiliary_function_type2(&mut data, o),
@@ -76,15 +79,15 @@ impl Object {
             Object::ColourPalette(o) => Self::write_colour_palette(&mut data, o),
             Object::GraphicData(o) => Self::write_graphic_data(&mut data, o),
             Object::WorkingSetSpecialControls(o) => {
-                Self::write_working_set_special_controls(&mut data, o)
+                Self::write_working_set_special_controls(&mut data, o)?
             }
             Object::ScaledGraphic(o) => Self::write_scaled_graphic(&mut data, o),
         }
 
-        data
+        Ok(data)
     }
 
-    fn write_objects(data: &mut Vec<u8>, objects: &Vec<ObjectId>) {
+    fn write_objects(data: &mut Vec<u8>, objects: &[impl Into<u16> + Copy]) {
         for d in objects {
             Self::write_u16(data, *d);
         }
@@ -107,10 +110,14 @@ impl Object {
             Self::write_u8(data, *d);
         }
     }
-    fn write_language_codes(data: &mut Vec<u8>, language_codes: &Vec<String>) {
+    fn write_language_codes(
+        data: &mut Vec<u8>,
+        language_codes: &Vec<String>,
+    ) -> Result<(), EncodingError> {
         for d in language_codes {
-            Self::write_string(data, d);
+            Self::write_string(data, d)?;
         }
+        Ok(())
     }
     fn write_points(data: &mut Vec<u8>, points: &Vec<Point<u16>>) {
         for d in points {
@@ -134,11 +141,15 @@ impl Object {
             Self::write_u16(data, d.graphic_representation);
         }
     }
-    fn write_language_pairs(data: &mut Vec<u8>, language_pairs: &Vec<(String, String)>) {
+    fn write_language_pairs(
+        data: &mut Vec<u8>,
+        language_pairs: &Vec<(String, String)>,
+    ) -> Result<(), EncodingError> {
         for d in language_pairs {
-            Self::write_string(data, &d.0);
-            Self::write_string(data, &d.1);
+            Self::write_string(data, &d.0)?;
+            Self::write_string(data, &d.1)?;
         }
+        Ok(())
     }
 
     fn write_character_ranges(data: &mut Vec<u8>, character_ranges: &Vec<CharacterRange>) {
@@ -182,15 +193,15 @@ impl Object {
         let val: f32 = val.into();
         data.extend(val.to_le_bytes());
     }
-    fn write_string(data: &mut Vec<u8>, val: impl Into<String>) {
-        let val: String = val.into();
-        data.extend(val.as_bytes());
+    fn write_string(data: &mut Vec<u8>, val: &str) -> Result<(), EncodingError> {
+        data.extend(FontType::Iso8859_1.encode(val)?);
+        Ok(())
     }
     fn write_name(data: &mut Vec<u8>, val: impl Into<NAME>) {
         let val: NAME = val.into();
         data.extend::<[u8; 8]>(val.into());
     }
-    fn write_working_set(data: &mut Vec<u8>, o: &WorkingSet) {
+    fn write_working_set(data: &mut Vec<u8>, o: &WorkingSet) -> Result<(), EncodingError> {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::WorkingSet);
         Self::write_u8(data, o.background_colour);
@@ -202,7 +213,7 @@ impl Object {
 
         Self::write_object_refs(data, &o.object_refs);
         Self::write_macro_refs(data, &o.macro_refs);
-        Self::write_language_codes(data, &o.language_codes);
+        Self::write_language_codes(data, &o.language_codes)
     }
     fn write_data_mask(data: &mut Vec<u8>, o: &DataMask) {
         Self::write_u16(data, o.id);
@@ -289,7 +300,7 @@ impl Object {
 
         Self::write_macro_refs(data, &o.macro_refs);
     }
-    fn write_input_string(data: &mut Vec<u8>, o: &InputString) {
+    fn write_input_string(data: &mut Vec<u8>, o: &InputString) -> Result<(), EncodingError> {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::InputString);
         Self::write_u16(data, o.width);
@@ -300,11 +311,13 @@ impl Object {
         Self::write_u8(data, o.options);
         Self::write_u16(data, o.variable_reference);
         Self::write_u8(data, o.justification);
-        Self::write_string(data, &o.value);
+        Self::write_u8(data, o.value.chars().count() as u8);
+        Self::write_string(data, &o.value)?;
         Self::write_u8(data, o.enabled);
         Self::write_u8(data, o.macro_refs.len() as u8);
 
         Self::write_macro_refs(data, &o.macro_refs);
+        Ok(())
     }
     fn write_input_number(data: &mut Vec<u8>, o: &InputNumber) {
         Self::write_u16(data, o.id);
@@ -342,7 +355,7 @@ impl Object {
         Self::write_objects(data, &o.list_items);
         Self::write_macro_refs(data, &o.macro_refs);
     }
-    fn write_output_string(data: &mut Vec<u8>, o: &OutputString) {
+    fn write_output_string(data: &mut Vec<u8>, o: &OutputString) -> Result<(), EncodingError> {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::OutputString);
         Self::write_u16(data, o.width);
@@ -352,11 +365,12 @@ impl Object {
         Self::write_u8(data, o.options);
         Self::write_u16(data, o.variable_reference);
         Self::write_u8(data, o.justification);
-        Self::write_u16(data, o.value.len() as u16);
-        Self::write_string(data, &o.value);
+        Self::write_u16(data, o.value.chars().count() as u16);
+        Self::write_string(data, &o.value)?;
         Self::write_u8(data, o.macro_refs.len() as u8);
 
         Self::write_macro_refs(data, &o.macro_refs);
+        Ok(())
     }
     fn write_output_number(data: &mut Vec<u8>, o: &OutputNumber) {
         Self::write_u16(data, o.id);
@@ -507,10 +521,11 @@ impl Object {
         Self::write_u8(data, ObjectType::NumberVariable);
         Self::write_u32(data, o.value);
     }
-    fn write_string_variable(data: &mut Vec<u8>, o: &StringVariable) {
+    fn write_string_variable(data: &mut Vec<u8>, o: &StringVariable) -> Result<(), EncodingError> {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::StringVariable);
-        Self::write_string(data, &o.value);
+        Self::write_u16(data, o.value.chars().count() as u16);
+        Self::write_string(data, &o.value)
     }
     fn write_font_attributes(data: &mut Vec<u8>, o: &FontAttributes) {
         Self::write_u16(data, o.id);
@@ -518,7 +533,7 @@ impl Object {
         Self::write_u8(data, o.font_colour);
         Self::write_u8(data, o.font_size);
         Self::write_u8(data, o.font_type);
-        Self::write_u8(data, o.font_style);
+        Self::write_u8(data, o.font_size.style(o.font_style));
         Self::write_u8(data, o.macro_refs.len() as u8);
 
         Self::write_macro_refs(data, &o.macro_refs);
@@ -543,29 +558,33 @@ impl Object {
 
         Self::write_macro_refs(data, &o.macro_refs);
     }
-    fn write_input_attributes(data: &mut Vec<u8>, o: &InputAttributes) {
+    fn write_input_attributes(
+        data: &mut Vec<u8>,
+        o: &InputAttributes,
+    ) -> Result<(), EncodingError> {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::InputAttributes);
         Self::write_u8(data, o.validation_type);
-        Self::write_string(data, &o.validation_string);
+        Self::write_u8(data, o.validation_string.chars().count() as u8);
+        Self::write_string(data, &o.validation_string)?;
         Self::write_u8(data, o.macro_refs.len() as u8);
 
         Self::write_macro_refs(data, &o.macro_refs);
+        Ok(())
     }
     fn write_object_pointer(data: &mut Vec<u8>, o: &ObjectPointer) {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::ObjectPointer);
         Self::write_u16(data, o.value);
-        Self::write_u16(data, o.id);
-        Self::write_u8(data, ObjectType::ObjectPointer);
-        Self::write_u16(data, o.value);
     }
-    fn write_macro(data: &mut Vec<u8>, o: &Macro) {
+    fn write_macro(data: &mut Vec<u8>, o: &Macro) -> Result<(), EncodingError> {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::Macro);
-        Self::write_u16(data, o.commands.len() as u16);
+        let commands = MacroCommand::write_all(&o.commands)?;
+        Self::write_u16(data, commands.len() as u16);
 
-        Self::write_bytes(data, &o.commands);
+        Self::write_bytes(data, &commands);
+        Ok(())
     }
     fn write_auxiliary_function_type1(data: &mut Vec<u8>, o: &AuxiliaryFunctionType1) {
         Self::write_u16(data, o.id);
@@ -681,7 +700,7 @@ impl Object {
     fn write_extended_input_attributes(data: &mut Vec<u8>, o: &ExtendedInputAttributes) {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::ExtendedInputAttributes);
-        Self::write_u8(data, o.validation_type as u8);
+        Self::write_u8(data, o.validation_type);
         Self::write_code_planes(data, &o.code_planes);
     }
     fn write_colour_map(data: &mut Vec<u8>, o: &ColourMap) {
@@ -754,14 +773,17 @@ impl Object {
 
         Self::write_bytes(data, &o.data);
     }
-    fn write_working_set_special_controls(data: &mut Vec<u8>, o: &WorkingSetSpecialControls) {
+    fn write_working_set_special_controls(
+        data: &mut Vec<u8>,
+        o: &WorkingSetSpecialControls,
+    ) -> Result<(), EncodingError> {
         Self::write_u16(data, o.id);
         Self::write_u8(data, ObjectType::WorkingSetSpecialControls);
         Self::write_u16(data, o.id_of_colour_map);
         Self::write_u16(data, o.id_of_colour_palette);
         Self::write_u8(data, o.language_pairs.len() as u8);
 
-        Self::write_language_pairs(data, &o.language_pairs);
+        Self::write_language_pairs(data, &o.language_pairs)
     }
     fn write_scaled_graphic(data: &mut Vec<u8>, o: &ScaledGraphic) {
         Self::write_u16(data, o.id);
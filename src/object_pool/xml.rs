@@ -0,0 +1,1792 @@
+// Copyright 2023 Raven Industries inc.
+use crate::network_management::name::NAME;
+use crate::object_pool::colour::Colour;
+use crate::object_pool::macro_command::{GraphicsContextCommand, MacroCommand};
+use crate::object_pool::object::*;
+use crate::object_pool::object_attributes::*;
+use crate::object_pool::object_id::{NullableObjectId, ObjectId};
+use crate::object_pool::ObjectPool;
+use roxmltree::{Document, Node, NodeId};
+use std::collections::{BTreeMap, HashMap};
+
+const ROOT: &str = "objectpool";
+
+/// The name of a NULL reference
+const NULL: &str = "NULL";
+
+/// Symbolic names of the objects of a pool
+///
+/// Names are used in XML object pools to reference objects, the table maps them to the object IDs
+/// the VT uses so application code can look objects up by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameTable {
+    by_name: BTreeMap<String, ObjectId>,
+    by_id: HashMap<ObjectId, String>,
+}
+
+impl NameTable {
+    pub fn new() -> Self {
+        NameTable::default()
+    }
+
+    /// Generates a name for every object of the pool from its type and ID, like `datamask_1000`
+    pub fn generate(pool: &ObjectPool) -> Self {
+        let mut names = NameTable::new();
+        for object in pool.objects() {
+            let name = format!("{}_{}", element_name(object), u16::from(object.id()));
+            names.insert(name, object.id());
+        }
+        names
+    }
+
+    /// Adds a name for an object, replacing the previous name of the object and the previous
+    /// object of the name
+    pub fn insert(&mut self, name: impl Into<String>, id: ObjectId) {
+        let name = name.into();
+        if let Some(previous) = self.by_id.insert(id, name.clone()) {
+            self.by_name.remove(&previous);
+        }
+        if let Some(previous) = self.by_name.insert(name, id) {
+            if previous != id {
+                self.by_id.remove(&previous);
+            }
+        }
+    }
+
+    /// The ID of the object with this name
+    pub fn id(&self, name: &str) -> Option<ObjectId> {
+        self.by_name.get(name).copied()
+    }
+
+    /// The name of the object with this ID
+    pub fn name(&self, id: ObjectId) -> Option<&str> {
+        self.by_id.get(&id).map(String::as_str)
+    }
+
+    /// All names with the ID of their object, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ObjectId)> {
+        self.by_name.iter().map(|(name, id)| (name.as_str(), *id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XmlError {
+    /// The document is not well-formed XML
+    Syntax(String),
+    /// The root element is not `<objectpool>`
+    InvalidRoot(String),
+    /// An element is not an object type or macro command
+    UnknownElement {
+        line: u32,
+        element: String,
+    },
+    MissingAttribute {
+        line: u32,
+        attribute: String,
+    },
+    InvalidAttribute {
+        line: u32,
+        attribute: String,
+        value: String,
+    },
+    /// A reference uses a name that no object has
+    UnknownName {
+        line: u32,
+        name: String,
+    },
+    /// Two objects have the same name
+    DuplicateName {
+        line: u32,
+        name: String,
+    },
+    /// There are no object IDs left for objects without an `id` attribute
+    NoFreeObjectId,
+}
+
+impl std::fmt::Display for XmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for XmlError {}
+
+impl ObjectPool {
+    /// Loads an object pool from XML in the vocabulary of the vt2iso pool compiler
+    ///
+    /// Every object is an element named after its type (`<datamask>`, `<outputstring>`, ...)
+    /// below the `<objectpool>` root, with the optional `name` and `id` attributes. Objects
+    /// without an `id` get the lowest free object ID. Attributes use symbolic values: colour
+    /// names, `yes`/`no`, option flags joined with `+`, font sizes like `8x12` and so on.
+    ///
+    /// Child objects are listed with `<include_object name="..." pos_x="..." pos_y="..."/>`, or
+    /// defined in place as child elements. Macros are attached with
+    /// `<include_macro name="..." event="on_activate"/>`, or defined in place with an `event`
+    /// attribute. Macro commands are `<command_...>` child elements of `<macro>`. References to
+    /// other objects use the name of the object, its numeric ID, or `NULL`.
+    ///
+    /// Returns the pool and the names of its objects.
+    pub fn from_xml(xml: &str) -> Result<(ObjectPool, NameTable), XmlError> {
+        let document = Document::parse(xml).map_err(|e| XmlError::Syntax(e.to_string()))?;
+        let root = document.root_element();
+        if root.tag_name().name() != ROOT {
+            return Err(XmlError::InvalidRoot(root.tag_name().name().to_string()));
+        }
+        let mut elements: Vec<Node> = Vec::new();
+        for child in root.children().filter(Node::is_element) {
+            collect_objects(child, &mut elements)?;
+        }
+
+        // Assign the IDs first, so references can point to objects further down
+        let mut ids = Vec::with_capacity(elements.len());
+        for element in &elements {
+            match element.attribute("id") {
+                Some(_) => ids.push(Some(parse::<u16>(element, "id")?)),
+                None => ids.push(None),
+            }
+        }
+        let mut used: Vec<u16> = ids.iter().flatten().copied().collect();
+        used.sort_unstable();
+        let mut free = (0..u16::MAX).filter(|id| used.binary_search(id).is_err());
+        let mut context = Context {
+            names: NameTable::new(),
+            ids: HashMap::with_capacity(elements.len()),
+        };
+        for (element, id) in elements.iter().zip(ids) {
+            let id = match id {
+                Some(id) => id,
+                None => free.next().ok_or(XmlError::NoFreeObjectId)?,
+            };
+            let id = ObjectId::new(id).map_err(|_| invalid(element, "id"))?;
+            if let Some(name) = element.attribute("name") {
+                if context.names.id(name).is_some() || name == NULL {
+                    return Err(XmlError::DuplicateName {
+                        line: line(element),
+                        name: name.to_string(),
+                    });
+                }
+                context.names.insert(name, id);
+            }
+            context.ids.insert(element.id(), id);
+        }
+
+        let mut pool = ObjectPool::new();
+        for element in &elements {
+            pool.add(read_object(element, context.ids[&element.id()], &context)?);
+        }
+        Ok((pool, context.names))
+    }
+
+    /// Writes the pool as XML, see [`ObjectPool::from_xml`] for the format
+    ///
+    /// Every object is written at the top level and included by its parents. Objects and
+    /// references are written with their name from `names`, or only their ID if they have no
+    /// name.
+    pub fn to_xml(&self, names: &NameTable) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<");
+        xml.push_str(ROOT);
+        xml.push_str(">\n");
+        for object in self.objects() {
+            write_object(object, names).write(&mut xml, 1);
+        }
+        xml.push_str("</");
+        xml.push_str(ROOT);
+        xml.push_str(">\n");
+        xml
+    }
+}
+
+/// The object elements below `node` in document order, starting with `node`
+fn collect_objects<'a, 'i>(
+    node: Node<'a, 'i>,
+    elements: &mut Vec<Node<'a, 'i>>,
+) -> Result<(), XmlError> {
+    if !is_object_element(node.tag_name().name()) {
+        return Err(XmlError::UnknownElement {
+            line: line(&node),
+            element: node.tag_name().name().to_string(),
+        });
+    }
+    elements.push(node);
+    for child in node.children().filter(Node::is_element) {
+        if is_object_element(child.tag_name().name()) {
+            collect_objects(child, elements)?;
+        }
+    }
+    Ok(())
+}
+
+/// The object names and the IDs of the object elements of the document that is read
+struct Context {
+    names: NameTable,
+    ids: HashMap<NodeId, ObjectId>,
+}
+
+/// An XML element that is being written
+struct Element {
+    tag: &'static str,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn new(tag: &'static str) -> Self {
+        Element {
+            tag,
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn attribute(&mut self, name: impl Into<String>, value: impl ToString) {
+        self.attributes.push((name.into(), value.to_string()));
+    }
+
+    fn write(&self, xml: &mut String, depth: usize) {
+        xml.push_str(&"  ".repeat(depth));
+        xml.push('<');
+        xml.push_str(self.tag);
+        for (name, value) in &self.attributes {
+            xml.push(' ');
+            xml.push_str(name);
+            xml.push_str("=\"");
+            escape(value, xml);
+            xml.push('"');
+        }
+        if self.children.is_empty() {
+            xml.push_str("/>\n");
+            return;
+        }
+        xml.push_str(">\n");
+        for child in &self.children {
+            child.write(xml, depth + 1);
+        }
+        xml.push_str(&"  ".repeat(depth));
+        xml.push_str("</");
+        xml.push_str(self.tag);
+        xml.push_str(">\n");
+    }
+}
+
+fn escape(value: &str, xml: &mut String) {
+    for c in value.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            // Literal whitespace is normalized to spaces in attribute values
+            '\t' | '\n' | '\r' => xml.push_str(&format!("&#{};", c as u32)),
+            c => xml.push(c),
+        }
+    }
+}
+
+/// Whether a character can be written to an XML 1.0 document
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}')
+        || c > '\u{FFFF}'
+}
+
+fn line(node: &Node) -> u32 {
+    node.document().text_pos_at(node.range().start).row
+}
+
+fn invalid(node: &Node, attribute: &str) -> XmlError {
+    XmlError::InvalidAttribute {
+        line: line(node),
+        attribute: attribute.to_string(),
+        value: node.attribute(attribute).unwrap_or_default().to_string(),
+    }
+}
+
+fn attribute<'a>(node: &Node<'a, '_>, name: &str) -> Result<&'a str, XmlError> {
+    node.attribute(name)
+        .ok_or_else(|| XmlError::MissingAttribute {
+            line: line(node),
+            attribute: name.to_string(),
+        })
+}
+
+fn parse<T: std::str::FromStr>(node: &Node, name: &str) -> Result<T, XmlError> {
+    attribute(node, name)?
+        .parse()
+        .map_err(|_| invalid(node, name))
+}
+
+/// Resolves a reference by name, or by ID if it is a number
+fn resolve(node: &Node, name: &str, context: &Context) -> Result<NullableObjectId, XmlError> {
+    let value = attribute(node, name)?;
+    if value == NULL {
+        return Ok(NullableObjectId::NULL);
+    }
+    if let Some(id) = context.names.id(value) {
+        return Ok(id.into());
+    }
+    match value.parse::<u16>() {
+        Ok(id) => Ok(ObjectId::new(id).map_err(|_| invalid(node, name))?.into()),
+        Err(_) => Err(XmlError::UnknownName {
+            line: line(node),
+            name: value.to_string(),
+        }),
+    }
+}
+
+fn reference(id: NullableObjectId, names: &NameTable) -> String {
+    match id.0 {
+        Some(id) => match names.name(id) {
+            Some(name) => name.to_string(),
+            None => u16::from(id).to_string(),
+        },
+        None => NULL.to_string(),
+    }
+}
+
+/// A field of an object that can be written to and read from an XML element
+trait XmlValue: Sized {
+    fn write(&self, name: &'static str, element: &mut Element, names: &NameTable);
+    fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError>;
+}
+
+/// An entry of a list, written as a child element
+trait XmlItem: Sized {
+    fn write(&self, element: &mut Element, names: &NameTable);
+    fn read(node: &Node, context: &Context) -> Result<Self, XmlError>;
+}
+
+macro_rules! xml_number {
+    ($($t:ty),*) => {
+        $(impl XmlValue for $t {
+            fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+                element.attribute(name, self);
+            }
+            fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+                parse(node, name)
+            }
+        })*
+    };
+}
+xml_number!(u8, u16, u32, i16, i32, f32);
+
+impl XmlValue for bool {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        element.attribute(name, if *self { "yes" } else { "no" });
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        match attribute(node, name)? {
+            "yes" | "true" | "1" => Ok(true),
+            "no" | "false" | "0" => Ok(false),
+            _ => Err(invalid(node, name)),
+        }
+    }
+}
+
+/// Byte values with symbolic names, the name of a value is at its index in the table
+///
+/// Values without a name are written as number, numbers are accepted for every value.
+struct Names(&'static [&'static str]);
+
+impl Names {
+    fn name(&self, value: u8) -> String {
+        match self.0.get(value as usize) {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => value.to_string(),
+        }
+    }
+
+    fn value(&self, node: &Node, name: &str) -> Result<u8, XmlError> {
+        let value = attribute(node, name)?;
+        match self.0.iter().position(|n| !n.is_empty() && *n == value) {
+            Some(position) => Ok(position as u8),
+            None => value.parse().map_err(|_| invalid(node, name)),
+        }
+    }
+
+    fn write(&self, value: &u8, name: &'static str, element: &mut Element, _: &NameTable) {
+        element.attribute(name, self.name(*value));
+    }
+
+    fn read(&self, node: &Node, name: &'static str, _: &Context) -> Result<u8, XmlError> {
+        self.value(node, name)
+    }
+}
+
+/// Bits of a byte with symbolic names, written joined with `+`
+///
+/// Each flag is a name with the mask of its bits and the value they have when it is set. The
+/// attribute is left out when no flag is set.
+struct Flags(&'static [(&'static str, u8, u8)]);
+
+impl Flags {
+    fn write(&self, value: &u8, name: &'static str, element: &mut Element, _: &NameTable) {
+        let flags: Vec<&str> = self
+            .0
+            .iter()
+            .filter(|(_, mask, bits)| value & mask == *bits)
+            .map(|(flag, _, _)| *flag)
+            .collect();
+        if !flags.is_empty() {
+            element.attribute(name, flags.join("+"));
+        }
+    }
+
+    fn read(&self, node: &Node, name: &'static str, _: &Context) -> Result<u8, XmlError> {
+        let Some(value) = node.attribute(name) else {
+            return Ok(0);
+        };
+        if let Ok(value) = value.parse() {
+            return Ok(value);
+        }
+        value.split('+').try_fold(0, |value, flag| {
+            match self.0.iter().find(|(name, _, _)| *name == flag.trim()) {
+                Some((_, _, bits)) => Ok(value | bits),
+                None => Err(invalid(node, name)),
+            }
+        })
+    }
+}
+
+/// A flag that is a single bit
+const fn bit(name: &'static str, bit: u8) -> (&'static str, u8, u8) {
+    (name, 1 << bit, 1 << bit)
+}
+
+const COLOURS: Names = Names(&[
+    "black", "white", "green", "teal", "maroon", "purple", "olive", "silver", "grey", "blue",
+    "lime", "cyan", "red", "magenta", "yellow", "navy",
+]);
+const PRIORITIES: Names = Names(&["high", "medium", "low"]);
+const ACOUSTIC_SIGNALS: Names = Names(&["highest", "medium", "lowest", "none"]);
+const ELLIPSE_TYPES: Names = Names(&["closed", "open", "closedsegment", "closedsection"]);
+const POLYGON_TYPES: Names = Names(&["convex", "nonconvex", "complex", "open"]);
+const PICTURE_FORMATS: Names = Names(&["monochrome", "4bit", "8bit"]);
+const HORIZONTAL_JUSTIFICATION: Names = Names(&["left", "middle", "right"]);
+const VERTICAL_JUSTIFICATION: Names = Names(&["top", "middle", "bottom"]);
+const LINE_DIRECTIONS: Names = Names(&["toplefttobottomright", "bottomlefttotopright"]);
+const FILL_TYPES: Names = Names(&["nofill", "linecolour", "fillcolour", "pattern"]);
+const VALIDATION_TYPES: Names = Names(&["validcharacters", "invalidcharacters"]);
+const FONT_TYPES: Names = Names(&[
+    "latin1", "latin9", "latin2", "", "latin4", "cyrillic", "", "greek",
+]);
+const EVENTS: Names = Names(&[
+    "",
+    "on_activate",
+    "on_deactivate",
+    "on_show",
+    "on_hide",
+    "on_enable",
+    "on_disable",
+    "on_change_active_mask",
+    "on_change_soft_key_mask",
+    "on_change_attribute",
+    "on_change_background_colour",
+    "on_change_font_attributes",
+    "on_change_line_attributes",
+    "on_change_fill_attributes",
+    "on_change_child_location",
+    "on_change_size",
+    "on_change_value",
+    "on_change_priority",
+    "on_change_end_point",
+    "on_input_field_selection",
+    "on_input_field_deselection",
+    "on_esc",
+    "on_entry_of_value",
+    "on_entry_of_new_value",
+    "on_key_press",
+    "on_key_release",
+    "on_change_child_position",
+    "on_pointing_event_press",
+    "on_pointing_event_release",
+]);
+
+const LINE_SUPPRESSION: Flags = Flags(&[
+    bit("top", 0),
+    bit("right", 1),
+    bit("bottom", 2),
+    bit("left", 3),
+]);
+
+/// Option bytes that are written as flags
+macro_rules! xml_flags {
+    ($($t:ty => [$($flag:expr),* $(,)?]),* $(,)?) => {
+        $(impl XmlValue for $t {
+            fn write(&self, name: &'static str, element: &mut Element, names: &NameTable) {
+                const FLAGS: Flags = Flags(&[$($flag),*]);
+                FLAGS.write(&u8::from(*self), name, element, names);
+            }
+            fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError> {
+                const FLAGS: Flags = Flags(&[$($flag),*]);
+                Ok(FLAGS.read(node, name, context)?.into())
+            }
+        })*
+    };
+}
+xml_flags!(
+    AnimationOptions => [
+        bit("loop", 0),
+        ("resettofirst", 0x06, 0x02),
+        ("defaultobject", 0x06, 0x04),
+        ("blank", 0x06, 0x06),
+    ],
+    ButtonOptions => [
+        bit("latchable", 0),
+        bit("latched", 1),
+        bit("suppressborder", 2),
+        bit("transparentbackground", 3),
+        bit("disabled", 4),
+        bit("noborder", 5),
+    ],
+    ColourPaletteOptions => [],
+    ExternalObjectDefinitionOptions => [bit("enabled", 0)],
+    ExternalReferenceNameOptions => [bit("enabled", 0)],
+    FontStyle => [
+        bit("bold", 0),
+        bit("crossed", 1),
+        bit("underlined", 2),
+        bit("italic", 3),
+        bit("inverted", 4),
+        bit("flashinginverted", 5),
+        bit("flashinghidden", 6),
+        bit("proportional", 7),
+    ],
+    GraphicsContextOptions => [bit("transparent", 0), bit("linefontfill", 1)],
+    InputListOptions => [bit("enabled", 0), bit("realtimeediting", 1)],
+    InputNumberOptions => [bit("enabled", 0), bit("realtimeediting", 1)],
+    InputStringOptions => [bit("transparent", 0), bit("autowrap", 1), bit("wraponhyphen", 2)],
+    KeyGroupOptions => [bit("available", 0), bit("transparent", 1)],
+    NumberOptions => [
+        bit("transparent", 0),
+        bit("leadingzeros", 1),
+        bit("blankzero", 2),
+        bit("truncate", 3),
+    ],
+    OutputArchedBarGraphOptions => [
+        bit("border", 0),
+        bit("targetline", 1),
+        bit("nofill", 3),
+        bit("horizontal", 4),
+        bit("growpositive", 5),
+        bit("clockwise", 6),
+    ],
+    OutputLinearBarGraphOptions => [
+        bit("border", 0),
+        bit("targetline", 1),
+        bit("ticks", 2),
+        bit("nofill", 3),
+        bit("horizontal", 4),
+        bit("growpositive", 5),
+    ],
+    OutputMeterOptions => [
+        bit("arc", 0),
+        bit("border", 1),
+        bit("ticks", 2),
+        bit("clockwise", 3),
+    ],
+    OutputStringOptions => [bit("transparent", 0), bit("autowrap", 1), bit("wraponhyphen", 2)],
+    PictureGraphicOptions => [bit("transparent", 0), bit("flashing", 1), bit("rle", 2)],
+    ScaledGraphicOptions => [bit("flashing", 0)],
+    WindowMaskOptions => [bit("available", 0), bit("transparent", 1)],
+);
+
+/// Attributes that are stored as a byte in the binary format, written with a symbolic name
+macro_rules! xml_named {
+    ($($t:ty => $names:expr),* $(,)?) => {
+        $(impl XmlValue for $t {
+            fn write(&self, name: &'static str, element: &mut Element, names: &NameTable) {
+                $names.write(&u8::from(*self), name, element, names);
+            }
+            fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError> {
+                $names
+                    .read(node, name, context)?
+                    .try_into()
+                    .map_err(|_| invalid(node, name))
+            }
+        })*
+    };
+}
+xml_named!(
+    ColorFormat => PICTURE_FORMATS,
+    FillType => FILL_TYPES,
+    FontType => FONT_TYPES,
+    LineDirection => LINE_DIRECTIONS,
+    ValidationType => VALIDATION_TYPES,
+);
+
+impl XmlValue for WindowType {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        element.attribute(name, u8::from(*self));
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        parse::<u8>(node, name)?
+            .try_into()
+            .map_err(|_| invalid(node, name))
+    }
+}
+
+/// Written as `horizontal_justification` and `vertical_justification`
+impl XmlValue for Alignment {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        let value = u8::from(*self);
+        element.attribute(
+            format!("horizontal_{}", name),
+            HORIZONTAL_JUSTIFICATION.name(value & 0x03),
+        );
+        element.attribute(
+            format!("vertical_{}", name),
+            VERTICAL_JUSTIFICATION.name(value >> 2 & 0x03),
+        );
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        let horizontal = format!("horizontal_{}", name);
+        let vertical = format!("vertical_{}", name);
+        let horizontal = match node.attribute(horizontal.as_str()) {
+            Some(_) => HORIZONTAL_JUSTIFICATION.value(node, &horizontal)?,
+            None => 0,
+        };
+        let vertical = match node.attribute(vertical.as_str()) {
+            Some(_) => VERTICAL_JUSTIFICATION.value(node, &vertical)?,
+            None => 0,
+        };
+        Ok(Alignment::from(
+            (horizontal & 0x03) | (vertical & 0x03) << 2,
+        ))
+    }
+}
+
+impl XmlValue for FormatType {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        let format = match self {
+            FormatType::Decimal => "fixed",
+            FormatType::Exponential => "exponential",
+        };
+        element.attribute(name, format);
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        match attribute(node, name)? {
+            "fixed" => Ok(FormatType::Decimal),
+            "exponential" => Ok(FormatType::Exponential),
+            _ => Err(invalid(node, name)),
+        }
+    }
+}
+
+/// Fixed sizes are written as their character cell like `8x12`, proportional sizes as their
+/// height in pixels
+impl XmlValue for FontSize {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        match self.size() {
+            Some(size) => element.attribute(name, format!("{}x{}", size.x, size.y)),
+            None => element.attribute(name, self.height()),
+        }
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        let value = attribute(node, name)?;
+        if let Some(size) = FontSize::FIXED.into_iter().find(|size| {
+            size.size()
+                .is_some_and(|s| format!("{}x{}", s.x, s.y) == value)
+        }) {
+            return Ok(size);
+        }
+        match value.parse() {
+            Ok(height @ 8..=255) => Ok(FontSize::Proportional(height)),
+            _ => Err(invalid(node, name)),
+        }
+    }
+}
+
+/// The pixels of the pattern as 16 `0` and `1` characters, starting with the first pixel
+impl XmlValue for LineArt {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        element.attribute(name, format!("{:016b}", u16::from(*self)));
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        let value = attribute(node, name)?;
+        if value.len() != 16 {
+            return Err(invalid(node, name));
+        }
+        u16::from_str_radix(value, 2)
+            .map(LineArt::from)
+            .map_err(|_| invalid(node, name))
+    }
+}
+
+impl XmlValue for WindowMaskCellFormat {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        let size = self.size();
+        element.attribute(name, format!("{}x{}", size.x, size.y));
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        let value = attribute(node, name)?;
+        let (x, y) = value.split_once('x').ok_or_else(|| invalid(node, name))?;
+        let x: u8 = x.parse().map_err(|_| invalid(node, name))?;
+        let y: u8 = y.parse().map_err(|_| invalid(node, name))?;
+        let format = WindowMaskCellFormat::from(u16::from_be_bytes([x, y]));
+        if format.size() != (Point { x, y }) {
+            return Err(invalid(node, name));
+        }
+        Ok(format)
+    }
+}
+
+impl XmlValue for Colour {
+    fn write(&self, name: &'static str, element: &mut Element, names: &NameTable) {
+        COLOURS.write(&self.id, name, element, names);
+    }
+    fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError> {
+        Ok(Colour::new_by_id(COLOURS.read(node, name, context)?))
+    }
+}
+
+impl XmlValue for NAME {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        element.attribute(name, format!("{:#018X}", u64::from(*self)));
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        let value = attribute(node, name)?;
+        let value = value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+            .ok_or_else(|| invalid(node, name))?;
+        u64::from_str_radix(value, 16)
+            .map(NAME::new)
+            .map_err(|_| invalid(node, name))
+    }
+}
+
+/// Strings with characters XML can't hold are written as hex of their UTF-8 encoding, in an
+/// attribute with a `_hex` suffix
+impl XmlValue for String {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        if self.chars().all(is_xml_char) {
+            element.attribute(name, self);
+        } else {
+            element.attribute(format!("{}_hex", name), to_hex(self.as_bytes()));
+        }
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        let hex_name = format!("{}_hex", name);
+        match node.attribute(hex_name.as_str()) {
+            Some(hex) => from_hex(hex)
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .ok_or_else(|| invalid(node, &hex_name)),
+            None => Ok(attribute(node, name)?.to_string()),
+        }
+    }
+}
+
+impl XmlValue for ObjectId {
+    fn write(&self, name: &'static str, element: &mut Element, names: &NameTable) {
+        element.attribute(name, reference((*self).into(), names));
+    }
+    fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError> {
+        resolve(node, name, context)?
+            .0
+            .ok_or_else(|| invalid(node, name))
+    }
+}
+
+/// NULL references are left out
+impl XmlValue for NullableObjectId {
+    fn write(&self, name: &'static str, element: &mut Element, names: &NameTable) {
+        if self.0.is_some() {
+            element.attribute(name, reference(*self, names));
+        }
+    }
+    fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError> {
+        match node.attribute(name) {
+            Some(_) => resolve(node, name, context),
+            None => Ok(NullableObjectId::NULL),
+        }
+    }
+}
+
+/// Binary data, like the pixels of a picture graphic, is written as hex
+impl XmlValue for Vec<u8> {
+    fn write(&self, name: &'static str, element: &mut Element, _: &NameTable) {
+        element.attribute(name, to_hex(self));
+    }
+    fn read(node: &Node, name: &'static str, _: &Context) -> Result<Self, XmlError> {
+        from_hex(attribute(node, name)?).ok_or_else(|| invalid(node, name))
+    }
+}
+
+/// The indices of a colour map, separated by spaces
+struct ColourIndices;
+
+impl ColourIndices {
+    fn write(&self, value: &[u8], name: &'static str, element: &mut Element, _: &NameTable) {
+        let indices: Vec<String> = value.iter().map(u8::to_string).collect();
+        element.attribute(name, indices.join(" "));
+    }
+
+    fn read(&self, node: &Node, name: &'static str, _: &Context) -> Result<Vec<u8>, XmlError> {
+        attribute(node, name)?
+            .split_whitespace()
+            .map(|index| index.parse().map_err(|_| invalid(node, name)))
+            .collect()
+    }
+}
+
+/// A macro, referenced by name if the name table has a macro with that ID
+struct MacroId;
+
+impl MacroId {
+    fn write(&self, value: &u8, name: &'static str, element: &mut Element, names: &NameTable) {
+        let id = ObjectId::new(*value as u16).unwrap_or_default();
+        element.attribute(name, reference(id.into(), names));
+    }
+
+    fn read(&self, node: &Node, name: &'static str, context: &Context) -> Result<u8, XmlError> {
+        let id: u16 = ObjectId::read(node, name, context)?.into();
+        u8::try_from(id).map_err(|_| invalid(node, name))
+    }
+}
+
+/// The children of an object without a position, as `<include_object>` or defined in place
+///
+/// Window masks have a list of objects and object references, their children with a position
+/// are the object references. Other objects only have one list of children.
+fn listed(node: &Node, context: &Context) -> Result<Vec<NullableObjectId>, XmlError> {
+    let is_window_mask = node.tag_name().name() == "windowmask";
+    let mut items = Vec::new();
+    for child in node.children().filter(Node::is_element) {
+        let has_position = child.has_attribute("pos_x") || child.has_attribute("pos_y");
+        if is_window_mask && has_position {
+            continue;
+        }
+        match child.tag_name().name() {
+            "include_object" => items.push(resolve(&child, "name", context)?),
+            "macro" => {}
+            tag if is_object_element(tag) => items.push(context.ids[&child.id()].into()),
+            _ => {}
+        }
+    }
+    Ok(items)
+}
+
+fn position(node: &Node, name: &str) -> Result<i16, XmlError> {
+    match node.attribute(name) {
+        Some(_) => parse(node, name),
+        None => Ok(0),
+    }
+}
+
+/// Object references are `<include_object>` children with a position
+impl XmlValue for Vec<ObjectRef> {
+    fn write(&self, _: &'static str, element: &mut Element, names: &NameTable) {
+        for object_ref in self {
+            let mut child = Element::new("include_object");
+            child.attribute("name", reference(object_ref.id.into(), names));
+            child.attribute("pos_x", object_ref.offset.x);
+            child.attribute("pos_y", object_ref.offset.y);
+            element.children.push(child);
+        }
+    }
+    fn read(node: &Node, _: &'static str, context: &Context) -> Result<Self, XmlError> {
+        let is_window_mask = node.tag_name().name() == "windowmask";
+        let mut object_refs = Vec::new();
+        for child in node.children().filter(Node::is_element) {
+            let has_position = child.has_attribute("pos_x") || child.has_attribute("pos_y");
+            if is_window_mask && !has_position {
+                continue;
+            }
+            let id = match child.tag_name().name() {
+                "include_object" => ObjectId::read(&child, "name", context)?,
+                "macro" => continue,
+                tag if is_object_element(tag) => context.ids[&child.id()],
+                _ => continue,
+            };
+            object_refs.push(ObjectRef {
+                id,
+                offset: Point {
+                    x: position(&child, "pos_x")?,
+                    y: position(&child, "pos_y")?,
+                },
+            });
+        }
+        Ok(object_refs)
+    }
+}
+
+/// Lists of objects without a position are `<include_object>` children
+impl XmlValue for Vec<NullableObjectId> {
+    fn write(&self, _: &'static str, element: &mut Element, names: &NameTable) {
+        for id in self {
+            let mut child = Element::new("include_object");
+            child.attribute("name", reference(*id, names));
+            element.children.push(child);
+        }
+    }
+    fn read(node: &Node, _: &'static str, context: &Context) -> Result<Self, XmlError> {
+        listed(node, context)
+    }
+}
+
+impl XmlValue for Vec<ObjectId> {
+    fn write(&self, name: &'static str, element: &mut Element, names: &NameTable) {
+        let ids: Vec<NullableObjectId> = self.iter().map(|&id| id.into()).collect();
+        ids.write(name, element, names);
+    }
+    fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError> {
+        listed(node, context)?
+            .into_iter()
+            .map(|id| id.0.ok_or_else(|| invalid(node, name)))
+            .collect()
+    }
+}
+
+/// Macros are `<include_macro>` children, or `<macro>` children with an `event` attribute
+impl XmlValue for Vec<MacroRef> {
+    fn write(&self, _: &'static str, element: &mut Element, names: &NameTable) {
+        for macro_ref in self {
+            let mut child = Element::new("include_macro");
+            MacroId.write(&macro_ref.macro_id, "name", &mut child, names);
+            EVENTS.write(&macro_ref.event_id, "event", &mut child, names);
+            element.children.push(child);
+        }
+    }
+    fn read(node: &Node, _: &'static str, context: &Context) -> Result<Self, XmlError> {
+        let mut macro_refs = Vec::new();
+        for child in node.children().filter(Node::is_element) {
+            let macro_id = match child.tag_name().name() {
+                "include_macro" => MacroId.read(&child, "name", context)?,
+                "macro" => u8::try_from(u16::from(context.ids[&child.id()]))
+                    .map_err(|_| invalid(&child, "id"))?,
+                _ => continue,
+            };
+            macro_refs.push(MacroRef {
+                macro_id,
+                event_id: EVENTS.read(&child, "event", context)?,
+            });
+        }
+        Ok(macro_refs)
+    }
+}
+
+impl<T: XmlItem> XmlValue for Vec<T> {
+    fn write(&self, name: &'static str, element: &mut Element, names: &NameTable) {
+        for item in self {
+            let mut child = Element::new(name);
+            item.write(&mut child, names);
+            element.children.push(child);
+        }
+    }
+    fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError> {
+        node.children()
+            .filter(|child| child.has_tag_name(name))
+            .map(|child| T::read(&child, context))
+            .collect()
+    }
+}
+
+/// Points are `<point>` children
+impl<T: XmlValue> XmlItem for Point<T> {
+    fn write(&self, element: &mut Element, names: &NameTable) {
+        self.x.write("pos_x", element, names);
+        self.y.write("pos_y", element, names);
+    }
+    fn read(node: &Node, context: &Context) -> Result<Self, XmlError> {
+        Ok(Point {
+            x: T::read(node, "pos_x", context)?,
+            y: T::read(node, "pos_y", context)?,
+        })
+    }
+}
+
+/// Language codes are `<language>` children
+impl XmlItem for String {
+    fn write(&self, element: &mut Element, names: &NameTable) {
+        XmlValue::write(self, "code", element, names);
+    }
+    fn read(node: &Node, context: &Context) -> Result<Self, XmlError> {
+        XmlValue::read(node, "code", context)
+    }
+}
+
+impl XmlItem for (String, String) {
+    fn write(&self, element: &mut Element, names: &NameTable) {
+        XmlValue::write(&self.0, "language", element, names);
+        XmlValue::write(&self.1, "country", element, names);
+    }
+    fn read(node: &Node, context: &Context) -> Result<Self, XmlError> {
+        Ok((
+            XmlValue::read(node, "language", context)?,
+            XmlValue::read(node, "country", context)?,
+        ))
+    }
+}
+
+impl XmlItem for Colour {
+    fn write(&self, element: &mut Element, _: &NameTable) {
+        element.attribute("red", self.r);
+        element.attribute("green", self.g);
+        element.attribute("blue", self.b);
+        element.attribute("alpha", self.a);
+    }
+    fn read(node: &Node, _: &Context) -> Result<Self, XmlError> {
+        Ok(Colour::new_by_rgba(
+            parse(node, "red")?,
+            parse(node, "green")?,
+            parse(node, "blue")?,
+            parse(node, "alpha")?,
+        ))
+    }
+}
+
+impl XmlItem for ObjectLabel {
+    fn write(&self, element: &mut Element, names: &NameTable) {
+        XmlValue::write(&self.id, "object", element, names);
+        XmlValue::write(
+            &self.string_variable_reference,
+            "string_variable_reference",
+            element,
+            names,
+        );
+        XmlValue::write(&self.font_type, "font_type", element, names);
+        XmlValue::write(
+            &self.graphic_representation,
+            "graphic_representation",
+            element,
+            names,
+        );
+    }
+    fn read(node: &Node, context: &Context) -> Result<Self, XmlError> {
+        Ok(ObjectLabel {
+            id: XmlValue::read(node, "object", context)?,
+            string_variable_reference: XmlValue::read(node, "string_variable_reference", context)?,
+            font_type: XmlValue::read(node, "font_type", context)?,
+            graphic_representation: XmlValue::read(node, "graphic_representation", context)?,
+        })
+    }
+}
+
+impl XmlItem for CharacterRange {
+    fn write(&self, element: &mut Element, _: &NameTable) {
+        element.attribute("first_character", self.first_character);
+        element.attribute("last_character", self.last_character);
+    }
+    fn read(node: &Node, _: &Context) -> Result<Self, XmlError> {
+        Ok(CharacterRange {
+            first_character: parse(node, "first_character")?,
+            last_character: parse(node, "last_character")?,
+        })
+    }
+}
+
+impl XmlItem for CodePlane {
+    fn write(&self, element: &mut Element, names: &NameTable) {
+        element.attribute("number", self.number);
+        XmlValue::write(&self.character_ranges, "character_range", element, names);
+    }
+    fn read(node: &Node, context: &Context) -> Result<Self, XmlError> {
+        Ok(CodePlane {
+            number: parse(node, "number")?,
+            character_ranges: XmlValue::read(node, "character_range", context)?,
+        })
+    }
+}
+
+/// Macro commands are `<command_...>` children, commands that could not be decoded are written
+/// as `<command_unknown>` with their binary encoding as hex
+impl XmlValue for Vec<MacroCommand> {
+    fn write(&self, _: &'static str, element: &mut Element, names: &NameTable) {
+        for command in self {
+            let mut child = Element::new(command_name(command));
+            match command {
+                MacroCommand::Unknown(data) => data.write("data", &mut child, names),
+                command => write_command(command, &mut child, names),
+            }
+            element.children.push(child);
+        }
+    }
+    fn read(node: &Node, _: &'static str, context: &Context) -> Result<Self, XmlError> {
+        let mut commands = Vec::new();
+        for child in node.children().filter(Node::is_element) {
+            let tag = child.tag_name().name();
+            let command = match tag {
+                "command_unknown" => {
+                    MacroCommand::Unknown(XmlValue::read(&child, "data", context)?)
+                }
+                tag => {
+                    read_command(tag, &child, context)?.ok_or_else(|| XmlError::UnknownElement {
+                        line: line(&child),
+                        element: tag.to_string(),
+                    })?
+                }
+            };
+            commands.push(command);
+        }
+        Ok(commands)
+    }
+}
+
+/// The sub-command is the `sub_command` attribute, its parameters are attributes of the command
+impl XmlValue for GraphicsContextCommand {
+    fn write(&self, name: &'static str, element: &mut Element, names: &NameTable) {
+        element.attribute(name, sub_command_name(self));
+        write_sub_command(self, element, names);
+    }
+    fn read(node: &Node, name: &'static str, context: &Context) -> Result<Self, XmlError> {
+        read_sub_command(attribute(node, name)?, node, context)?.ok_or_else(|| invalid(node, name))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// The attribute name of a field, `field as "name"` uses a different name than the field
+macro_rules! attribute_name {
+    ($field:ident) => {
+        stringify!($field)
+    };
+    ($field:ident $xml:literal) => {
+        $xml
+    };
+}
+
+/// Writes a field with its [`XmlValue`] implementation, or with the codec after `with`
+macro_rules! write_field {
+    ($value:expr, $name:expr, $element:expr, $names:expr) => {
+        XmlValue::write($value, $name, $element, $names)
+    };
+    ($value:expr, $name:expr, $element:expr, $names:expr, $codec:expr) => {
+        $codec.write($value, $name, $element, $names)
+    };
+}
+
+macro_rules! read_field {
+    ($node:expr, $name:expr, $context:expr) => {
+        XmlValue::read($node, $name, $context)?
+    };
+    ($node:expr, $name:expr, $context:expr, $codec:expr) => {
+        $codec.read($node, $name, $context)?
+    };
+}
+
+/// Defines the element name and the XML attribute name of every field of every object type
+///
+/// Fields are written in the order they are listed here. `field as "name"` writes a field with
+/// a different attribute name, `field with CODEC` writes it with a codec instead of its
+/// [`XmlValue`] implementation, like the colour names for colour indices.
+macro_rules! xml_objects {
+    ($($variant:ident => $element:literal {
+        $($field:ident $(as $xml:literal)? $(with $codec:expr)?),* $(,)?
+    }),* $(,)?) => {
+        fn element_name(object: &Object) -> &'static str {
+            match object {
+                $(Object::$variant(_) => $element,)*
+            }
+        }
+
+        fn is_object_element(tag: &str) -> bool {
+            matches!(tag, $($element)|*)
+        }
+
+        fn write_object(object: &Object, names: &NameTable) -> Element {
+            match object {
+                $(Object::$variant(o) => {
+                    let mut element = Element::new($element);
+                    if let Some(name) = names.name(o.id) {
+                        element.attribute("name", name);
+                    }
+                    element.attribute("id", u16::from(o.id));
+                    $(write_field!(
+                        &o.$field,
+                        attribute_name!($field $($xml)?),
+                        &mut element,
+                        names
+                        $(, $codec)?
+                    );)*
+                    element
+                })*
+            }
+        }
+
+        fn read_object(node: &Node, id: ObjectId, context: &Context) -> Result<Object, XmlError> {
+            match node.tag_name().name() {
+                $($element => Ok(Object::$variant($variant {
+                    id,
+                    $($field: read_field!(
+                        node,
+                        attribute_name!($field $($xml)?),
+                        context
+                        $(, $codec)?
+                    ),)*
+                })),)*
+                element => Err(XmlError::UnknownElement {
+                    line: line(node),
+                    element: element.to_string(),
+                }),
+            }
+        }
+    };
+}
+
+xml_objects! {
+    WorkingSet => "workingset" {
+        background_colour, selectable, active_mask, object_refs as "include_object",
+        macro_refs as "include_macro", language_codes as "language",
+    },
+    DataMask => "datamask" {
+        background_colour with COLOURS, soft_key_mask, object_refs as "include_object",
+        macro_refs as "include_macro",
+    },
+    AlarmMask => "alarmmask" {
+        background_colour with COLOURS, soft_key_mask, priority with PRIORITIES,
+        acoustic_signal with ACOUSTIC_SIGNALS, object_refs as "include_object",
+        macro_refs as "include_macro",
+    },
+    Container => "container" {
+        width, height, hidden, object_refs as "include_object", macro_refs as "include_macro",
+    },
+    SoftKeyMask => "softkeymask" {
+        background_colour with COLOURS, objects as "include_object",
+        macro_refs as "include_macro",
+    },
+    Key => "key" {
+        background_colour with COLOURS, key_code, object_refs as "include_object",
+        macro_refs as "include_macro",
+    },
+    Button => "button" {
+        width, height, background_colour with COLOURS, border_colour with COLOURS, key_code,
+        options, object_refs as "include_object", macro_refs as "include_macro",
+    },
+    InputBoolean => "inputboolean" {
+        background_colour with COLOURS, width, foreground_colour, variable_reference, value,
+        enabled, macro_refs as "include_macro",
+    },
+    InputString => "inputstring" {
+        width, height, background_colour with COLOURS, font_attributes, input_attributes,
+        options, variable_reference, justification, value, enabled,
+        macro_refs as "include_macro",
+    },
+    InputNumber => "inputnumber" {
+        width, height, background_colour with COLOURS, font_attributes, options,
+        variable_reference, value, min_value, max_value, offset, scale,
+        nr_of_decimals as "number_of_decimals", format, justification,
+        options2 as "inputobject_options", macro_refs as "include_macro",
+    },
+    InputList => "inputlist" {
+        width, height, variable_reference, value, options, list_items as "include_object",
+        macro_refs as "include_macro",
+    },
+    OutputString => "outputstring" {
+        width, height, background_colour with COLOURS, font_attributes, options,
+        variable_reference, justification, value, macro_refs as "include_macro",
+    },
+    OutputNumber => "outputnumber" {
+        width, height, background_colour with COLOURS, font_attributes, options,
+        variable_reference, value, offset, scale, nr_of_decimals as "number_of_decimals",
+        format, justification, macro_refs as "include_macro",
+    },
+    OutputLine => "line" {
+        line_attributes, width, height, line_direction, macro_refs as "include_macro",
+    },
+    OutputRectangle => "rectangle" {
+        line_attributes, width, height, line_suppression with LINE_SUPPRESSION,
+        fill_attributes, macro_refs as "include_macro",
+    },
+    OutputEllipse => "ellipse" {
+        line_attributes, width, height, ellipse_type with ELLIPSE_TYPES, start_angle,
+        end_angle, fill_attributes, macro_refs as "include_macro",
+    },
+    OutputPolygon => "polygon" {
+        width, height, line_attributes, fill_attributes, polygon_type with POLYGON_TYPES,
+        points as "point", macro_refs as "include_macro",
+    },
+    OutputMeter => "meter" {
+        width, needle_colour with COLOURS, border_colour with COLOURS,
+        arc_and_tick_colour with COLOURS, options, nr_of_ticks as "number_of_ticks",
+        start_angle, end_angle, min_value, max_value, variable_reference, value,
+        macro_refs as "include_macro",
+    },
+    OutputLinearBarGraph => "linearbargraph" {
+        width, height, colour with COLOURS, target_line_colour with COLOURS, options,
+        nr_of_ticks as "number_of_ticks", min_value, max_value, variable_reference, value,
+        target_value_variable_reference, target_value, macro_refs as "include_macro",
+    },
+    OutputArchedBarGraph => "archedbargraph" {
+        width, height, colour with COLOURS, target_line_colour with COLOURS, options,
+        start_angle, end_angle, bar_graph_width, min_value, max_value, variable_reference,
+        value, target_value_variable_reference, target_value, macro_refs as "include_macro",
+    },
+    PictureGraphic => "picturegraphic" {
+        width, actual_width, actual_height, format with PICTURE_FORMATS, options,
+        transparency_colour with COLOURS, data, macro_refs as "include_macro",
+    },
+    NumberVariable => "numbervariable" { value },
+    StringVariable => "stringvariable" { value },
+    FontAttributes => "fontattributes" {
+        font_colour with COLOURS, font_size, font_type, font_style,
+        macro_refs as "include_macro",
+    },
+    LineAttributes => "lineattributes" {
+        line_colour with COLOURS, line_width, line_art, macro_refs as "include_macro",
+    },
+    FillAttributes => "fillattributes" {
+        fill_type, fill_colour with COLOURS, fill_pattern, macro_refs as "include_macro",
+    },
+    InputAttributes => "inputattributes" {
+        validation_type, validation_string, macro_refs as "include_macro",
+    },
+    ObjectPointer => "objectpointer" { value },
+    Macro => "macro" { commands },
+    AuxiliaryFunctionType1 => "auxfunction" {
+        background_colour with COLOURS, function_type, object_refs as "include_object",
+    },
+    AuxiliaryInputType1 => "auxinput" {
+        background_colour with COLOURS, function_type, input_id,
+        object_refs as "include_object",
+    },
+    AuxiliaryFunctionType2 => "auxfunction2" {
+        background_colour with COLOURS, function_attributes, object_refs as "include_object",
+    },
+    AuxiliaryInputType2 => "auxinput2" {
+        background_colour with COLOURS, function_attributes, object_refs as "include_object",
+    },
+    AuxiliaryControlDesignatorType2 => "auxcontroldesignatorobjectpointer" {
+        pointer_type, auxiliary_object_id as "value",
+    },
+    WindowMask => "windowmask" {
+        cell_format, window_type, background_colour with COLOURS, options,
+        name as "window_name", window_title, window_icon, objects as "include_object",
+        object_refs as "include_object", macro_refs as "include_macro",
+    },
+    KeyGroup => "keygroup" {
+        options, name as "key_group_name", key_group_icon, objects as "include_object",
+        macro_refs as "include_macro",
+    },
+    GraphicsContext => "graphicscontext" {
+        viewport_width, viewport_height, viewport_x, viewport_y, canvas_width, canvas_height,
+        viewport_zoom, graphics_cursor_x, graphics_cursor_y,
+        foreground_colour with COLOURS, background_colour with COLOURS,
+        font_attributes_object as "font_attributes",
+        line_attributes_object as "line_attributes",
+        fill_attributes_object as "fill_attributes", format, options,
+        transparency_colour with COLOURS,
+    },
+    OutputList => "outputlist" {
+        width, height, variable_reference, value, list_items as "include_object",
+        macro_refs as "include_macro",
+    },
+    ExtendedInputAttributes => "extendedinputattributes" {
+        validation_type, code_planes as "code_plane",
+    },
+    ColourMap => "colourmap" { colour_map as "colours" with ColourIndices },
+    ObjectLabelReferenceList => "objectlabelreferencelist" {
+        object_labels as "object_label",
+    },
+    ExternalObjectDefinition => "externalobjectdefinition" {
+        options, name as "iso_name", objects as "include_object",
+    },
+    ExternalReferenceName => "externalreferencename" { options, name as "iso_name" },
+    ExternalObjectPointer => "externalobjectpointer" {
+        default_object_id as "default_object", external_reference_name_id as "external_reference_name",
+        external_object_id as "external_object",
+    },
+    Animation => "animation" {
+        width, height, refresh_interval, value, enabled, first_child_index, last_child_index,
+        default_child_index, options, object_refs as "include_object",
+        macro_refs as "include_macro",
+    },
+    ColourPalette => "colourpalette" { options, colours as "colour" },
+    GraphicData => "graphicdata" { format, data },
+    WorkingSetSpecialControls => "workingsetspecialcontrols" {
+        id_of_colour_map as "colour_map", id_of_colour_palette as "colour_palette",
+        language_pairs as "language_pair",
+    },
+    ScaledGraphic => "scaledgraphic" {
+        width, height, scale_type, options, value, macro_refs as "include_macro",
+    },
+}
+
+/// Defines the element or attribute value of every variant of an enum with named fields, see
+/// [`xml_objects`] for the fields
+macro_rules! xml_variants {
+    ($enum:ident, $name_fn:ident, $write_fn:ident, $read_fn:ident, {
+        $($variant:ident => $element:literal {
+            $($field:ident $(as $xml:literal)? $(with $codec:expr)?),* $(,)?
+        }),* $(,)?
+    }) => {
+        fn $name_fn(value: &$enum) -> &'static str {
+            match value {
+                $($enum::$variant { .. } => $element,)*
+                #[allow(unreachable_patterns)]
+                _ => "command_unknown",
+            }
+        }
+
+        fn $write_fn(value: &$enum, element: &mut Element, names: &NameTable) {
+            match value {
+                $($enum::$variant { $($field),* } => {
+                    $(write_field!(
+                        $field,
+                        attribute_name!($field $($xml)?),
+                        element,
+                        names
+                        $(, $codec)?
+                    );)*
+                })*
+                #[allow(unreachable_patterns)]
+                _ => {}
+            }
+        }
+
+        fn $read_fn(tag: &str, node: &Node, context: &Context) -> Result<Option<$enum>, XmlError> {
+            Ok(Some(match tag {
+                $($element => $enum::$variant {
+                    $($field: read_field!(
+                        node,
+                        attribute_name!($field $($xml)?),
+                        context
+                        $(, $codec)?
+                    ),)*
+                },)*
+                _ => return Ok(None),
+            }))
+        }
+    };
+}
+
+xml_variants!(MacroCommand, command_name, write_command, read_command, {
+    HideShowObject => "command_hide_show_object" { object as "object_id", show },
+    EnableDisableObject => "command_enable_disable_object" { object as "object_id", enable },
+    SelectInputObject => "command_select_input_object" {
+        object as "object_id", open_for_input,
+    },
+    ControlAudioSignal => "command_control_audio_signal" {
+        activations, frequency, on_time, off_time,
+    },
+    SetAudioVolume => "command_set_audio_volume" { volume },
+    ChangeChildLocation => "command_change_child_location" {
+        parent as "parent_object_id", child as "object_id", x_change, y_change,
+    },
+    ChangeSize => "command_change_size" { object as "object_id", width, height },
+    ChangeBackgroundColour => "command_change_background_colour" {
+        object as "object_id", colour with COLOURS,
+    },
+    ChangeNumericValue => "command_change_numeric_value" { object as "object_id", value },
+    ChangeEndPoint => "command_change_end_point" {
+        object as "object_id", width, height, line_direction,
+    },
+    ChangeFontAttributes => "command_change_font_attributes" {
+        object as "object_id", colour as "font_colour" with COLOURS, size as "font_size",
+        font_type, style as "font_style",
+    },
+    ChangeLineAttributes => "command_change_line_attributes" {
+        object as "object_id", colour as "line_colour" with COLOURS, width as "line_width",
+        line_art,
+    },
+    ChangeFillAttributes => "command_change_fill_attributes" {
+        object as "object_id", fill_type, colour as "fill_colour" with COLOURS,
+        pattern as "fill_pattern",
+    },
+    ChangeActiveMask => "command_change_active_mask" {
+        working_set as "working_set_object_id", mask as "new_active_mask",
+    },
+    ChangeSoftKeyMask => "command_change_soft_key_mask" {
+        mask_type, mask as "data_or_alarm_mask", soft_key_mask as "new_soft_key_mask",
+    },
+    ChangeAttribute => "command_change_attribute" {
+        object as "object_id", attribute_id, value as "new_value",
+    },
+    ChangePriority => "command_change_priority" {
+        object as "object_id", priority with PRIORITIES,
+    },
+    ChangeListItem => "command_change_list_item" {
+        object as "object_id", index as "list_index", new_object as "new_object_id",
+    },
+    ChangeStringValue => "command_change_string_value" { object as "object_id", value },
+    ChangeChildPosition => "command_change_child_position" {
+        parent as "parent_object_id", child as "object_id", x as "pos_x", y as "pos_y",
+    },
+    ChangeObjectLabel => "command_change_object_label" {
+        object as "object_id", string_variable, font_type, graphic,
+    },
+    ChangePolygonPoint => "command_change_polygon_point" {
+        object as "object_id", index as "point_index", x as "pos_x", y as "pos_y",
+    },
+    ChangePolygonScale => "command_change_polygon_scale" {
+        object as "object_id", width, height,
+    },
+    GraphicsContext => "command_graphics_context" {
+        object as "object_id", command as "sub_command",
+    },
+    SelectColourMap => "command_select_colour_map" { object as "object_id" },
+    ExecuteExtendedMacro => "command_execute_extended_macro" { object as "object_id" },
+    LockUnlockMask => "command_lock_unlock_mask" { lock, mask as "object_id", timeout },
+    ExecuteMacro => "command_execute_macro" { macro_id as "object_id" with MacroId },
+});
+
+xml_variants!(GraphicsContextCommand, sub_command_name, write_sub_command, read_sub_command, {
+    SetGraphicsCursor => "set_graphics_cursor" { x, y },
+    MoveGraphicsCursor => "move_graphics_cursor" { x, y },
+    SetForegroundColour => "set_foreground_colour" { colour with COLOURS },
+    SetBackgroundColour => "set_background_colour" { colour with COLOURS },
+    SetLineAttributesObjectId => "set_line_attributes" { object as "line_attributes" },
+    SetFillAttributesObjectId => "set_fill_attributes" { object as "fill_attributes" },
+    SetFontAttributesObjectId => "set_font_attributes" { object as "font_attributes" },
+    EraseRectangle => "erase_rectangle" { width, height },
+    DrawPoint => "draw_point" { x, y },
+    DrawLine => "draw_line" { x, y },
+    DrawRectangle => "draw_rectangle" { width, height },
+    DrawClosedEllipse => "draw_closed_ellipse" { width, height },
+    DrawPolygon => "draw_polygon" { points as "point" },
+    DrawText => "draw_text" { transparent, text },
+    PanViewport => "pan_viewport" { x, y },
+    ZoomViewport => "zoom_viewport" { zoom },
+    PanAndZoomViewport => "pan_and_zoom_viewport" { x, y, zoom },
+    ChangeViewportSize => "change_viewport_size" { width, height },
+    DrawVtObject => "draw_vt_object" { object as "vt_object" },
+    CopyCanvasToPictureGraphic => "copy_canvas_to_picture_graphic" {
+        object as "picture_graphic",
+    },
+    CopyViewportToPictureGraphic => "copy_viewport_to_picture_graphic" {
+        object as "picture_graphic",
+    },
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::ObjectType;
+
+    /// An object pool with every object type and macro command, in the format [`ObjectPool::to_xml`]
+    /// writes
+    const ALL_OBJECTS: &str = include_str!("../../resources/test/all-objects.xml");
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    #[test]
+    fn xml_round_trip_test() {
+        let (pool, names) = ObjectPool::from_xml(ALL_OBJECTS).unwrap();
+
+        let mut types: Vec<ObjectType> = pool.objects().iter().map(Object::object_type).collect();
+        types.sort_by_key(|t| u8::from(*t));
+        types.dedup();
+        assert_eq!(types.len(), 49);
+
+        assert_eq!(pool.to_xml(&names), ALL_OBJECTS);
+    }
+
+    #[test]
+    fn xml_iop_round_trip_test() {
+        let (pool, names) = ObjectPool::from_xml(ALL_OBJECTS).unwrap();
+
+        let iop_pool = ObjectPool::from_iop(pool.as_iop().unwrap());
+        assert_eq!(iop_pool.objects(), pool.objects());
+        assert_eq!(iop_pool.to_xml(&names), ALL_OBJECTS);
+    }
+
+    #[test]
+    fn xml_round_trip_test_pool() {
+        let data = include_bytes!("../../resources/test/AgIsoStack-rs-test-pool.iop");
+        let (pool, _) = ObjectPool::from_iop_lenient(data.iter().copied());
+        let names = NameTable::generate(&pool);
+
+        let (xml_pool, xml_names) = ObjectPool::from_xml(&pool.to_xml(&names)).unwrap();
+        assert_eq!(xml_pool.objects(), pool.objects());
+        assert_eq!(xml_names, names);
+    }
+
+    #[test]
+    fn from_xml_names_test() {
+        let (pool, names) = ObjectPool::from_xml(ALL_OBJECTS).unwrap();
+
+        let title = names.id("title").unwrap();
+        assert_eq!(title, id(11000));
+        assert_eq!(names.name(title), Some("title"));
+        assert_eq!(names.len(), pool.objects().len());
+
+        let Some(Object::OutputString(control_characters)) =
+            pool.object_by_id(names.id("control_characters").unwrap())
+        else {
+            panic!("control_characters is not an output string");
+        };
+        assert_eq!(control_characters.value, "Line\u{b}");
+
+        let Some(Object::DataMask(mask)) = pool.object_by_id(names.id("main_mask").unwrap()) else {
+            panic!("main_mask is not a data mask");
+        };
+        assert_eq!(mask.macro_refs[0].macro_id, 1);
+        assert_eq!(mask.macro_refs[0].event_id, 2);
+    }
+
+    #[test]
+    fn from_xml_nested_test() {
+        let xml = r#"<objectpool>
+  <workingset name="working_set" background_colour="white" selectable="yes" active_mask="mask">
+    <datamask name="mask" id="1000" background_colour="black" pos_x="1">
+      <outputstring name="text" width="80" height="16" background_colour="white"
+          font_attributes="font" pos_x="10" pos_y="20" value="Text">
+        <macro event="on_change_value">
+          <command_change_background_colour object_id="text" colour="red"/>
+        </macro>
+      </outputstring>
+      <include_object name="text" pos_x="10" pos_y="40"/>
+    </datamask>
+    <language code="en"/>
+  </workingset>
+  <fontattributes name="font" font_colour="black" font_size="8x8" font_type="latin1"/>
+</objectpool>"#;
+        let (pool, names) = ObjectPool::from_xml(xml).unwrap();
+
+        // Objects without an ID get the lowest free one, in document order
+        assert_eq!(names.id("working_set"), Some(id(0)));
+        assert_eq!(names.id("text"), Some(id(1)));
+        assert_eq!(names.id("font"), Some(id(3)));
+
+        let Some(Object::WorkingSet(working_set)) = pool.object_by_id(id(0)) else {
+            panic!("working_set is not a working set");
+        };
+        assert_eq!(working_set.object_refs.len(), 1);
+        assert_eq!(working_set.object_refs[0].id, id(1000));
+        assert_eq!(working_set.object_refs[0].offset, Point { x: 1, y: 0 });
+        assert_eq!(working_set.language_codes, vec!["en".to_string()]);
+
+        let Some(Object::DataMask(mask)) = pool.object_by_id(id(1000)) else {
+            panic!("mask is not a data mask");
+        };
+        let offsets: Vec<(ObjectId, Point<i16>)> = mask
+            .object_refs
+            .iter()
+            .map(|object_ref| (object_ref.id, object_ref.offset))
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![
+                (id(1), Point { x: 10, y: 20 }),
+                (id(1), Point { x: 10, y: 40 })
+            ]
+        );
+
+        let Some(Object::OutputString(text)) = pool.object_by_id(id(1)) else {
+            panic!("text is not an output string");
+        };
+        assert_eq!(
+            text.macro_refs,
+            vec![MacroRef {
+                macro_id: 2,
+                event_id: 16
+            }]
+        );
+        assert_eq!(
+            pool.object_by_id(id(2)),
+            Some(&Object::Macro(Macro {
+                id: id(2),
+                commands: vec![MacroCommand::ChangeBackgroundColour {
+                    object: id(1),
+                    colour: 12
+                }]
+            }))
+        );
+    }
+
+    #[test]
+    fn to_xml_generated_names_test() {
+        let (pool, _) = ObjectPool::from_xml(ALL_OBJECTS).unwrap();
+        let names = NameTable::generate(&pool);
+        assert_eq!(names.name(ObjectId::default()), Some("workingset_0"));
+
+        let (xml_pool, _) = ObjectPool::from_xml(&pool.to_xml(&names)).unwrap();
+        assert_eq!(xml_pool.objects(), pool.objects());
+
+        let (xml_pool, xml_names) = ObjectPool::from_xml(&pool.to_xml(&NameTable::new())).unwrap();
+        assert_eq!(xml_pool.objects(), pool.objects());
+        assert!(xml_names.is_empty());
+    }
+
+    fn xml_error(xml: &str) -> Option<XmlError> {
+        ObjectPool::from_xml(xml).err()
+    }
+
+    #[test]
+    fn from_xml_errors_test() {
+        assert!(matches!(
+            xml_error("<objectpool>"),
+            Some(XmlError::Syntax(_))
+        ));
+        assert_eq!(
+            xml_error("<pool/>"),
+            Some(XmlError::InvalidRoot("pool".to_string()))
+        );
+        assert_eq!(
+            xml_error("<objectpool>\n<mask/>\n</objectpool>"),
+            Some(XmlError::UnknownElement {
+                line: 2,
+                element: "mask".to_string()
+            })
+        );
+        assert_eq!(
+            xml_error("<objectpool>\n<numbervariable/>\n</objectpool>"),
+            Some(XmlError::MissingAttribute {
+                line: 2,
+                attribute: "value".to_string()
+            })
+        );
+        assert_eq!(
+            xml_error("<objectpool><numbervariable value=\"-1\"/></objectpool>"),
+            Some(XmlError::InvalidAttribute {
+                line: 1,
+                attribute: "value".to_string(),
+                value: "-1".to_string()
+            })
+        );
+        assert_eq!(
+            xml_error(
+                "<objectpool><datamask background_colour=\"black\" soft_key_mask=\"NULL\" \
+                 options=\"latchable+unknown\"/></objectpool>"
+            ),
+            None
+        );
+        assert_eq!(
+            xml_error(
+                "<objectpool><button width=\"1\" height=\"1\" background_colour=\"black\" \
+                 border_colour=\"pink\" key_code=\"0\"/></objectpool>"
+            ),
+            Some(XmlError::InvalidAttribute {
+                line: 1,
+                attribute: "border_colour".to_string(),
+                value: "pink".to_string()
+            })
+        );
+        assert_eq!(
+            xml_error("<objectpool><objectpointer value=\"nothing\"/></objectpool>"),
+            Some(XmlError::UnknownName {
+                line: 1,
+                name: "nothing".to_string()
+            })
+        );
+        assert_eq!(
+            xml_error(
+                "<objectpool>\n<numbervariable name=\"a\" value=\"1\"/>\n\
+                 <numbervariable name=\"a\" value=\"2\"/>\n</objectpool>"
+            ),
+            Some(XmlError::DuplicateName {
+                line: 3,
+                name: "a".to_string()
+            })
+        );
+    }
+}
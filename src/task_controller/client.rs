@@ -0,0 +1,1477 @@
+// Copyright 2023 Raven Industries inc.
+
+use std::time::{Duration, Instant};
+
+use super::{
+    process_data_message, DdiDefinition, DdiError, DeviceDescriptorObjectPool, ProcessDataCommand,
+    ProcessDataMessage, REQUEST_DEFAULT_PROCESS_DATA,
+};
+use crate::driver::{Address, CanId, Pgn, Priority};
+use crate::network_management::can_message::CANMessage;
+use crate::network_management::common_parameter_group_numbers::CommonParameterGroupNumbers;
+use crate::network_management::name::{NameField, NAME};
+
+/// How often the client sends its task message, and the TC its status
+pub const STATUS_INTERVAL: Duration = Duration::from_secs(2);
+/// How long to wait for a response of the TC, or for its status before it counts as gone
+pub const SERVER_TIMEOUT: Duration = Duration::from_secs(6);
+
+/// The default triggers of the default process data of a pool, in ms, mm and raw units
+const DEFAULT_TIME_INTERVAL: u32 = 1000;
+const DEFAULT_DISTANCE_INTERVAL: u32 = 1000;
+const DEFAULT_CHANGE_THRESHOLD: i32 = 1;
+
+/// The NAME function of a task controller
+const TASK_CONTROLLER_FUNCTION: u8 = 130;
+
+const REQUEST_STRUCTURE_LABEL: u8 = 0x01;
+const STRUCTURE_LABEL: u8 = 0x11;
+const REQUEST_LOCALIZATION_LABEL: u8 = 0x21;
+const LOCALIZATION_LABEL: u8 = 0x31;
+const REQUEST_OBJECT_POOL_TRANSFER: u8 = 0x41;
+const REQUEST_OBJECT_POOL_TRANSFER_RESPONSE: u8 = 0x51;
+const OBJECT_POOL_TRANSFER: u8 = 0x61;
+const OBJECT_POOL_TRANSFER_RESPONSE: u8 = 0x71;
+const ACTIVATE_OBJECT_POOL: u8 = 0x81;
+const ACTIVATE_OBJECT_POOL_RESPONSE: u8 = 0x91;
+const DELETE_OBJECT_POOL: u8 = 0xA1;
+const DELETE_OBJECT_POOL_RESPONSE: u8 = 0xB1;
+
+const REQUEST_VERSION: u8 = 0x00;
+const VERSION: u8 = 0x10;
+
+/// Error bits of the Process Data Acknowledge message
+const ACK_COMMAND_NOT_SUPPORTED: u8 = 1 << 0;
+const ACK_INVALID_ELEMENT: u8 = 1 << 1;
+const ACK_DDI_NOT_SUPPORTED: u8 = 1 << 2;
+
+/// Label sent by a TC that has no device descriptor object pool of the client
+const NO_LABEL: [u8; 7] = [0xFF; 7];
+
+/// The version and options of a TC or client, from the Version message
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TechnicalCapabilities {
+    pub version: u8,
+    /// The maximum boot time in seconds, 0xFF if not available
+    pub boot_time: u8,
+    /// Bit 0 documentation, bit 1 TC-BAS, bit 2 TC-GEO with position based control, bit 3
+    /// TC-GEO without, bit 4 peer control assignment and bit 5 TC-SC
+    pub options: u8,
+    pub booms: u8,
+    pub sections: u8,
+    pub channels: u8,
+}
+
+impl TechnicalCapabilities {
+    /// Decodes a Version message, `None` if it is not one
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        match data {
+            [VERSION, version, boot_time, options, _, booms, sections, channels, ..] => {
+                Some(TechnicalCapabilities {
+                    version: *version,
+                    boot_time: *boot_time,
+                    options: *options,
+                    booms: *booms,
+                    sections: *sections,
+                    channels: *channels,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 8] {
+        [
+            VERSION,
+            self.version,
+            self.boot_time,
+            self.options,
+            0x00,
+            self.booms,
+            self.sections,
+            self.channels,
+        ]
+    }
+}
+
+/// A trigger the TC set up for sending the value of a DDI of an element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeasurementTrigger {
+    /// Send every this many milliseconds
+    TimeInterval(u32),
+    /// Send every this many millimetres driven
+    DistanceInterval(u32),
+    /// Send changed values above this one
+    MinimumThreshold(i32),
+    /// Send changed values below this one
+    MaximumThreshold(i32),
+    /// Send values that changed by at least this much
+    ChangeThreshold(i32),
+}
+
+impl MeasurementTrigger {
+    fn from_message(message: &ProcessDataMessage) -> Option<Self> {
+        match message.command {
+            ProcessDataCommand::MeasurementTimeInterval => {
+                Some(MeasurementTrigger::TimeInterval(message.value as u32))
+            }
+            ProcessDataCommand::MeasurementDistanceInterval => {
+                Some(MeasurementTrigger::DistanceInterval(message.value as u32))
+            }
+            ProcessDataCommand::MeasurementMinimumWithinThreshold => {
+                Some(MeasurementTrigger::MinimumThreshold(message.value))
+            }
+            ProcessDataCommand::MeasurementMaximumWithinThreshold => {
+                Some(MeasurementTrigger::MaximumThreshold(message.value))
+            }
+            ProcessDataCommand::MeasurementChangeThreshold => {
+                Some(MeasurementTrigger::ChangeThreshold(message.value))
+            }
+            _ => None,
+        }
+    }
+
+    fn to_message(self, element: u16, ddi: u16) -> ProcessDataMessage {
+        let (command, value) = match self {
+            MeasurementTrigger::TimeInterval(ms) => {
+                (ProcessDataCommand::MeasurementTimeInterval, ms as i32)
+            }
+            MeasurementTrigger::DistanceInterval(mm) => {
+                (ProcessDataCommand::MeasurementDistanceInterval, mm as i32)
+            }
+            MeasurementTrigger::MinimumThreshold(minimum) => (
+                ProcessDataCommand::MeasurementMinimumWithinThreshold,
+                minimum,
+            ),
+            MeasurementTrigger::MaximumThreshold(maximum) => (
+                ProcessDataCommand::MeasurementMaximumWithinThreshold,
+                maximum,
+            ),
+            MeasurementTrigger::ChangeThreshold(change) => {
+                (ProcessDataCommand::MeasurementChangeThreshold, change)
+            }
+        };
+        ProcessDataMessage {
+            command,
+            element,
+            ddi,
+            value,
+        }
+    }
+
+    /// An interval of 0 stops sending on that trigger
+    fn is_stop(&self) -> bool {
+        matches!(
+            self,
+            MeasurementTrigger::TimeInterval(0) | MeasurementTrigger::DistanceInterval(0)
+        )
+    }
+}
+
+/// Where the client is in connecting to the TC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcClientState {
+    /// No TC found yet, or it has not sent its status
+    WaitForServer,
+    WaitForVersion,
+    WaitForStructureLabel,
+    WaitForLocalizationLabel,
+    WaitForDelete,
+    WaitForTransferResponse,
+    /// The device descriptor object pool is being sent, this state has no timeout as large pools
+    /// take a while with the transport protocol
+    WaitForTransferComplete,
+    WaitForActivation,
+    Connected,
+    /// The TC refused the device descriptor object pool, stays until
+    /// [`restart`](TaskControllerClient::restart)
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcClientError {
+    /// The TC did not respond in the given state
+    Timeout(TcClientState),
+    /// The TC stopped sending its status, or another node took its address
+    StatusLost,
+    /// The TC has no room for the device descriptor object pool
+    TransferRejected,
+    /// The TC reported an error code after the transfer
+    TransferFailed(u8),
+    /// The TC could not activate the device descriptor object pool
+    ActivationFailed {
+        errors: u8,
+        parent: u16,
+        object: u16,
+        pool_errors: u8,
+    },
+}
+
+impl std::fmt::Display for TcClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for TcClientError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcClientEvent {
+    /// The device descriptor object pool is active on the TC
+    Connected(TechnicalCapabilities),
+    Disconnected(TcClientError),
+    /// The TC set a value, the application should apply it
+    ValueCommand {
+        element: u16,
+        ddi: u16,
+        value: i32,
+    },
+    TaskStarted,
+    TaskStopped,
+}
+
+#[derive(Debug, Clone)]
+struct ProcessValue {
+    element: u16,
+    ddi: u16,
+    value: i32,
+    /// Totals are sent when the task stops
+    total: bool,
+    triggers: Vec<MeasurementTrigger>,
+    /// Sent when the TC requests the default process data
+    default_triggers: Vec<MeasurementTrigger>,
+    last_sent: Option<i32>,
+    last_time: Option<Instant>,
+    last_distance: u64,
+}
+
+impl ProcessValue {
+    /// Whether a trigger fires, the intervals count from the last time the value was sent
+    fn is_due(&self, now: Instant, distance: u64) -> bool {
+        let mut interval = false;
+        let mut threshold = None;
+        for trigger in &self.triggers {
+            match *trigger {
+                MeasurementTrigger::TimeInterval(ms) => {
+                    interval |= self
+                        .last_time
+                        .is_none_or(|last| now - last >= Duration::from_millis(u64::from(ms)))
+                }
+                MeasurementTrigger::DistanceInterval(mm) => {
+                    interval |= distance - self.last_distance >= u64::from(mm)
+                }
+                MeasurementTrigger::MinimumThreshold(minimum) => {
+                    threshold = Some(threshold.unwrap_or(true) && self.value > minimum)
+                }
+                MeasurementTrigger::MaximumThreshold(maximum) => {
+                    threshold = Some(threshold.unwrap_or(true) && self.value < maximum)
+                }
+                MeasurementTrigger::ChangeThreshold(change) => {
+                    let changed_enough = self.last_sent.is_none_or(|last| {
+                        (i64::from(self.value) - i64::from(last)).abs() >= i64::from(change)
+                    });
+                    threshold = Some(threshold.unwrap_or(true) && changed_enough)
+                }
+            }
+        }
+        interval || (threshold == Some(true) && self.last_sent != Some(self.value))
+    }
+}
+
+/// A Task Controller client, for section and rate control by the TC
+///
+/// The client finds the TC by its NAME, uploads the device descriptor object pool (DDOP) if the
+/// TC does not have it yet and activates it. Once connected it answers value requests, proposes
+/// the triggers of the default process data when the TC requests them, and sends measurements
+/// from the values the application keeps up to date with [`set_value`](Self::set_value).
+///
+/// The DDOP transfer is one message longer than 8 bytes, the application has to send it with
+/// the transport protocol. Before talking to a TC the client announces itself as a working set
+/// of one member with the Working Set Master message.
+#[derive(Debug, Clone)]
+pub struct TaskControllerClient {
+    ddop: Vec<u8>,
+    /// The element numbers of the DDOP with the DDIs of their process data, when known
+    elements: Option<Vec<(u16, Vec<u16>)>>,
+    structure_label: [u8; 7],
+    localization_label: [u8; 7],
+    capabilities: TechnicalCapabilities,
+    server_name: Vec<NameField>,
+    server: Option<(Address, NAME)>,
+    server_capabilities: Option<TechnicalCapabilities>,
+    state: TcClientState,
+    /// Stamped by the next update after entering a state
+    state_since: Option<Instant>,
+    status_received: bool,
+    last_status: Option<Instant>,
+    last_task_message: Option<Instant>,
+    task_active: bool,
+    values: Vec<ProcessValue>,
+    distance: u64,
+    messages: Vec<CANMessage>,
+}
+
+impl TaskControllerClient {
+    /// A client with the binary `ddop` and its labels, connecting to any TC
+    ///
+    /// The client does not know which process data the pool has, and answers the TC for the
+    /// values set with [`set_value`](Self::set_value). Use [`from_pool`](Self::from_pool) to
+    /// answer for all process data of the pool.
+    pub fn new(ddop: Vec<u8>, structure_label: [u8; 7], localization_label: [u8; 7]) -> Self {
+        TaskControllerClient {
+            ddop,
+            elements: None,
+            structure_label,
+            localization_label,
+            capabilities: TechnicalCapabilities {
+                version: 4,
+                boot_time: 0xFF,
+                ..Default::default()
+            },
+            server_name: vec![NameField::Function(TASK_CONTROLLER_FUNCTION)],
+            server: None,
+            server_capabilities: None,
+            state: TcClientState::WaitForServer,
+            state_since: None,
+            status_received: false,
+            last_status: None,
+            last_task_message: None,
+            task_active: false,
+            values: Vec::new(),
+            distance: 0,
+            messages: Vec::new(),
+        }
+    }
+
+    /// A client with `pool` and the labels of its Device object
+    ///
+    /// Process data that is a member of the default set gets default triggers for the trigger
+    /// methods it supports: every second, every metre and on every change.
+    pub fn from_pool(pool: &DeviceDescriptorObjectPool) -> Self {
+        let (structure_label, localization_label) =
+            pool.device().map_or(([0xFF; 7], [0xFF; 7]), |device| {
+                (device.structure_label, device.localization_label)
+            });
+        let mut client =
+            TaskControllerClient::new(pool.to_bytes(), structure_label, localization_label);
+        client.capabilities.version = pool.version;
+        client.elements = Some(
+            pool.elements()
+                .map(|element| {
+                    let ddis = pool.process_data(element.element_number);
+                    let ddis = ddis.iter().map(|process_data| process_data.ddi);
+                    (element.element_number, ddis.collect())
+                })
+                .collect(),
+        );
+        for element in pool.elements() {
+            for process_data in pool.process_data(element.element_number) {
+                if !process_data.properties.member_of_default_set {
+                    continue;
+                }
+                let methods = process_data.trigger_methods;
+                let triggers = [
+                    methods
+                        .time_interval
+                        .then_some(MeasurementTrigger::TimeInterval(DEFAULT_TIME_INTERVAL)),
+                    methods
+                        .distance_interval
+                        .then_some(MeasurementTrigger::DistanceInterval(
+                            DEFAULT_DISTANCE_INTERVAL,
+                        )),
+                    methods
+                        .on_change
+                        .then_some(MeasurementTrigger::ChangeThreshold(
+                            DEFAULT_CHANGE_THRESHOLD,
+                        )),
+                ];
+                client.set_default_triggers(
+                    element.element_number,
+                    process_data.ddi,
+                    triggers.into_iter().flatten().collect(),
+                );
+            }
+        }
+        client
+    }
+
+    /// Only connects to a TC whose NAME has all of `fields`
+    pub fn with_server_name(mut self, fields: Vec<NameField>) -> Self {
+        self.server_name = fields;
+        self
+    }
+
+    /// The capabilities to report when the TC asks for our version
+    pub fn with_capabilities(mut self, capabilities: TechnicalCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    pub fn state(&self) -> TcClientState {
+        self.state
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.state == TcClientState::Connected
+    }
+
+    /// The address and NAME of the TC
+    pub fn server(&self) -> Option<(Address, NAME)> {
+        self.server
+    }
+
+    /// The capabilities the TC reported in its Version message
+    pub fn server_capabilities(&self) -> Option<TechnicalCapabilities> {
+        self.server_capabilities
+    }
+
+    /// Whether the TC reports a running task
+    pub fn is_task_active(&self) -> bool {
+        self.task_active
+    }
+
+    /// Connects again after the TC refused the DDOP, for example after changing it
+    pub fn restart(&mut self) {
+        self.set_state(TcClientState::WaitForServer);
+    }
+
+    /// Sets the current value of `ddi` of `element`, adding it if new
+    pub fn set_value(&mut self, element: u16, ddi: u16, value: i32) {
+        self.value_entry(element, ddi).value = value;
+    }
+
+    /// Sets the value of a data dictionary DDI in its unit, like mm³/m² for DDI 1, converting it
+    /// to the raw value with the resolution of the DDI
+    pub fn set_engineering_value(
+        &mut self,
+        element: u16,
+        ddi: u16,
+        value: f64,
+    ) -> Result<(), DdiError> {
+        let definition = DdiDefinition::lookup(ddi).ok_or(DdiError::UnknownDdi(ddi))?;
+        self.set_value(element, ddi, definition.to_raw(value)?);
+        Ok(())
+    }
+
+    /// The value of a data dictionary DDI in its unit
+    pub fn engineering_value(&self, element: u16, ddi: u16) -> Option<f64> {
+        let definition = DdiDefinition::lookup(ddi)?;
+        self.value(element, ddi)
+            .map(|raw| definition.to_engineering(raw))
+    }
+
+    /// Like [`set_value`](Self::set_value) for totals, which are also sent when the task stops
+    pub fn set_total(&mut self, element: u16, ddi: u16, value: i32) {
+        let entry = self.value_entry(element, ddi);
+        entry.value = value;
+        entry.total = true;
+    }
+
+    pub fn value(&self, element: u16, ddi: u16) -> Option<i32> {
+        self.find_value(element, ddi).map(|v| v.value)
+    }
+
+    /// The triggers the TC set up for `ddi` of `element`
+    pub fn measurement_triggers(&self, element: u16, ddi: u16) -> &[MeasurementTrigger] {
+        self.find_value(element, ddi)
+            .map_or(&[], |v| v.triggers.as_slice())
+    }
+
+    /// Sets the triggers the client proposes when the TC requests the default process data of
+    /// `element`, adding the value if new
+    pub fn set_default_triggers(
+        &mut self,
+        element: u16,
+        ddi: u16,
+        triggers: Vec<MeasurementTrigger>,
+    ) {
+        self.value_entry(element, ddi).default_triggers = triggers;
+    }
+
+    /// Adds driven distance in millimetres, for distance interval triggers
+    pub fn add_distance(&mut self, millimetres: u32) {
+        self.distance += u64::from(millimetres);
+    }
+
+    fn find_value(&self, element: u16, ddi: u16) -> Option<&ProcessValue> {
+        self.values
+            .iter()
+            .find(|v| v.element == element && v.ddi == ddi)
+    }
+
+    fn value_entry(&mut self, element: u16, ddi: u16) -> &mut ProcessValue {
+        let index = match self
+            .values
+            .iter()
+            .position(|v| v.element == element && v.ddi == ddi)
+        {
+            Some(index) => index,
+            None => {
+                self.values.push(ProcessValue {
+                    element,
+                    ddi,
+                    value: 0,
+                    total: false,
+                    triggers: Vec::new(),
+                    default_triggers: Vec::new(),
+                    last_sent: None,
+                    last_time: None,
+                    last_distance: self.distance,
+                });
+                self.values.len() - 1
+            }
+        };
+        &mut self.values[index]
+    }
+
+    fn set_state(&mut self, state: TcClientState) {
+        self.state = state;
+        self.state_since = None;
+    }
+
+    fn send(&mut self, data: Vec<u8>, own_address: Address) {
+        if let Some((server, _)) = self.server {
+            self.messages
+                .push(process_data_message(data, own_address, server));
+        }
+    }
+
+    fn send_value(&mut self, element: u16, ddi: u16, value: i32, own_address: Address) {
+        let message = ProcessDataMessage {
+            command: ProcessDataCommand::Value,
+            element,
+            ddi,
+            value,
+        };
+        self.send(message.to_bytes().to_vec(), own_address);
+    }
+
+    fn acknowledge(&mut self, message: &ProcessDataMessage, errors: u8, own_address: Address) {
+        let mut data = ProcessDataMessage {
+            command: ProcessDataCommand::Acknowledge,
+            ..*message
+        }
+        .to_bytes();
+        data[4..].copy_from_slice(&[errors, u8::from(message.command), 0xFF, 0xFF]);
+        self.send(data.to_vec(), own_address);
+    }
+
+    /// Answers a request for the default process data of an element with a measurement command
+    /// for every default trigger of its values
+    fn send_default_triggers(&mut self, message: &ProcessDataMessage, own_address: Address) {
+        let triggers: Vec<ProcessDataMessage> = self
+            .values
+            .iter()
+            .filter(|v| v.element == message.element)
+            .flat_map(|v| {
+                v.default_triggers
+                    .iter()
+                    .map(|trigger| trigger.to_message(v.element, v.ddi))
+            })
+            .collect();
+        if triggers.is_empty() {
+            let known = match &self.elements {
+                Some(elements) => elements.iter().any(|(e, _)| *e == message.element),
+                None => self.values.iter().any(|v| v.element == message.element),
+            };
+            let errors = if known {
+                ACK_DDI_NOT_SUPPORTED
+            } else {
+                ACK_INVALID_ELEMENT
+            };
+            self.acknowledge(message, errors, own_address);
+        }
+        for trigger in triggers {
+            self.send(trigger.to_bytes().to_vec(), own_address);
+        }
+    }
+
+    /// Acknowledge error bits for a message about a value we do not have, 0 if we have it
+    fn value_errors(&self, message: &ProcessDataMessage) -> u8 {
+        if let Some(elements) = &self.elements {
+            return match elements.iter().find(|(e, _)| *e == message.element) {
+                Some((_, ddis)) if ddis.contains(&message.ddi) => 0,
+                Some(_) => ACK_DDI_NOT_SUPPORTED,
+                None => ACK_INVALID_ELEMENT,
+            };
+        }
+        if self.find_value(message.element, message.ddi).is_some() {
+            0
+        } else if self.values.iter().any(|v| v.element == message.element) {
+            ACK_DDI_NOT_SUPPORTED
+        } else {
+            ACK_INVALID_ELEMENT
+        }
+    }
+
+    /// Announces us as the master of a working set with no other members
+    fn send_working_set_master(&mut self, own_address: Address) {
+        let id = CanId::try_encode(
+            Pgn::from_raw(CommonParameterGroupNumbers::WorkingSetMaster as u32),
+            own_address,
+            Address::GLOBAL,
+            Priority::Seven,
+        );
+        self.messages.push(CANMessage::new(
+            vec![1, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+            id.unwrap(),
+        ));
+    }
+
+    fn request_transfer(&mut self, own_address: Address) {
+        let mut data = vec![REQUEST_OBJECT_POOL_TRANSFER];
+        data.extend_from_slice(&(self.ddop.len() as u32).to_le_bytes());
+        self.send(data, own_address);
+        self.set_state(TcClientState::WaitForTransferResponse);
+    }
+
+    fn disconnect(&mut self, error: TcClientError, state: TcClientState) -> TcClientEvent {
+        self.set_state(state);
+        self.server_capabilities = None;
+        TcClientEvent::Disconnected(error)
+    }
+
+    /// Handles a received message, `own_address` is the address of our control function
+    pub fn process_message(
+        &mut self,
+        message: &CANMessage,
+        own_address: Address,
+    ) -> Vec<TcClientEvent> {
+        let id = message.get_identifier();
+        let source = id.source_address();
+        let data = message.get_data();
+        if id.pgn() == Pgn::from_raw(CommonParameterGroupNumbers::AddressClaim as u32) {
+            return match data.try_into() {
+                Ok(bytes) => self.address_claimed(source, NAME::new(u64::from_le_bytes(bytes))),
+                Err(_) => vec![],
+            };
+        }
+        if id.pgn() != Pgn::from_raw(CommonParameterGroupNumbers::ProcessData as u32)
+            || self.server.is_none_or(|(server, _)| server != source)
+        {
+            return vec![];
+        }
+        match ProcessDataMessage::from_bytes(data) {
+            Some(message) if message.command == ProcessDataCommand::Status => {
+                self.status(data, own_address)
+            }
+            Some(_) if id.destination_address() != own_address => vec![],
+            Some(message) => match message.command {
+                ProcessDataCommand::TechnicalCapabilities => self.version(data, own_address),
+                ProcessDataCommand::DeviceDescriptor => self.device_descriptor(data, own_address),
+                _ => self.process_data(&message, own_address),
+            },
+            None => vec![],
+        }
+    }
+
+    fn address_claimed(&mut self, source: Address, name: NAME) -> Vec<TcClientEvent> {
+        let was_server = self.server.is_some_and(|(address, _)| address == source);
+        if name.has_field_values(&self.server_name) {
+            if was_server || self.server.is_none_or(|(_, server)| server == name) {
+                self.server = Some((source, name));
+            }
+            return vec![];
+        }
+        if !was_server {
+            return vec![];
+        }
+        self.server = None;
+        self.last_status = None;
+        match self.state {
+            TcClientState::WaitForServer | TcClientState::Failed => vec![],
+            _ => vec![self.disconnect(TcClientError::StatusLost, TcClientState::WaitForServer)],
+        }
+    }
+
+    fn status(&mut self, data: &[u8], own_address: Address) -> Vec<TcClientEvent> {
+        self.status_received = true;
+        let mut events = Vec::new();
+        let task_active = data[4] & 0x01 != 0;
+        if task_active != self.task_active {
+            self.task_active = task_active;
+            if task_active {
+                events.push(TcClientEvent::TaskStarted);
+            } else {
+                events.push(TcClientEvent::TaskStopped);
+                if self.state == TcClientState::Connected {
+                    let totals: Vec<_> = self
+                        .values
+                        .iter()
+                        .filter(|v| v.total)
+                        .map(|v| (v.element, v.ddi, v.value))
+                        .collect();
+                    for (element, ddi, value) in totals {
+                        self.send_value(element, ddi, value, own_address);
+                    }
+                }
+            }
+        }
+        if self.state == TcClientState::WaitForServer {
+            self.send_working_set_master(own_address);
+            self.send(vec![REQUEST_VERSION], own_address);
+            self.set_state(TcClientState::WaitForVersion);
+        }
+        events
+    }
+
+    fn version(&mut self, data: &[u8], own_address: Address) -> Vec<TcClientEvent> {
+        if data[0] == REQUEST_VERSION {
+            self.send(self.capabilities.to_bytes().to_vec(), own_address);
+        } else if let (TcClientState::WaitForVersion, Some(capabilities)) =
+            (self.state, TechnicalCapabilities::from_bytes(data))
+        {
+            self.server_capabilities = Some(capabilities);
+            self.send(vec![REQUEST_STRUCTURE_LABEL], own_address);
+            self.set_state(TcClientState::WaitForStructureLabel);
+        }
+        vec![]
+    }
+
+    fn device_descriptor(&mut self, data: &[u8], own_address: Address) -> Vec<TcClientEvent> {
+        let label = &data[1..8];
+        match (self.state, data[0]) {
+            (TcClientState::WaitForStructureLabel, STRUCTURE_LABEL) => {
+                if label == self.structure_label {
+                    self.send(vec![REQUEST_LOCALIZATION_LABEL], own_address);
+                    self.set_state(TcClientState::WaitForLocalizationLabel);
+                } else if label == NO_LABEL {
+                    self.request_transfer(own_address);
+                } else {
+                    self.send(vec![DELETE_OBJECT_POOL], own_address);
+                    self.set_state(TcClientState::WaitForDelete);
+                }
+            }
+            (TcClientState::WaitForLocalizationLabel, LOCALIZATION_LABEL) => {
+                if label == self.localization_label {
+                    self.send(vec![ACTIVATE_OBJECT_POOL, 0xFF], own_address);
+                    self.set_state(TcClientState::WaitForActivation);
+                } else {
+                    self.send(vec![DELETE_OBJECT_POOL], own_address);
+                    self.set_state(TcClientState::WaitForDelete);
+                }
+            }
+            // Whatever the TC deleted, the upload replaces it
+            (TcClientState::WaitForDelete, DELETE_OBJECT_POOL_RESPONSE) => {
+                self.request_transfer(own_address)
+            }
+            (TcClientState::WaitForTransferResponse, REQUEST_OBJECT_POOL_TRANSFER_RESPONSE) => {
+                if data[1] != 0 {
+                    return vec![
+                        self.disconnect(TcClientError::TransferRejected, TcClientState::Failed)
+                    ];
+                }
+                let mut transfer = vec![OBJECT_POOL_TRANSFER];
+                transfer.extend_from_slice(&self.ddop);
+                self.send(transfer, own_address);
+                self.set_state(TcClientState::WaitForTransferComplete);
+            }
+            (TcClientState::WaitForTransferComplete, OBJECT_POOL_TRANSFER_RESPONSE) => {
+                if data[1] != 0 {
+                    return vec![self.disconnect(
+                        TcClientError::TransferFailed(data[1]),
+                        TcClientState::Failed,
+                    )];
+                }
+                self.send(vec![ACTIVATE_OBJECT_POOL, 0xFF], own_address);
+                self.set_state(TcClientState::WaitForActivation);
+            }
+            (TcClientState::WaitForActivation, ACTIVATE_OBJECT_POOL_RESPONSE) => {
+                if data[1] != 0 {
+                    let error = TcClientError::ActivationFailed {
+                        errors: data[1],
+                        parent: u16::from_le_bytes([data[2], data[3]]),
+                        object: u16::from_le_bytes([data[4], data[5]]),
+                        pool_errors: data[6],
+                    };
+                    return vec![self.disconnect(error, TcClientState::Failed)];
+                }
+                self.set_state(TcClientState::Connected);
+                return vec![TcClientEvent::Connected(
+                    self.server_capabilities.unwrap_or_default(),
+                )];
+            }
+            _ => {}
+        }
+        vec![]
+    }
+
+    fn process_data(
+        &mut self,
+        message: &ProcessDataMessage,
+        own_address: Address,
+    ) -> Vec<TcClientEvent> {
+        if self.state != TcClientState::Connected {
+            return vec![];
+        }
+        if message.command == ProcessDataCommand::RequestValue
+            && message.ddi == REQUEST_DEFAULT_PROCESS_DATA
+        {
+            self.send_default_triggers(message, own_address);
+            return vec![];
+        }
+        let errors = self.value_errors(message);
+        match message.command {
+            ProcessDataCommand::RequestValue if errors == 0 => {
+                let value = self.value(message.element, message.ddi).unwrap_or_default();
+                self.send_value(message.element, message.ddi, value, own_address);
+                vec![]
+            }
+            ProcessDataCommand::Value | ProcessDataCommand::SetValueAndAcknowledge => {
+                if message.command == ProcessDataCommand::SetValueAndAcknowledge || errors != 0 {
+                    self.acknowledge(message, errors, own_address);
+                }
+                if errors != 0 {
+                    return vec![];
+                }
+                vec![TcClientEvent::ValueCommand {
+                    element: message.element,
+                    ddi: message.ddi,
+                    value: message.value,
+                }]
+            }
+            _ => {
+                let trigger = MeasurementTrigger::from_message(message);
+                match trigger {
+                    Some(trigger) if errors == 0 => {
+                        let distance = self.distance;
+                        let entry = self.value_entry(message.element, message.ddi);
+                        entry.triggers.retain(|t| {
+                            std::mem::discriminant(t) != std::mem::discriminant(&trigger)
+                        });
+                        if !trigger.is_stop() {
+                            entry.triggers.push(trigger);
+                        }
+                        entry.last_time = None;
+                        entry.last_distance = distance;
+                    }
+                    Some(_) => self.acknowledge(message, errors, own_address),
+                    None if message.command == ProcessDataCommand::RequestValue => {
+                        self.acknowledge(message, errors, own_address)
+                    }
+                    None => self.acknowledge(message, ACK_COMMAND_NOT_SUPPORTED, own_address),
+                }
+                vec![]
+            }
+        }
+    }
+
+    /// Handles timeouts and queues the task message and the measurements that are due at `now`
+    pub fn update(&mut self, now: Instant, own_address: Address) -> Vec<TcClientEvent> {
+        let mut events = Vec::new();
+        if std::mem::take(&mut self.status_received) {
+            self.last_status = Some(now);
+        }
+        let since = *self.state_since.get_or_insert(now);
+        if self
+            .last_status
+            .is_some_and(|last| now - last > SERVER_TIMEOUT)
+        {
+            self.last_status = None;
+            if !matches!(
+                self.state,
+                TcClientState::WaitForServer | TcClientState::Failed
+            ) {
+                events
+                    .push(self.disconnect(TcClientError::StatusLost, TcClientState::WaitForServer));
+            }
+        } else if !matches!(
+            self.state,
+            TcClientState::WaitForServer
+                | TcClientState::WaitForTransferComplete
+                | TcClientState::Connected
+                | TcClientState::Failed
+        ) && now - since > SERVER_TIMEOUT
+        {
+            let state = self.state;
+            events
+                .push(self.disconnect(TcClientError::Timeout(state), TcClientState::WaitForServer));
+        }
+        if self.last_status.is_none() {
+            return events;
+        }
+        if self
+            .last_task_message
+            .is_none_or(|last| now - last >= STATUS_INTERVAL)
+        {
+            let task_active = u8::from(self.task_active);
+            self.send(
+                vec![0xFF, 0xFF, 0xFF, 0xFF, task_active, 0xFF, 0xFF, 0xFF],
+                own_address,
+            );
+            self.last_task_message = Some(now);
+        }
+        if self.state == TcClientState::Connected {
+            for index in 0..self.values.len() {
+                if !self.values[index].is_due(now, self.distance) {
+                    continue;
+                }
+                let value = &mut self.values[index];
+                value.last_sent = Some(value.value);
+                value.last_time = Some(now);
+                value.last_distance = self.distance;
+                let (element, ddi, value) = (value.element, value.ddi, value.value);
+                self.send_value(element, ddi, value, own_address);
+            }
+        }
+        events
+    }
+
+    /// The messages to send, the DDOP transfer needs the transport protocol
+    pub fn take_messages(&mut self) -> Vec<CANMessage> {
+        std::mem::take(&mut self.messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driver::{CanId, Priority};
+
+    const TC: Address = Address(0xF7);
+    const CLIENT: Address = Address(0x80);
+    const STRUCTURE: [u8; 7] = *b"SPRAYR1";
+    const LOCALIZATION: [u8; 7] = [b'e', b'n', 0x50, 0x00, 0x55, 0x55, 0xFF];
+
+    fn tc_name() -> NAME {
+        NAME::builder()
+            .function_code(TASK_CONTROLLER_FUNCTION)
+            .identity_number(7_u32)
+            .build()
+    }
+
+    fn address_claim(name: NAME, source: Address) -> CANMessage {
+        let id = CanId::try_encode(
+            Pgn::from_raw(CommonParameterGroupNumbers::AddressClaim as u32),
+            source,
+            Address::GLOBAL,
+            Priority::Default,
+        );
+        CANMessage::new(<[u8; 8]>::from(name).to_vec(), id.unwrap())
+    }
+
+    fn from_tc(data: &[u8]) -> CANMessage {
+        process_data_message(data.to_vec(), TC, CLIENT)
+    }
+
+    fn status(task_active: bool) -> CANMessage {
+        let data = [0xFE, 0xFF, 0xFF, 0xFF, u8::from(task_active), 0, 0, 0xFF];
+        process_data_message(data.to_vec(), TC, Address::GLOBAL)
+    }
+
+    fn sent(client: &mut TaskControllerClient) -> Vec<Vec<u8>> {
+        client
+            .take_messages()
+            .into_iter()
+            .inspect(|m| assert_eq!(m.get_identifier().destination_address(), TC))
+            .map(|m| m.get_data().to_vec())
+            .collect()
+    }
+
+    fn padded(data: &[u8]) -> Vec<u8> {
+        let mut data = data.to_vec();
+        data.resize(8, 0xFF);
+        data
+    }
+
+    fn client() -> TaskControllerClient {
+        let mut client = TaskControllerClient::new(vec![1, 2, 3, 4], STRUCTURE, LOCALIZATION);
+        client.process_message(&address_claim(tc_name(), TC), CLIENT);
+        client.process_message(&status(false), CLIENT);
+        client
+    }
+
+    fn connected(start: Instant) -> TaskControllerClient {
+        let mut client = client();
+        client.update(start, CLIENT);
+        client.process_message(&from_tc(&[0x10, 4, 5, 0x1F, 0, 1, 16, 16]), CLIENT);
+        let mut label = vec![STRUCTURE_LABEL];
+        label.extend_from_slice(&STRUCTURE);
+        client.process_message(&from_tc(&label), CLIENT);
+        let mut label = vec![LOCALIZATION_LABEL];
+        label.extend_from_slice(&LOCALIZATION);
+        client.process_message(&from_tc(&label), CLIENT);
+        let events = client.process_message(
+            &from_tc(&[0x91, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0xFF]),
+            CLIENT,
+        );
+        assert!(matches!(events[..], [TcClientEvent::Connected(_)]));
+        client.take_messages();
+        client
+    }
+
+    /// A client of `pool` that is connected to a TC with the pool stored
+    fn connected_pool(pool: &DeviceDescriptorObjectPool) -> TaskControllerClient {
+        let mut client = TaskControllerClient::from_pool(pool);
+        client.process_message(&address_claim(tc_name(), TC), CLIENT);
+        client.process_message(&status(false), CLIENT);
+        client.process_message(&from_tc(&[0x10, 4, 5, 0x1F, 0, 1, 16, 16]), CLIENT);
+        let mut label = vec![STRUCTURE_LABEL];
+        label.extend_from_slice(&pool.device().unwrap().structure_label);
+        client.process_message(&from_tc(&label), CLIENT);
+        let mut label = vec![LOCALIZATION_LABEL];
+        label.extend_from_slice(&pool.device().unwrap().localization_label);
+        client.process_message(&from_tc(&label), CLIENT);
+        client.process_message(
+            &from_tc(&[0x91, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0xFF]),
+            CLIENT,
+        );
+        assert!(client.is_connected());
+        client.take_messages();
+        client
+    }
+
+    fn request(command: ProcessDataCommand, element: u16, ddi: u16, value: i32) -> CANMessage {
+        let message = ProcessDataMessage {
+            command,
+            element,
+            ddi,
+            value,
+        };
+        from_tc(&message.to_bytes())
+    }
+
+    #[test]
+    fn uploads_the_ddop() {
+        let mut client = TaskControllerClient::new(vec![1, 2, 3, 4], STRUCTURE, LOCALIZATION);
+        // Another node with the wrong function is not a TC
+        client.process_message(&address_claim(NAME::new(0), Address(0x26)), CLIENT);
+        assert_eq!(client.server(), None);
+        client.process_message(&address_claim(tc_name(), TC), CLIENT);
+        assert_eq!(client.server(), Some((TC, tc_name())));
+
+        client.process_message(&status(false), CLIENT);
+        assert_eq!(client.state(), TcClientState::WaitForVersion);
+        let mut messages = client.take_messages();
+        let version_request = messages.pop().unwrap();
+        assert_eq!(version_request.get_data(), padded(&[0x00]));
+        // The working set master message comes first
+        let working_set_master = messages.pop().unwrap();
+        assert_eq!(
+            working_set_master.get_identifier().pgn(),
+            Pgn::from_raw(CommonParameterGroupNumbers::WorkingSetMaster as u32)
+        );
+        assert_eq!(working_set_master.get_data()[0], 1);
+        assert!(messages.is_empty());
+
+        let version = [0x10, 4, 5, 0x1F, 0, 1, 16, 16];
+        client.process_message(&from_tc(&version), CLIENT);
+        assert_eq!(sent(&mut client), [padded(&[0x01])]);
+        assert_eq!(client.server_capabilities().unwrap().sections, 16);
+
+        client.process_message(&from_tc(&padded(&[0x11])), CLIENT);
+        assert_eq!(sent(&mut client), [padded(&[0x41, 4, 0, 0, 0])]);
+
+        client.process_message(&from_tc(&padded(&[0x51, 0])), CLIENT);
+        assert_eq!(sent(&mut client), [padded(&[0x61, 1, 2, 3, 4])]);
+        assert_eq!(client.state(), TcClientState::WaitForTransferComplete);
+
+        client.process_message(&from_tc(&padded(&[0x71, 0])), CLIENT);
+        assert_eq!(sent(&mut client), [padded(&[0x81, 0xFF])]);
+
+        let events = client.process_message(
+            &from_tc(&[0x91, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0xFF]),
+            CLIENT,
+        );
+        assert_eq!(
+            events,
+            [TcClientEvent::Connected(
+                TechnicalCapabilities::from_bytes(&version).unwrap()
+            )]
+        );
+        assert!(client.is_connected());
+    }
+
+    #[test]
+    fn uploads_a_built_pool() {
+        let mut builder = crate::task_controller::DdopBuilder::new("Sprayer", NAME::default());
+        builder
+            .add_property(builder.device_element(), 0x0043, 3000, "Width")
+            .unwrap();
+        let pool = builder
+            .build(&crate::network_management::language_command::LanguageCommand::default())
+            .unwrap();
+        let mut client = TaskControllerClient::from_pool(&pool);
+        client.process_message(&address_claim(tc_name(), TC), CLIENT);
+        client.process_message(&status(false), CLIENT);
+        client.process_message(&from_tc(&[0x10, 4, 5, 0x1F, 0, 1, 16, 16]), CLIENT);
+        let mut label = vec![STRUCTURE_LABEL];
+        label.extend_from_slice(&pool.device().unwrap().structure_label);
+        client.process_message(&from_tc(&label), CLIENT);
+        assert_eq!(client.state(), TcClientState::WaitForLocalizationLabel);
+    }
+
+    #[test]
+    fn answers_for_the_pool() {
+        let mut builder = crate::task_controller::DdopBuilder::new("Sprayer", NAME::default());
+        let boom = builder
+            .add_element(
+                builder.device_element(),
+                crate::task_controller::DeviceElementType::Function,
+                "Boom",
+            )
+            .unwrap();
+        builder
+            .add_process_data(boom, 0x0001, "Rate", Default::default(), Default::default())
+            .unwrap();
+        let pool = builder
+            .build(&crate::network_management::language_command::LanguageCommand::default())
+            .unwrap();
+        let mut client = connected_pool(&pool);
+
+        // Process data of the pool is commanded before the application set any value
+        let events = client.process_message(
+            &request(ProcessDataCommand::SetValueAndAcknowledge, 1, 0x0001, 300),
+            CLIENT,
+        );
+        assert_eq!(
+            events,
+            [TcClientEvent::ValueCommand {
+                element: 1,
+                ddi: 0x0001,
+                value: 300
+            }]
+        );
+        assert_eq!(
+            sent(&mut client),
+            [[0x1D, 0x00, 0x01, 0x00, 0x00, 0x0A, 0xFF, 0xFF]]
+        );
+
+        client.process_message(
+            &request(ProcessDataCommand::RequestValue, 1, 0x0002, 0),
+            CLIENT,
+        );
+        client.process_message(
+            &request(ProcessDataCommand::RequestValue, 2, 0x0001, 0),
+            CLIENT,
+        );
+        let errors: Vec<u8> = sent(&mut client).iter().map(|data| data[4]).collect();
+        assert_eq!(errors, [ACK_DDI_NOT_SUPPORTED, ACK_INVALID_ELEMENT]);
+    }
+
+    #[test]
+    fn answers_the_default_process_data() {
+        let mut builder = crate::task_controller::DdopBuilder::new("Sprayer", NAME::default());
+        let boom = builder
+            .add_element(
+                builder.device_element(),
+                crate::task_controller::DeviceElementType::Function,
+                "Boom",
+            )
+            .unwrap();
+        let default_set = crate::task_controller::ProcessDataProperties {
+            member_of_default_set: true,
+            ..Default::default()
+        };
+        let triggers = crate::task_controller::TriggerMethods {
+            time_interval: true,
+            on_change: true,
+            ..Default::default()
+        };
+        builder
+            .add_process_data(boom, 0x0001, "Rate", default_set, triggers)
+            .unwrap();
+        builder
+            .add_process_data(boom, 0x0002, "Actual", Default::default(), triggers)
+            .unwrap();
+        let pool = builder
+            .build(&crate::network_management::language_command::LanguageCommand::default())
+            .unwrap();
+        let mut client = connected_pool(&pool);
+
+        client.process_message(
+            &request(
+                ProcessDataCommand::RequestValue,
+                1,
+                REQUEST_DEFAULT_PROCESS_DATA,
+                0,
+            ),
+            CLIENT,
+        );
+        assert_eq!(
+            sent(&mut client),
+            [
+                [0x14, 0x00, 0x01, 0x00, 0xE8, 0x03, 0x00, 0x00],
+                [0x18, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00]
+            ]
+        );
+        // Proposing the triggers does not set them up
+        assert!(client.measurement_triggers(1, 0x0001).is_empty());
+
+        // Elements without default process data
+        client.set_default_triggers(1, 0x0001, Vec::new());
+        client.process_message(
+            &request(
+                ProcessDataCommand::RequestValue,
+                1,
+                REQUEST_DEFAULT_PROCESS_DATA,
+                0,
+            ),
+            CLIENT,
+        );
+        client.process_message(
+            &request(
+                ProcessDataCommand::RequestValue,
+                2,
+                REQUEST_DEFAULT_PROCESS_DATA,
+                0,
+            ),
+            CLIENT,
+        );
+        let errors: Vec<u8> = sent(&mut client).iter().map(|data| data[4]).collect();
+        assert_eq!(errors, [ACK_DDI_NOT_SUPPORTED, ACK_INVALID_ELEMENT]);
+    }
+
+    #[test]
+    fn checks_the_labels() {
+        let start = Instant::now();
+        // Matching labels activate the stored pool
+        connected(start);
+
+        // An old pool is deleted and replaced
+        let mut client = client();
+        client.process_message(&from_tc(&[0x10, 4, 5, 0x1F, 0, 1, 16, 16]), CLIENT);
+        client.take_messages();
+        let mut label = vec![STRUCTURE_LABEL];
+        label.extend_from_slice(b"SPRAYR0");
+        client.process_message(&from_tc(&label), CLIENT);
+        assert_eq!(sent(&mut client), [padded(&[0xA1])]);
+        client.process_message(&from_tc(&padded(&[0xB1, 0])), CLIENT);
+        assert_eq!(sent(&mut client), [padded(&[0x41, 4, 0, 0, 0])]);
+
+        // A refused pool fails until restarted
+        let events = client.process_message(&from_tc(&padded(&[0x51, 1])), CLIENT);
+        assert_eq!(
+            events,
+            [TcClientEvent::Disconnected(TcClientError::TransferRejected)]
+        );
+        assert_eq!(client.state(), TcClientState::Failed);
+        client.restart();
+        client.process_message(&status(false), CLIENT);
+        assert_eq!(client.state(), TcClientState::WaitForVersion);
+    }
+
+    #[test]
+    fn answers_process_data() {
+        let mut client = connected(Instant::now());
+        client.set_value(1, 0x0001, 500);
+
+        client.process_message(
+            &request(ProcessDataCommand::RequestValue, 1, 0x0001, 0),
+            CLIENT,
+        );
+        assert_eq!(
+            sent(&mut client),
+            [[0x13, 0x00, 0x01, 0x00, 0xF4, 0x01, 0x00, 0x00]]
+        );
+
+        client.process_message(
+            &request(ProcessDataCommand::RequestValue, 1, 0x0002, 0),
+            CLIENT,
+        );
+        client.process_message(
+            &request(ProcessDataCommand::RequestValue, 2, 0x0001, 0),
+            CLIENT,
+        );
+        client.process_message(
+            &request(ProcessDataCommand::PeerControlAssignment, 1, 0x0001, 0),
+            CLIENT,
+        );
+        assert_eq!(
+            sent(&mut client),
+            [
+                [
+                    0x1D,
+                    0x00,
+                    0x02,
+                    0x00,
+                    ACK_DDI_NOT_SUPPORTED,
+                    0x02,
+                    0xFF,
+                    0xFF
+                ],
+                [
+                    0x2D,
+                    0x00,
+                    0x01,
+                    0x00,
+                    ACK_INVALID_ELEMENT,
+                    0x02,
+                    0xFF,
+                    0xFF
+                ],
+                [
+                    0x1D,
+                    0x00,
+                    0x01,
+                    0x00,
+                    ACK_COMMAND_NOT_SUPPORTED,
+                    0x09,
+                    0xFF,
+                    0xFF
+                ],
+            ]
+        );
+
+        let events = client.process_message(
+            &request(ProcessDataCommand::SetValueAndAcknowledge, 1, 0x0001, 300),
+            CLIENT,
+        );
+        assert_eq!(
+            events,
+            [TcClientEvent::ValueCommand {
+                element: 1,
+                ddi: 0x0001,
+                value: 300
+            }]
+        );
+        assert_eq!(
+            sent(&mut client),
+            [[0x1D, 0x00, 0x01, 0x00, 0x00, 0x0A, 0xFF, 0xFF]]
+        );
+
+        let events =
+            client.process_message(&request(ProcessDataCommand::Value, 1, 0x0001, 200), CLIENT);
+        assert_eq!(events.len(), 1);
+        assert!(sent(&mut client).is_empty());
+    }
+
+    #[test]
+    fn converts_engineering_values() {
+        let mut client = connected(Instant::now());
+        assert_eq!(client.set_engineering_value(1, 0x0001, 5.0), Ok(()));
+        assert_eq!(client.value(1, 0x0001), Some(500));
+        assert_eq!(client.engineering_value(1, 0x0001), Some(5.0));
+        assert_eq!(
+            client.set_engineering_value(1, 0xE000, 5.0),
+            Err(DdiError::UnknownDdi(0xE000))
+        );
+        assert_eq!(
+            client.set_engineering_value(1, 0x0001, -1.0),
+            Err(DdiError::OutOfRange(0x0001))
+        );
+        assert_eq!(client.value(1, 0x0001), Some(500));
+        assert_eq!(client.engineering_value(1, 0xE000), None);
+    }
+
+    #[test]
+    fn sends_measurements() {
+        let start = Instant::now();
+        let mut client = connected(start);
+        client.set_value(1, 0x0001, 500);
+        client.set_value(2, 0x0002, 0);
+        client.set_value(3, 0x0003, 0);
+        let measurements = |client: &mut TaskControllerClient| -> Vec<u16> {
+            client
+                .take_messages()
+                .iter()
+                .filter_map(|m| ProcessDataMessage::from_bytes(m.get_data()))
+                .filter(|m| m.command == ProcessDataCommand::Value)
+                .map(|m| m.element)
+                .collect()
+        };
+
+        client.process_message(
+            &request(ProcessDataCommand::MeasurementTimeInterval, 1, 0x0001, 1000),
+            CLIENT,
+        );
+        client.process_message(
+            &request(
+                ProcessDataCommand::MeasurementDistanceInterval,
+                2,
+                0x0002,
+                1000,
+            ),
+            CLIENT,
+        );
+        client.process_message(
+            &request(
+                ProcessDataCommand::MeasurementChangeThreshold,
+                3,
+                0x0003,
+                10,
+            ),
+            CLIENT,
+        );
+        assert_eq!(
+            client.measurement_triggers(1, 0x0001),
+            [MeasurementTrigger::TimeInterval(1000)]
+        );
+
+        client.update(start, CLIENT);
+        assert_eq!(measurements(&mut client), [1, 3]);
+        client.update(start + Duration::from_millis(500), CLIENT);
+        assert!(measurements(&mut client).is_empty());
+
+        client.add_distance(600);
+        client.set_value(3, 0x0003, 5);
+        client.update(start + Duration::from_millis(600), CLIENT);
+        assert!(measurements(&mut client).is_empty());
+
+        client.add_distance(500);
+        client.set_value(3, 0x0003, 12);
+        client.update(start + Duration::from_millis(1000), CLIENT);
+        assert_eq!(measurements(&mut client), [1, 2, 3]);
+
+        // An interval of 0 stops the trigger
+        client.process_message(
+            &request(ProcessDataCommand::MeasurementTimeInterval, 1, 0x0001, 0),
+            CLIENT,
+        );
+        client.update(start + Duration::from_millis(3000), CLIENT);
+        assert!(!measurements(&mut client).contains(&1));
+    }
+
+    #[test]
+    fn sends_totals_when_the_task_stops() {
+        let mut client = connected(Instant::now());
+        client.set_total(1, 0x0074, 1234);
+        client.set_value(1, 0x0001, 500);
+
+        let events = client.process_message(&status(true), CLIENT);
+        assert_eq!(events, [TcClientEvent::TaskStarted]);
+        assert!(client.is_task_active());
+        assert!(client.take_messages().is_empty());
+
+        let events = client.process_message(&status(false), CLIENT);
+        assert_eq!(events, [TcClientEvent::TaskStopped]);
+        assert_eq!(
+            sent(&mut client),
+            [[0x13, 0x00, 0x74, 0x00, 0xD2, 0x04, 0x00, 0x00]]
+        );
+    }
+
+    #[test]
+    fn times_out() {
+        let start = Instant::now();
+        let mut client = client();
+        client.update(start, CLIENT);
+        let messages = client.take_messages();
+        assert_eq!(
+            messages[0].get_identifier().destination_address(),
+            Address::GLOBAL
+        );
+        let data: Vec<_> = messages[1..].iter().map(|m| m.get_data()).collect();
+        assert_eq!(
+            data,
+            [padded(&[0x00]), padded(&[0xFF, 0xFF, 0xFF, 0xFF, 0])]
+        );
+
+        client.process_message(&status(false), CLIENT);
+        let events = client.update(start + Duration::from_secs(7), CLIENT);
+        assert_eq!(
+            events,
+            [TcClientEvent::Disconnected(TcClientError::Timeout(
+                TcClientState::WaitForVersion
+            ))]
+        );
+        assert_eq!(client.state(), TcClientState::WaitForServer);
+
+        let mut client = connected(start);
+        let events = client.update(start + Duration::from_secs(7), CLIENT);
+        assert_eq!(
+            events,
+            [TcClientEvent::Disconnected(TcClientError::StatusLost)]
+        );
+        assert!(client.take_messages().is_empty());
+    }
+}
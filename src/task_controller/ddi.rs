@@ -0,0 +1,486 @@
+// Copyright 2023 Raven Industries inc.
+
+/// The first proprietary DDI, DDIs from here up to [`LAST_PROPRIETARY_DDI`] are defined by
+/// manufacturers
+pub const FIRST_PROPRIETARY_DDI: u16 = 0xE000;
+/// The largest DDI a device can use, 0xFFFF is reserved
+pub const LAST_PROPRIETARY_DDI: u16 = 0xFFFE;
+/// The DDI the TC uses to request the default process data of an element
+pub const REQUEST_DEFAULT_PROCESS_DATA: u16 = 0xDFFF;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DdiError {
+    /// The DDI is not in the data dictionary table
+    UnknownDdi(u16),
+    /// The value is outside of the range of the DDI
+    OutOfRange(u16),
+}
+
+impl std::fmt::Display for DdiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for DdiError {}
+
+/// The definition of a data dictionary identifier (DDI) from ISO 11783-11
+///
+/// Process data values are integers, the value in the unit of the DDI is the integer times
+/// the resolution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DdiDefinition {
+    pub ddi: u16,
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub resolution: f64,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl DdiDefinition {
+    /// The definition of `ddi`, `None` for proprietary DDIs and DDIs missing in the table
+    pub fn lookup(ddi: u16) -> Option<&'static DdiDefinition> {
+        DEFINITIONS
+            .binary_search_by_key(&ddi, |d| d.ddi)
+            .ok()
+            .map(|index| &DEFINITIONS[index])
+    }
+
+    /// The definition named `name`, ignoring case
+    pub fn by_name(name: &str) -> Option<&'static DdiDefinition> {
+        DEFINITIONS
+            .iter()
+            .find(|d| d.name.eq_ignore_ascii_case(name))
+    }
+
+    /// All definitions in the table, ordered by DDI
+    pub fn all() -> &'static [DdiDefinition] {
+        DEFINITIONS
+    }
+
+    pub fn is_proprietary(ddi: u16) -> bool {
+        (FIRST_PROPRIETARY_DDI..=LAST_PROPRIETARY_DDI).contains(&ddi)
+    }
+
+    /// The number of decimals the resolution has
+    pub fn decimals(&self) -> u8 {
+        (-self.resolution.log10()).round().max(0.0) as u8
+    }
+
+    /// The value of `raw` in the unit of the DDI
+    pub fn to_engineering(&self, raw: i32) -> f64 {
+        f64::from(raw) * self.resolution
+    }
+
+    /// The raw value of `value` in the unit of the DDI, rounded to the resolution
+    pub fn to_raw(&self, value: f64) -> Result<i32, DdiError> {
+        let raw = (value / self.resolution).round();
+        if raw.is_nan() || raw < f64::from(self.min) || raw > f64::from(self.max) {
+            return Err(DdiError::OutOfRange(self.ddi));
+        }
+        Ok(raw as i32)
+    }
+}
+
+/// Table entries, `ddi "name", "unit", resolution, min, max;`
+macro_rules! definitions {
+    ($($ddi:literal $name:literal, $unit:literal, $resolution:literal, $min:expr, $max:expr;)*) => {
+        &[$(DdiDefinition {
+            ddi: $ddi,
+            name: $name,
+            unit: $unit,
+            resolution: $resolution,
+            min: $min,
+            max: $max,
+        }),*]
+    };
+}
+
+const MAX: i32 = i32::MAX;
+const MIN: i32 = i32::MIN;
+
+/// The data dictionary of ISO 11783-11, up to DDI 333
+///
+/// Refresh the entries from a newer export of the ISO 11783-11 online data base with the output
+/// of `cargo run --example ddi_table -- <export>`.
+static DEFINITIONS: &[DdiDefinition] = definitions! {
+    1 "Setpoint Volume Per Area Application Rate", "mm³/m²", 0.01, 0, MAX;
+    2 "Actual Volume Per Area Application Rate", "mm³/m²", 0.01, 0, MAX;
+    3 "Default Volume Per Area Application Rate", "mm³/m²", 0.01, 0, MAX;
+    4 "Minimum Volume Per Area Application Rate", "mm³/m²", 0.01, 0, MAX;
+    5 "Maximum Volume Per Area Application Rate", "mm³/m²", 0.01, 0, MAX;
+    6 "Setpoint Mass Per Area Application Rate", "mg/m²", 1.0, 0, MAX;
+    7 "Actual Mass Per Area Application Rate", "mg/m²", 1.0, 0, MAX;
+    8 "Default Mass Per Area Application Rate", "mg/m²", 1.0, 0, MAX;
+    9 "Minimum Mass Per Area Application Rate", "mg/m²", 1.0, 0, MAX;
+    10 "Maximum Mass Per Area Application Rate", "mg/m²", 1.0, 0, MAX;
+    11 "Setpoint Count Per Area Application Rate", "/m²", 0.001, 0, MAX;
+    12 "Actual Count Per Area Application Rate", "/m²", 0.001, 0, MAX;
+    13 "Default Count Per Area Application Rate", "/m²", 0.001, 0, MAX;
+    14 "Minimum Count Per Area Application Rate", "/m²", 0.001, 0, MAX;
+    15 "Maximum Count Per Area Application Rate", "/m²", 0.001, 0, MAX;
+    16 "Setpoint Spacing Application Rate", "mm", 1.0, 0, MAX;
+    17 "Actual Spacing Application Rate", "mm", 1.0, 0, MAX;
+    18 "Default Spacing Application Rate", "mm", 1.0, 0, MAX;
+    19 "Minimum Spacing Application Rate", "mm", 1.0, 0, MAX;
+    20 "Maximum Spacing Application Rate", "mm", 1.0, 0, MAX;
+    21 "Setpoint Volume Per Volume Application Rate", "mm³/m³", 1.0, 0, MAX;
+    22 "Actual Volume Per Volume Application Rate", "mm³/m³", 1.0, 0, MAX;
+    23 "Default Volume Per Volume Application Rate", "mm³/m³", 1.0, 0, MAX;
+    24 "Minimum Volume Per Volume Application Rate", "mm³/m³", 1.0, 0, MAX;
+    25 "Maximum Volume Per Volume Application Rate", "mm³/m³", 1.0, 0, MAX;
+    26 "Setpoint Mass Per Mass Application Rate", "mg/kg", 1.0, 0, MAX;
+    27 "Actual Mass Per Mass Application Rate", "mg/kg", 1.0, 0, MAX;
+    28 "Default Mass Per Mass Application Rate", "mg/kg", 1.0, 0, MAX;
+    29 "Minimum Mass Per Mass Application Rate", "mg/kg", 1.0, 0, MAX;
+    30 "Maximum Mass Per Mass Application Rate", "mg/kg", 1.0, 0, MAX;
+    31 "Setpoint Volume Per Mass Application Rate", "mm³/kg", 1.0, 0, MAX;
+    32 "Actual Volume Per Mass Application Rate", "mm³/kg", 1.0, 0, MAX;
+    33 "Default Volume Per Mass Application Rate", "mm³/kg", 1.0, 0, MAX;
+    34 "Minimum Volume Per Mass Application Rate", "mm³/kg", 1.0, 0, MAX;
+    35 "Maximum Volume Per Mass Application Rate", "mm³/kg", 1.0, 0, MAX;
+    36 "Setpoint Volume Per Time Application Rate", "mm³/s", 1.0, 0, MAX;
+    37 "Actual Volume Per Time Application Rate", "mm³/s", 1.0, 0, MAX;
+    38 "Default Volume Per Time Application Rate", "mm³/s", 1.0, 0, MAX;
+    39 "Minimum Volume Per Time Application Rate", "mm³/s", 1.0, 0, MAX;
+    40 "Maximum Volume Per Time Application Rate", "mm³/s", 1.0, 0, MAX;
+    41 "Setpoint Mass Per Time Application Rate", "mg/s", 1.0, 0, MAX;
+    42 "Actual Mass Per Time Application Rate", "mg/s", 1.0, 0, MAX;
+    43 "Default Mass Per Time Application Rate", "mg/s", 1.0, 0, MAX;
+    44 "Minimum Mass Per Time Application Rate", "mg/s", 1.0, 0, MAX;
+    45 "Maximum Mass Per Time Application Rate", "mg/s", 1.0, 0, MAX;
+    46 "Setpoint Count Per Time Application Rate", "/s", 0.001, 0, MAX;
+    47 "Actual Count Per Time Application Rate", "/s", 0.001, 0, MAX;
+    48 "Default Count Per Time Application Rate", "/s", 0.001, 0, MAX;
+    49 "Minimum Count Per Time Application Rate", "/s", 0.001, 0, MAX;
+    50 "Maximum Count Per Time Application Rate", "/s", 0.001, 0, MAX;
+    51 "Setpoint Tillage Depth", "mm", 1.0, MIN, MAX;
+    52 "Actual Tillage Depth", "mm", 1.0, MIN, MAX;
+    53 "Default Tillage Depth", "mm", 1.0, MIN, MAX;
+    54 "Minimum Tillage Depth", "mm", 1.0, MIN, MAX;
+    55 "Maximum Tillage Depth", "mm", 1.0, MIN, MAX;
+    56 "Setpoint Seeding Depth", "mm", 1.0, MIN, MAX;
+    57 "Actual Seeding Depth", "mm", 1.0, MIN, MAX;
+    58 "Default Seeding Depth", "mm", 1.0, MIN, MAX;
+    59 "Minimum Seeding Depth", "mm", 1.0, MIN, MAX;
+    60 "Maximum Seeding Depth", "mm", 1.0, MIN, MAX;
+    61 "Setpoint Working Height", "mm", 1.0, MIN, MAX;
+    62 "Actual Working Height", "mm", 1.0, MIN, MAX;
+    63 "Default Working Height", "mm", 1.0, MIN, MAX;
+    64 "Minimum Working Height", "mm", 1.0, MIN, MAX;
+    65 "Maximum Working Height", "mm", 1.0, MIN, MAX;
+    66 "Setpoint Working Width", "mm", 1.0, 0, MAX;
+    67 "Actual Working Width", "mm", 1.0, 0, MAX;
+    68 "Default Working Width", "mm", 1.0, 0, MAX;
+    69 "Minimum Working Width", "mm", 1.0, 0, MAX;
+    70 "Maximum Working Width", "mm", 1.0, 0, MAX;
+    71 "Setpoint Volume Content", "ml", 1.0, 0, MAX;
+    72 "Actual Volume Content", "ml", 1.0, 0, MAX;
+    73 "Maximum Volume Content", "ml", 1.0, 0, MAX;
+    74 "Setpoint Mass Content", "g", 1.0, 0, MAX;
+    75 "Actual Mass Content", "g", 1.0, 0, MAX;
+    76 "Maximum Mass Content", "g", 1.0, 0, MAX;
+    77 "Setpoint Count Content", "#", 1.0, 0, MAX;
+    78 "Actual Count Content", "#", 1.0, 0, MAX;
+    79 "Maximum Count Content", "#", 1.0, 0, MAX;
+    80 "Application Total Volume", "L", 1.0, 0, MAX;
+    81 "Application Total Mass", "kg", 1.0, 0, MAX;
+    82 "Application Total Count", "#", 1.0, 0, MAX;
+    83 "Volume Per Area Yield", "ml/m²", 1.0, 0, MAX;
+    84 "Mass Per Area Yield", "mg/m²", 1.0, 0, MAX;
+    85 "Count Per Area Yield", "/m²", 0.001, 0, MAX;
+    86 "Volume Per Time Yield", "ml/s", 1.0, 0, MAX;
+    87 "Mass Per Time Yield", "mg/s", 1.0, 0, MAX;
+    88 "Count Per Time Yield", "/s", 0.001, 0, MAX;
+    89 "Yield Total Volume", "L", 1.0, 0, MAX;
+    90 "Yield Total Mass", "kg", 1.0, 0, MAX;
+    91 "Yield Total Count", "#", 1.0, 0, MAX;
+    92 "Volume Per Area Crop Loss", "ml/m²", 1.0, 0, MAX;
+    93 "Mass Per Area Crop Loss", "mg/m²", 1.0, 0, MAX;
+    94 "Count Per Area Crop Loss", "/m²", 0.001, 0, MAX;
+    95 "Volume Per Time Crop Loss", "ml/s", 1.0, 0, MAX;
+    96 "Mass Per Time Crop Loss", "mg/s", 1.0, 0, MAX;
+    97 "Count Per Time Crop Loss", "/s", 0.001, 0, MAX;
+    98 "Percentage Crop Loss", "ppm", 1.0, 0, MAX;
+    99 "Crop Moisture", "ppm", 1.0, 0, MAX;
+    100 "Crop Contamination", "ppm", 1.0, 0, MAX;
+    101 "Setpoint Bale Width", "mm", 1.0, 0, MAX;
+    102 "Actual Bale Width", "mm", 1.0, 0, MAX;
+    103 "Default Bale Width", "mm", 1.0, 0, MAX;
+    104 "Minimum Bale Width", "mm", 1.0, 0, MAX;
+    105 "Maximum Bale Width", "mm", 1.0, 0, MAX;
+    106 "Setpoint Bale Height", "mm", 1.0, 0, MAX;
+    107 "Actual Bale Height", "mm", 1.0, 0, MAX;
+    108 "Default Bale Height", "mm", 1.0, 0, MAX;
+    109 "Minimum Bale Height", "mm", 1.0, 0, MAX;
+    110 "Maximum Bale Height", "mm", 1.0, 0, MAX;
+    111 "Setpoint Bale Size", "mm", 1.0, 0, MAX;
+    112 "Actual Bale Size", "mm", 1.0, 0, MAX;
+    113 "Default Bale Size", "mm", 1.0, 0, MAX;
+    114 "Minimum Bale Size", "mm", 1.0, 0, MAX;
+    115 "Maximum Bale Size", "mm", 1.0, 0, MAX;
+    116 "Total Area", "m²", 1.0, 0, MAX;
+    117 "Effective Total Distance", "mm", 1.0, 0, MAX;
+    118 "Ineffective Total Distance", "mm", 1.0, 0, MAX;
+    119 "Effective Total Time", "s", 1.0, 0, MAX;
+    120 "Ineffective Total Time", "s", 1.0, 0, MAX;
+    121 "Product Density Mass Per Volume", "mg/l", 1.0, 0, MAX;
+    122 "Product Density Mass Per Count", "mg/1000", 1.0, 0, MAX;
+    123 "Product Density Volume Per Count", "ml/1000", 1.0, 0, MAX;
+    124 "Auxiliary Valve Scaling Extend", "%", 0.1, 0, 1000;
+    125 "Auxiliary Valve Scaling Retract", "%", 0.1, 0, 1000;
+    126 "Auxiliary Valve Ramp Extend Up", "ms", 1.0, 0, MAX;
+    127 "Auxiliary Valve Ramp Extend Down", "ms", 1.0, 0, MAX;
+    128 "Auxiliary Valve Ramp Retract Up", "ms", 1.0, 0, MAX;
+    129 "Auxiliary Valve Ramp Retract Down", "ms", 1.0, 0, MAX;
+    130 "Auxiliary Valve Float Threshold", "%", 0.1, 0, 1000;
+    131 "Auxiliary Valve Progressivity Extend", "", 1.0, 0, MAX;
+    132 "Auxiliary Valve Progressivity Retract", "", 1.0, 0, MAX;
+    133 "Auxiliary Valve Invert Ports", "", 1.0, 0, 1;
+    134 "Device Element Offset X", "mm", 1.0, MIN, MAX;
+    135 "Device Element Offset Y", "mm", 1.0, MIN, MAX;
+    136 "Device Element Offset Z", "mm", 1.0, MIN, MAX;
+    137 "Device Volume Capacity", "ml", 1.0, 0, MAX;
+    138 "Device Mass Capacity", "g", 1.0, 0, MAX;
+    139 "Device Count Capacity", "#", 1.0, 0, MAX;
+    140 "Setpoint Percentage Application Rate", "ppm", 1.0, 0, MAX;
+    141 "Actual Work State", "", 1.0, 0, 3;
+    142 "Physical Setpoint Time Latency", "ms", 1.0, 0, 60000;
+    143 "Physical Actual Value Time Latency", "ms", 1.0, -60000, 60000;
+    144 "Yaw Angle", "°", 0.001, -180000, 180000;
+    145 "Roll Angle", "°", 0.001, -180000, 180000;
+    146 "Pitch Angle", "°", 0.001, -180000, 180000;
+    147 "Log Count", "", 1.0, 0, MAX;
+    148 "Total Fuel Consumption", "ml", 1.0, 0, MAX;
+    149 "Instantaneous Fuel Consumption per Time", "mm³/s", 1.0, 0, MAX;
+    150 "Instantaneous Fuel Consumption per Area", "mm³/m²", 1.0, 0, MAX;
+    151 "Instantaneous Area Per Time Capacity", "mm²/s", 1.0, 0, MAX;
+    153 "Actual Normalized Difference Vegetative Index (NDVI)", "", 0.001, -1000, 1000;
+    154 "Physical Object Length", "mm", 1.0, 0, MAX;
+    155 "Physical Object Width", "mm", 1.0, 0, MAX;
+    156 "Physical Object Height", "mm", 1.0, 0, MAX;
+    157 "Connector Type", "", 1.0, 0, MAX;
+    158 "Prescription Control State", "", 1.0, 0, 3;
+    159 "Number of Sub-Units per Section", "#", 1.0, 0, MAX;
+    160 "Section Control State", "", 1.0, 0, 3;
+    161 "Actual Condensed Work State (1-16)", "", 1.0, MIN, MAX;
+    162 "Actual Condensed Work State (17-32)", "", 1.0, MIN, MAX;
+    163 "Actual Condensed Work State (33-48)", "", 1.0, MIN, MAX;
+    164 "Actual Condensed Work State (49-64)", "", 1.0, MIN, MAX;
+    165 "Actual Condensed Work State (65-80)", "", 1.0, MIN, MAX;
+    166 "Actual Condensed Work State (81-96)", "", 1.0, MIN, MAX;
+    167 "Actual Condensed Work State (97-112)", "", 1.0, MIN, MAX;
+    168 "Actual Condensed Work State (113-128)", "", 1.0, MIN, MAX;
+    169 "Actual Condensed Work State (129-144)", "", 1.0, MIN, MAX;
+    170 "Actual Condensed Work State (145-160)", "", 1.0, MIN, MAX;
+    171 "Actual Condensed Work State (161-176)", "", 1.0, MIN, MAX;
+    172 "Actual Condensed Work State (177-192)", "", 1.0, MIN, MAX;
+    173 "Actual Condensed Work State (193-208)", "", 1.0, MIN, MAX;
+    174 "Actual Condensed Work State (209-224)", "", 1.0, MIN, MAX;
+    175 "Actual Condensed Work State (225-240)", "", 1.0, MIN, MAX;
+    176 "Actual Condensed Work State (241-256)", "", 1.0, MIN, MAX;
+    177 "Actual length of cut", "mm", 0.001, 0, MAX;
+    178 "Element Type Instance", "", 1.0, 0, 65533;
+    179 "Actual Cultural Practice", "", 1.0, MIN, MAX;
+    180 "Device Reference Point (DRP) to Ground distance", "mm", 1.0, MIN, MAX;
+    181 "Dry Mass Per Area Yield", "mg/m²", 1.0, 0, MAX;
+    182 "Dry Mass Per Time Yield", "mg/s", 1.0, 0, MAX;
+    183 "Yield Total Dry Mass", "kg", 1.0, 0, MAX;
+    184 "Reference Moisture For Dry Mass", "ppm", 1.0, 0, MAX;
+    185 "Seed Cotton Mass Per Area Yield", "mg/m²", 1.0, 0, MAX;
+    186 "Lint Cotton Mass Per Area Yield", "mg/m²", 1.0, 0, MAX;
+    187 "Lint Turnout Percentage", "ppm", 1.0, 0, 1000000;
+    188 "Ambient temperature", "mK", 1.0, 0, 1000000;
+    189 "Setpoint Product Pressure", "Pa", 0.1, MIN, MAX;
+    190 "Actual Product Pressure", "Pa", 0.1, MIN, MAX;
+    191 "Minimum Product Pressure", "Pa", 0.1, MIN, MAX;
+    192 "Maximum Product Pressure", "Pa", 0.1, MIN, MAX;
+    193 "Setpoint Pump Output Pressure", "Pa", 0.1, MIN, MAX;
+    194 "Actual Pump Output Pressure", "Pa", 0.1, MIN, MAX;
+    195 "Minimum Pump Output Pressure", "Pa", 0.1, MIN, MAX;
+    196 "Maximum Pump Output Pressure", "Pa", 0.1, MIN, MAX;
+    197 "Setpoint Tank Agitation Pressure", "Pa", 0.1, MIN, MAX;
+    198 "Actual Tank Agitation Pressure", "Pa", 0.1, MIN, MAX;
+    199 "Minimum Tank Agitation Pressure", "Pa", 0.1, MIN, MAX;
+    200 "Maximum Tank Agitation Pressure", "Pa", 0.1, MIN, MAX;
+    201 "SC Turn On Time", "ms", 1.0, 0, MAX;
+    202 "SC Turn Off Time", "ms", 1.0, 0, MAX;
+    203 "Wind speed", "mm/s", 1.0, 0, MAX;
+    204 "Wind direction", "°", 1.0, 0, 359;
+    205 "Air Humidity", "%", 1.0, 0, 100;
+    206 "Sky conditions", "", 1.0, MIN, MAX;
+    207 "Last Bale Flakes per Bale", "#", 1.0, 0, MAX;
+    208 "Last Bale Average Moisture", "ppm", 1.0, 0, MAX;
+    209 "Last Bale Average Strokes per Flake", "#", 1.0, 0, MAX;
+    210 "Lifetime Bale Count", "#", 1.0, 0, MAX;
+    211 "Lifetime Working Hours", "h", 0.05, 0, MAX;
+    212 "Actual Bale Hydraulic Pressure", "Pa", 1.0, 0, MAX;
+    213 "Last Bale Average Hydraulic Pressure", "Pa", 1.0, 0, MAX;
+    214 "Setpoint Bale Compression Plunger Load", "", 1.0, 0, 1000;
+    215 "Actual Bale Compression Plunger Load", "", 1.0, 0, 1000;
+    216 "Last Bale Average Bale Compression Plunger Load", "", 1.0, 0, 1000;
+    217 "Last Bale Applied Preservative", "ml", 1.0, 0, MAX;
+    218 "Last Bale Tag Number", "", 1.0, 0, MAX;
+    219 "Last Bale Mass", "g", 1.0, 0, MAX;
+    220 "Delta T", "mK", 1.0, MIN, MAX;
+    221 "Setpoint Working Length", "mm", 1.0, 0, MAX;
+    222 "Actual Working Length", "mm", 1.0, 0, MAX;
+    223 "Minimum Working Length", "mm", 1.0, 0, MAX;
+    224 "Maximum Working Length", "mm", 1.0, 0, MAX;
+    225 "Actual Net Weight", "g", 1.0, MIN, MAX;
+    226 "Net Weight State", "", 1.0, 0, 3;
+    227 "Setpoint Net Weight", "g", 1.0, MIN, MAX;
+    228 "Actual Gross Weight", "g", 1.0, MIN, MAX;
+    229 "Gross Weight State", "", 1.0, 0, 3;
+    230 "Minimum Gross Weight", "g", 1.0, MIN, MAX;
+    231 "Maximum Gross Weight", "g", 1.0, MIN, MAX;
+    232 "Thresher Engagement Total Time", "s", 1.0, 0, MAX;
+    233 "Actual Header Working Height Status", "", 1.0, 0, 3;
+    234 "Actual Header Rotational Speed Status", "", 1.0, 0, 3;
+    235 "Yield Hold Status", "", 1.0, 0, 3;
+    236 "Actual (Un)Loading System Status", "", 1.0, 0, MAX;
+    237 "Crop Temperature", "mK", 1.0, 0, MAX;
+    238 "Setpoint Sieve Clearance", "mm", 0.001, 0, MAX;
+    239 "Actual Sieve Clearance", "mm", 0.001, 0, MAX;
+    240 "Minimum Sieve Clearance", "mm", 0.001, 0, MAX;
+    241 "Maximum Sieve Clearance", "mm", 0.001, 0, MAX;
+    242 "Setpoint Chaffer Clearance", "mm", 0.001, 0, MAX;
+    243 "Actual Chaffer Clearance", "mm", 0.001, 0, MAX;
+    244 "Minimum Chaffer Clearance", "mm", 0.001, 0, MAX;
+    245 "Maximum Chaffer Clearance", "mm", 0.001, 0, MAX;
+    246 "Setpoint Concave Clearance", "mm", 0.001, 0, MAX;
+    247 "Actual Concave Clearance", "mm", 0.001, 0, MAX;
+    248 "Minimum Concave Clearance", "mm", 0.001, 0, MAX;
+    249 "Maximum Concave Clearance", "mm", 0.001, 0, MAX;
+    250 "Setpoint Separation Fan Rotational Speed", "r/min", 0.001, 0, MAX;
+    251 "Actual Separation Fan Rotational Speed", "r/min", 0.001, 0, MAX;
+    252 "Minimum Separation Fan Rotational Speed", "r/min", 0.001, 0, MAX;
+    253 "Maximum Separation Fan Rotational Speed", "r/min", 0.001, 0, MAX;
+    254 "Hydraulic Oil Temperature", "mK", 1.0, 0, 2000000;
+    255 "Yield Lag Ignore Time", "ms", 1.0, 0, MAX;
+    256 "Yield Lead Ignore Time", "ms", 1.0, 0, MAX;
+    257 "Average Yield Mass Per Time", "mg/s", 1.0, 0, MAX;
+    258 "Average Crop Moisture", "ppm", 1.0, 0, MAX;
+    259 "Average Yield Mass Per Area", "mg/m²", 1.0, 0, MAX;
+    260 "Connector Pivot X-Offset", "mm", 1.0, MIN, MAX;
+    261 "Remaining Area", "m²", 1.0, 0, MAX;
+    262 "Lifetime Application Total Mass", "kg", 1.0, 0, MAX;
+    263 "Lifetime Application Total Count", "#", 1.0, 0, MAX;
+    264 "Lifetime Yield Total Volume", "L", 1.0, 0, MAX;
+    265 "Lifetime Yield Total Mass", "kg", 1.0, 0, MAX;
+    266 "Lifetime Yield Total Count", "#", 1.0, 0, MAX;
+    267 "Lifetime Total Area", "m²", 1.0, 0, MAX;
+    268 "Lifetime Effective Total Distance", "m", 1.0, 0, MAX;
+    269 "Lifetime Ineffective Total Distance", "m", 1.0, 0, MAX;
+    270 "Lifetime Effective Total Time", "h", 0.05, 0, MAX;
+    271 "Lifetime Ineffective Total Time", "h", 0.05, 0, MAX;
+    272 "Lifetime Fuel Consumption", "L", 0.5, 0, MAX;
+    273 "Lifetime Average Fuel Consumption per Time", "mm³/s", 1.0, 0, MAX;
+    274 "Lifetime Average Fuel Consumption per Area", "mm³/m²", 1.0, 0, MAX;
+    275 "Lifetime Yield Total Dry Mass", "kg", 1.0, 0, MAX;
+    276 "Lifetime Seed Cotton Total Mass", "kg", 1.0, 0, MAX;
+    277 "Lifetime Lint Cotton Total Mass", "kg", 1.0, 0, MAX;
+    278 "Lifetime Threshing Engagement Total Time", "h", 0.05, 0, MAX;
+    279 "Precut Total Count", "#", 1.0, 0, MAX;
+    280 "Uncut Total Count", "#", 1.0, 0, MAX;
+    281 "Lifetime Precut Total Count", "#", 1.0, 0, MAX;
+    282 "Lifetime Uncut Total Count", "#", 1.0, 0, MAX;
+    283 "Setpoint Prescription Mode", "", 1.0, 0, 5;
+    284 "Actual Prescription Mode", "", 1.0, 0, 5;
+    289 "Setpoint Work State", "", 1.0, 0, 3;
+    290 "Setpoint Condensed Work State (1-16)", "", 1.0, MIN, MAX;
+    291 "Setpoint Condensed Work State (17-32)", "", 1.0, MIN, MAX;
+    292 "Setpoint Condensed Work State (33-48)", "", 1.0, MIN, MAX;
+    293 "Setpoint Condensed Work State (49-64)", "", 1.0, MIN, MAX;
+    294 "Setpoint Condensed Work State (65-80)", "", 1.0, MIN, MAX;
+    295 "Setpoint Condensed Work State (81-96)", "", 1.0, MIN, MAX;
+    296 "Setpoint Condensed Work State (97-112)", "", 1.0, MIN, MAX;
+    297 "Setpoint Condensed Work State (113-128)", "", 1.0, MIN, MAX;
+    298 "Setpoint Condensed Work State (129-144)", "", 1.0, MIN, MAX;
+    299 "Setpoint Condensed Work State (145-160)", "", 1.0, MIN, MAX;
+    300 "Setpoint Condensed Work State (161-176)", "", 1.0, MIN, MAX;
+    301 "Setpoint Condensed Work State (177-192)", "", 1.0, MIN, MAX;
+    302 "Setpoint Condensed Work State (193-208)", "", 1.0, MIN, MAX;
+    303 "Setpoint Condensed Work State (209-224)", "", 1.0, MIN, MAX;
+    304 "Setpoint Condensed Work State (225-240)", "", 1.0, MIN, MAX;
+    305 "Setpoint Condensed Work State (241-256)", "", 1.0, MIN, MAX;
+    306 "True Rotation Point X-Offset", "mm", 1.0, MIN, MAX;
+    307 "True Rotation Point Y-Offset", "mm", 1.0, MIN, MAX;
+    308 "Actual Percentage Application Rate", "ppm", 1.0, 0, MAX;
+    309 "Minimum Percentage Application Rate", "ppm", 1.0, 0, MAX;
+    310 "Maximum Percentage Application Rate", "ppm", 1.0, 0, MAX;
+    311 "Relative Yield Potential", "ppm", 1.0, 0, MAX;
+    312 "Minimum Relative Yield Potential", "ppm", 1.0, 0, MAX;
+    313 "Maximum Relative Yield Potential", "ppm", 1.0, 0, MAX;
+    314 "Actual Percentage Crop Dry Matter", "ppm", 1.0, 0, MAX;
+    315 "Average Percentage Crop Dry Matter", "ppm", 1.0, 0, MAX;
+    316 "Effective Total Fuel Consumption", "ml", 1.0, 0, MAX;
+    317 "Ineffective Total Fuel Consumption", "ml", 1.0, 0, MAX;
+    318 "Effective Total Diesel Exhaust Fluid Consumption", "ml", 1.0, 0, MAX;
+    319 "Ineffective Total Diesel Exhaust Fluid Consumption", "ml", 1.0, 0, MAX;
+    320 "Last loaded Weight", "g", 1.0, MIN, MAX;
+    321 "Last unloaded Weight", "g", 1.0, MIN, MAX;
+    322 "Load Identification Number", "", 1.0, 0, MAX;
+    323 "Unload Identification Number", "", 1.0, 0, MAX;
+    324 "Chopper Engagement Total Time", "s", 1.0, 0, MAX;
+    325 "Lifetime Application Total Volume", "L", 1.0, 0, MAX;
+    326 "Setpoint Header Speed", "mm/s", 1.0, 0, MAX;
+    327 "Actual Header Speed", "mm/s", 1.0, 0, MAX;
+    328 "Minimum Header Speed", "mm/s", 1.0, 0, MAX;
+    329 "Maximum Header Speed", "mm/s", 1.0, 0, MAX;
+    330 "Setpoint Cutting drum speed", "r/min", 0.001, 0, MAX;
+    331 "Actual Cutting drum speed", "r/min", 0.001, 0, MAX;
+    332 "Minimum Cutting drum speed", "r/min", 0.001, 0, MAX;
+    333 "Maximum Cutting drum speed", "r/min", 0.001, 0, MAX;
+    57343 "Request Default Process Data", "", 1.0, MIN, MAX;
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_ordered() {
+        assert!(DEFINITIONS.windows(2).all(|w| w[0].ddi < w[1].ddi));
+        assert!(DEFINITIONS.iter().all(|d| d.min <= d.max));
+    }
+
+    #[test]
+    fn lookup() {
+        let rate = DdiDefinition::lookup(1).unwrap();
+        assert_eq!(rate.name, "Setpoint Volume Per Area Application Rate");
+        assert_eq!(rate.unit, "mm³/m²");
+        assert_eq!(rate.decimals(), 2);
+        assert_eq!(
+            DdiDefinition::by_name("actual work state").map(|d| d.ddi),
+            Some(141)
+        );
+        assert_eq!(
+            DdiDefinition::lookup(REQUEST_DEFAULT_PROCESS_DATA)
+                .unwrap()
+                .name,
+            "Request Default Process Data"
+        );
+        assert_eq!(DdiDefinition::lookup(0xE001), None);
+        assert!(DdiDefinition::is_proprietary(0xE001));
+        assert!(DdiDefinition::is_proprietary(0xFE00));
+        assert!(DdiDefinition::is_proprietary(LAST_PROPRIETARY_DDI));
+        assert!(!DdiDefinition::is_proprietary(0xFFFF));
+    }
+
+    #[test]
+    fn conversions() {
+        // 200 l/ha is 20000 mm³/m²
+        let rate = DdiDefinition::lookup(1).unwrap();
+        assert_eq!(rate.to_raw(20000.0), Ok(2_000_000));
+        assert_eq!(rate.to_engineering(2_000_000), 20000.0);
+        assert_eq!(rate.to_raw(0.004), Ok(0));
+        assert_eq!(rate.to_raw(-1.0), Err(DdiError::OutOfRange(1)));
+
+        let work_state = DdiDefinition::lookup(141).unwrap();
+        assert_eq!(work_state.to_raw(1.0), Ok(1));
+        assert_eq!(work_state.to_raw(4.0), Err(DdiError::OutOfRange(141)));
+        assert_eq!(work_state.to_raw(f64::NAN), Err(DdiError::OutOfRange(141)));
+        assert_eq!(work_state.decimals(), 0);
+    }
+}
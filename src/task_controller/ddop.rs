@@ -0,0 +1,770 @@
+// Copyright 2023 Raven Industries inc.
+
+use crate::network_management::language_command::LanguageCommand;
+use crate::network_management::name::NAME;
+use crate::object_pool::{NullableObjectId, ObjectId};
+
+/// The object types of a device descriptor object pool, with their table IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DdopObjectType {
+    Device,
+    DeviceElement,
+    DeviceProcessData,
+    DeviceProperty,
+    DeviceValuePresentation,
+}
+
+impl DdopObjectType {
+    /// The three letter table ID that starts an object of this type
+    pub fn table_id(self) -> [u8; 3] {
+        *match self {
+            DdopObjectType::Device => b"DVC",
+            DdopObjectType::DeviceElement => b"DET",
+            DdopObjectType::DeviceProcessData => b"DPD",
+            DdopObjectType::DeviceProperty => b"DPT",
+            DdopObjectType::DeviceValuePresentation => b"DVP",
+        }
+    }
+
+    pub fn from_table_id(table_id: [u8; 3]) -> Option<Self> {
+        [
+            DdopObjectType::Device,
+            DdopObjectType::DeviceElement,
+            DdopObjectType::DeviceProcessData,
+            DdopObjectType::DeviceProperty,
+            DdopObjectType::DeviceValuePresentation,
+        ]
+        .into_iter()
+        .find(|object_type| object_type.table_id() == table_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceElementType {
+    /// The root element, there is exactly one with element number 0
+    Device,
+    Function,
+    Bin,
+    Section,
+    Unit,
+    Connector,
+    NavigationReference,
+    Reserved(u8),
+}
+
+impl From<u8> for DeviceElementType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => DeviceElementType::Device,
+            2 => DeviceElementType::Function,
+            3 => DeviceElementType::Bin,
+            4 => DeviceElementType::Section,
+            5 => DeviceElementType::Unit,
+            6 => DeviceElementType::Connector,
+            7 => DeviceElementType::NavigationReference,
+            value => DeviceElementType::Reserved(value),
+        }
+    }
+}
+
+impl From<DeviceElementType> for u8 {
+    fn from(value: DeviceElementType) -> Self {
+        match value {
+            DeviceElementType::Device => 1,
+            DeviceElementType::Function => 2,
+            DeviceElementType::Bin => 3,
+            DeviceElementType::Section => 4,
+            DeviceElementType::Unit => 5,
+            DeviceElementType::Connector => 6,
+            DeviceElementType::NavigationReference => 7,
+            DeviceElementType::Reserved(value) => value,
+        }
+    }
+}
+
+/// The properties of a Device Process Data object
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessDataProperties {
+    /// Sent when the TC requests the default process data
+    pub member_of_default_set: bool,
+    /// The TC may set the value
+    pub settable: bool,
+    /// TC version 4: the value is the source of control, not settable at the same time
+    pub control_source: bool,
+}
+
+impl From<u8> for ProcessDataProperties {
+    fn from(value: u8) -> Self {
+        ProcessDataProperties {
+            member_of_default_set: value & 0x01 != 0,
+            settable: value & 0x02 != 0,
+            control_source: value & 0x04 != 0,
+        }
+    }
+}
+
+impl From<ProcessDataProperties> for u8 {
+    fn from(value: ProcessDataProperties) -> Self {
+        u8::from(value.member_of_default_set)
+            | u8::from(value.settable) << 1
+            | u8::from(value.control_source) << 2
+    }
+}
+
+/// The measurement triggers a Device Process Data object supports
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TriggerMethods {
+    pub time_interval: bool,
+    pub distance_interval: bool,
+    pub threshold_limits: bool,
+    pub on_change: bool,
+    pub total: bool,
+}
+
+impl From<u8> for TriggerMethods {
+    fn from(value: u8) -> Self {
+        TriggerMethods {
+            time_interval: value & 0x01 != 0,
+            distance_interval: value & 0x02 != 0,
+            threshold_limits: value & 0x04 != 0,
+            on_change: value & 0x08 != 0,
+            total: value & 0x10 != 0,
+        }
+    }
+}
+
+impl From<TriggerMethods> for u8 {
+    fn from(value: TriggerMethods) -> Self {
+        u8::from(value.time_interval)
+            | u8::from(value.distance_interval) << 1
+            | u8::from(value.threshold_limits) << 2
+            | u8::from(value.on_change) << 3
+            | u8::from(value.total) << 4
+    }
+}
+
+/// The device itself, every pool has exactly one
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Device {
+    pub id: ObjectId,
+    pub designator: String,
+    pub software_version: String,
+    pub working_set_master: NAME,
+    pub serial_number: String,
+    /// Changes whenever the structure of the pool changes, see
+    /// [`DeviceDescriptorObjectPool::generate_structure_label`]
+    pub structure_label: [u8; 7],
+    /// The language and units of the designators, see [`localization_label`]
+    pub localization_label: [u8; 7],
+    /// TC version 4 only
+    pub extended_structure_label: Vec<u8>,
+}
+
+/// A part of the device, like a boom or a section
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceElement {
+    pub id: ObjectId,
+    pub element_type: DeviceElementType,
+    pub designator: String,
+    /// The number process data messages address the element with
+    pub element_number: u16,
+    /// The Device object for the device element, otherwise another element
+    pub parent: ObjectId,
+    /// Device Process Data and Device Property objects of the element
+    pub object_refs: Vec<ObjectId>,
+}
+
+/// A value of an element that the TC can request, set or measure
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceProcessData {
+    pub id: ObjectId,
+    pub ddi: u16,
+    pub properties: ProcessDataProperties,
+    pub trigger_methods: TriggerMethods,
+    pub designator: String,
+    pub presentation: NullableObjectId,
+}
+
+/// A fixed value of an element, like the width of a section
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceProperty {
+    pub id: ObjectId,
+    pub ddi: u16,
+    pub value: i32,
+    pub designator: String,
+    pub presentation: NullableObjectId,
+}
+
+/// How to display a value: `(value + offset) * scale` with a number of decimals and a unit
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceValuePresentation {
+    pub id: ObjectId,
+    pub offset: i32,
+    pub scale: f32,
+    pub decimals: u8,
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DdopObject {
+    Device(Device),
+    DeviceElement(DeviceElement),
+    DeviceProcessData(DeviceProcessData),
+    DeviceProperty(DeviceProperty),
+    DeviceValuePresentation(DeviceValuePresentation),
+}
+
+impl DdopObject {
+    pub fn id(&self) -> ObjectId {
+        match self {
+            DdopObject::Device(o) => o.id,
+            DdopObject::DeviceElement(o) => o.id,
+            DdopObject::DeviceProcessData(o) => o.id,
+            DdopObject::DeviceProperty(o) => o.id,
+            DdopObject::DeviceValuePresentation(o) => o.id,
+        }
+    }
+
+    pub fn object_type(&self) -> DdopObjectType {
+        match self {
+            DdopObject::Device(_) => DdopObjectType::Device,
+            DdopObject::DeviceElement(_) => DdopObjectType::DeviceElement,
+            DdopObject::DeviceProcessData(_) => DdopObjectType::DeviceProcessData,
+            DdopObject::DeviceProperty(_) => DdopObjectType::DeviceProperty,
+            DdopObject::DeviceValuePresentation(_) => DdopObjectType::DeviceValuePresentation,
+        }
+    }
+
+    /// The designator, or the unit of a value presentation
+    pub fn designator(&self) -> &str {
+        match self {
+            DdopObject::Device(o) => &o.designator,
+            DdopObject::DeviceElement(o) => &o.designator,
+            DdopObject::DeviceProcessData(o) => &o.designator,
+            DdopObject::DeviceProperty(o) => &o.designator,
+            DdopObject::DeviceValuePresentation(o) => &o.unit,
+        }
+    }
+
+    fn write(&self, version: u8, data: &mut Vec<u8>) {
+        data.extend_from_slice(&self.object_type().table_id());
+        data.extend_from_slice(&u16::from(self.id()).to_le_bytes());
+        match self {
+            DdopObject::Device(o) => {
+                write_string(&o.designator, version, data);
+                write_string(&o.software_version, version, data);
+                data.extend_from_slice(&<[u8; 8]>::from(o.working_set_master));
+                write_string(&o.serial_number, version, data);
+                data.extend_from_slice(&o.structure_label);
+                data.extend_from_slice(&o.localization_label);
+                if version >= 4 {
+                    data.push(o.extended_structure_label.len() as u8);
+                    data.extend_from_slice(&o.extended_structure_label);
+                }
+            }
+            DdopObject::DeviceElement(o) => {
+                data.push(o.element_type.into());
+                write_string(&o.designator, version, data);
+                data.extend_from_slice(&o.element_number.to_le_bytes());
+                data.extend_from_slice(&u16::from(o.parent).to_le_bytes());
+                data.extend_from_slice(&(o.object_refs.len() as u16).to_le_bytes());
+                for id in &o.object_refs {
+                    data.extend_from_slice(&u16::from(*id).to_le_bytes());
+                }
+            }
+            DdopObject::DeviceProcessData(o) => {
+                data.extend_from_slice(&o.ddi.to_le_bytes());
+                data.push(o.properties.into());
+                data.push(o.trigger_methods.into());
+                write_string(&o.designator, version, data);
+                data.extend_from_slice(&u16::from(o.presentation).to_le_bytes());
+            }
+            DdopObject::DeviceProperty(o) => {
+                data.extend_from_slice(&o.ddi.to_le_bytes());
+                data.extend_from_slice(&o.value.to_le_bytes());
+                write_string(&o.designator, version, data);
+                data.extend_from_slice(&u16::from(o.presentation).to_le_bytes());
+            }
+            DdopObject::DeviceValuePresentation(o) => {
+                data.extend_from_slice(&o.offset.to_le_bytes());
+                data.extend_from_slice(&o.scale.to_le_bytes());
+                data.push(o.decimals);
+                write_string(&o.unit, version, data);
+            }
+        }
+    }
+
+    fn read(version: u8, reader: &mut Reader) -> Result<Self, DdopError> {
+        let table_id = reader.bytes()?;
+        let object_type =
+            DdopObjectType::from_table_id(table_id).ok_or(DdopError::UnknownTableId(table_id))?;
+        let id = reader.object_id()?;
+        Ok(match object_type {
+            DdopObjectType::Device => DdopObject::Device(Device {
+                id,
+                designator: reader.string(version)?,
+                software_version: reader.string(version)?,
+                working_set_master: NAME::new(u64::from_le_bytes(reader.bytes()?)),
+                serial_number: reader.string(version)?,
+                structure_label: reader.bytes()?,
+                localization_label: reader.bytes()?,
+                extended_structure_label: if version >= 4 {
+                    let len = reader.u8()?;
+                    reader.slice(len.into())?.to_vec()
+                } else {
+                    vec![]
+                },
+            }),
+            DdopObjectType::DeviceElement => DdopObject::DeviceElement(DeviceElement {
+                id,
+                element_type: reader.u8()?.into(),
+                designator: reader.string(version)?,
+                element_number: reader.u16()?,
+                parent: reader.object_id()?,
+                object_refs: {
+                    let count = reader.u16()?;
+                    (0..count)
+                        .map(|_| reader.object_id())
+                        .collect::<Result<_, _>>()?
+                },
+            }),
+            DdopObjectType::DeviceProcessData => DdopObject::DeviceProcessData(DeviceProcessData {
+                id,
+                ddi: reader.u16()?,
+                properties: reader.u8()?.into(),
+                trigger_methods: reader.u8()?.into(),
+                designator: reader.string(version)?,
+                presentation: reader.u16()?.into(),
+            }),
+            DdopObjectType::DeviceProperty => DdopObject::DeviceProperty(DeviceProperty {
+                id,
+                ddi: reader.u16()?,
+                value: i32::from_le_bytes(reader.bytes()?),
+                designator: reader.string(version)?,
+                presentation: reader.u16()?.into(),
+            }),
+            DdopObjectType::DeviceValuePresentation => {
+                DdopObject::DeviceValuePresentation(DeviceValuePresentation {
+                    id,
+                    offset: i32::from_le_bytes(reader.bytes()?),
+                    scale: f32::from_le_bytes(reader.bytes()?),
+                    decimals: reader.u8()?,
+                    unit: reader.string(version)?,
+                })
+            }
+        })
+    }
+}
+
+/// The bytes of `value` in a pool for TC `version`, ISO 8859-1 before version 4 and UTF-8
+/// from version 4
+///
+/// Characters ISO 8859-1 does not have are written as `?`, validation reports them as
+/// [`UnrepresentableCharacter`](super::DdopValidationError::UnrepresentableCharacter).
+pub(super) fn encode_string(value: &str, version: u8) -> Vec<u8> {
+    if version >= 4 {
+        value.as_bytes().to_vec()
+    } else {
+        value
+            .chars()
+            .map(|c| u8::try_from(c).unwrap_or(b'?'))
+            .collect()
+    }
+}
+
+/// Strings have a one byte length, longer ones are cut at a character boundary
+fn write_string(value: &str, version: u8, data: &mut Vec<u8>) {
+    let bytes = encode_string(value, version);
+    let mut len = bytes.len().min(u8::MAX as usize);
+    while version >= 4 && !value.is_char_boundary(len) {
+        len -= 1;
+    }
+    data.push(len as u8);
+    data.extend_from_slice(&bytes[..len]);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DdopError {
+    /// The data ended in the middle of a value of `expected` bytes, only `found` bytes were left
+    UnexpectedEnd { expected: usize, found: usize },
+    /// An object starts with a table ID that is not a DDOP object type
+    UnknownTableId([u8; 3]),
+    /// An object ID of 0xFFFF where a reference may not be null
+    NullObjectId { offset: usize },
+    /// A string of a version 4 pool is not valid UTF-8
+    InvalidString { offset: usize },
+}
+
+impl std::fmt::Display for DdopError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for DdopError {}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn slice(&mut self, len: usize) -> Result<&'a [u8], DdopError> {
+        let slice =
+            self.data
+                .get(self.offset..self.offset + len)
+                .ok_or(DdopError::UnexpectedEnd {
+                    expected: len,
+                    found: self.data.len() - self.offset,
+                })?;
+        self.offset += len;
+        Ok(slice)
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], DdopError> {
+        Ok(self.slice(N)?.try_into().unwrap_or([0; N]))
+    }
+
+    fn u8(&mut self) -> Result<u8, DdopError> {
+        Ok(self.bytes::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, DdopError> {
+        Ok(u16::from_le_bytes(self.bytes()?))
+    }
+
+    fn object_id(&mut self) -> Result<ObjectId, DdopError> {
+        let offset = self.offset;
+        ObjectId::new(self.u16()?).map_err(|_| DdopError::NullObjectId { offset })
+    }
+
+    /// A string in the encoding of TC `version`, see [`encode_string`]
+    fn string(&mut self, version: u8) -> Result<String, DdopError> {
+        let len = self.u8()?;
+        let offset = self.offset;
+        let bytes = self.slice(len.into())?;
+        if version >= 4 {
+            String::from_utf8(bytes.to_vec()).map_err(|_| DdopError::InvalidString { offset })
+        } else {
+            Ok(bytes.iter().map(|&b| char::from(b)).collect())
+        }
+    }
+}
+
+/// The localization label for designators in the language and units of `language`
+///
+/// These are the first six bytes of the Language Command, followed by a reserved 0xFF.
+pub fn localization_label(language: &LanguageCommand) -> [u8; 7] {
+    let mut label = [0xFF; 7];
+    label[..6].copy_from_slice(&language.to_bytes()[..6]);
+    label
+}
+
+/// The device descriptor object pool (DDOP) of a Task Controller client, describing the device
+/// and the process data it offers
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceDescriptorObjectPool {
+    /// The TC version the pool is for, version 4 adds the extended structure label
+    pub version: u8,
+    objects: Vec<DdopObject>,
+}
+
+impl Default for DeviceDescriptorObjectPool {
+    fn default() -> Self {
+        DeviceDescriptorObjectPool::new(4)
+    }
+}
+
+impl DeviceDescriptorObjectPool {
+    pub fn new(version: u8) -> Self {
+        DeviceDescriptorObjectPool {
+            version,
+            objects: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, object: DdopObject) {
+        self.objects.push(object);
+    }
+
+    pub fn remove(&mut self, id: ObjectId) -> Option<DdopObject> {
+        let index = self.objects.iter().position(|o| o.id() == id)?;
+        Some(self.objects.remove(index))
+    }
+
+    pub fn objects(&self) -> &[DdopObject] {
+        &self.objects
+    }
+
+    pub fn object_by_id(&self, id: ObjectId) -> Option<&DdopObject> {
+        self.objects.iter().find(|o| o.id() == id)
+    }
+
+    pub fn object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut DdopObject> {
+        self.objects.iter_mut().find(|o| o.id() == id)
+    }
+
+    pub fn device(&self) -> Option<&Device> {
+        self.objects.iter().find_map(|o| match o {
+            DdopObject::Device(o) => Some(o),
+            _ => None,
+        })
+    }
+
+    pub fn device_mut(&mut self) -> Option<&mut Device> {
+        self.objects.iter_mut().find_map(|o| match o {
+            DdopObject::Device(o) => Some(o),
+            _ => None,
+        })
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &DeviceElement> {
+        self.objects.iter().filter_map(|o| match o {
+            DdopObject::DeviceElement(o) => Some(o),
+            _ => None,
+        })
+    }
+
+    pub fn element_by_number(&self, element_number: u16) -> Option<&DeviceElement> {
+        self.elements().find(|e| e.element_number == element_number)
+    }
+
+    /// The process data objects of the element with `element_number`
+    pub fn process_data(&self, element_number: u16) -> Vec<&DeviceProcessData> {
+        self.element_by_number(element_number)
+            .map_or(vec![], |element| {
+                element
+                    .object_refs
+                    .iter()
+                    .filter_map(|&id| match self.object_by_id(id) {
+                        Some(DdopObject::DeviceProcessData(o)) => Some(o),
+                        _ => None,
+                    })
+                    .collect()
+            })
+    }
+
+    /// The pool in the binary format of the Device Descriptor Object Pool Transfer message
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        for object in &self.objects {
+            object.write(self.version, &mut data);
+        }
+        data
+    }
+
+    /// Parses a pool in the binary format for TC `version`
+    pub fn from_bytes(data: &[u8], version: u8) -> Result<Self, DdopError> {
+        let mut reader = Reader { data, offset: 0 };
+        let mut pool = DeviceDescriptorObjectPool::new(version);
+        while reader.offset < data.len() {
+            pool.add(DdopObject::read(version, &mut reader)?);
+        }
+        Ok(pool)
+    }
+
+    /// A structure label that changes with any change to the pool other than to the labels
+    ///
+    /// The label is a hash of the binary pool, with the labels of the Device object left out.
+    pub fn generate_structure_label(&self) -> [u8; 7] {
+        // 64 bit FNV-1a
+        let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+        let mut data = Vec::new();
+        for object in &self.objects {
+            match object {
+                DdopObject::Device(device) => {
+                    let device = Device {
+                        structure_label: [0; 7],
+                        localization_label: [0; 7],
+                        extended_structure_label: vec![],
+                        ..device.clone()
+                    };
+                    DdopObject::Device(device).write(self.version, &mut data);
+                }
+                object => object.write(self.version, &mut data),
+            }
+        }
+        for byte in data {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3);
+        }
+        let mut label = [0; 7];
+        label.copy_from_slice(&hash.to_le_bytes()[..7]);
+        label
+    }
+
+    /// Sets the labels of the Device object, the structure label from
+    /// [`generate_structure_label`](Self::generate_structure_label)
+    pub fn update_labels(&mut self, language: &LanguageCommand) {
+        let structure_label = self.generate_structure_label();
+        if let Some(device) = self.device_mut() {
+            device.structure_label = structure_label;
+            device.localization_label = localization_label(language);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    fn sprayer() -> DeviceDescriptorObjectPool {
+        let mut pool = DeviceDescriptorObjectPool::new(4);
+        pool.add(DdopObject::Device(Device {
+            id: id(0),
+            designator: "Sprayer".to_string(),
+            software_version: "1.0".to_string(),
+            working_set_master: NAME::new(0xA000_8200_0C2F_E5A8),
+            serial_number: "123".to_string(),
+            structure_label: [0; 7],
+            localization_label: [0; 7],
+            extended_structure_label: vec![],
+        }));
+        pool.add(DdopObject::DeviceElement(DeviceElement {
+            id: id(1),
+            element_type: DeviceElementType::Device,
+            designator: "Sprayer".to_string(),
+            element_number: 0,
+            parent: id(0),
+            object_refs: vec![id(3)],
+        }));
+        pool.add(DdopObject::DeviceElement(DeviceElement {
+            id: id(2),
+            element_type: DeviceElementType::Section,
+            designator: "Section 1".to_string(),
+            element_number: 1,
+            parent: id(1),
+            object_refs: vec![id(4)],
+        }));
+        pool.add(DdopObject::DeviceProcessData(DeviceProcessData {
+            id: id(3),
+            ddi: 0x0001,
+            properties: ProcessDataProperties {
+                member_of_default_set: true,
+                settable: true,
+                control_source: false,
+            },
+            trigger_methods: TriggerMethods {
+                time_interval: true,
+                on_change: true,
+                ..Default::default()
+            },
+            designator: "Rate".to_string(),
+            presentation: id(5).into(),
+        }));
+        pool.add(DdopObject::DeviceProperty(DeviceProperty {
+            id: id(4),
+            ddi: 0x0043,
+            value: -3000,
+            designator: "Offset".to_string(),
+            presentation: NullableObjectId::NULL,
+        }));
+        pool.add(DdopObject::DeviceValuePresentation(
+            DeviceValuePresentation {
+                id: id(5),
+                offset: 0,
+                scale: 0.001,
+                decimals: 1,
+                unit: "l/ha".to_string(),
+            },
+        ));
+        pool
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let pool = sprayer();
+        let data = pool.to_bytes();
+        assert_eq!(&data[..5], b"DVC\x00\x00");
+        assert_eq!(DeviceDescriptorObjectPool::from_bytes(&data, 4), Ok(pool));
+
+        // The value presentation is the last object
+        let presentation = [
+            b'D', b'V', b'P', 5, 0, 0, 0, 0, 0, 0x6F, 0x12, 0x83, 0x3A, 1, 4, b'l', b'/', b'h',
+            b'a',
+        ];
+        assert!(data.ends_with(&presentation));
+        assert_eq!(
+            DeviceDescriptorObjectPool::from_bytes(&data[..data.len() - 1], 4),
+            Err(DdopError::UnexpectedEnd {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            DeviceDescriptorObjectPool::from_bytes(b"XYZ\x00\x00", 4),
+            Err(DdopError::UnknownTableId(*b"XYZ"))
+        );
+    }
+
+    #[test]
+    fn version_3_has_no_extended_structure_label() {
+        let mut pool = sprayer();
+        pool.version = 3;
+        let data = pool.to_bytes();
+        assert_eq!(data.len(), sprayer().to_bytes().len() - 1);
+        assert_eq!(DeviceDescriptorObjectPool::from_bytes(&data, 3), Ok(pool));
+    }
+
+    #[test]
+    fn version_3_strings_are_latin_1() {
+        let mut pool = DeviceDescriptorObjectPool::new(3);
+        pool.add(DdopObject::DeviceValuePresentation(
+            DeviceValuePresentation {
+                id: id(5),
+                offset: 0,
+                scale: 0.001,
+                decimals: 1,
+                unit: "mm³/m²".to_string(),
+            },
+        ));
+        let data = pool.to_bytes();
+        assert!(data.ends_with(&[6, b'm', b'm', 0xB3, b'/', b'm', 0xB2]));
+        assert_eq!(DeviceDescriptorObjectPool::from_bytes(&data, 3), Ok(pool));
+
+        // Version 4 pools are UTF-8, where these bytes are invalid
+        assert_eq!(
+            DeviceDescriptorObjectPool::from_bytes(&data, 4),
+            Err(DdopError::InvalidString {
+                offset: data.len() - 6
+            })
+        );
+    }
+
+    #[test]
+    fn labels() {
+        let mut pool = sprayer();
+        let label = pool.generate_structure_label();
+        pool.update_labels(&LanguageCommand::default());
+        assert_eq!(pool.device().unwrap().structure_label, label);
+        assert_eq!(pool.generate_structure_label(), label);
+        assert_eq!(&pool.device().unwrap().localization_label[..2], b"en");
+        assert_eq!(pool.device().unwrap().localization_label[6], 0xFF);
+
+        pool.remove(id(4));
+        assert_ne!(pool.generate_structure_label(), label);
+    }
+
+    #[test]
+    fn lookups() {
+        let pool = sprayer();
+        assert_eq!(pool.device().unwrap().designator, "Sprayer");
+        assert_eq!(pool.element_by_number(1).unwrap().id, id(2));
+        assert_eq!(pool.process_data(0)[0].ddi, 0x0001);
+        assert!(pool.process_data(1).is_empty());
+        assert_eq!(pool.object_by_id(id(5)).unwrap().designator(), "l/ha");
+    }
+}
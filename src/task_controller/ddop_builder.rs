@@ -0,0 +1,449 @@
+// Copyright 2023 Raven Industries inc.
+
+use super::ddi::DdiDefinition;
+use super::ddop::{
+    encode_string, DdopObject, Device, DeviceDescriptorObjectPool, DeviceElement,
+    DeviceElementType, DeviceProcessData, DeviceProperty, DeviceValuePresentation,
+    ProcessDataProperties, TriggerMethods,
+};
+use super::ddop_validation::{max_designator_len, DdopValidationError};
+use super::MAX_ELEMENT_NUMBER;
+use crate::network_management::language_command::LanguageCommand;
+use crate::network_management::name::NAME;
+use crate::object_pool::{NullableObjectId, ObjectId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DdopBuilderError {
+    /// The element passed to an `add_` method is not in the pool
+    UnknownElement(ObjectId),
+    /// The object passed to `set_presentation` is not process data or a property
+    NotPresentable(ObjectId),
+    /// The DDI passed to `add_standard_process_data` is not in the data dictionary table
+    UnknownDdi(u16),
+    /// All 4096 element numbers are in use
+    NoFreeElementNumber,
+    /// The finished pool does not pass [`DeviceDescriptorObjectPool::validate`]
+    Invalid(Vec<DdopValidationError>),
+}
+
+impl std::fmt::Display for DdopBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for DdopBuilderError {}
+
+/// Builds a device descriptor object pool, allocating object IDs and element numbers
+///
+/// ```
+/// # use ag_iso_stack::network_management::language_command::LanguageCommand;
+/// # use ag_iso_stack::network_management::name::NAME;
+/// # use ag_iso_stack::task_controller::*;
+/// let mut builder = DdopBuilder::new("Sprayer", NAME::default());
+/// let boom = builder
+///     .add_element(builder.device_element(), DeviceElementType::Function, "Boom")
+///     .unwrap();
+/// let section = builder
+///     .add_element(boom, DeviceElementType::Section, "Section 1")
+///     .unwrap();
+/// builder
+///     .add_property(section, 0x0046, 6000, "Width")
+///     .unwrap();
+///
+/// // The labels are filled in when building
+/// let pool = builder.build(&LanguageCommand::default()).unwrap();
+/// assert_eq!(pool.element_by_number(2).unwrap().designator, "Section 1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct DdopBuilder {
+    pool: DeviceDescriptorObjectPool,
+    device_element: ObjectId,
+    next_id: u16,
+    next_element_number: u16,
+}
+
+impl DdopBuilder {
+    /// Starts a pool for TC version 4 with the Device object and the device element, both
+    /// called `designator`
+    pub fn new(designator: impl Into<String>, working_set_master: NAME) -> Self {
+        let designator = designator.into();
+        let mut pool = DeviceDescriptorObjectPool::new(4);
+        let device = ObjectId::default();
+        let device_element = ObjectId::new(1).unwrap_or_default();
+        pool.add(DdopObject::Device(Device {
+            id: device,
+            designator: designator.clone(),
+            software_version: String::new(),
+            working_set_master,
+            serial_number: String::new(),
+            structure_label: [0xFF; 7],
+            localization_label: [0xFF; 7],
+            extended_structure_label: vec![],
+        }));
+        pool.add(DdopObject::DeviceElement(DeviceElement {
+            id: device_element,
+            element_type: DeviceElementType::Device,
+            designator,
+            element_number: 0,
+            parent: device,
+            object_refs: vec![],
+        }));
+        DdopBuilder {
+            pool,
+            device_element,
+            next_id: 2,
+            next_element_number: 1,
+        }
+    }
+
+    /// The TC version to build the pool for
+    pub fn version(mut self, version: u8) -> Self {
+        self.pool.version = version;
+        self
+    }
+
+    pub fn software_version(mut self, software_version: impl Into<String>) -> Self {
+        if let Some(device) = self.pool.device_mut() {
+            device.software_version = software_version.into();
+        }
+        self
+    }
+
+    pub fn serial_number(mut self, serial_number: impl Into<String>) -> Self {
+        if let Some(device) = self.pool.device_mut() {
+            device.serial_number = serial_number.into();
+        }
+        self
+    }
+
+    /// The element of type Device, element number 0, the root of all other elements
+    pub fn device_element(&self) -> ObjectId {
+        self.device_element
+    }
+
+    /// Adds an element below `parent` with the next free element number, returns its ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if all object IDs are in use.
+    pub fn add_element(
+        &mut self,
+        parent: ObjectId,
+        element_type: DeviceElementType,
+        designator: impl Into<String>,
+    ) -> Result<ObjectId, DdopBuilderError> {
+        self.element_mut(parent)?;
+        if self.next_element_number > MAX_ELEMENT_NUMBER {
+            return Err(DdopBuilderError::NoFreeElementNumber);
+        }
+        let id = self.next_id();
+        self.pool.add(DdopObject::DeviceElement(DeviceElement {
+            id,
+            element_type,
+            designator: designator.into(),
+            element_number: self.next_element_number,
+            parent,
+            object_refs: vec![],
+        }));
+        self.next_element_number += 1;
+        Ok(id)
+    }
+
+    /// Adds process data to `element`, returns its ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if all object IDs are in use.
+    pub fn add_process_data(
+        &mut self,
+        element: ObjectId,
+        ddi: u16,
+        designator: impl Into<String>,
+        properties: ProcessDataProperties,
+        trigger_methods: TriggerMethods,
+    ) -> Result<ObjectId, DdopBuilderError> {
+        self.add_to_element(element, |id| {
+            DdopObject::DeviceProcessData(DeviceProcessData {
+                id,
+                ddi,
+                properties,
+                trigger_methods,
+                designator: designator.into(),
+                presentation: NullableObjectId::NULL,
+            })
+        })
+    }
+
+    /// Adds process data for a DDI of the data dictionary to `element`, named like the DDI and
+    /// with a value presentation in its unit
+    ///
+    /// The name is cut to the designator length the TC version allows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all object IDs are in use.
+    pub fn add_standard_process_data(
+        &mut self,
+        element: ObjectId,
+        ddi: u16,
+        properties: ProcessDataProperties,
+        trigger_methods: TriggerMethods,
+    ) -> Result<ObjectId, DdopBuilderError> {
+        let definition = DdiDefinition::lookup(ddi).ok_or(DdopBuilderError::UnknownDdi(ddi))?;
+        let designator = shorten(definition.name, self.pool.version);
+        let id = self.add_process_data(element, ddi, designator, properties, trigger_methods)?;
+        if !definition.unit.is_empty() {
+            let presentation = self.add_value_presentation(
+                0,
+                definition.resolution as f32,
+                definition.decimals(),
+                definition.unit,
+            );
+            self.set_presentation(id, presentation)?;
+        }
+        Ok(id)
+    }
+
+    /// Adds a property to `element`, returns its ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if all object IDs are in use.
+    pub fn add_property(
+        &mut self,
+        element: ObjectId,
+        ddi: u16,
+        value: i32,
+        designator: impl Into<String>,
+    ) -> Result<ObjectId, DdopBuilderError> {
+        self.add_to_element(element, |id| {
+            DdopObject::DeviceProperty(DeviceProperty {
+                id,
+                ddi,
+                value,
+                designator: designator.into(),
+                presentation: NullableObjectId::NULL,
+            })
+        })
+    }
+
+    /// Adds a value presentation, or returns the ID of an identical one added before
+    ///
+    /// # Panics
+    ///
+    /// Panics if all object IDs are in use.
+    pub fn add_value_presentation(
+        &mut self,
+        offset: i32,
+        scale: f32,
+        decimals: u8,
+        unit: impl Into<String>,
+    ) -> ObjectId {
+        let unit = unit.into();
+        let existing = self.pool.objects().iter().find_map(|o| match o {
+            DdopObject::DeviceValuePresentation(p)
+                if p.offset == offset
+                    && p.scale == scale
+                    && p.decimals == decimals
+                    && p.unit == unit =>
+            {
+                Some(p.id)
+            }
+            _ => None,
+        });
+        if let Some(id) = existing {
+            return id;
+        }
+        let id = self.next_id();
+        self.pool.add(DdopObject::DeviceValuePresentation(
+            DeviceValuePresentation {
+                id,
+                offset,
+                scale,
+                decimals,
+                unit,
+            },
+        ));
+        id
+    }
+
+    /// Displays process data or a property with `presentation`
+    pub fn set_presentation(
+        &mut self,
+        object: ObjectId,
+        presentation: ObjectId,
+    ) -> Result<(), DdopBuilderError> {
+        match self.pool.object_by_id_mut(object) {
+            Some(DdopObject::DeviceProcessData(o)) => o.presentation = presentation.into(),
+            Some(DdopObject::DeviceProperty(o)) => o.presentation = presentation.into(),
+            _ => return Err(DdopBuilderError::NotPresentable(object)),
+        }
+        Ok(())
+    }
+
+    /// Finishes the pool
+    ///
+    /// The structure label is generated from the pool and the localization label from
+    /// `language`, the language the designators are in. Returns the validation errors if the
+    /// pool does not pass [`DeviceDescriptorObjectPool::validate`].
+    pub fn build(
+        mut self,
+        language: &LanguageCommand,
+    ) -> Result<DeviceDescriptorObjectPool, DdopBuilderError> {
+        self.pool.update_labels(language);
+        self.pool.validate().map_err(DdopBuilderError::Invalid)?;
+        Ok(self.pool)
+    }
+
+    fn next_id(&mut self) -> ObjectId {
+        let id = ObjectId::new(self.next_id)
+            .expect("a device descriptor object pool can't have more than 65535 objects");
+        self.next_id += 1;
+        id
+    }
+
+    fn element_mut(&mut self, element: ObjectId) -> Result<&mut DeviceElement, DdopBuilderError> {
+        match self.pool.object_by_id_mut(element) {
+            Some(DdopObject::DeviceElement(o)) => Ok(o),
+            _ => Err(DdopBuilderError::UnknownElement(element)),
+        }
+    }
+
+    fn add_to_element(
+        &mut self,
+        element: ObjectId,
+        object: impl FnOnce(ObjectId) -> DdopObject,
+    ) -> Result<ObjectId, DdopBuilderError> {
+        self.element_mut(element)?;
+        let id = self.next_id();
+        self.element_mut(element)?.object_refs.push(id);
+        self.pool.add(object(id));
+        Ok(id)
+    }
+}
+
+/// `designator` cut at a character boundary to the length TC `version` allows
+fn shorten(designator: &str, version: u8) -> String {
+    let mut designator = designator.to_string();
+    while encode_string(&designator, version).len() > max_designator_len(version) {
+        designator.pop();
+    }
+    designator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_a_pool() {
+        let mut builder = DdopBuilder::new("Sprayer", NAME::new(0xA000_8200_0C2F_E5A8))
+            .software_version("1.0")
+            .serial_number("123");
+        let root = builder.device_element();
+        let rate = builder
+            .add_process_data(
+                root,
+                0x0001,
+                "Rate",
+                ProcessDataProperties {
+                    settable: true,
+                    ..Default::default()
+                },
+                TriggerMethods {
+                    on_change: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let boom = builder
+            .add_element(root, DeviceElementType::Function, "Boom")
+            .unwrap();
+        let width = builder.add_property(boom, 0x0043, 24000, "Width").unwrap();
+        let litres = builder.add_value_presentation(0, 0.001, 1, "l/ha");
+        let millimetres = builder.add_value_presentation(0, 1.0, 0, "mm");
+        assert_eq!(builder.add_value_presentation(0, 0.001, 1, "l/ha"), litres);
+        builder.set_presentation(rate, litres).unwrap();
+        builder.set_presentation(width, millimetres).unwrap();
+        assert_eq!(
+            builder.set_presentation(boom, litres),
+            Err(DdopBuilderError::NotPresentable(boom))
+        );
+        assert_eq!(
+            builder.add_property(rate, 0x0043, 0, "Width"),
+            Err(DdopBuilderError::UnknownElement(rate))
+        );
+
+        let pool = builder.build(&LanguageCommand::default()).unwrap();
+        let device = pool.device().unwrap();
+        assert_eq!(device.software_version, "1.0");
+        assert_eq!(device.structure_label, pool.generate_structure_label());
+        assert_eq!(pool.element_by_number(1).unwrap().id, boom);
+        assert_eq!(pool.element_by_number(0).unwrap().object_refs, [rate]);
+        assert_eq!(pool.process_data(0)[0].presentation, litres.into());
+        assert_eq!(
+            DeviceDescriptorObjectPool::from_bytes(&pool.to_bytes(), 4),
+            Ok(pool)
+        );
+    }
+
+    #[test]
+    fn adds_standard_process_data() {
+        let mut builder = DdopBuilder::new("Sprayer", NAME::default()).version(3);
+        let root = builder.device_element();
+        let rate = builder
+            .add_standard_process_data(root, 0x0001, Default::default(), Default::default())
+            .unwrap();
+        let state = builder
+            .add_standard_process_data(root, 141, Default::default(), Default::default())
+            .unwrap();
+        assert_eq!(
+            builder.add_standard_process_data(root, 0xE000, Default::default(), Default::default()),
+            Err(DdopBuilderError::UnknownDdi(0xE000))
+        );
+
+        let pool = builder.build(&LanguageCommand::default()).unwrap();
+        let process_data = pool.process_data(0);
+        assert_eq!(process_data[0].id, rate);
+        assert_eq!(
+            process_data[0].designator,
+            "Setpoint Volume Per Area Applica"
+        );
+        let presentation = process_data[0].presentation.0.unwrap();
+        assert!(matches!(
+            pool.object_by_id(presentation),
+            Some(DdopObject::DeviceValuePresentation(p)) if p.unit == "mm³/m²" && p.decimals == 2
+        ));
+        // Work states have no unit to present
+        assert_eq!(process_data[1].id, state);
+        assert_eq!(process_data[1].presentation, NullableObjectId::NULL);
+        assert_eq!(
+            DeviceDescriptorObjectPool::from_bytes(&pool.to_bytes(), 3),
+            Ok(pool)
+        );
+    }
+
+    #[test]
+    fn shortens_at_character_boundaries() {
+        let name = format!("{}³", "x".repeat(127));
+        assert_eq!(shorten(&name, 4), "x".repeat(127));
+        // ISO 8859-1 has ³ as one byte
+        assert_eq!(shorten("Volume in mm³", 3), "Volume in mm³");
+        assert_eq!(shorten(&name, 3), "x".repeat(32));
+    }
+
+    #[test]
+    fn rejects_an_invalid_pool() {
+        let mut builder = DdopBuilder::new("Sprayer", NAME::default());
+        let root = builder.device_element();
+        builder.add_property(root, 0xFFFF, 0, "Reserved").unwrap();
+        assert!(matches!(
+            builder.build(&LanguageCommand::default()),
+            Err(DdopBuilderError::Invalid(errors))
+                if errors == [DdopValidationError::InvalidDdi {
+                    object: ObjectId::new(2).unwrap(),
+                    ddi: 0xFFFF
+                }]
+        ));
+    }
+}
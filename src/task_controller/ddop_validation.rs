@@ -0,0 +1,381 @@
+// Copyright 2023 Raven Industries inc.
+
+use std::collections::HashMap;
+
+use super::ddop::{
+    encode_string, DdopObject, DdopObjectType, DeviceDescriptorObjectPool, DeviceElementType,
+};
+use super::{LAST_PROPRIETARY_DDI, MAX_ELEMENT_NUMBER};
+use crate::object_pool::{NullableObjectId, ObjectId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DdopValidationError {
+    /// The pool does not contain a Device object
+    MissingDevice,
+    /// The pool contains more than one Device object
+    MultipleDevices(Vec<ObjectId>),
+    /// More than one object in the pool uses this ID
+    DuplicateObjectId(ObjectId),
+    /// The pool has no element of type Device, with element number 0 and the Device object as
+    /// its parent
+    MissingDeviceElement,
+    /// An element of type Device that is not element 0, or a second one
+    InvalidDeviceElement(ObjectId),
+    /// More than one element uses this element number
+    DuplicateElementNumber(u16),
+    /// Element numbers have 12 bits
+    InvalidElementNumber {
+        object: ObjectId,
+        element_number: u16,
+    },
+    /// A reference points at an object that is not in the pool
+    MissingObject {
+        object: ObjectId,
+        reference: ObjectId,
+    },
+    /// A reference points at an object of a type that is not allowed in that place
+    InvalidObjectType {
+        object: ObjectId,
+        reference: ObjectId,
+        object_type: DdopObjectType,
+    },
+    /// An element is its own ancestor
+    ParentCycle(ObjectId),
+    /// A DDI in the reserved range
+    InvalidDdi { object: ObjectId, ddi: u16 },
+    /// A designator is longer than the TC version allows, 32 bytes before version 4 and 128
+    /// bytes from version 4
+    DesignatorTooLong { object: ObjectId, len: usize },
+    /// A string of a pool before version 4 has a character that ISO 8859-1 does not have
+    UnrepresentableCharacter { object: ObjectId, character: char },
+}
+
+impl std::fmt::Display for DdopValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for DdopValidationError {}
+
+impl DeviceDescriptorObjectPool {
+    /// Checks that there is one device with one device element, that the references point at
+    /// objects of the right type, and that element numbers, DDIs and designators are in range
+    /// and encodable for the TC version
+    pub fn validate(&self) -> Result<(), Vec<DdopValidationError>> {
+        let mut errors = Vec::new();
+
+        let devices: Vec<ObjectId> = self
+            .objects()
+            .iter()
+            .filter(|o| o.object_type() == DdopObjectType::Device)
+            .map(DdopObject::id)
+            .collect();
+        match devices.len() {
+            0 => errors.push(DdopValidationError::MissingDevice),
+            1 => {}
+            _ => errors.push(DdopValidationError::MultipleDevices(devices)),
+        }
+
+        let mut types = HashMap::new();
+        for object in self.objects() {
+            if types.insert(object.id(), object.object_type()).is_some() {
+                let error = DdopValidationError::DuplicateObjectId(object.id());
+                if !errors.contains(&error) {
+                    errors.push(error);
+                }
+            }
+        }
+        let mut check = |object: ObjectId, reference: ObjectId, allowed: DdopObjectType| match types
+            .get(&reference)
+        {
+            None => errors.push(DdopValidationError::MissingObject { object, reference }),
+            Some(&object_type) if object_type != allowed => {
+                errors.push(DdopValidationError::InvalidObjectType {
+                    object,
+                    reference,
+                    object_type,
+                })
+            }
+            Some(_) => {}
+        };
+
+        let mut device_element = None;
+        let mut element_numbers = HashMap::new();
+        for object in self.objects() {
+            match object {
+                DdopObject::DeviceElement(o) => {
+                    if o.element_type == DeviceElementType::Device {
+                        check(o.id, o.parent, DdopObjectType::Device);
+                    } else {
+                        check(o.id, o.parent, DdopObjectType::DeviceElement);
+                    }
+                    for &reference in &o.object_refs {
+                        match types.get(&reference) {
+                            Some(DdopObjectType::DeviceProperty) => {}
+                            _ => check(o.id, reference, DdopObjectType::DeviceProcessData),
+                        }
+                    }
+                }
+                DdopObject::DeviceProcessData(o) => {
+                    check_presentation(o.id, o.presentation, &mut check)
+                }
+                DdopObject::DeviceProperty(o) => {
+                    check_presentation(o.id, o.presentation, &mut check)
+                }
+                _ => {}
+            }
+        }
+        for object in self.objects() {
+            match object {
+                DdopObject::DeviceElement(o) => {
+                    if o.element_type == DeviceElementType::Device {
+                        if o.element_number == 0 && device_element.is_none() {
+                            device_element = Some(o.id);
+                        } else {
+                            errors.push(DdopValidationError::InvalidDeviceElement(o.id));
+                        }
+                    }
+                    if o.element_number > MAX_ELEMENT_NUMBER {
+                        errors.push(DdopValidationError::InvalidElementNumber {
+                            object: o.id,
+                            element_number: o.element_number,
+                        });
+                    }
+                    if element_numbers.insert(o.element_number, o.id).is_some() {
+                        let error = DdopValidationError::DuplicateElementNumber(o.element_number);
+                        if !errors.contains(&error) {
+                            errors.push(error);
+                        }
+                    }
+                    if self.has_parent_cycle(o.id) {
+                        errors.push(DdopValidationError::ParentCycle(o.id));
+                    }
+                }
+                DdopObject::DeviceProcessData(o) if o.ddi > LAST_PROPRIETARY_DDI => {
+                    errors.push(DdopValidationError::InvalidDdi {
+                        object: o.id,
+                        ddi: o.ddi,
+                    })
+                }
+                DdopObject::DeviceProperty(o) if o.ddi > LAST_PROPRIETARY_DDI => {
+                    errors.push(DdopValidationError::InvalidDdi {
+                        object: o.id,
+                        ddi: o.ddi,
+                    })
+                }
+                _ => {}
+            }
+            let len = encode_string(object.designator(), self.version).len();
+            if len > max_designator_len(self.version) {
+                errors.push(DdopValidationError::DesignatorTooLong {
+                    object: object.id(),
+                    len,
+                });
+            }
+            if self.version < 4 {
+                let strings = match object {
+                    DdopObject::Device(o) => {
+                        vec![&o.designator, &o.software_version, &o.serial_number]
+                    }
+                    _ => vec![],
+                };
+                let unrepresentable = strings
+                    .into_iter()
+                    .map(String::as_str)
+                    .chain([object.designator()])
+                    .flat_map(str::chars)
+                    .find(|&c| u8::try_from(c).is_err());
+                if let Some(character) = unrepresentable {
+                    errors.push(DdopValidationError::UnrepresentableCharacter {
+                        object: object.id(),
+                        character,
+                    });
+                }
+            }
+        }
+        if device_element.is_none() {
+            errors.push(DdopValidationError::MissingDeviceElement);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Whether following the parents of `element` leads back to it
+    fn has_parent_cycle(&self, element: ObjectId) -> bool {
+        let mut current = element;
+        // Without a cycle the walk ends within as many steps as there are objects
+        for _ in 0..self.objects().len() {
+            match self.object_by_id(current) {
+                Some(DdopObject::DeviceElement(o)) if o.parent == element => return true,
+                Some(DdopObject::DeviceElement(o)) => current = o.parent,
+                _ => return false,
+            }
+        }
+        false
+    }
+}
+
+/// The longest designator in bytes a pool for TC `version` may have
+pub(super) fn max_designator_len(version: u8) -> usize {
+    if version >= 4 {
+        128
+    } else {
+        32
+    }
+}
+
+fn check_presentation(
+    object: ObjectId,
+    presentation: NullableObjectId,
+    check: &mut impl FnMut(ObjectId, ObjectId, DdopObjectType),
+) {
+    if let Some(presentation) = presentation.0 {
+        check(
+            object,
+            presentation,
+            DdopObjectType::DeviceValuePresentation,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network_management::language_command::LanguageCommand;
+    use crate::network_management::name::NAME;
+    use crate::task_controller::ddop::{DeviceElement, DeviceProcessData};
+    use crate::task_controller::DdopBuilder;
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    /// Device element 1 with process data 3, section 2 with property 4, presentation 5
+    fn sprayer() -> DeviceDescriptorObjectPool {
+        let mut builder = DdopBuilder::new("Sprayer", NAME::default());
+        let root = builder.device_element();
+        let section = builder
+            .add_element(root, DeviceElementType::Section, "Section 1")
+            .unwrap();
+        let rate = builder
+            .add_process_data(root, 0x0001, "Rate", Default::default(), Default::default())
+            .unwrap();
+        builder
+            .add_property(section, 0x0043, 3000, "Width")
+            .unwrap();
+        let presentation = builder.add_value_presentation(0, 0.001, 1, "l/ha");
+        builder.set_presentation(rate, presentation).unwrap();
+        builder.build(&LanguageCommand::default()).unwrap()
+    }
+
+    fn element(pool: &mut DeviceDescriptorObjectPool, id: ObjectId) -> &mut DeviceElement {
+        match pool.object_by_id_mut(id) {
+            Some(DdopObject::DeviceElement(o)) => o,
+            _ => panic!("no element {:?}", id),
+        }
+    }
+
+    fn process_data(pool: &mut DeviceDescriptorObjectPool, id: ObjectId) -> &mut DeviceProcessData {
+        match pool.object_by_id_mut(id) {
+            Some(DdopObject::DeviceProcessData(o)) => o,
+            _ => panic!("no process data {:?}", id),
+        }
+    }
+
+    #[test]
+    fn valid_pool() {
+        assert_eq!(sprayer().validate(), Ok(()));
+    }
+
+    #[test]
+    fn references() {
+        let mut pool = sprayer();
+        element(&mut pool, id(2)).parent = id(3);
+        element(&mut pool, id(2)).object_refs.push(id(5));
+        element(&mut pool, id(2)).object_refs.push(id(9));
+        process_data(&mut pool, id(3)).presentation = id(4).into();
+        assert_eq!(
+            pool.validate(),
+            Err(vec![
+                DdopValidationError::InvalidObjectType {
+                    object: id(2),
+                    reference: id(3),
+                    object_type: DdopObjectType::DeviceProcessData
+                },
+                DdopValidationError::InvalidObjectType {
+                    object: id(2),
+                    reference: id(5),
+                    object_type: DdopObjectType::DeviceValuePresentation
+                },
+                DdopValidationError::MissingObject {
+                    object: id(2),
+                    reference: id(9)
+                },
+                DdopValidationError::InvalidObjectType {
+                    object: id(3),
+                    reference: id(4),
+                    object_type: DdopObjectType::DeviceProperty
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn elements() {
+        let mut pool = sprayer();
+        element(&mut pool, id(1)).parent = id(2);
+        element(&mut pool, id(2)).element_number = 0;
+        process_data(&mut pool, id(3)).ddi = 0xFFFF;
+        let errors = pool.validate().unwrap_err();
+        assert!(errors.contains(&DdopValidationError::DuplicateElementNumber(0)));
+        assert!(errors.contains(&DdopValidationError::ParentCycle(id(1))));
+        assert!(errors.contains(&DdopValidationError::InvalidDdi {
+            object: id(3),
+            ddi: 0xFFFF
+        }));
+        // Proprietary DDIs are fine
+        let mut pool = sprayer();
+        process_data(&mut pool, id(3)).ddi = 0xFFFE;
+        assert_eq!(pool.validate(), Ok(()));
+
+        let mut pool = sprayer();
+        element(&mut pool, id(1)).element_type = DeviceElementType::Function;
+        element(&mut pool, id(2)).designator = "x".repeat(129);
+        assert_eq!(
+            pool.validate(),
+            Err(vec![
+                DdopValidationError::InvalidObjectType {
+                    object: id(1),
+                    reference: id(0),
+                    object_type: DdopObjectType::Device
+                },
+                DdopValidationError::DesignatorTooLong {
+                    object: id(2),
+                    len: 129
+                },
+                DdopValidationError::MissingDeviceElement,
+            ])
+        );
+    }
+
+    #[test]
+    fn version_3_strings() {
+        let mut pool = sprayer();
+        pool.version = 3;
+        // ISO 8859-1 has one byte characters
+        element(&mut pool, id(2)).designator = "Ä".repeat(32);
+        assert_eq!(pool.validate(), Ok(()));
+        process_data(&mut pool, id(3)).designator = "Rate €".to_string();
+        assert_eq!(
+            pool.validate(),
+            Err(vec![DdopValidationError::UnrepresentableCharacter {
+                object: id(3),
+                character: '€'
+            }])
+        );
+    }
+}
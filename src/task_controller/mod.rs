@@ -0,0 +1,189 @@
+// Copyright 2023 Raven Industries inc.
+
+//! Task Controller (ISO 11783-10) protocol
+//!
+//! Like the [`virtual_terminal`](crate::virtual_terminal) module, the types here are driven by
+//! handing them received messages and collecting the messages they want to send, they do not
+//! touch the CAN bus themselves.
+//!
+//! The [`TaskControllerClient`] connects a working set to a TC: it uploads the device
+//! descriptor object pool, answers the process data requests of the TC and sends measurements
+//! when the triggers the TC set up fire. The [`DeviceDescriptorObjectPool`] describes the device
+//! to the TC, it is put together with a [`DdopBuilder`]. [`DdiDefinition`] has the units and
+//! resolution of the process data in the data dictionary.
+
+mod client;
+mod ddi;
+mod ddop;
+mod ddop_builder;
+mod ddop_validation;
+
+pub use client::{
+    MeasurementTrigger, TaskControllerClient, TcClientError, TcClientEvent, TcClientState,
+    TechnicalCapabilities, SERVER_TIMEOUT, STATUS_INTERVAL,
+};
+pub use ddi::{
+    DdiDefinition, DdiError, FIRST_PROPRIETARY_DDI, LAST_PROPRIETARY_DDI,
+    REQUEST_DEFAULT_PROCESS_DATA,
+};
+pub use ddop::{
+    localization_label, DdopError, DdopObject, DdopObjectType, Device, DeviceDescriptorObjectPool,
+    DeviceElement, DeviceElementType, DeviceProcessData, DeviceProperty, DeviceValuePresentation,
+    ProcessDataProperties, TriggerMethods,
+};
+pub use ddop_builder::{DdopBuilder, DdopBuilderError};
+pub use ddop_validation::DdopValidationError;
+
+use crate::driver::{Address, CanId, Pgn, Priority};
+use crate::network_management::can_message::CANMessage;
+use crate::network_management::common_parameter_group_numbers::CommonParameterGroupNumbers;
+
+/// The largest element number, element numbers have 12 bits
+pub const MAX_ELEMENT_NUMBER: u16 = 0x0FFF;
+
+/// The command in the low nibble of the first byte of a process data message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessDataCommand {
+    TechnicalCapabilities,
+    DeviceDescriptor,
+    RequestValue,
+    Value,
+    MeasurementTimeInterval,
+    MeasurementDistanceInterval,
+    MeasurementMinimumWithinThreshold,
+    MeasurementMaximumWithinThreshold,
+    MeasurementChangeThreshold,
+    PeerControlAssignment,
+    SetValueAndAcknowledge,
+    Reserved(u8),
+    Acknowledge,
+    /// The status broadcast by the TC
+    Status,
+    /// The task message of a client
+    ClientTask,
+}
+
+impl From<u8> for ProcessDataCommand {
+    fn from(value: u8) -> Self {
+        match value & 0x0F {
+            0x0 => ProcessDataCommand::TechnicalCapabilities,
+            0x1 => ProcessDataCommand::DeviceDescriptor,
+            0x2 => ProcessDataCommand::RequestValue,
+            0x3 => ProcessDataCommand::Value,
+            0x4 => ProcessDataCommand::MeasurementTimeInterval,
+            0x5 => ProcessDataCommand::MeasurementDistanceInterval,
+            0x6 => ProcessDataCommand::MeasurementMinimumWithinThreshold,
+            0x7 => ProcessDataCommand::MeasurementMaximumWithinThreshold,
+            0x8 => ProcessDataCommand::MeasurementChangeThreshold,
+            0x9 => ProcessDataCommand::PeerControlAssignment,
+            0xA => ProcessDataCommand::SetValueAndAcknowledge,
+            0xD => ProcessDataCommand::Acknowledge,
+            0xE => ProcessDataCommand::Status,
+            0xF => ProcessDataCommand::ClientTask,
+            value => ProcessDataCommand::Reserved(value),
+        }
+    }
+}
+
+impl From<ProcessDataCommand> for u8 {
+    fn from(value: ProcessDataCommand) -> Self {
+        match value {
+            ProcessDataCommand::TechnicalCapabilities => 0x0,
+            ProcessDataCommand::DeviceDescriptor => 0x1,
+            ProcessDataCommand::RequestValue => 0x2,
+            ProcessDataCommand::Value => 0x3,
+            ProcessDataCommand::MeasurementTimeInterval => 0x4,
+            ProcessDataCommand::MeasurementDistanceInterval => 0x5,
+            ProcessDataCommand::MeasurementMinimumWithinThreshold => 0x6,
+            ProcessDataCommand::MeasurementMaximumWithinThreshold => 0x7,
+            ProcessDataCommand::MeasurementChangeThreshold => 0x8,
+            ProcessDataCommand::PeerControlAssignment => 0x9,
+            ProcessDataCommand::SetValueAndAcknowledge => 0xA,
+            ProcessDataCommand::Reserved(value) => value & 0x0F,
+            ProcessDataCommand::Acknowledge => 0xD,
+            ProcessDataCommand::Status => 0xE,
+            ProcessDataCommand::ClientTask => 0xF,
+        }
+    }
+}
+
+/// A process data message about the value of a DDI of a device element
+///
+/// Used for the commands that carry an element number, a DDI and a value, from
+/// [`ProcessDataCommand::RequestValue`] to [`ProcessDataCommand::SetValueAndAcknowledge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessDataMessage {
+    pub command: ProcessDataCommand,
+    pub element: u16,
+    pub ddi: u16,
+    pub value: i32,
+}
+
+impl ProcessDataMessage {
+    /// Decodes a process data message, `None` if it is shorter than 8 bytes
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let data: &[u8; 8] = data.get(..8)?.try_into().ok()?;
+        Some(ProcessDataMessage {
+            command: data[0].into(),
+            element: u16::from(data[0] >> 4) | u16::from(data[1]) << 4,
+            ddi: u16::from_le_bytes([data[2], data[3]]),
+            value: i32::from_le_bytes([data[4], data[5], data[6], data[7]]),
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let element = self.element & MAX_ELEMENT_NUMBER;
+        let ddi = self.ddi.to_le_bytes();
+        let value = self.value.to_le_bytes();
+        [
+            u8::from(self.command) | (element as u8 & 0x0F) << 4,
+            (element >> 4) as u8,
+            ddi[0],
+            ddi[1],
+            value[0],
+            value[1],
+            value[2],
+            value[3],
+        ]
+    }
+}
+
+/// Builds a process data message
+pub(crate) fn process_data_message(
+    data: Vec<u8>,
+    source: Address,
+    destination: Address,
+) -> CANMessage {
+    let mut data = data;
+    if data.len() < 8 {
+        data.resize(8, 0xFF);
+    }
+    // Process data is destination specific, so any destination encodes
+    let id = CanId::try_encode(
+        Pgn::from_raw(CommonParameterGroupNumbers::ProcessData as u32),
+        source,
+        destination,
+        Priority::Five,
+    );
+    CANMessage::new(data, id.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_data_message_test() {
+        let message = ProcessDataMessage {
+            command: ProcessDataCommand::Value,
+            element: 0x123,
+            ddi: 0x0001,
+            value: -2,
+        };
+        let data = message.to_bytes();
+        assert_eq!(data, [0x33, 0x12, 0x01, 0x00, 0xFE, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(ProcessDataMessage::from_bytes(&data), Some(message));
+        assert_eq!(ProcessDataMessage::from_bytes(&data[..7]), None);
+        assert_eq!(ProcessDataCommand::from(0xFE), ProcessDataCommand::Status);
+    }
+}
@@ -0,0 +1,1190 @@
+// Copyright 2023 Raven Industries inc.
+use super::{vt_message, Direction, Fields, VtFunction, VtMessageError};
+use crate::driver::{Address, Pgn, Priority};
+use crate::network_management::can_message::CANMessage;
+use crate::network_management::common_parameter_group_numbers::CommonParameterGroupNumbers;
+use crate::network_management::name::NAME;
+use crate::object_pool::{
+    AuxiliaryFunctionAttributes, AuxiliaryFunctionType, NullableObjectId, Object, ObjectId,
+    ObjectPool,
+};
+use std::time::{Duration, Instant};
+
+/// How often an input unit sends the maintenance message
+pub const MAINTENANCE_INTERVAL: Duration = Duration::from_millis(100);
+/// The shortest time between two status messages of an input
+pub const STATUS_CHANGE_INTERVAL: Duration = Duration::from_millis(50);
+/// How often the status of an input is repeated when it does not change
+pub const STATUS_REPEAT_INTERVAL: Duration = Duration::from_millis(200);
+
+fn object_id(value: u16) -> Result<ObjectId, VtMessageError> {
+    ObjectId::new(value).map_err(|_| VtMessageError::NullObjectId)
+}
+
+fn name(fields: &mut Fields) -> Result<NAME, VtMessageError> {
+    Ok(NAME::new(u64::from_le_bytes(fields.bytes()?)))
+}
+
+/// Auxiliary Assignment Type 2 command, sent by the VT to assign an input to a function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxiliaryAssignment {
+    /// The NAME of the input unit the input belongs to
+    pub input_unit: NAME,
+    /// The function should remember this assignment as its preferred assignment
+    pub preferred: bool,
+    pub function_type: AuxiliaryFunctionType,
+    /// NULL removes the assignment of the function
+    pub input: NullableObjectId,
+    /// NULL together with a NULL input removes all assignments of the input unit
+    pub function: NullableObjectId,
+}
+
+impl AuxiliaryAssignment {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryAssignmentType2)?;
+        let input_unit = name(&mut fields)?;
+        let flags = fields.u8()?;
+        Ok(AuxiliaryAssignment {
+            input_unit,
+            preferred: flags & 0x80 != 0,
+            function_type: flags.into(),
+            input: fields.u16()?.into(),
+            function: fields.u16()?.into(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![VtFunction::AuxiliaryAssignmentType2 as u8];
+        data.extend(<[u8; 8]>::from(self.input_unit));
+        data.push(u8::from(self.preferred) << 7 | u8::from(self.function_type));
+        data.extend(u16::from(self.input).to_le_bytes());
+        data.extend(u16::from(self.function).to_le_bytes());
+        data
+    }
+
+    /// The command sent by the VT at `source` to the function at `destination`
+    pub fn to_message(&self, source: Address, destination: Address) -> CANMessage {
+        vt_message(
+            Direction::VtToNode,
+            self.to_bytes(),
+            source,
+            destination,
+            Priority::Five,
+        )
+    }
+}
+
+/// The answer of a function to an [`AuxiliaryAssignment`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxiliaryAssignmentResponse {
+    pub function: NullableObjectId,
+    /// The assignment was not accepted
+    pub rejected: bool,
+    /// The function is already assigned to another input
+    pub already_assigned: bool,
+}
+
+impl AuxiliaryAssignmentResponse {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryAssignmentType2)?;
+        let function = fields.u16()?.into();
+        let error = fields.u8()?;
+        Ok(AuxiliaryAssignmentResponse {
+            function,
+            rejected: error & 0x01 != 0,
+            already_assigned: error & 0x02 != 0,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![VtFunction::AuxiliaryAssignmentType2 as u8];
+        data.extend(u16::from(self.function).to_le_bytes());
+        data.push(u8::from(self.rejected) | u8::from(self.already_assigned) << 1);
+        data
+    }
+
+    pub fn to_message(&self, source: Address, vt: Address) -> CANMessage {
+        vt_message(
+            Direction::NodeToVt,
+            self.to_bytes(),
+            source,
+            vt,
+            Priority::Five,
+        )
+    }
+}
+
+/// Auxiliary Input Type 2 Status, the state of an input sent by its input unit to all nodes
+///
+/// What the values mean depends on the [`AuxiliaryFunctionType`] of the input. Boolean inputs
+/// report 0 when released, 1 when pressed and 2 when held in `value1`, analogue inputs their
+/// position from 0 to 0xFAFF. `value2` counts the presses of boolean inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxiliaryInputStatus {
+    pub input: ObjectId,
+    pub value1: u16,
+    pub value2: u16,
+    /// The VT enabled the input for learning an assignment
+    pub learn_mode: bool,
+    /// The input is operated while in learn mode
+    pub active_in_learn_mode: bool,
+}
+
+impl AuxiliaryInputStatus {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryInputType2Status)?;
+        let input = object_id(fields.u16()?)?;
+        let value1 = fields.u16()?;
+        let value2 = fields.u16()?;
+        let state = fields.u8()?;
+        Ok(AuxiliaryInputStatus {
+            input,
+            value1,
+            value2,
+            learn_mode: state & 0x01 != 0,
+            active_in_learn_mode: state & 0x02 != 0,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![VtFunction::AuxiliaryInputType2Status as u8];
+        data.extend(u16::from(self.input).to_le_bytes());
+        data.extend(self.value1.to_le_bytes());
+        data.extend(self.value2.to_le_bytes());
+        data.push(u8::from(self.learn_mode) | u8::from(self.active_in_learn_mode) << 1);
+        data
+    }
+
+    /// The status broadcast by the input unit at `source`
+    pub fn to_message(&self, source: Address) -> CANMessage {
+        vt_message(
+            Direction::NodeToVt,
+            self.to_bytes(),
+            source,
+            Address::GLOBAL,
+            Priority::Three,
+        )
+    }
+}
+
+/// Auxiliary Input Type 2 Maintenance, broadcast by input units so VTs and functions know they
+/// are there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxiliaryInputMaintenance {
+    /// Identifies the model of input unit, preferred assignments are only valid for the same model
+    pub model_identification_code: u16,
+    /// The input unit finished initialising, its object pool is on the VT
+    pub ready: bool,
+}
+
+impl AuxiliaryInputMaintenance {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryInputType2Maintenance)?;
+        Ok(AuxiliaryInputMaintenance {
+            model_identification_code: fields.u16()?,
+            ready: fields.u8()? == 1,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![VtFunction::AuxiliaryInputType2Maintenance as u8];
+        data.extend(self.model_identification_code.to_le_bytes());
+        data.push(u8::from(self.ready));
+        data
+    }
+
+    pub fn to_message(&self, source: Address) -> CANMessage {
+        vt_message(
+            Direction::NodeToVt,
+            self.to_bytes(),
+            source,
+            Address::GLOBAL,
+            Priority::Three,
+        )
+    }
+}
+
+/// Auxiliary Input Status Type 2 Enable command, sent by the VT to enable status messages of an
+/// input while the operator assigns it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxiliaryInputStatusEnable {
+    pub input: ObjectId,
+    pub enable: bool,
+}
+
+impl AuxiliaryInputStatusEnable {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryInputStatusType2Enable)?;
+        Ok(AuxiliaryInputStatusEnable {
+            input: object_id(fields.u16()?)?,
+            enable: fields.u8()? == 1,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![VtFunction::AuxiliaryInputStatusType2Enable as u8];
+        data.extend(u16::from(self.input).to_le_bytes());
+        data.push(u8::from(self.enable));
+        data
+    }
+
+    pub fn to_message(&self, source: Address, destination: Address) -> CANMessage {
+        vt_message(
+            Direction::VtToNode,
+            self.to_bytes(),
+            source,
+            destination,
+            Priority::Five,
+        )
+    }
+}
+
+/// The answer of an input unit to an [`AuxiliaryInputStatusEnable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxiliaryInputStatusEnableResponse {
+    pub input: ObjectId,
+    pub enabled: bool,
+    /// The input unit has no input with this ID
+    pub invalid_input: bool,
+}
+
+impl AuxiliaryInputStatusEnableResponse {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryInputStatusType2Enable)?;
+        Ok(AuxiliaryInputStatusEnableResponse {
+            input: object_id(fields.u16()?)?,
+            enabled: fields.u8()? == 1,
+            invalid_input: fields.u8()? & 0x01 != 0,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![VtFunction::AuxiliaryInputStatusType2Enable as u8];
+        data.extend(u16::from(self.input).to_le_bytes());
+        data.push(u8::from(self.enabled));
+        data.push(u8::from(self.invalid_input));
+        data
+    }
+
+    pub fn to_message(&self, source: Address, vt: Address) -> CANMessage {
+        vt_message(
+            Direction::NodeToVt,
+            self.to_bytes(),
+            source,
+            vt,
+            Priority::Five,
+        )
+    }
+}
+
+/// The preferred assignments of a function to the inputs of one input unit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreferredAssignmentUnit {
+    pub input_unit: NAME,
+    pub model_identification_code: u16,
+    /// Pairs of function and input
+    pub functions: Vec<(ObjectId, ObjectId)>,
+}
+
+/// Preferred Assignment command, sent by a function to the VT after uploading its object pool
+///
+/// The VT restores these assignments if the input units are there. Applications store the
+/// assignments the operator marks as preferred, see [`AuxiliaryFunctionClient::preferred_assignment`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreferredAssignment {
+    pub units: Vec<PreferredAssignmentUnit>,
+}
+
+impl PreferredAssignment {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::PreferredAssignment)?;
+        let mut units = Vec::new();
+        for _ in 0..fields.u8()? {
+            let input_unit = name(&mut fields)?;
+            let model_identification_code = fields.u16()?;
+            let mut functions = Vec::new();
+            for _ in 0..fields.u8()? {
+                functions.push((object_id(fields.u16()?)?, object_id(fields.u16()?)?));
+            }
+            units.push(PreferredAssignmentUnit {
+                input_unit,
+                model_identification_code,
+                functions,
+            });
+        }
+        Ok(PreferredAssignment { units })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![
+            VtFunction::PreferredAssignment as u8,
+            self.units.len() as u8,
+        ];
+        for unit in &self.units {
+            data.extend(<[u8; 8]>::from(unit.input_unit));
+            data.extend(unit.model_identification_code.to_le_bytes());
+            data.push(unit.functions.len() as u8);
+            for &(function, input) in &unit.functions {
+                data.extend(u16::from(function).to_le_bytes());
+                data.extend(u16::from(input).to_le_bytes());
+            }
+        }
+        data
+    }
+
+    pub fn to_message(&self, source: Address, vt: Address) -> CANMessage {
+        vt_message(
+            Direction::NodeToVt,
+            self.to_bytes(),
+            source,
+            vt,
+            Priority::Five,
+        )
+    }
+}
+
+/// The answer of the VT to a [`PreferredAssignment`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreferredAssignmentResponse {
+    /// 0 if the preferred assignment was accepted, error bits as defined by ISO 11783-6 otherwise
+    pub error_codes: u8,
+    /// The function that caused the error, if any
+    pub faulty_function: NullableObjectId,
+}
+
+impl PreferredAssignmentResponse {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::PreferredAssignment)?;
+        Ok(PreferredAssignmentResponse {
+            error_codes: fields.u8()?,
+            faulty_function: fields.u16()?.into(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![VtFunction::PreferredAssignment as u8, self.error_codes];
+        data.extend(u16::from(self.faulty_function).to_le_bytes());
+        data
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.error_codes == 0
+    }
+}
+
+/// A group of identical inputs or functions of an auxiliary unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapabilitySet {
+    pub instances: u8,
+    pub function_attributes: AuxiliaryFunctionAttributes,
+    /// The assigned attributes as sent by the VT
+    pub assigned_attributes: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuxiliaryUnitCapabilities {
+    pub address: Address,
+    pub sets: Vec<CapabilitySet>,
+}
+
+/// Auxiliary Capabilities, the auxiliary units a VT knows of and what they offer
+///
+/// Nodes request it from the VT with [`AuxiliaryCapabilities::request`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuxiliaryCapabilities {
+    pub units: Vec<AuxiliaryUnitCapabilities>,
+}
+
+impl AuxiliaryCapabilities {
+    pub fn request(source: Address, vt: Address) -> CANMessage {
+        vt_message(
+            Direction::NodeToVt,
+            vec![VtFunction::AuxiliaryCapabilities as u8],
+            source,
+            vt,
+            Priority::Five,
+        )
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryCapabilities)?;
+        let mut units = Vec::new();
+        for _ in 0..fields.u8()? {
+            let address = Address(fields.u8()?);
+            let mut sets = Vec::new();
+            for _ in 0..fields.u8()? {
+                sets.push(CapabilitySet {
+                    instances: fields.u8()?,
+                    function_attributes: fields.u8()?.into(),
+                    assigned_attributes: fields.u8()?,
+                });
+            }
+            units.push(AuxiliaryUnitCapabilities { address, sets });
+        }
+        Ok(AuxiliaryCapabilities { units })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![
+            VtFunction::AuxiliaryCapabilities as u8,
+            self.units.len() as u8,
+        ];
+        for unit in &self.units {
+            data.push(unit.address.0);
+            data.push(unit.sets.len() as u8);
+            for set in &unit.sets {
+                data.push(set.instances);
+                data.push(set.function_attributes.into());
+                data.push(set.assigned_attributes);
+            }
+        }
+        data
+    }
+
+    /// The response sent by the VT at `source` to `destination`
+    pub fn to_message(&self, source: Address, destination: Address) -> CANMessage {
+        vt_message(
+            Direction::VtToNode,
+            self.to_bytes(),
+            source,
+            destination,
+            Priority::Five,
+        )
+    }
+}
+
+/// An input assigned to one of our functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment {
+    pub function: ObjectId,
+    pub input_unit: NAME,
+    pub input: ObjectId,
+    pub function_type: AuxiliaryFunctionType,
+    /// The operator wants this assignment restored next time
+    pub preferred: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuxiliaryFunctionEvent {
+    Assigned(Assignment),
+    Unassigned {
+        function: ObjectId,
+    },
+    /// The input assigned to `function` sent its status
+    Input {
+        function: ObjectId,
+        function_type: AuxiliaryFunctionType,
+        value1: u16,
+        value2: u16,
+    },
+}
+
+/// The function side of AUX-N, for implements with Auxiliary Function Type 2 objects
+///
+/// Handles the assignments the VT sends and turns the status of assigned inputs into
+/// [`AuxiliaryFunctionEvent`]s for our functions. Input units are identified by their NAME, so
+/// the client follows address claims to know which input unit sends a status. Claims made before
+/// the client was created are not seen again, pass those in with
+/// [`AuxiliaryFunctionClient::set_name`].
+#[derive(Debug, Clone, Default)]
+pub struct AuxiliaryFunctionClient {
+    functions: Vec<(ObjectId, AuxiliaryFunctionAttributes)>,
+    assignments: Vec<Assignment>,
+    names: Vec<(Address, NAME)>,
+    model_identification_codes: Vec<(NAME, u16)>,
+    messages: Vec<CANMessage>,
+}
+
+impl AuxiliaryFunctionClient {
+    /// A client for the Auxiliary Function Type 2 objects in `pool`
+    pub fn new(pool: &ObjectPool) -> Self {
+        let functions = pool
+            .objects()
+            .iter()
+            .filter_map(|object| match object {
+                Object::AuxiliaryFunctionType2(o) => Some((o.id, o.attributes())),
+                _ => None,
+            })
+            .collect();
+        AuxiliaryFunctionClient {
+            functions,
+            ..Default::default()
+        }
+    }
+
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    /// The input assigned to `function`
+    pub fn assignment(&self, function: ObjectId) -> Option<&Assignment> {
+        self.assignments.iter().find(|a| a.function == function)
+    }
+
+    /// The NAME of the node at `address`, as claimed on the bus
+    pub fn name_of(&self, address: Address) -> Option<NAME> {
+        self.names
+            .iter()
+            .find(|(a, _)| *a == address)
+            .map(|&(_, name)| name)
+    }
+
+    /// Records that the node with `name` claimed `address`
+    pub fn set_name(&mut self, address: Address, name: NAME) {
+        self.names.retain(|&(a, n)| a != address && n != name);
+        self.names.push((address, name));
+    }
+
+    /// The assignments marked as preferred, to store and send after the next start
+    pub fn preferred_assignment(&self) -> PreferredAssignment {
+        let mut preferred = PreferredAssignment::default();
+        for assignment in self.assignments.iter().filter(|a| a.preferred) {
+            let pair = (assignment.function, assignment.input);
+            match preferred
+                .units
+                .iter_mut()
+                .find(|unit| unit.input_unit == assignment.input_unit)
+            {
+                Some(unit) => unit.functions.push(pair),
+                None => preferred.units.push(PreferredAssignmentUnit {
+                    input_unit: assignment.input_unit,
+                    model_identification_code: self
+                        .model_identification_codes
+                        .iter()
+                        .find(|(name, _)| *name == assignment.input_unit)
+                        .map_or(0, |&(_, code)| code),
+                    functions: vec![pair],
+                }),
+            }
+        }
+        preferred
+    }
+
+    /// Queues the Preferred Assignment command to the VT
+    pub fn send_preferred_assignment(
+        &mut self,
+        preferred: &PreferredAssignment,
+        source: Address,
+        vt: Address,
+    ) {
+        self.messages.push(preferred.to_message(source, vt));
+    }
+
+    /// Handles a received message, `own_address` is the address of our control function
+    pub fn process_message(
+        &mut self,
+        message: &CANMessage,
+        own_address: Address,
+    ) -> Vec<AuxiliaryFunctionEvent> {
+        let id = message.get_identifier();
+        let source = id.source_address();
+        let data = message.get_data();
+        if id.pgn() == Pgn::from_raw(CommonParameterGroupNumbers::AddressClaim as u32) {
+            if let Ok(bytes) = data.try_into() {
+                self.set_name(source, NAME::new(u64::from_le_bytes(bytes)));
+            }
+            return vec![];
+        }
+        match (Direction::of(message), data.first().copied()) {
+            (Some(Direction::VtToNode), Some(f))
+                if f == VtFunction::AuxiliaryAssignmentType2 as u8
+                    && id.destination_address() == own_address =>
+            {
+                match AuxiliaryAssignment::from_bytes(data) {
+                    Ok(assignment) => self.assign(assignment, source, own_address),
+                    Err(_) => vec![],
+                }
+            }
+            (Some(Direction::NodeToVt), Some(f))
+                if f == VtFunction::AuxiliaryInputType2Maintenance as u8 =>
+            {
+                if let (Ok(maintenance), Some(name)) = (
+                    AuxiliaryInputMaintenance::from_bytes(data),
+                    self.name_of(source),
+                ) {
+                    self.model_identification_codes.retain(|(n, _)| *n != name);
+                    self.model_identification_codes
+                        .push((name, maintenance.model_identification_code));
+                }
+                vec![]
+            }
+            (Some(Direction::NodeToVt), Some(f))
+                if f == VtFunction::AuxiliaryInputType2Status as u8 =>
+            {
+                match (AuxiliaryInputStatus::from_bytes(data), self.name_of(source)) {
+                    (Ok(status), Some(name)) if !status.learn_mode => self.input(status, name),
+                    _ => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn assign(
+        &mut self,
+        command: AuxiliaryAssignment,
+        vt: Address,
+        own_address: Address,
+    ) -> Vec<AuxiliaryFunctionEvent> {
+        let mut response = AuxiliaryAssignmentResponse {
+            function: command.function,
+            rejected: false,
+            already_assigned: false,
+        };
+        let mut events = Vec::new();
+        match command.function.0 {
+            None if command.input.is_null() => {
+                let (removed, kept) = self
+                    .assignments
+                    .iter()
+                    .partition(|a| a.input_unit == command.input_unit);
+                self.assignments = kept;
+                events.extend(removed.into_iter().map(|a: Assignment| {
+                    AuxiliaryFunctionEvent::Unassigned {
+                        function: a.function,
+                    }
+                }));
+            }
+            None => response.rejected = true,
+            Some(function) => match self.functions.iter().find(|(id, _)| *id == function) {
+                None => response.rejected = true,
+                Some(_)
+                    if command.input.is_null()
+                        || command.function_type == AuxiliaryFunctionType::RemoveAssignment =>
+                {
+                    if self.assignment(function).is_some() {
+                        self.assignments.retain(|a| a.function != function);
+                        events.push(AuxiliaryFunctionEvent::Unassigned { function });
+                    }
+                }
+                Some((_, attributes)) if attributes.function_type != command.function_type => {
+                    response.rejected = true
+                }
+                // A function is assigned to one input, the VT has to remove the old assignment
+                // before assigning another input
+                Some(_)
+                    if self.assignment(function).is_some_and(|old| {
+                        old.input_unit != command.input_unit || Some(old.input) != command.input.0
+                    }) =>
+                {
+                    response.rejected = true;
+                    response.already_assigned = true;
+                }
+                Some(_) => {
+                    self.assignments.retain(|a| a.function != function);
+                    let assignment = Assignment {
+                        function,
+                        input_unit: command.input_unit,
+                        input: command.input.0.unwrap_or_default(),
+                        function_type: command.function_type,
+                        preferred: command.preferred,
+                    };
+                    self.assignments.push(assignment);
+                    events.push(AuxiliaryFunctionEvent::Assigned(assignment));
+                }
+            },
+        }
+        self.messages.push(response.to_message(own_address, vt));
+        events
+    }
+
+    fn input(&self, status: AuxiliaryInputStatus, name: NAME) -> Vec<AuxiliaryFunctionEvent> {
+        self.assignments
+            .iter()
+            .filter(|a| a.input_unit == name && a.input == status.input)
+            .map(|a| AuxiliaryFunctionEvent::Input {
+                function: a.function,
+                function_type: a.function_type,
+                value1: status.value1,
+                value2: status.value2,
+            })
+            .collect()
+    }
+
+    /// The messages to send, like responses to assignments
+    pub fn take_messages(&mut self) -> Vec<CANMessage> {
+        std::mem::take(&mut self.messages)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct InputState {
+    id: ObjectId,
+    value1: u16,
+    value2: u16,
+    /// Enabled by the VT for learn mode
+    enabled: bool,
+    changed: bool,
+    last_sent: Option<Instant>,
+}
+
+/// The input side of AUX-N, for joysticks and other input units with Auxiliary Input Type 2
+/// objects
+///
+/// The application reports the state of its inputs with [`set_input`](Self::set_input) and
+/// calls [`update`](Self::update) regularly. Status is sent on change, at most every
+/// [`STATUS_CHANGE_INTERVAL`], and repeated every [`STATUS_REPEAT_INTERVAL`].
+#[derive(Debug, Clone)]
+pub struct AuxiliaryInputDevice {
+    model_identification_code: u16,
+    ready: bool,
+    inputs: Vec<InputState>,
+    last_maintenance: Option<Instant>,
+    messages: Vec<CANMessage>,
+}
+
+impl AuxiliaryInputDevice {
+    /// An input unit with the Auxiliary Input Type 2 objects in `pool`
+    pub fn new(pool: &ObjectPool, model_identification_code: u16) -> Self {
+        let inputs = pool
+            .objects()
+            .iter()
+            .filter_map(|object| match object {
+                Object::AuxiliaryInputType2(o) => Some(InputState {
+                    id: o.id,
+                    value1: 0,
+                    value2: 0,
+                    enabled: false,
+                    changed: true,
+                    last_sent: None,
+                }),
+                _ => None,
+            })
+            .collect();
+        AuxiliaryInputDevice {
+            model_identification_code,
+            ready: false,
+            inputs,
+            last_maintenance: None,
+            messages: Vec::new(),
+        }
+    }
+
+    /// Marks the input unit ready once its object pool is on the VT, status is only sent when
+    /// ready
+    pub fn set_ready(&mut self, ready: bool) {
+        self.ready = ready;
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+
+    /// Whether the VT enabled any input for learning an assignment
+    pub fn is_learn_mode(&self) -> bool {
+        self.inputs.iter().any(|input| input.enabled)
+    }
+
+    /// Sets the values of `input`, returns false if there is no such input
+    pub fn set_input(&mut self, input: ObjectId, value1: u16, value2: u16) -> bool {
+        match self.inputs.iter_mut().find(|i| i.id == input) {
+            Some(state) => {
+                state.changed |= state.value1 != value1 || state.value2 != value2;
+                state.value1 = value1;
+                state.value2 = value2;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The current status of `input`
+    pub fn status(&self, input: ObjectId) -> Option<AuxiliaryInputStatus> {
+        self.inputs
+            .iter()
+            .find(|i| i.id == input)
+            .map(|state| AuxiliaryInputStatus {
+                input: state.id,
+                value1: state.value1,
+                value2: state.value2,
+                learn_mode: state.enabled,
+                active_in_learn_mode: state.enabled && state.value1 != 0,
+            })
+    }
+
+    /// Handles a received message, `own_address` is the address of the input unit
+    pub fn process_message(&mut self, message: &CANMessage, own_address: Address) {
+        let id = message.get_identifier();
+        if Direction::of(message) != Some(Direction::VtToNode)
+            || id.destination_address() != own_address
+        {
+            return;
+        }
+        let Ok(command) = AuxiliaryInputStatusEnable::from_bytes(message.get_data()) else {
+            return;
+        };
+        let state = self.inputs.iter_mut().find(|i| i.id == command.input);
+        let response = AuxiliaryInputStatusEnableResponse {
+            input: command.input,
+            enabled: command.enable && state.is_some(),
+            invalid_input: state.is_none(),
+        };
+        if let Some(state) = state {
+            state.enabled = command.enable;
+            state.changed = true;
+        }
+        self.messages
+            .push(response.to_message(own_address, id.source_address()));
+    }
+
+    /// Queues the maintenance and status messages that are due at `now`
+    pub fn update(&mut self, now: Instant, source: Address) {
+        if self
+            .last_maintenance
+            .is_none_or(|last| now - last >= MAINTENANCE_INTERVAL)
+        {
+            let maintenance = AuxiliaryInputMaintenance {
+                model_identification_code: self.model_identification_code,
+                ready: self.ready,
+            };
+            self.messages.push(maintenance.to_message(source));
+            self.last_maintenance = Some(now);
+        }
+        if !self.ready {
+            return;
+        }
+        for index in 0..self.inputs.len() {
+            let state = &self.inputs[index];
+            let due = match state.last_sent {
+                None => true,
+                Some(last) if state.changed => now - last >= STATUS_CHANGE_INTERVAL,
+                Some(last) => now - last >= STATUS_REPEAT_INTERVAL,
+            };
+            if due {
+                if let Some(status) = self.status(state.id) {
+                    self.messages.push(status.to_message(source));
+                }
+                let state = &mut self.inputs[index];
+                state.changed = false;
+                state.last_sent = Some(now);
+            }
+        }
+    }
+
+    /// The messages to send
+    pub fn take_messages(&mut self) -> Vec<CANMessage> {
+        std::mem::take(&mut self.messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driver::CanId;
+    use crate::object_pool::{AuxiliaryFunctionType2, AuxiliaryInputType2};
+
+    const VT: Address = Address(0x26);
+    const IMPLEMENT: Address = Address(0x80);
+    const JOYSTICK: Address = Address(0x90);
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    fn joystick_name() -> NAME {
+        NAME::new(0xA000_8200_0C2F_E5A8)
+    }
+
+    fn pool() -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        for (object_id, function_type) in [
+            (100, AuxiliaryFunctionType::BooleanNonLatching),
+            (101, AuxiliaryFunctionType::Analogue),
+        ] {
+            let attributes = AuxiliaryFunctionAttributes {
+                function_type,
+                critical_control: false,
+                assignment_restriction: false,
+                single_assignment: false,
+            };
+            pool.add(Object::AuxiliaryFunctionType2(AuxiliaryFunctionType2 {
+                id: id(object_id),
+                background_colour: 0,
+                function_attributes: attributes.into(),
+                object_refs: vec![],
+            }));
+            pool.add(Object::AuxiliaryInputType2(AuxiliaryInputType2 {
+                id: id(object_id + 100),
+                background_colour: 0,
+                function_attributes: attributes.into(),
+                object_refs: vec![],
+            }));
+        }
+        pool
+    }
+
+    fn address_claim(name: NAME, source: Address) -> CANMessage {
+        let id = CanId::try_encode(
+            Pgn::from_raw(CommonParameterGroupNumbers::AddressClaim as u32),
+            source,
+            Address::GLOBAL,
+            Priority::Default,
+        );
+        CANMessage::new(<[u8; 8]>::from(name).to_vec(), id.unwrap())
+    }
+
+    fn assignment(input: u16, function: u16, function_type: AuxiliaryFunctionType) -> CANMessage {
+        AuxiliaryAssignment {
+            input_unit: joystick_name(),
+            preferred: true,
+            function_type,
+            input: input.into(),
+            function: function.into(),
+        }
+        .to_message(VT, IMPLEMENT)
+    }
+
+    #[test]
+    fn message_round_trip_test() {
+        let assignment = AuxiliaryAssignment {
+            input_unit: joystick_name(),
+            preferred: true,
+            function_type: AuxiliaryFunctionType::Analogue,
+            input: 201.into(),
+            function: 101.into(),
+        };
+        let data = assignment.to_bytes();
+        assert_eq!(data.len(), 14);
+        assert_eq!(data[9], 0x81);
+        assert_eq!(AuxiliaryAssignment::from_bytes(&data), Ok(assignment));
+
+        let status = AuxiliaryInputStatus {
+            input: id(200),
+            value1: 0xFAFF,
+            value2: 3,
+            learn_mode: true,
+            active_in_learn_mode: true,
+        };
+        let message = status.to_message(JOYSTICK);
+        assert_eq!(message.get_data(), [0x26, 200, 0, 0xFF, 0xFA, 3, 0, 0x03]);
+        assert_eq!(message.get_identifier().raw(), 0x0CE7_FF90);
+        assert_eq!(
+            AuxiliaryInputStatus::from_bytes(message.get_data()),
+            Ok(status)
+        );
+
+        let preferred = PreferredAssignment {
+            units: vec![PreferredAssignmentUnit {
+                input_unit: joystick_name(),
+                model_identification_code: 7,
+                functions: vec![(id(100), id(200)), (id(101), id(201))],
+            }],
+        };
+        assert_eq!(
+            PreferredAssignment::from_bytes(&preferred.to_bytes()),
+            Ok(preferred)
+        );
+
+        let capabilities = AuxiliaryCapabilities {
+            units: vec![AuxiliaryUnitCapabilities {
+                address: JOYSTICK,
+                sets: vec![CapabilitySet {
+                    instances: 4,
+                    function_attributes: 0x02.into(),
+                    assigned_attributes: 0,
+                }],
+            }],
+        };
+        assert_eq!(
+            AuxiliaryCapabilities::from_bytes(&capabilities.to_bytes()),
+            Ok(capabilities)
+        );
+
+        assert_eq!(
+            AuxiliaryInputStatus::from_bytes(&[0x26, 200, 0]),
+            Err(VtMessageError::UnexpectedEnd {
+                expected: 5,
+                found: 3
+            })
+        );
+        assert_eq!(
+            AuxiliaryInputMaintenance::from_bytes(&[0x26, 0, 0, 0]),
+            Err(VtMessageError::UnexpectedFunction(0x26))
+        );
+    }
+
+    #[test]
+    fn function_client_test() {
+        let mut client = AuxiliaryFunctionClient::new(&pool());
+        client.process_message(&address_claim(joystick_name(), JOYSTICK), IMPLEMENT);
+        assert_eq!(client.name_of(JOYSTICK), Some(joystick_name()));
+
+        let events = client.process_message(
+            &assignment(200, 100, AuxiliaryFunctionType::BooleanNonLatching),
+            IMPLEMENT,
+        );
+        assert!(matches!(events[..], [AuxiliaryFunctionEvent::Assigned(a)] if a.input == id(200)));
+        let responses = client.take_messages();
+        assert_eq!(
+            responses[0].get_data(),
+            [0x24, 100, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+        assert_eq!(responses[0].get_identifier().destination_address(), VT);
+
+        // The function type has to match
+        let events = client.process_message(
+            &assignment(201, 101, AuxiliaryFunctionType::BooleanLatching),
+            IMPLEMENT,
+        );
+        assert!(events.is_empty());
+        let response =
+            AuxiliaryAssignmentResponse::from_bytes(client.take_messages()[0].get_data()).unwrap();
+        assert!(response.rejected);
+
+        // Assignments to other nodes are ignored
+        let events = client.process_message(
+            &assignment(201, 101, AuxiliaryFunctionType::Analogue),
+            Address(0x81),
+        );
+        assert!(events.is_empty() && client.take_messages().is_empty());
+
+        // The assigned function can't take another input before it's unassigned
+        let events = client.process_message(
+            &assignment(201, 100, AuxiliaryFunctionType::BooleanNonLatching),
+            IMPLEMENT,
+        );
+        assert!(events.is_empty());
+        let response =
+            AuxiliaryAssignmentResponse::from_bytes(client.take_messages()[0].get_data()).unwrap();
+        assert!(response.rejected && response.already_assigned);
+        assert_eq!(client.assignment(id(100)).unwrap().input, id(200));
+
+        let status = AuxiliaryInputStatus {
+            input: id(200),
+            value1: 1,
+            value2: 1,
+            learn_mode: false,
+            active_in_learn_mode: false,
+        };
+        let events = client.process_message(&status.to_message(JOYSTICK), IMPLEMENT);
+        assert_eq!(
+            events,
+            vec![AuxiliaryFunctionEvent::Input {
+                function: id(100),
+                function_type: AuxiliaryFunctionType::BooleanNonLatching,
+                value1: 1,
+                value2: 1
+            }]
+        );
+        // Learn mode does not operate functions, and unknown nodes are ignored
+        let learning = AuxiliaryInputStatus {
+            learn_mode: true,
+            ..status
+        };
+        assert!(client
+            .process_message(&learning.to_message(JOYSTICK), IMPLEMENT)
+            .is_empty());
+        assert!(client
+            .process_message(&status.to_message(Address(0x91)), IMPLEMENT)
+            .is_empty());
+
+        let maintenance = AuxiliaryInputMaintenance {
+            model_identification_code: 7,
+            ready: true,
+        };
+        client.process_message(&maintenance.to_message(JOYSTICK), IMPLEMENT);
+        let preferred = client.preferred_assignment();
+        assert_eq!(preferred.units[0].model_identification_code, 7);
+        assert_eq!(preferred.units[0].functions, vec![(id(100), id(200))]);
+
+        let events = client.process_message(
+            &assignment(0xFFFF, 0xFFFF, AuxiliaryFunctionType::RemoveAssignment),
+            IMPLEMENT,
+        );
+        assert_eq!(
+            events,
+            vec![AuxiliaryFunctionEvent::Unassigned { function: id(100) }]
+        );
+        assert!(client.assignments().is_empty());
+    }
+
+    #[test]
+    fn known_names_test() {
+        let mut client = AuxiliaryFunctionClient::new(&pool());
+        // The joystick claimed its address before the client existed
+        client.set_name(JOYSTICK, joystick_name());
+        client.process_message(
+            &assignment(200, 100, AuxiliaryFunctionType::BooleanNonLatching),
+            IMPLEMENT,
+        );
+        let status = AuxiliaryInputStatus {
+            input: id(200),
+            value1: 1,
+            value2: 0,
+            learn_mode: false,
+            active_in_learn_mode: false,
+        };
+        assert_eq!(
+            client
+                .process_message(&status.to_message(JOYSTICK), IMPLEMENT)
+                .len(),
+            1
+        );
+
+        // A new claim moves the NAME to the new address
+        client.process_message(&address_claim(joystick_name(), Address(0x91)), IMPLEMENT);
+        assert_eq!(client.name_of(JOYSTICK), None);
+        assert_eq!(client.name_of(Address(0x91)), Some(joystick_name()));
+    }
+
+    #[test]
+    fn input_device_test() {
+        let mut device = AuxiliaryInputDevice::new(&pool(), 7);
+        let start = Instant::now();
+        device.update(start, JOYSTICK);
+        let messages = device.take_messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].get_data(),
+            [0x23, 7, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+
+        device.set_ready(true);
+        device.update(start + Duration::from_millis(10), JOYSTICK);
+        // No maintenance yet, but the status of both inputs
+        assert_eq!(device.take_messages().len(), 2);
+
+        assert!(device.set_input(id(200), 1, 1));
+        assert!(!device.set_input(id(300), 1, 1));
+        device.update(start + Duration::from_millis(40), JOYSTICK);
+        assert!(device.take_messages().is_empty());
+        device.update(start + Duration::from_millis(60), JOYSTICK);
+        let messages = device.take_messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].get_data(), [0x26, 200, 0, 1, 0, 1, 0, 0]);
+
+        device.update(start + Duration::from_millis(210), JOYSTICK);
+        let messages = device.take_messages();
+        // Maintenance and the repeated status of the unchanged input 201
+        assert_eq!(messages.len(), 2);
+
+        let enable = AuxiliaryInputStatusEnable {
+            input: id(200),
+            enable: true,
+        };
+        device.process_message(&enable.to_message(VT, JOYSTICK), JOYSTICK);
+        assert!(device.is_learn_mode());
+        let response = device.take_messages();
+        assert_eq!(
+            AuxiliaryInputStatusEnableResponse::from_bytes(response[0].get_data()),
+            Ok(AuxiliaryInputStatusEnableResponse {
+                input: id(200),
+                enabled: true,
+                invalid_input: false
+            })
+        );
+        assert!(device.status(id(200)).unwrap().active_in_learn_mode);
+
+        let enable = AuxiliaryInputStatusEnable {
+            input: id(999),
+            enable: true,
+        };
+        device.process_message(&enable.to_message(VT, JOYSTICK), JOYSTICK);
+        let response = device.take_messages();
+        assert!(
+            AuxiliaryInputStatusEnableResponse::from_bytes(response[0].get_data())
+                .unwrap()
+                .invalid_input
+        );
+    }
+}
@@ -0,0 +1,431 @@
+// Copyright 2023 Raven Industries inc.
+use super::{vt_message, Direction, Fields, VtFunction, VtMessageError};
+use crate::driver::{Address, Priority};
+use crate::network_management::can_message::CANMessage;
+use crate::object_pool::{AuxOFunctionType, Object, ObjectId, ObjectPool};
+
+/// The input number that removes the assignment of a function
+const UNASSIGN: u8 = 0xFF;
+
+/// Auxiliary Assignment Type 1 command, sent by the VT to assign an input to a function
+///
+/// AUX-O identifies inputs by the address of their input unit and their input number. The
+/// function answers with the same data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxOAssignment {
+    pub input_address: Address,
+    /// The input number of the Auxiliary Input Type 1 object, 0xFF removes the assignment
+    pub input_number: u8,
+    pub function: ObjectId,
+}
+
+impl AuxOAssignment {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryAssignmentType1)?;
+        Ok(AuxOAssignment {
+            input_address: Address(fields.u8()?),
+            input_number: fields.u8()?,
+            function: ObjectId::new(fields.u16()?).map_err(|_| VtMessageError::NullObjectId)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![
+            VtFunction::AuxiliaryAssignmentType1 as u8,
+            self.input_address.0,
+            self.input_number,
+        ];
+        data.extend(u16::from(self.function).to_le_bytes());
+        data
+    }
+
+    /// Whether this removes the assignment of the function
+    pub fn is_unassign(&self) -> bool {
+        self.input_number == UNASSIGN
+    }
+
+    /// The command sent by the VT at `source` to the function at `destination`
+    pub fn to_message(&self, source: Address, destination: Address) -> CANMessage {
+        vt_message(
+            Direction::VtToNode,
+            self.to_bytes(),
+            source,
+            destination,
+            Priority::Five,
+        )
+    }
+
+    /// The response of the function at `source` to the VT
+    pub fn to_response(&self, source: Address, vt: Address) -> CANMessage {
+        vt_message(
+            Direction::NodeToVt,
+            self.to_bytes(),
+            source,
+            vt,
+            Priority::Five,
+        )
+    }
+}
+
+/// Auxiliary Input Type 1 Status, the state of an input broadcast by its input unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxOInputStatus {
+    pub input_number: u8,
+    /// The position of analogue inputs, non-zero while boolean inputs are pressed
+    pub value1: u16,
+    /// The number of times a boolean input was pressed
+    pub value2: u16,
+}
+
+impl AuxOInputStatus {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VtMessageError> {
+        let mut fields = Fields::new(data, VtFunction::AuxiliaryInputType1Status)?;
+        Ok(AuxOInputStatus {
+            input_number: fields.u8()?,
+            value1: fields.u16()?,
+            value2: fields.u16()?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![
+            VtFunction::AuxiliaryInputType1Status as u8,
+            self.input_number,
+        ];
+        data.extend(self.value1.to_le_bytes());
+        data.extend(self.value2.to_le_bytes());
+        data
+    }
+
+    pub fn to_message(&self, source: Address) -> CANMessage {
+        vt_message(
+            Direction::NodeToVt,
+            self.to_bytes(),
+            source,
+            Address::GLOBAL,
+            Priority::Three,
+        )
+    }
+}
+
+/// The value of a function, decoded according to its [`AuxOFunctionType`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuxOValue {
+    Boolean(bool),
+    Analogue(u16),
+}
+
+/// An AUX-O input assigned to one of our functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxOFunctionAssignment {
+    pub function: ObjectId,
+    pub function_type: AuxOFunctionType,
+    pub input_address: Address,
+    pub input_number: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuxOEvent {
+    Assigned(AuxOFunctionAssignment),
+    Unassigned {
+        function: ObjectId,
+    },
+    /// The value of `function` changed
+    Value {
+        function: ObjectId,
+        value: AuxOValue,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct FunctionState {
+    assignment: AuxOFunctionAssignment,
+    /// Whether the input was pressed in its last status, to find the presses of latching inputs
+    pressed: bool,
+    value: Option<AuxOValue>,
+}
+
+/// The function side of AUX-O, for implements with Auxiliary Function Type 1 objects
+///
+/// Handles the assignments the VT sends and turns the status of assigned inputs into values of
+/// our functions. A latching boolean function toggles each time its input is pressed, a
+/// non-latching boolean function is on while its input is pressed.
+#[derive(Debug, Clone, Default)]
+pub struct AuxOFunctionClient {
+    functions: Vec<(ObjectId, AuxOFunctionType)>,
+    states: Vec<FunctionState>,
+    messages: Vec<CANMessage>,
+}
+
+impl AuxOFunctionClient {
+    /// A client for the Auxiliary Function Type 1 objects in `pool`
+    pub fn new(pool: &ObjectPool) -> Self {
+        let functions = pool
+            .objects()
+            .iter()
+            .filter_map(|object| match object {
+                Object::AuxiliaryFunctionType1(o) => Some((o.id, o.function_type.into())),
+                _ => None,
+            })
+            .collect();
+        AuxOFunctionClient {
+            functions,
+            ..Default::default()
+        }
+    }
+
+    pub fn assignments(&self) -> Vec<AuxOFunctionAssignment> {
+        self.states.iter().map(|s| s.assignment).collect()
+    }
+
+    /// The current value of `function`, `None` until its input sent its status
+    pub fn value(&self, function: ObjectId) -> Option<AuxOValue> {
+        self.states
+            .iter()
+            .find(|s| s.assignment.function == function)
+            .and_then(|s| s.value)
+    }
+
+    /// Handles a received message, `own_address` is the address of our control function
+    pub fn process_message(
+        &mut self,
+        message: &CANMessage,
+        own_address: Address,
+    ) -> Vec<AuxOEvent> {
+        let id = message.get_identifier();
+        let data = message.get_data();
+        match Direction::of(message) {
+            Some(Direction::VtToNode) if id.destination_address() == own_address => {
+                match AuxOAssignment::from_bytes(data) {
+                    Ok(assignment) => {
+                        self.messages
+                            .push(assignment.to_response(own_address, id.source_address()));
+                        self.assign(assignment)
+                    }
+                    Err(_) => vec![],
+                }
+            }
+            Some(Direction::NodeToVt) => match AuxOInputStatus::from_bytes(data) {
+                Ok(status) => self.input(status, id.source_address()),
+                Err(_) => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    fn assign(&mut self, command: AuxOAssignment) -> Vec<AuxOEvent> {
+        let Some(&(function, function_type)) = self
+            .functions
+            .iter()
+            .find(|(id, _)| *id == command.function)
+        else {
+            return vec![];
+        };
+        let assigned = self
+            .states
+            .iter()
+            .any(|s| s.assignment.function == function);
+        self.states.retain(|s| s.assignment.function != function);
+        if command.is_unassign() {
+            return match assigned {
+                true => vec![AuxOEvent::Unassigned { function }],
+                false => vec![],
+            };
+        }
+        let assignment = AuxOFunctionAssignment {
+            function,
+            function_type,
+            input_address: command.input_address,
+            input_number: command.input_number,
+        };
+        self.states.push(FunctionState {
+            assignment,
+            pressed: false,
+            value: None,
+        });
+        vec![AuxOEvent::Assigned(assignment)]
+    }
+
+    fn input(&mut self, status: AuxOInputStatus, source: Address) -> Vec<AuxOEvent> {
+        let mut events = Vec::new();
+        for state in self.states.iter_mut().filter(|s| {
+            s.assignment.input_address == source && s.assignment.input_number == status.input_number
+        }) {
+            let pressed = status.value1 != 0;
+            let value = match state.assignment.function_type {
+                AuxOFunctionType::Analogue => AuxOValue::Analogue(status.value1),
+                AuxOFunctionType::NonLatchingBoolean => AuxOValue::Boolean(pressed),
+                AuxOFunctionType::LatchingBoolean => {
+                    let on = matches!(state.value, Some(AuxOValue::Boolean(true)));
+                    AuxOValue::Boolean(on ^ (pressed && !state.pressed))
+                }
+                AuxOFunctionType::Reserved(_) => continue,
+            };
+            state.pressed = pressed;
+            if state.value != Some(value) {
+                state.value = Some(value);
+                events.push(AuxOEvent::Value {
+                    function: state.assignment.function,
+                    value,
+                });
+            }
+        }
+        events
+    }
+
+    /// The messages to send, the responses to assignments
+    pub fn take_messages(&mut self) -> Vec<CANMessage> {
+        std::mem::take(&mut self.messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_pool::{AuxiliaryFunctionType1, ObjectBuilder};
+
+    const VT: Address = Address(0x26);
+    const IMPLEMENT: Address = Address(0x80);
+    const JOYSTICK: Address = Address(0x90);
+
+    fn id(id: u16) -> ObjectId {
+        ObjectId::new(id).unwrap()
+    }
+
+    fn client() -> AuxOFunctionClient {
+        let mut pool = ObjectPool::new();
+        for (object_id, function_type) in [
+            (100, AuxOFunctionType::LatchingBoolean),
+            (101, AuxOFunctionType::Analogue),
+            (102, AuxOFunctionType::NonLatchingBoolean),
+        ] {
+            pool.add(
+                AuxiliaryFunctionType1::builder()
+                    .function_type(function_type.into())
+                    .build(id(object_id)),
+            );
+        }
+        AuxOFunctionClient::new(&pool)
+    }
+
+    fn assign(client: &mut AuxOFunctionClient, input_number: u8, function: u16) -> Vec<AuxOEvent> {
+        let command = AuxOAssignment {
+            input_address: JOYSTICK,
+            input_number,
+            function: id(function),
+        };
+        client.process_message(&command.to_message(VT, IMPLEMENT), IMPLEMENT)
+    }
+
+    fn press(client: &mut AuxOFunctionClient, input_number: u8, value1: u16) -> Vec<AuxOEvent> {
+        let status = AuxOInputStatus {
+            input_number,
+            value1,
+            value2: 0,
+        };
+        client.process_message(&status.to_message(JOYSTICK), IMPLEMENT)
+    }
+
+    fn value(function: u16, value: AuxOValue) -> Vec<AuxOEvent> {
+        vec![AuxOEvent::Value {
+            function: id(function),
+            value,
+        }]
+    }
+
+    #[test]
+    fn message_test() {
+        let assignment = AuxOAssignment {
+            input_address: JOYSTICK,
+            input_number: 3,
+            function: id(100),
+        };
+        let message = assignment.to_message(VT, IMPLEMENT);
+        assert_eq!(
+            message.get_data(),
+            [0x20, 0x90, 3, 100, 0, 0xFF, 0xFF, 0xFF]
+        );
+        assert_eq!(
+            AuxOAssignment::from_bytes(message.get_data()),
+            Ok(assignment)
+        );
+
+        let status = AuxOInputStatus {
+            input_number: 3,
+            value1: 0x1234,
+            value2: 2,
+        };
+        assert_eq!(AuxOInputStatus::from_bytes(&status.to_bytes()), Ok(status));
+        assert_eq!(
+            AuxOInputStatus::from_bytes(&assignment.to_bytes()),
+            Err(VtMessageError::UnexpectedFunction(0x20))
+        );
+    }
+
+    #[test]
+    fn assignment_test() {
+        let mut client = client();
+        let events = assign(&mut client, 3, 100);
+        assert!(matches!(events[..], [AuxOEvent::Assigned(a)] if a.input_number == 3));
+        let responses = client.take_messages();
+        assert_eq!(responses[0].get_data()[..5], [0x20, 0x90, 3, 100, 0]);
+        assert_eq!(responses[0].get_identifier().destination_address(), VT);
+
+        // Unknown functions are not assigned, but answered
+        assert!(assign(&mut client, 4, 999).is_empty());
+        assert_eq!(client.take_messages().len(), 1);
+
+        assert_eq!(
+            assign(&mut client, UNASSIGN, 100),
+            vec![AuxOEvent::Unassigned { function: id(100) }]
+        );
+        assert!(client.assignments().is_empty());
+    }
+
+    #[test]
+    fn function_type_test() {
+        let mut client = client();
+        assign(&mut client, 1, 100);
+        assign(&mut client, 2, 101);
+        assign(&mut client, 3, 102);
+
+        // Latching toggles on every press
+        assert_eq!(
+            press(&mut client, 1, 1),
+            value(100, AuxOValue::Boolean(true))
+        );
+        assert!(press(&mut client, 1, 1).is_empty());
+        assert!(press(&mut client, 1, 0).is_empty());
+        assert_eq!(
+            press(&mut client, 1, 1),
+            value(100, AuxOValue::Boolean(false))
+        );
+        assert_eq!(client.value(id(100)), Some(AuxOValue::Boolean(false)));
+
+        assert_eq!(
+            press(&mut client, 2, 500),
+            value(101, AuxOValue::Analogue(500))
+        );
+        assert_eq!(press(&mut client, 2, 0), value(101, AuxOValue::Analogue(0)));
+
+        // Non-latching follows the input
+        assert_eq!(
+            press(&mut client, 3, 1),
+            value(102, AuxOValue::Boolean(true))
+        );
+        assert_eq!(
+            press(&mut client, 3, 0),
+            value(102, AuxOValue::Boolean(false))
+        );
+
+        // Inputs of other units are not assigned
+        let status = AuxOInputStatus {
+            input_number: 1,
+            value1: 1,
+            value2: 0,
+        };
+        assert!(client
+            .process_message(&status.to_message(Address(0x91)), IMPLEMENT)
+            .is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2023 Raven Industries inc.
+
+//! Virtual Terminal (ISO 11783-6) protocol
+//!
+//! The types in this module do not send or receive anything themselves. Received messages are
+//! handed to them with `process_message` and the messages they want to send are collected with
+//! `take_messages`, so they work with any way of driving the CAN bus.
+//!
+//! Supported so far:
+//! 1. Auxiliary control type 2 (AUX-N), the [`AuxiliaryFunctionClient`] for implements and the
+//!    [`AuxiliaryInputDevice`] for joysticks and other input units
+//! 2. Auxiliary control type 1 (AUX-O), the [`AuxOFunctionClient`] for implements
+
+mod aux_n;
+mod aux_o;
+
+pub use aux_n::{
+    Assignment, AuxiliaryAssignment, AuxiliaryAssignmentResponse, AuxiliaryCapabilities,
+    AuxiliaryFunctionClient, AuxiliaryFunctionEvent, AuxiliaryInputDevice,
+    AuxiliaryInputMaintenance, AuxiliaryInputStatus, AuxiliaryInputStatusEnable,
+    AuxiliaryInputStatusEnableResponse, AuxiliaryUnitCapabilities, CapabilitySet,
+    PreferredAssignment, PreferredAssignmentResponse, PreferredAssignmentUnit,
+    MAINTENANCE_INTERVAL, STATUS_CHANGE_INTERVAL, STATUS_REPEAT_INTERVAL,
+};
+pub use aux_o::{
+    AuxOAssignment, AuxOEvent, AuxOFunctionAssignment, AuxOFunctionClient, AuxOInputStatus,
+    AuxOValue,
+};
+
+use crate::driver::{Address, CanId, Pgn, Priority};
+use crate::network_management::can_message::CANMessage;
+use crate::network_management::common_parameter_group_numbers::CommonParameterGroupNumbers;
+
+/// The first byte of a VT message, selecting the command or message it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum VtFunction {
+    AuxiliaryAssignmentType1 = 0x20,
+    AuxiliaryInputType1Status = 0x21,
+    PreferredAssignment = 0x22,
+    AuxiliaryInputType2Maintenance = 0x23,
+    AuxiliaryAssignmentType2 = 0x24,
+    AuxiliaryInputStatusType2Enable = 0x25,
+    AuxiliaryInputType2Status = 0x26,
+    AuxiliaryCapabilities = 0x27,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VtMessageError {
+    /// The message is not a VT message or not the expected one
+    UnexpectedFunction(u8),
+    /// The message ended before all of its fields
+    UnexpectedEnd { expected: usize, found: usize },
+    /// An object ID that may not be null is 0xFFFF
+    NullObjectId,
+}
+
+impl std::fmt::Display for VtMessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for VtMessageError {}
+
+/// The direction of a VT message, each has its own PGN
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent by a VT, PGN 0xE600
+    VtToNode,
+    /// Sent to a VT or to other nodes that listen for VT messages, PGN 0xE700
+    NodeToVt,
+}
+
+impl Direction {
+    pub fn pgn(self) -> Pgn {
+        Pgn::from_raw(match self {
+            Direction::VtToNode => CommonParameterGroupNumbers::VirtualTerminalToNode as u32,
+            Direction::NodeToVt => CommonParameterGroupNumbers::NodeToVirtualTerminal as u32,
+        })
+    }
+
+    /// The direction of a received message, `None` if it is not a VT message
+    pub fn of(message: &CANMessage) -> Option<Direction> {
+        let pgn = message.get_identifier().pgn();
+        [Direction::VtToNode, Direction::NodeToVt]
+            .into_iter()
+            .find(|direction| direction.pgn() == pgn)
+    }
+}
+
+/// Builds a VT message, data shorter than 8 bytes is padded with 0xFF
+pub(crate) fn vt_message(
+    direction: Direction,
+    mut data: Vec<u8>,
+    source: Address,
+    destination: Address,
+    priority: Priority,
+) -> CANMessage {
+    if data.len() < 8 {
+        data.resize(8, 0xFF);
+    }
+    // Both VT PGNs are destination specific, so any destination encodes
+    let id = CanId::try_encode(direction.pgn(), source, destination, priority);
+    CANMessage::new(data, id.unwrap_or_default())
+}
+
+/// Reads the fields of a VT message
+pub(crate) struct Fields<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Fields<'a> {
+    /// Starts reading after the function byte, which must be `function`
+    pub(crate) fn new(data: &'a [u8], function: VtFunction) -> Result<Self, VtMessageError> {
+        match data.first() {
+            Some(&f) if f == function as u8 => Ok(Fields { data, offset: 1 }),
+            Some(&f) => Err(VtMessageError::UnexpectedFunction(f)),
+            None => Err(VtMessageError::UnexpectedEnd {
+                expected: 1,
+                found: 0,
+            }),
+        }
+    }
+
+    pub(crate) fn bytes<const N: usize>(&mut self) -> Result<[u8; N], VtMessageError> {
+        let bytes =
+            self.data
+                .get(self.offset..self.offset + N)
+                .ok_or(VtMessageError::UnexpectedEnd {
+                    expected: self.offset + N,
+                    found: self.data.len(),
+                })?;
+        self.offset += N;
+        Ok(bytes.try_into().unwrap_or([0; N]))
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, VtMessageError> {
+        Ok(self.bytes::<1>()?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, VtMessageError> {
+        Ok(u16::from_le_bytes(self.bytes()?))
+    }
+}